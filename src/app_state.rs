@@ -1,7 +1,13 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::collections::HashMap;
+use uuid::Uuid;
 use burncloud_service_models::{InstalledModel, AvailableModel, ModelStatus, ModelType};
-use crate::{IntegratedModelService, ClientError};
+use serde::{Deserialize, Serialize};
+use crate::{IntegratedModelService, IntegratedServiceConfig, ClientError, InstallSource};
+use crate::discovery::{ModelDiscoveryClient, ModelSearchRequest};
+use crate::i18n::{t, Locale};
+use crate::model_type_display::serialize_type_counts;
 
 /// 应用全局状态
 #[derive(Clone)]
@@ -11,77 +17,158 @@ pub struct AppState {
     pub available_models: Vec<AvailableModel>,
     pub loading: bool,
     pub error: Option<String>,
+    /// 不影响使用的加载问题（如可用模型加载失败），界面以提示条显示
+    pub warning: Option<String>,
+    /// 各模型的安装来源，未记录的模型不在其中
+    pub install_sources: HashMap<Uuid, InstallSource>,
+    /// 加载可用模型前从中同步模型目录，未设置时只读本地数据库
+    discovery: Option<Arc<ModelDiscoveryClient>>,
+    /// 加载代数，在克隆之间共享，用于丢弃过期的加载结果
+    load_generation: Arc<AtomicU64>,
 }
 
-// 手动实现PartialEq，忽略service字段
+/// 一次加载得到的完整数据快照
+#[derive(Debug, Clone)]
+pub struct AppSnapshot {
+    pub installed_models: Vec<InstalledModel>,
+    pub available_models: Vec<AvailableModel>,
+    /// 可用模型加载失败时的提示，此时 `available_models` 为空
+    pub warning: Option<String>,
+    pub install_sources: HashMap<Uuid, InstallSource>,
+}
+
+// 手动实现PartialEq，忽略service和load_generation字段
 impl PartialEq for AppState {
     fn eq(&self, other: &Self) -> bool {
         self.installed_models == other.installed_models
             && self.available_models == other.available_models
             && self.loading == other.loading
             && self.error == other.error
+            && self.warning == other.warning
+            && self.install_sources == other.install_sources
     }
 }
 
 impl AppState {
-    /// 创建新的应用状态
-    pub async fn new() -> Result<Self, ClientError> {
-        // 使用 IntegratedModelService 的默认数据库路径 ($HOME/burncloud/models.db)
-        println!("🚀 AppState: 使用默认数据库路径初始化服务");
-        let service = Arc::new(IntegratedModelService::new(None).await?);
+    /// 按配置初始化服务并创建应用状态
+    ///
+    /// 桌面应用和命令行工具统一从这里启动：`IntegratedServiceConfig::default()` 使用默认
+    /// 数据库路径（`$HOME/burncloud/models.db`），测试可传入 `":memory:"`。创建后的状态尚未
+    /// 加载数据，需再调用 `load_data`（或交给 `load_app_state`）。
+    pub async fn initialize(config: IntegratedServiceConfig) -> Result<Self, ClientError> {
+        println!("🚀 AppState: 初始化服务");
+        let service = Arc::new(IntegratedModelService::with_config(config).await?);
         println!("✅ AppState: 数据库连接初始化成功");
 
-        Ok(Self {
+        Ok(Self::with_service(service))
+    }
+
+    /// 使用已有的服务创建应用状态
+    pub fn with_service(service: Arc<IntegratedModelService>) -> Self {
+        Self {
             service,
             installed_models: Vec::new(),
             available_models: Vec::new(),
             loading: false,
             error: None,
-        })
+            warning: None,
+            install_sources: HashMap::new(),
+            discovery: None,
+            load_generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 加载可用模型前先从发现服务同步模型目录
+    pub fn with_discovery(mut self, client: ModelDiscoveryClient) -> Self {
+        self.discovery = Some(Arc::new(client));
+        self
     }
 
     /// 加载所有数据
-    pub async fn load_data(&mut self) -> Result<(), ClientError> {
-        self.loading = true;
-        self.error = None;
+    ///
+    /// 先在本地构建完整快照，完成后一次性替换状态。若加载期间同一状态（或其克隆）
+    /// 又发起了新的加载，本次结果视为过期并被丢弃，状态保持不变，返回 `Ok(false)`；
+    /// 调用方此时不应再使用这份状态覆盖界面数据。
+    pub async fn load_data(&mut self) -> Result<bool, ClientError> {
+        let generation = self.begin_load();
+        let result = self.load_snapshot().await;
+        self.apply_load(generation, result)
+    }
 
-        // 加载已安装模型
-        println!("🔍 AppState: 正在从数据库加载已安装模型...");
-        match self.service.get_installed_models().await {
-            Ok(models) => {
-                println!("📊 AppState: 数据库中找到 {} 个已安装模型", models.len());
-                for model in &models {
-                    println!("  - 已安装: {} (状态: {:?})", model.model.display_name, model.status);
-                }
-                self.installed_models = models;
+    /// 登记一次新的加载，返回其代数
+    fn begin_load(&self) -> u64 {
+        self.load_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// 若 `generation` 仍是最新的加载，则用其结果替换状态
+    fn apply_load(&mut self, generation: u64, result: Result<AppSnapshot, ClientError>) -> Result<bool, ClientError> {
+        if self.load_generation.load(Ordering::SeqCst) != generation {
+            println!("⏭️ AppState: 丢弃过期的加载结果");
+            return Ok(false);
+        }
+
+        match result {
+            Ok(snapshot) => {
+                self.installed_models = snapshot.installed_models;
+                self.available_models = snapshot.available_models;
+                self.loading = false;
+                self.error = None;
+                self.warning = snapshot.warning;
+                self.install_sources = snapshot.install_sources;
+                Ok(true)
             }
             Err(e) => {
-                let error_msg = format!("加载已安装模型失败: {}", e);
-                println!("❌ AppState: {}", error_msg);
-                self.error = Some(error_msg);
+                self.error = Some(format!("加载模型失败: {}", e));
                 self.loading = false;
-                return Err(e);
+                Err(e)
             }
         }
+    }
+
+    /// 从数据库读取完整快照，不修改当前状态
+    ///
+    /// 已安装模型读取失败（数据库不可用）视为致命错误返回 `Err`；可用模型加载失败
+    /// （网络或目录问题）只记录在 `warning` 中，已安装模型照常可用。
+    pub async fn load_snapshot(&self) -> Result<AppSnapshot, ClientError> {
+        // 加载已安装模型
+        println!("🔍 AppState: 正在从数据库加载已安装模型...");
+        let installed_models = self.service.get_installed_models().await
+            .inspect_err(|e| println!("❌ AppState: 加载已安装模型失败: {}", e))?;
+        println!("📊 AppState: 数据库中找到 {} 个已安装模型", installed_models.len());
+        for model in &installed_models {
+            println!("  - 已安装: {} (状态: {:?})", model.model.display_name, model.status);
+        }
 
-        // 加载可用模型（从真实数据库）
-        match self.load_available_models().await {
-            Ok(models) => self.available_models = models,
+        // 加载可用模型（从真实数据库），失败时只显示已安装模型
+        let (available_models, warning) = match self.load_available_models().await {
+            Ok(available_models) => (available_models, None),
             Err(e) => {
-                let error_msg = format!("加载可用模型失败: {}", e);
-                println!("❌ AppState: {}", error_msg);
-                self.error = Some(error_msg);
-                self.loading = false;
-                return Err(e);
+                println!("⚠️ AppState: 加载可用模型失败，仅显示已安装模型: {}", e);
+                let message = format!("{}: {}", t("notify.available_failed", Locale::default()), e.user_message());
+                (Vec::new(), Some(message))
             }
-        }
+        };
 
-        self.loading = false;
-        Ok(())
+        // 安装来源只用于详情展示，读取失败时不显示
+        let install_sources = self.service.get_install_sources().await
+            .inspect_err(|e| println!("⚠️ AppState: 加载安装来源失败: {}", e))
+            .unwrap_or_default();
+
+        Ok(AppSnapshot {
+            installed_models,
+            available_models,
+            warning,
+            install_sources,
+        })
     }
 
     /// 加载可用模型（从数据库获取真实数据）
     async fn load_available_models(&self) -> Result<Vec<AvailableModel>, ClientError> {
+        if let Some(discovery) = &self.discovery {
+            println!("🌐 AppState: 正在从发现服务同步模型目录...");
+            self.service.sync_from_discovery(discovery, ModelSearchRequest::default()).await?;
+        }
+
         println!("🔍 AppState: 正在从数据库加载可用模型...");
 
         // 获取数据库中的所有模型（不创建示例数据）
@@ -106,7 +193,7 @@ impl AppState {
     }
 
     /// 刷新数据
-    pub async fn refresh(&mut self) -> Result<(), ClientError> {
+    pub async fn refresh(&mut self) -> Result<bool, ClientError> {
         self.load_data().await
     }
 
@@ -175,19 +262,55 @@ impl AppState {
             available_count,
             total_size_bytes: total_size,
             models_by_type,
+            installation_rate: percentage(total_installed, available_count),
+            running_rate: percentage(running_count, total_installed),
         }
     }
 }
 
+/// 取得应用状态并加载数据，供启动和“重试”共用
+///
+/// 传入已有状态时复用它，使并发的刷新共享加载代数；否则调用 `create` 创建新状态。
+/// 返回 `Ok(None)` 表示已有更新的加载在进行，本次结果应被丢弃。
+pub async fn load_app_state<F, Fut>(current: Option<AppState>, create: F) -> Result<Option<AppState>, ClientError>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<AppState, ClientError>>,
+{
+    let mut state = match current {
+        Some(state) => state,
+        None => create().await?,
+    };
+    if state.load_data().await? {
+        Ok(Some(state))
+    } else {
+        Ok(None)
+    }
+}
+
 /// 应用统计信息
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppStats {
     pub total_installed: usize,
     pub running_count: usize,
     pub stopped_count: usize,
     pub available_count: usize,
     pub total_size_bytes: u64,
+    #[serde(serialize_with = "serialize_type_counts")]
     pub models_by_type: HashMap<ModelType, usize>,
+    /// 已安装模型占全部模型的百分比，没有模型时为 0
+    pub installation_rate: f64,
+    /// 运行中模型占已安装模型的百分比，没有已安装模型时为 0
+    pub running_rate: f64,
+}
+
+/// `part` 占 `total` 的百分比，`total` 为 0 时为 0
+fn percentage(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
 }
 
 impl AppStats {
@@ -195,4 +318,171 @@ impl AppStats {
     pub fn format_total_size(&self) -> String {
         crate::IntegratedModelService::format_file_size(self.total_size_bytes)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burncloud_service_models::CreateModelRequest;
+    use uuid::Uuid;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+    use wiremock::matchers::any;
+
+    fn memory_config() -> IntegratedServiceConfig {
+        IntegratedServiceConfig {
+            database_path: Some(":memory:".to_string()),
+            default_install_dir: std::env::temp_dir(),
+            ..Default::default()
+        }
+    }
+
+    async fn setup_state() -> AppState {
+        AppState::initialize(memory_config()).await.unwrap()
+    }
+
+    async fn create_model(state: &AppState, name: &str) -> Uuid {
+        state.service.create_model(CreateModelRequest {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            version: "1.0.0".to_string(),
+            model_type: ModelType::Chat,
+            provider: "TestProvider".to_string(),
+            file_size: 1024,
+            description: None,
+            license: None,
+            tags: vec![],
+            languages: vec![],
+            file_path: None,
+            download_url: None,
+            config: HashMap::new(),
+            is_official: false,
+        }).await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_initialize_with_memory_database() {
+        let loaded = load_app_state(None, || AppState::initialize(memory_config())).await.unwrap();
+        let state = loaded.expect("nothing else is loading");
+        assert!(state.installed_models.is_empty());
+        assert!(state.available_models.is_empty());
+        assert!(state.error.is_none());
+        assert!(!state.loading);
+    }
+
+    #[tokio::test]
+    async fn test_stats_serialize_rates() {
+        let mut state = setup_state().await;
+        let installed = create_model(&state, "installed").await;
+        create_model(&state, "catalog-only").await;
+        state.service.install_model(installed, None).await.unwrap();
+        state.service.update_model_status(installed, ModelStatus::Running).await.unwrap();
+        assert!(state.load_data().await.unwrap());
+
+        let stats = state.get_stats();
+        assert_eq!(stats.installation_rate, 50.0);
+        assert_eq!(stats.running_rate, 100.0);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["installation_rate"], 50.0);
+        assert_eq!(json["running_rate"], 100.0);
+
+        // 没有模型时不除以零
+        let empty = setup_state().await.get_stats();
+        assert_eq!(empty.installation_rate, 0.0);
+        assert_eq!(empty.running_rate, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_loads_keep_latest_snapshot() {
+        let state = setup_state().await;
+        create_model(&state, "first").await;
+
+        // 两个克隆各自发起加载，第一次加载在第二次开始后才完成
+        let mut stale = state.clone();
+        let mut latest = state.clone();
+
+        let stale_generation = stale.begin_load();
+        let stale_result = stale.load_snapshot().await;
+
+        create_model(&state, "second").await;
+        let latest_generation = latest.begin_load();
+        let latest_result = latest.load_snapshot().await;
+
+        assert!(latest.apply_load(latest_generation, latest_result).unwrap());
+        assert!(!stale.apply_load(stale_generation, stale_result).unwrap());
+
+        assert_eq!(latest.available_models.len(), 2);
+        assert!(stale.available_models.is_empty());
+
+        // 之后的完整加载仍然生效
+        assert!(stale.load_data().await.unwrap());
+        assert!(stale == latest);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_failed_initialization_loads_data() {
+        let ready = setup_state().await;
+        create_model(&ready, "retried").await;
+
+        // 第一次创建失败，第二次成功
+        let attempts = std::sync::atomic::AtomicUsize::new(0);
+        let create = || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(ClientError::InitializationFailed("database locked".to_string()))
+            } else {
+                Ok(ready.clone())
+            }
+        };
+
+        let first = load_app_state(None, create).await;
+        assert!(matches!(first, Err(ClientError::InitializationFailed(_))));
+
+        let retried = load_app_state(None, create).await.unwrap().unwrap();
+        assert_eq!(retried.available_models.len(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+        // 已有状态时直接重新加载，不再创建
+        let reloaded = load_app_state(Some(retried), create).await.unwrap().unwrap();
+        assert_eq!(reloaded.available_models.len(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_catalog_still_loads_installed_models() {
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let state = setup_state().await;
+        let model_id = create_model(&state, "local-only").await;
+        state.service.install_model(model_id, None).await.unwrap();
+        let discovery = ModelDiscoveryClient::new(server.uri()).unwrap();
+        let mut state = state.with_discovery(discovery);
+
+        // 目录同步失败不阻止加载，已安装模型照常可用
+        assert!(state.load_data().await.unwrap());
+        assert_eq!(state.installed_models.len(), 1);
+        assert!(state.available_models.is_empty());
+        assert!(state.error.is_none());
+        assert!(state.warning.as_deref().unwrap().contains(t("notify.available_failed", Locale::default())));
+        assert_eq!(state.search_models("local").0.len(), 1);
+
+        // 目录恢复后警告随下一次加载消失
+        server.reset().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [],
+                "total_count": 0,
+                "page": 1,
+                "page_size": 20,
+                "has_next": false,
+                "search_time_ms": 1,
+            })))
+            .mount(&server)
+            .await;
+        assert!(state.load_data().await.unwrap());
+        assert!(state.warning.is_none());
+        assert_eq!(state.available_models.len(), 1);
+    }
+}
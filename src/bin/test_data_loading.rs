@@ -1,4 +1,5 @@
 use burncloud_client_models::app_state::AppState;
+use burncloud_client_models::IntegratedServiceConfig;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -6,7 +7,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 创建 AppState
     println!("1. 创建 AppState...");
-    let mut app_state = AppState::new().await?;
+    let mut app_state = AppState::initialize(IntegratedServiceConfig::default()).await?;
 
     // 加载数据
     println!("2. 加载数据...");
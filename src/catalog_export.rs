@@ -0,0 +1,160 @@
+// 模型目录导出（JSON / CSV / NDJSON）
+
+use std::path::{Path, PathBuf};
+use burncloud_service_models::Model;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio_util::sync::CancellationToken;
+use crate::integrated_service::ClientError;
+
+/// CSV 导出的列
+const CSV_HEADER: &str = "id,name,display_name,version,model_type,provider,file_size,tags,download_url";
+
+/// 整体写出的目录格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFormat {
+    /// 单个 JSON 数组
+    Json,
+    /// 带表头的 CSV，标签以 `;` 连接
+    Csv,
+}
+
+/// 将模型目录写入 `path`
+///
+/// 先写入同目录下的 `.tmp` 文件，完成后再改名，因此取消或出错时 `path` 保持原样。
+/// 每写出一个模型调用一次 `on_progress(已写出, 总数)`；取消后返回 `ClientError::Cancelled`。
+pub async fn write_catalog(
+    models: &[Model],
+    path: &Path,
+    format: CatalogFormat,
+    on_progress: impl FnMut(usize, usize),
+    cancel: &CancellationToken,
+) -> Result<usize, ClientError> {
+    let temp_path = temp_path_for(path);
+    let result = write_catalog_file(models, &temp_path, format, on_progress, cancel).await;
+    match result {
+        Ok(written) => {
+            tokio::fs::rename(&temp_path, path).await?;
+            Ok(written)
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            Err(e)
+        }
+    }
+}
+
+async fn write_catalog_file(
+    models: &[Model],
+    path: &Path,
+    format: CatalogFormat,
+    mut on_progress: impl FnMut(usize, usize),
+    cancel: &CancellationToken,
+) -> Result<usize, ClientError> {
+    let total = models.len();
+    let mut writer = BufWriter::new(tokio::fs::File::create(path).await?);
+    match format {
+        CatalogFormat::Json => writer.write_all(b"[").await?,
+        CatalogFormat::Csv => writer.write_all(format!("{}\n", CSV_HEADER).as_bytes()).await?,
+    }
+
+    for (written, model) in models.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err(cancelled(written, total));
+        }
+        let record = match format {
+            CatalogFormat::Json => {
+                let separator = if written == 0 { "\n" } else { ",\n" };
+                format!("{}{}", separator, serde_json::to_string(model)?)
+            }
+            CatalogFormat::Csv => csv_row(model)?,
+        };
+        writer.write_all(record.as_bytes()).await?;
+        on_progress(written + 1, total);
+    }
+
+    if format == CatalogFormat::Json {
+        writer.write_all(b"\n]\n").await?;
+    }
+    writer.flush().await?;
+    Ok(total)
+}
+
+/// 以 NDJSON（每行一个模型）将目录写入 `path`
+///
+/// 每行写完立即刷新，取消或出错时已写出的行仍是完整的 JSON，可逐行解析。
+/// 每写出一个模型调用一次 `on_progress(已写出, 总数)`；取消后返回 `ClientError::Cancelled`。
+pub async fn write_catalog_ndjson(
+    models: &[Model],
+    path: &Path,
+    mut on_progress: impl FnMut(usize, usize),
+    cancel: &CancellationToken,
+) -> Result<usize, ClientError> {
+    let total = models.len();
+    let mut file = tokio::fs::File::create(path).await?;
+
+    for (written, model) in models.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err(cancelled(written, total));
+        }
+        let line = format!("{}\n", serde_json::to_string(model)?);
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        on_progress(written + 1, total);
+    }
+    Ok(total)
+}
+
+fn cancelled(written: usize, total: usize) -> ClientError {
+    ClientError::Cancelled(format!("Catalog export stopped after {} of {} models", written, total))
+}
+
+/// 导出过程中使用的临时文件
+fn temp_path_for(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// 一个模型对应的 CSV 行（含换行）
+fn csv_row(model: &Model) -> Result<String, ClientError> {
+    let model_type = serde_json::to_value(model.model_type)?;
+    let fields = [
+        model.id.to_string(),
+        model.name.clone(),
+        model.display_name.clone(),
+        model.version.clone(),
+        model_type.as_str().unwrap_or_default().to_string(),
+        model.provider.clone(),
+        model.file_size.to_string(),
+        model.tags.join(";"),
+        model.download_url.clone().unwrap_or_default(),
+    ];
+    let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    Ok(format!("{}\n", row.join(",")))
+}
+
+/// 按 RFC 4180 转义 CSV 字段
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("llama"), "llama");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_temp_path_sits_next_to_target() {
+        assert_eq!(temp_path_for(Path::new("/tmp/catalog.json")), PathBuf::from("/tmp/catalog.json.tmp"));
+    }
+}
@@ -0,0 +1,235 @@
+// 可插拔的校验和算法
+
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256, Sha512};
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use crate::validation::ChecksumType;
+
+/// 流式读取时的缓冲区大小
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// 校验和算法
+///
+/// 内置 MD5/SHA256/SHA512，注册表使用 BLAKE3、xxHash 等算法时可自行实现。
+#[async_trait]
+pub trait Checksummer: Send + Sync {
+    /// 算法名称，同时作为校验和前缀（如 `sha256:...`）
+    fn name(&self) -> &str;
+
+    /// 读取全部内容并返回小写十六进制摘要
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String>;
+
+    /// 逐块计算摘要的哈希状态，供下载时边写边算
+    ///
+    /// 默认返回 `None`，调用方改为在内容写完后用 `digest` 重新读取计算。
+    fn streaming(&self) -> Option<Box<dyn StreamingHasher>> {
+        None
+    }
+}
+
+/// 逐块输入内容的哈希状态
+pub trait StreamingHasher: Send {
+    /// 输入下一块内容
+    fn update(&mut self, chunk: &[u8]);
+
+    /// 结束计算，返回小写十六进制摘要
+    fn finalize(self: Box<Self>) -> String;
+}
+
+impl StreamingHasher for md5::Context {
+    fn update(&mut self, chunk: &[u8]) {
+        self.consume(chunk);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:x}", self.compute())
+    }
+}
+
+impl StreamingHasher for Sha256 {
+    fn update(&mut self, chunk: &[u8]) {
+        Digest::update(self, chunk);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:x}", Digest::finalize(*self))
+    }
+}
+
+impl StreamingHasher for Sha512 {
+    fn update(&mut self, chunk: &[u8]) {
+        Digest::update(self, chunk);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:x}", Digest::finalize(*self))
+    }
+}
+
+/// MD5 校验
+pub struct Md5Checksummer;
+
+/// SHA256 校验
+pub struct Sha256Checksummer;
+
+/// SHA512 校验
+pub struct Sha512Checksummer;
+
+#[async_trait]
+impl Checksummer for Md5Checksummer {
+    fn name(&self) -> &str {
+        "md5"
+    }
+
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String> {
+        let mut context = md5::Context::new();
+        read_chunks(reader, |chunk| context.consume(chunk)).await?;
+        Ok(format!("{:x}", context.compute()))
+    }
+
+    fn streaming(&self) -> Option<Box<dyn StreamingHasher>> {
+        Some(Box::new(md5::Context::new()))
+    }
+}
+
+#[async_trait]
+impl Checksummer for Sha256Checksummer {
+    fn name(&self) -> &str {
+        "sha256"
+    }
+
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String> {
+        let mut hasher = Sha256::new();
+        read_chunks(reader, |chunk| Digest::update(&mut hasher, chunk)).await?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    fn streaming(&self) -> Option<Box<dyn StreamingHasher>> {
+        Some(Box::new(Sha256::new()))
+    }
+}
+
+#[async_trait]
+impl Checksummer for Sha512Checksummer {
+    fn name(&self) -> &str {
+        "sha512"
+    }
+
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String> {
+        let mut hasher = Sha512::new();
+        read_chunks(reader, |chunk| Digest::update(&mut hasher, chunk)).await?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    fn streaming(&self) -> Option<Box<dyn StreamingHasher>> {
+        Some(Box::new(Sha512::new()))
+    }
+}
+
+impl ChecksumType {
+    /// 对应的内置校验实现
+    pub fn checksummer(&self) -> Arc<dyn Checksummer> {
+        match self {
+            ChecksumType::MD5 => Arc::new(Md5Checksummer),
+            ChecksumType::SHA256 => Arc::new(Sha256Checksummer),
+            ChecksumType::SHA512 => Arc::new(Sha512Checksummer),
+        }
+    }
+}
+
+/// 计算文件摘要
+pub async fn digest_file(checksummer: &dyn Checksummer, path: &Path) -> std::io::Result<String> {
+    let mut file = tokio::fs::File::open(path).await?;
+    checksummer.digest(&mut file).await
+}
+
+/// 把文件内容送入哈希状态，用于续传时补上已下载的部分
+pub async fn hash_file_into(hasher: &mut dyn StreamingHasher, path: &Path) -> std::io::Result<()> {
+    let mut file = tokio::fs::File::open(path).await?;
+    read_chunks(&mut file, |chunk| hasher.update(chunk)).await
+}
+
+/// 校验和计算进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumProgress {
+    /// 已计算的字节数
+    pub hashed: u64,
+    /// 文件总字节数
+    pub total: u64,
+}
+
+/// 计算文件摘要并报告进度
+///
+/// 每读取一块向 `progress` 发送一次已计算字节数；`cancel` 触发后在下一次读取时中止并返回错误。
+pub async fn digest_file_with_progress(
+    checksummer: &dyn Checksummer,
+    path: &Path,
+    progress: Option<UnboundedSender<ChecksumProgress>>,
+    cancel: &CancellationToken,
+) -> std::io::Result<String> {
+    let file = tokio::fs::File::open(path).await?;
+    let total = file.metadata().await?.len();
+    let mut reader = ProgressReader { inner: file, hashed: 0, total, progress, cancel };
+    checksummer.digest(&mut reader).await
+}
+
+/// 统计已读取字节数并检查取消状态的读取器
+struct ProgressReader<'a, R> {
+    inner: R,
+    hashed: u64,
+    total: u64,
+    progress: Option<UnboundedSender<ChecksumProgress>>,
+    cancel: &'a CancellationToken,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ProgressReader<'_, R> {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        if self.cancel.is_cancelled() {
+            return Poll::Ready(Err(std::io::Error::other("校验和计算已取消")));
+        }
+
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            let read = (buf.filled().len() - filled) as u64;
+            if read > 0 {
+                self.hashed += read;
+                if let Some(progress) = &self.progress {
+                    // 接收方已关闭时不再关心进度
+                    let _ = progress.send(ChecksumProgress { hashed: self.hashed, total: self.total });
+                }
+            }
+        }
+        result
+    }
+}
+
+/// 去掉与算法名匹配的前缀（忽略大小写），如 `blake3:abcd` → `abcd`
+pub fn strip_checksum_prefix<'a>(checksummer: &dyn Checksummer, checksum: &'a str) -> &'a str {
+    let checksum = checksum.trim();
+    match checksum.split_once(':') {
+        Some((prefix, hex)) if prefix.eq_ignore_ascii_case(checksummer.name()) => hex,
+        _ => checksum,
+    }
+}
+
+/// 按块读取直到结束
+async fn read_chunks(
+    reader: &mut (dyn AsyncRead + Unpin + Send),
+    mut consume: impl FnMut(&[u8]) + Send,
+) -> std::io::Result<()> {
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        consume(&buffer[..read]);
+    }
+}
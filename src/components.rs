@@ -0,0 +1,161 @@
+// 各页面共用的展示组件
+
+use dioxus::prelude::*;
+use chrono::{DateTime, Utc};
+use crate::download_queue::format_queue_eta;
+use crate::i18n::{t, Locale};
+use crate::model_capabilities::ModelCapabilities;
+
+/// 统计卡片的布局
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatCardVariant {
+    /// 图标在左，数值和标题上下排列
+    #[default]
+    Compact,
+    /// 图标和标题作为卡片头，数值和说明在下方
+    Detailed,
+}
+
+/// 统计卡片
+///
+/// `color` 取 blue / green / red / purple，其他值使用默认配色；`description` 为空时不显示说明。
+#[component]
+pub fn StatCard(
+    title: String,
+    value: String,
+    icon: String,
+    description: Option<String>,
+    color: String,
+    #[props(default)] variant: StatCardVariant,
+) -> Element {
+    let color_class = match color.as_str() {
+        "blue" => "stat-card-blue",
+        "green" => "stat-card-green",
+        "red" => "stat-card-red",
+        "purple" => "stat-card-purple",
+        _ => "stat-card-default"
+    };
+
+    match variant {
+        StatCardVariant::Compact => rsx! {
+            div { class: "stat-card {color_class}",
+                div { class: "stat-icon", "{icon}" }
+                div { class: "stat-content",
+                    div { class: "stat-value", "{value}" }
+                    div { class: "stat-title", "{title}" }
+                    if let Some(description) = description {
+                        div { class: "stat-description", "{description}" }
+                    }
+                }
+            }
+        },
+        StatCardVariant::Detailed => rsx! {
+            div { class: "stat-card {color_class}",
+                div { class: "stat-header",
+                    div { class: "stat-icon", "{icon}" }
+                    div { class: "stat-title", "{title}" }
+                }
+                div { class: "stat-value", "{value}" }
+                if let Some(description) = description {
+                    div { class: "stat-description", "{description}" }
+                }
+            }
+        },
+    }
+}
+
+/// 排队下载的预计时间，如“约 2 分钟后开始，约 8 分钟后完成”；未能估算时显示“正在估算”，`locale` 默认取系统语言
+#[component]
+pub fn DownloadEta(estimate: Option<(DateTime<Utc>, DateTime<Utc>)>, #[props(default)] locale: Locale) -> Element {
+    let text = match estimate {
+        Some((start, finish)) => format_queue_eta(start, finish, Utc::now()),
+        None => t("download.estimating", locale).to_string(),
+    };
+
+    rsx! {
+        span { class: "text-caption text-secondary download-eta", "⏱️ {text}" }
+    }
+}
+
+/// 模型支持的可选能力标签，如“视觉”“函数调用”；没有可选能力时不显示，`locale` 默认取系统语言
+#[component]
+pub fn CapabilityChips(capabilities: ModelCapabilities, #[props(default)] locale: Locale) -> Element {
+    rsx! {
+        for capability in capabilities.list() {
+            span { class: "text-caption capability-chip",
+                style: "background: #6366F1; color: white; padding: 2px 6px; border-radius: 4px; margin-left: 8px;",
+                "{capability.label(locale)}"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_test::render;
+
+    #[test]
+    fn test_stat_card_with_description() {
+        let rendered = render(|| rsx! {
+            StatCard {
+                title: "运行中",
+                value: "3",
+                icon: "🟢",
+                description: "当前正在运行的模型".to_string(),
+                color: "green",
+                variant: StatCardVariant::Detailed,
+            }
+        });
+        assert!(rendered.contains("运行中"));
+        assert!(rendered.contains("3"));
+        assert!(rendered.contains("当前正在运行的模型"));
+        assert!(rendered.contains("stat-header"));
+    }
+
+    #[test]
+    fn test_download_eta_waiting_and_unknown() {
+        let rendered = render(|| {
+            let now = Utc::now();
+            let start = now + chrono::Duration::seconds(110);
+            let finish = now + chrono::Duration::seconds(470);
+            rsx! {
+                DownloadEta { estimate: (start, finish) }
+                DownloadEta { locale: Locale::Zh }
+            }
+        });
+        assert!(rendered.contains("约 2 分钟后开始，约 8 分钟后完成"));
+        assert!(rendered.contains(t("download.estimating", Locale::Zh)));
+
+        let english = render(|| rsx! { DownloadEta { locale: Locale::En } });
+        assert!(english.contains(t("download.estimating", Locale::En)));
+    }
+
+    #[test]
+    fn test_capability_chips() {
+        let rendered = render(|| rsx! {
+            CapabilityChips { capabilities: ModelCapabilities { vision: true, json_mode: true, ..Default::default() }, locale: Locale::Zh }
+        });
+        assert!(rendered.contains("视觉"));
+        assert!(rendered.contains("JSON 模式"));
+        assert!(!rendered.contains("函数调用"));
+
+        let english = render(|| rsx! {
+            CapabilityChips { capabilities: ModelCapabilities { function_calling: true, ..Default::default() }, locale: Locale::En }
+        });
+        assert!(english.contains("Function calling"));
+        assert!(!english.contains("函数调用"));
+    }
+
+    #[test]
+    fn test_stat_card_without_description() {
+        let rendered = render(|| rsx! {
+            StatCard { title: "可下载", value: "12", icon: "📥", color: "purple" }
+        });
+        assert!(rendered.contains("可下载"));
+        assert!(rendered.contains("12"));
+        assert!(rendered.contains("📥"));
+        assert!(rendered.contains("stat-content"));
+        assert!(!rendered.contains("stat-description"));
+    }
+}
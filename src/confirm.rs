@@ -0,0 +1,192 @@
+// 破坏性操作前的统一确认弹窗
+
+use std::rc::Rc;
+use dioxus::prelude::*;
+use crate::i18n::{t, Locale};
+
+/// 一次确认请求
+#[derive(Clone)]
+pub struct ConfirmRequest {
+    pub title: String,
+    pub message: String,
+    /// 危险操作的确认按钮以红色显示
+    pub danger: bool,
+    /// 用户点击确认后调用
+    pub on_confirm: Rc<dyn Fn()>,
+}
+
+impl ConfirmRequest {
+    pub fn new(title: impl Into<String>, message: impl Into<String>, on_confirm: impl Fn() + 'static) -> Self {
+        Self {
+            title: title.into(),
+            message: message.into(),
+            danger: false,
+            on_confirm: Rc::new(on_confirm),
+        }
+    }
+
+    /// 标记为危险操作
+    pub fn danger(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+}
+
+/// 确认弹窗的状态：空闲或等待用户确认某个请求
+#[derive(Clone, Default)]
+pub struct ConfirmState {
+    pending: Option<ConfirmRequest>,
+}
+
+impl ConfirmState {
+    /// 打开确认弹窗，替换尚未处理的请求
+    pub fn open(&mut self, request: ConfirmRequest) {
+        self.pending = Some(request);
+    }
+
+    /// 当前等待确认的请求
+    pub fn pending(&self) -> Option<&ConfirmRequest> {
+        self.pending.as_ref()
+    }
+
+    /// 确认并关闭弹窗，返回需要调用的回调
+    ///
+    /// 回调由调用方在释放状态的借用后再调用，回调中可以再次打开确认弹窗。
+    pub fn confirm(&mut self) -> Option<Rc<dyn Fn()>> {
+        self.pending.take().map(|request| request.on_confirm)
+    }
+
+    /// 取消并关闭弹窗，不调用回调
+    pub fn cancel(&mut self) {
+        self.pending = None;
+    }
+}
+
+/// 确认钩子
+///
+/// 返回打开确认弹窗的函数和共用的弹窗元素，组件把弹窗元素放进自己的 rsx 中即可。
+pub fn use_confirm() -> (impl Fn(ConfirmRequest) + Copy, Element) {
+    let mut state = use_signal(ConfirmState::default);
+    let open_confirm = move |request: ConfirmRequest| {
+        let mut state = state;
+        state.write().open(request);
+    };
+
+    let dialog = match state.read().pending() {
+        Some(request) => rsx! {
+            ConfirmDialog {
+                title: request.title.clone(),
+                message: request.message.clone(),
+                danger: request.danger,
+                on_confirm: move |_| {
+                    let on_confirm = state.write().confirm();
+                    if let Some(on_confirm) = on_confirm {
+                        on_confirm();
+                    }
+                },
+                on_cancel: move |_| state.write().cancel(),
+            }
+        },
+        None => rsx! {},
+    };
+
+    (open_confirm, dialog)
+}
+
+/// 确认弹窗
+#[component]
+pub fn ConfirmDialog(
+    title: String,
+    message: String,
+    #[props(default)] danger: bool,
+    on_confirm: EventHandler,
+    on_cancel: EventHandler,
+    #[props(default)] locale: Locale,
+) -> Element {
+    let confirm_style = if danger { "background: #e74c3c; border-color: #e74c3c;" } else { "" };
+    let cancel_label = t("confirm.cancel", locale);
+    let confirm_label = t("confirm.ok", locale);
+
+    rsx! {
+        div { class: "modal-overlay",
+            style: "position: fixed; inset: 0; background: rgba(0, 0, 0, 0.4); display: flex; align-items: center; justify-content: center; z-index: 200;",
+            onclick: move |_| on_cancel.call(()),
+            div { class: "card p-lg",
+                style: "width: min(420px, 90vw);",
+                onclick: move |evt| evt.stop_propagation(),
+                h2 { class: "text-title font-semibold m-0 mb-md", "{title}" }
+                p { class: "text-secondary", "{message}" }
+                div { class: "flex justify-end gap-sm mt-lg",
+                    button { class: "btn btn-secondary", onclick: move |_| on_cancel.call(()), "{cancel_label}" }
+                    button { class: "btn btn-primary", style: "{confirm_style}", onclick: move |_| on_confirm.call(()), "{confirm_label}" }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_test::render;
+    use std::cell::Cell;
+
+    fn counting_request(count: &Rc<Cell<u32>>) -> ConfirmRequest {
+        let count = count.clone();
+        ConfirmRequest::new("卸载模型", "确定卸载吗？", move || count.set(count.get() + 1)).danger()
+    }
+
+    #[test]
+    fn test_confirm_fires_callback_once() {
+        let count = Rc::new(Cell::new(0));
+        let mut state = ConfirmState::default();
+        assert!(state.pending().is_none());
+
+        state.open(counting_request(&count));
+        let pending = state.pending().unwrap();
+        assert_eq!(pending.title, "卸载模型");
+        assert!(pending.danger);
+
+        let on_confirm = state.confirm().expect("a request is pending");
+        on_confirm();
+        assert_eq!(count.get(), 1);
+        assert!(state.pending().is_none());
+
+        // 弹窗已关闭，再次确认不会重复调用
+        assert!(state.confirm().is_none());
+        assert_eq!(count.get(), 1);
+    }
+
+    #[test]
+    fn test_cancel_skips_callback() {
+        let count = Rc::new(Cell::new(0));
+        let mut state = ConfirmState::default();
+
+        state.open(counting_request(&count));
+        state.cancel();
+        assert!(state.pending().is_none());
+        assert!(state.confirm().is_none());
+        assert_eq!(count.get(), 0);
+    }
+
+    #[test]
+    fn test_buttons_follow_locale() {
+        let english = render(|| rsx! {
+            ConfirmDialog {
+                title: "Uninstall",
+                message: "Sure?",
+                locale: Locale::En,
+                on_confirm: |_| {},
+                on_cancel: |_| {},
+            }
+        });
+        assert!(english.contains(t("confirm.cancel", Locale::En)));
+        assert!(english.contains(t("confirm.ok", Locale::En)));
+
+        let chinese = render(|| rsx! {
+            ConfirmDialog { title: "卸载模型", message: "确定卸载吗？", locale: Locale::Zh, on_confirm: |_| {}, on_cancel: |_| {} }
+        });
+        assert!(chinese.contains(t("confirm.cancel", Locale::Zh)));
+        assert!(chinese.contains(t("confirm.ok", Locale::Zh)));
+    }
+}
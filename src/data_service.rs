@@ -1,11 +1,23 @@
 use burncloud_service_models::{
-    InstalledModel, ModelStatus, ModelType, AvailableModel, RuntimeConfig, ModelsService
+    InstalledModel, Model, ModelStatus, ModelType, AvailableModel, RuntimeConfig, ModelsService
 };
 use burncloud_database::Database;
 use uuid::Uuid;
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::ClientError;
+use crate::integrated_service::LocalStatus;
+use crate::model_type_display::serialize_type_counts;
+
+/// 等待模型端口就绪的默认时长
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 检查端口是否就绪的间隔
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
 #[derive(Debug, Clone)]
 pub struct ModelRuntime {
@@ -24,12 +36,53 @@ pub struct RuntimeMetrics {
     pub uptime_seconds: u64,
 }
 
-#[derive(Debug, Clone)]
+/// 运行模型所需的系统资源
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SystemRequirements {
     pub min_memory_gb: f32,
+    pub recommended_memory_gb: f32,
     pub min_disk_space_gb: f32,
     pub gpu_required: bool,
     pub cpu_cores: u32,
+    /// 支持的操作系统，None 表示不限
+    pub supported_os: Option<Vec<String>>,
+    /// 支持的 CPU 架构，None 表示不限
+    pub supported_arch: Option<Vec<String>>,
+}
+
+/// 统一搜索的一条结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub model: Model,
+    /// 已安装为 `Installed`，仅在可用列表中为 `Registered`
+    pub availability: LocalStatus,
+    /// 匹配程度，0 到 1，越大越相关
+    pub relevance: f32,
+}
+
+/// 模型数据来源，内存服务与数据库服务共用，使组件与后端无关
+#[async_trait]
+pub trait ModelDataSource: Send + Sync {
+    /// 已安装模型
+    async fn installed_models(&self) -> Result<Vec<InstalledModel>, ClientError>;
+
+    /// 可用模型
+    async fn available_models(&self) -> Result<Vec<AvailableModel>, ClientError>;
+
+    /// 安装模型
+    async fn install_model(&mut self, model_id: Uuid, install_path: String) -> Result<(), ClientError>;
+
+    /// 卸载模型
+    async fn uninstall_model(&mut self, model_id: Uuid) -> Result<(), ClientError>;
+
+    /// 在指定端口启动模型
+    async fn start_model(&mut self, model_id: Uuid, port: u16) -> Result<(), ClientError>;
+
+    /// 停止模型
+    async fn stop_model(&mut self, model_id: Uuid) -> Result<(), ClientError>;
+
+    /// 按名称、显示名称、描述或提供方搜索已安装模型（忽略大小写），结果顺序与 `installed_models` 一致
+    async fn search_installed_models(&self, query: &str) -> Result<Vec<InstalledModel>, ClientError>;
 }
 
 /// 模型数据服务 - 提供模型数据的增删改查功能
@@ -104,22 +157,44 @@ impl ModelDataService {
     }
 
     /// 搜索模型（按名称、描述等）
+    ///
+    /// 返回借用，不复制模型；需要与数据库后端写同一套代码时用
+    /// `ModelDataSource::search_installed_models`，它会克隆每个匹配的模型。
     pub fn search_models(&self, query: &str) -> Vec<&InstalledModel> {
         let query_lower = query.to_lowercase();
         self.installed_models
             .iter()
-            .filter(|model| {
-                model.model.name.to_lowercase().contains(&query_lower)
-                    || model.model.display_name.to_lowercase().contains(&query_lower)
-                    || model.model.description
-                        .as_ref()
-                        .map(|desc| desc.to_lowercase().contains(&query_lower))
-                        .unwrap_or(false)
-                    || model.model.provider.to_lowercase().contains(&query_lower)
-            })
+            .filter(|model| installed_model_matches(model, &query_lower))
             .collect()
     }
 
+    /// 同时搜索已安装和可用模型，按相关度从高到低排列
+    ///
+    /// 同一模型只出现一次，已安装的优先；相关度相同时按显示名称排序。空查询返回全部模型。
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let query_lower = query.trim().to_lowercase();
+        let installed = self.installed_models.iter().map(|m| (&m.model, LocalStatus::Installed));
+        let available = self.available_models.iter().map(|m| (&m.model, LocalStatus::Registered));
+
+        let mut hits: Vec<SearchHit> = Vec::new();
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        for (model, availability) in installed.chain(available) {
+            if seen.contains(&model.id) {
+                continue;
+            }
+            if let Some(relevance) = search_relevance(model, &query_lower) {
+                seen.insert(model.id);
+                hits.push(SearchHit { model: model.clone(), availability, relevance });
+            }
+        }
+
+        hits.sort_by(|a, b| {
+            b.relevance.total_cmp(&a.relevance)
+                .then_with(|| a.model.display_name.to_lowercase().cmp(&b.model.display_name.to_lowercase()))
+        });
+        hits
+    }
+
     /// 根据ID获取已安装模型
     pub fn get_installed_model_by_id(&self, id: &Uuid) -> Option<&InstalledModel> {
         self.installed_models
@@ -135,14 +210,14 @@ impl ModelDataService {
     }
 
     /// 安装模型（从可用模型列表）
-    pub fn install_model(&mut self, model_id: &Uuid, install_path: String) -> Result<(), String> {
+    pub async fn install_model(&mut self, model_id: &Uuid, install_path: String) -> Result<(), ClientError> {
         // 查找可用模型
         let available_model = self.get_available_model_by_id(model_id)
-            .ok_or("模型不存在")?;
+            .ok_or_else(|| ClientError::ValidationFailed("模型不存在".to_string()))?;
 
         // 检查是否已安装
         if self.get_installed_model_by_id(model_id).is_some() {
-            return Err("模型已安装".to_string());
+            return Err(ClientError::OperationNotAllowed("模型已安装".to_string()));
         }
 
         // 创建已安装模型
@@ -166,64 +241,103 @@ impl ModelDataService {
     }
 
     /// 卸载模型
-    pub fn uninstall_model(&mut self, model_id: &Uuid) -> Result<(), String> {
+    pub async fn uninstall_model(&mut self, model_id: &Uuid) -> Result<(), ClientError> {
         // 查找并删除已安装模型
         let index = self.installed_models
             .iter()
             .position(|model| &model.model.id == model_id)
-            .ok_or("模型未安装")?;
+            .ok_or_else(|| ClientError::ValidationFailed("模型未安装".to_string()))?;
 
         let removed_model = self.installed_models.remove(index);
 
         // 检查模型是否在运行
         if matches!(removed_model.status, ModelStatus::Running) {
-            return Err("请先停止模型再卸载".to_string());
+            return Err(ClientError::OperationNotAllowed("请先停止模型再卸载".to_string()));
         }
 
         Ok(())
     }
 
     /// 启动模型
-    pub fn start_model(&mut self, model_id: &Uuid, port: u16) -> Result<(), String> {
+    pub async fn start_model(&mut self, model_id: &Uuid, port: u16) -> Result<(), ClientError> {
+        let model = self.begin_start(model_id, port)?;
+
+        // 模拟启动过程
+        model.status = ModelStatus::Running;
+        model.process_id = Some((rand::random::<u64>() % 65536 + 1000) as u32); // 模拟进程ID
+
+        Ok(())
+    }
+
+    /// 启动模型并等待其端口可以连接
+    ///
+    /// 记录 `Starting` 后轮询 `127.0.0.1:port`，连接成功才转为 `Running`；
+    /// 超过 `timeout` 仍未就绪则转为 `Error` 并返回错误。每次状态变化都通过 `on_status` 通知，
+    /// 便于界面显示启动中的动画。
+    pub async fn start_model_when_ready(
+        &mut self,
+        model_id: &Uuid,
+        port: u16,
+        timeout: Duration,
+        mut on_status: impl FnMut(ModelStatus),
+    ) -> Result<(), ClientError> {
+        self.begin_start(model_id, port)?;
+        on_status(ModelStatus::Starting);
+
+        let ready = wait_for_port(port, timeout).await;
+        let model = self.installed_models
+            .iter_mut()
+            .find(|model| &model.model.id == model_id)
+            .ok_or_else(|| ClientError::ValidationFailed("模型未安装".to_string()))?;
+
+        if ready {
+            model.status = ModelStatus::Running;
+            model.process_id = Some((rand::random::<u64>() % 65536 + 1000) as u32); // 模拟进程ID
+            on_status(ModelStatus::Running);
+            Ok(())
+        } else {
+            model.status = ModelStatus::Error;
+            on_status(ModelStatus::Error);
+            Err(ClientError::OperationNotAllowed(format!("模型在 {} 秒内未能在端口 {} 上就绪", timeout.as_secs(), port)))
+        }
+    }
+
+    /// 检查能否启动并将模型标记为 `Starting`
+    fn begin_start(&mut self, model_id: &Uuid, port: u16) -> Result<&mut InstalledModel, ClientError> {
         // 先检查端口是否被占用
         if self.installed_models
             .iter()
             .any(|m| m.port == Some(port) && matches!(m.status, ModelStatus::Running))
         {
-            return Err(format!("端口 {} 已被占用", port));
+            return Err(ClientError::OperationNotAllowed(format!("端口 {} 已被占用", port)));
         }
 
         let model = self.installed_models
             .iter_mut()
             .find(|model| &model.model.id == model_id)
-            .ok_or("模型未安装")?;
+            .ok_or_else(|| ClientError::ValidationFailed("模型未安装".to_string()))?;
 
         match model.status {
-            ModelStatus::Running => return Err("模型已在运行".to_string()),
-            ModelStatus::Starting => return Err("模型正在启动".to_string()),
+            ModelStatus::Running => return Err(ClientError::OperationNotAllowed("模型已在运行".to_string())),
+            ModelStatus::Starting => return Err(ClientError::OperationNotAllowed("模型正在启动".to_string())),
             _ => {}
         }
 
         model.status = ModelStatus::Starting;
         model.port = Some(port);
-
-        // 模拟启动过程
-        model.status = ModelStatus::Running;
-        model.process_id = Some((rand::random::<u64>() % 65536 + 1000) as u32); // 模拟进程ID
-
-        Ok(())
+        Ok(model)
     }
 
     /// 停止模型
-    pub fn stop_model(&mut self, model_id: &Uuid) -> Result<(), String> {
+    pub async fn stop_model(&mut self, model_id: &Uuid) -> Result<(), ClientError> {
         let model = self.installed_models
             .iter_mut()
             .find(|model| &model.model.id == model_id)
-            .ok_or("模型未安装")?;
+            .ok_or_else(|| ClientError::ValidationFailed("模型未安装".to_string()))?;
 
         match model.status {
-            ModelStatus::Stopped => return Err("模型已停止".to_string()),
-            ModelStatus::Stopping => return Err("模型正在停止".to_string()),
+            ModelStatus::Stopped => return Err(ClientError::OperationNotAllowed("模型已停止".to_string())),
+            ModelStatus::Stopping => return Err(ClientError::OperationNotAllowed("模型正在停止".to_string())),
             _ => {}
         }
 
@@ -302,18 +416,98 @@ impl ModelDataService {
     }
 }
 
+/// 模型与查询词（已转小写）的相关度，不匹配时返回 `None`
+///
+/// 名称完全一致最相关，其次是名称前缀、名称或显示名称包含、提供商包含，最后是描述包含。
+fn search_relevance(model: &Model, query_lower: &str) -> Option<f32> {
+    if query_lower.is_empty() {
+        return Some(0.0);
+    }
+    let name = model.name.to_lowercase();
+    if name == query_lower {
+        Some(1.0)
+    } else if name.starts_with(query_lower) {
+        Some(0.8)
+    } else if name.contains(query_lower) || model.display_name.to_lowercase().contains(query_lower) {
+        Some(0.6)
+    } else if model.provider.to_lowercase().contains(query_lower) {
+        Some(0.4)
+    } else if model.description.as_deref().is_some_and(|d| d.to_lowercase().contains(query_lower)) {
+        Some(0.2)
+    } else {
+        None
+    }
+}
+
+/// 已安装模型的名称、显示名称、描述或提供方是否包含 `query_lower`（已转为小写）
+pub(crate) fn installed_model_matches(model: &InstalledModel, query_lower: &str) -> bool {
+    model.model.name.to_lowercase().contains(query_lower)
+        || model.model.display_name.to_lowercase().contains(query_lower)
+        || model.model.description
+            .as_ref()
+            .map(|desc| desc.to_lowercase().contains(query_lower))
+            .unwrap_or(false)
+        || model.model.provider.to_lowercase().contains(query_lower)
+}
+
+#[async_trait]
+impl ModelDataSource for ModelDataService {
+    async fn installed_models(&self) -> Result<Vec<InstalledModel>, ClientError> {
+        Ok(self.installed_models.clone())
+    }
+
+    async fn available_models(&self) -> Result<Vec<AvailableModel>, ClientError> {
+        Ok(self.available_models.clone())
+    }
+
+    async fn install_model(&mut self, model_id: Uuid, install_path: String) -> Result<(), ClientError> {
+        ModelDataService::install_model(self, &model_id, install_path).await
+    }
+
+    async fn uninstall_model(&mut self, model_id: Uuid) -> Result<(), ClientError> {
+        ModelDataService::uninstall_model(self, &model_id).await
+    }
+
+    async fn start_model(&mut self, model_id: Uuid, port: u16) -> Result<(), ClientError> {
+        ModelDataService::start_model(self, &model_id, port).await
+    }
+
+    async fn stop_model(&mut self, model_id: Uuid) -> Result<(), ClientError> {
+        ModelDataService::stop_model(self, &model_id).await
+    }
+
+    /// 克隆每个匹配的模型（含完整配置），结果较多时优先用借用的 `search_models`
+    async fn search_installed_models(&self, query: &str) -> Result<Vec<InstalledModel>, ClientError> {
+        Ok(self.search_models(query).into_iter().cloned().collect())
+    }
+}
+
+/// 在 `timeout` 内反复尝试连接本机端口，能连上时返回 true
+async fn wait_for_port(port: u16, timeout: Duration) -> bool {
+    let poll = async {
+        loop {
+            if tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_ok() {
+                return;
+            }
+            tokio::time::sleep(READINESS_POLL_INTERVAL).await;
+        }
+    };
+    tokio::time::timeout(timeout, poll).await.is_ok()
+}
+
 /// 模型使用统计
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ModelUsageStats {
     pub total_models: usize,
     pub running_models: usize,
     pub stopped_models: usize,
     pub total_usage_count: u64,
+    #[serde(serialize_with = "serialize_type_counts")]
     pub models_by_type: HashMap<ModelType, usize>,
 }
 
 /// 资源使用概览
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ResourceOverview {
     pub total_disk_usage_bytes: u64,
     pub ports_in_use: Vec<u16>,
@@ -1,16 +1,87 @@
 // 模型发现和搜索API模块
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use futures_util::stream::{self, Stream, TryStreamExt};
 use crate::validation::ChecksumType;
 
+/// 响应缓存最多保留的条目数
+const RESPONSE_CACHE_CAPACITY: usize = 256;
+
 /// 模型发现客户端
 pub struct ModelDiscoveryClient {
     base_url: String,
     timeout: std::time::Duration,
+    timeouts: DiscoveryTimeouts,
     client: reqwest::Client,
+    /// 离线模式下不发出任何请求，只返回缓存的结果
+    offline: bool,
+    cache: Mutex<ResponseCache>,
+    /// 缓存写入的文件，设置后离线模式在进程重启后仍能返回此前的结果
+    cache_file: Option<PathBuf>,
+}
+
+/// 成功响应的内存缓存，超出容量时淘汰最早写入的条目
+#[derive(Debug, Default)]
+struct ResponseCache {
+    entries: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+impl ResponseCache {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    fn insert(&mut self, key: String, body: String) {
+        if self.entries.insert(key.clone(), body).is_none() {
+            self.order.push_back(key);
+            if self.order.len() > RESPONSE_CACHE_CAPACITY {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    /// 读取保存的缓存，文件不存在或已损坏时为空缓存
+    fn load(path: &Path) -> Self {
+        let mut cache = Self::default();
+        let saved: Vec<(String, String)> = std::fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+        for (key, body) in saved {
+            cache.insert(key, body);
+        }
+        cache
+    }
+
+    /// 按写入顺序保存缓存，先写临时文件再替换，中途失败不会留下半个文件
+    fn save(&self, path: &Path) -> std::io::Result<()> {
+        let saved: Vec<(&String, &String)> = self.order
+            .iter()
+            .filter_map(|key| self.entries.get_key_value(key))
+            .collect();
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(&saved)?)?;
+        std::fs::rename(&temp_path, path)
+    }
+}
+
+/// 按调用类型覆盖的超时时间，未设置的项使用客户端的默认超时
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryTimeouts {
+    /// 搜索请求（结果页可能较大）
+    pub search: Option<std::time::Duration>,
+    /// 模型详情、分类、标签、提供商等查询
+    pub details: Option<std::time::Duration>,
+    /// 可用性检查（HEAD 请求）
+    pub availability: Option<std::time::Duration>,
 }
 
 /// 模型搜索请求
@@ -65,6 +136,9 @@ pub struct DiscoveredModel {
     pub is_verified: bool,
     pub repository_url: Option<String>,
     pub documentation_url: Option<String>,
+    /// 需要先安装的配套模型（如重排序模型依赖的嵌入模型）
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
 }
 
 /// 模型类型
@@ -122,6 +196,8 @@ pub enum DiscoveryError {
     TimeoutError,
     #[error("配置错误: {0}")]
     ConfigError(String),
+    #[error("离线模式下没有缓存的结果: {0}")]
+    Offline(String),
 }
 
 impl ModelDiscoveryClient {
@@ -134,26 +210,75 @@ impl ModelDiscoveryClient {
         Ok(Self {
             base_url,
             timeout: std::time::Duration::from_secs(30),
+            timeouts: DiscoveryTimeouts::default(),
             client,
+            offline: false,
+            cache: Mutex::new(ResponseCache::default()),
+            cache_file: None,
         })
     }
 
+    /// 把响应缓存保存到 `path`，并载入其中已有的结果
+    ///
+    /// 保存失败不影响请求本身，只是下次启动时少了这些结果。
+    pub fn with_cache_file(mut self, path: PathBuf) -> Self {
+        self.cache = Mutex::new(ResponseCache::load(&path));
+        self.cache_file = Some(path);
+        self
+    }
+
+    /// 设置离线模式：不发出网络请求，只返回此前缓存的结果
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// 是否处于离线模式
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
     /// 设置请求超时时间
     pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
-    /// 搜索模型
-    pub async fn search_models(&self, request: ModelSearchRequest) -> Result<ModelSearchResponse, DiscoveryError> {
-        let url = format!("{}/api/v1/models/search", self.base_url);
+    /// 按调用类型设置超时时间
+    pub fn with_timeouts(mut self, timeouts: DiscoveryTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
 
-        let response = self.client
-            .post(&url)
-            .timeout(self.timeout)
-            .json(&request)
+    /// 发送请求，超时时返回 `TimeoutError`
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<reqwest::Response, DiscoveryError> {
+        request
+            .timeout(timeout.unwrap_or(self.timeout))
             .send()
-            .await?;
+            .await
+            .map_err(DiscoveryError::from_request)
+    }
+
+    /// 发送请求并解析 JSON 响应，成功的响应会被缓存
+    ///
+    /// 离线模式下直接读取缓存，未命中时返回 `Offline`。
+    async fn fetch_json<T: serde::de::DeserializeOwned>(
+        &self,
+        cache_key: String,
+        request: reqwest::RequestBuilder,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<T, DiscoveryError> {
+        if self.offline {
+            let cache = self.cache.lock().unwrap();
+            let body = cache.get(&cache_key).ok_or(DiscoveryError::Offline(cache_key.clone()))?;
+            return Ok(serde_json::from_str(body)?);
+        }
+
+        let response = self.send(request, timeout).await?;
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
@@ -161,8 +286,102 @@ impl ModelDiscoveryClient {
             return Err(DiscoveryError::ApiError { status, message });
         }
 
-        let search_response: ModelSearchResponse = response.json().await?;
-        Ok(search_response)
+        let body = response.text().await.map_err(DiscoveryError::from_request)?;
+        let value = serde_json::from_str(&body)?;
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert(cache_key, body);
+        if let Some(path) = &self.cache_file {
+            let _ = cache.save(path);
+        }
+        Ok(value)
+    }
+
+    /// 离线模式下拒绝需要联网的检查
+    fn ensure_online(&self, what: &str) -> Result<(), DiscoveryError> {
+        if self.offline {
+            return Err(DiscoveryError::Offline(what.to_string()));
+        }
+        Ok(())
+    }
+
+    /// 搜索模型
+    pub async fn search_models(&self, request: ModelSearchRequest) -> Result<ModelSearchResponse, DiscoveryError> {
+        let url = format!("{}/api/v1/models/search", self.base_url);
+        let cache_key = format!("POST {} {}", url, serde_json::to_string(&request)?);
+
+        self.fetch_json(cache_key, self.client.post(&url).json(&request), self.timeouts.search).await
+    }
+
+    /// 分页遍历搜索结果，每次产出一页模型
+    pub fn search_models_paginated(
+        &self,
+        request: ModelSearchRequest,
+    ) -> impl Stream<Item = Result<Vec<DiscoveredModel>, DiscoveryError>> + '_ {
+        let first_page = request.page.unwrap_or(1);
+        stream::try_unfold(Some((request, first_page)), move |state| async move {
+            let Some((mut request, page)) = state else {
+                return Ok(None);
+            };
+
+            request.page = Some(page);
+            let response = self.search_models(request.clone()).await?;
+            let next = if response.has_next && !response.models.is_empty() {
+                Some((request, page + 1))
+            } else {
+                None
+            };
+
+            Ok(Some((response.models, next)))
+        })
+    }
+
+    /// 获取指定提供商的全部模型（自动遍历所有分页）
+    pub async fn search_by_provider(
+        &self,
+        provider: &str,
+        page_size: Option<u32>,
+    ) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
+        let request = ModelSearchRequest {
+            provider: Some(provider.to_string()),
+            page: Some(1),
+            page_size: page_size.or(Some(50)),
+            sort_by: Some(SortBy::Name),
+            sort_order: Some(SortOrder::Asc),
+            ..Default::default()
+        };
+
+        let pages: Vec<Vec<DiscoveredModel>> = self.search_models_paginated(request).try_collect().await?;
+        Ok(pages
+            .into_iter()
+            .flatten()
+            .filter(|m| m.provider.eq_ignore_ascii_case(provider))
+            .collect())
+    }
+
+    /// 按能力筛选模型
+    ///
+    /// 服务端对 `capabilities` 过滤的支持程度不一，因此结果会在本地再做一次交集检查：
+    /// `match_all` 为 true 时要求包含全部能力，否则包含任意一项即可。
+    pub async fn search_by_capabilities(
+        &self,
+        caps: &[String],
+        match_all: bool,
+    ) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
+        let request = ModelSearchRequest {
+            capabilities: Some(caps.to_vec()),
+            page: Some(1),
+            page_size: Some(50),
+            sort_by: Some(SortBy::Rating),
+            sort_order: Some(SortOrder::Desc),
+            ..Default::default()
+        };
+
+        let pages: Vec<Vec<DiscoveredModel>> = self.search_models_paginated(request).try_collect().await?;
+        Ok(pages
+            .into_iter()
+            .flatten()
+            .filter(|m| m.has_capabilities(caps, match_all))
+            .collect())
     }
 
     /// 获取热门模型
@@ -185,6 +404,38 @@ impl ModelDiscoveryClient {
         Ok(response.models.into_iter().filter(|m| m.is_featured).collect())
     }
 
+    /// 获取最近发布的模型，按 `last_updated` 降序排列
+    ///
+    /// 指定 `since` 时只保留该时间之后更新的模型；服务端排序不可靠时会在本地重新排序。
+    pub async fn get_recent_models(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: Option<u32>,
+    ) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
+        let limit = limit.unwrap_or(20) as usize;
+        let request = ModelSearchRequest {
+            page: Some(1),
+            page_size: Some(limit as u32),
+            sort_by: Some(SortBy::LastUpdated),
+            sort_order: Some(SortOrder::Desc),
+            ..Default::default()
+        };
+
+        let mut models = Vec::new();
+        let mut pages = std::pin::pin!(self.search_models_paginated(request));
+        while let Some(page) = pages.try_next().await? {
+            let reached_since = since.is_some_and(|s| page.iter().any(|m| m.last_updated <= s));
+            models.extend(page.into_iter().filter(|m| since.is_none_or(|s| m.last_updated > s)));
+            if reached_since || models.len() >= limit {
+                break;
+            }
+        }
+
+        models.sort_by_key(|m| std::cmp::Reverse(m.last_updated));
+        models.truncate(limit);
+        Ok(models)
+    }
+
     /// 根据模型类型获取推荐模型
     pub async fn get_recommended_by_type(&self, model_type: ModelType, limit: Option<u32>) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
         let request = ModelSearchRequest {
@@ -209,31 +460,61 @@ impl ModelDiscoveryClient {
     pub async fn get_model_details(&self, model_id: Uuid) -> Result<DiscoveredModel, DiscoveryError> {
         let url = format!("{}/api/v1/models/{}", self.base_url, model_id);
 
-        let response = self.client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await?;
+        self.fetch_json(format!("GET {}", url), self.client.get(&url), self.timeouts.details).await
+    }
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(DiscoveryError::ApiError { status, message });
+    /// 获取与指定模型相似的模型，最多 `limit` 个
+    ///
+    /// 服务端没有相似模型接口（404/405/501）时在本地排序：从同类型和同提供商的模型中，
+    /// 按共同标签、类型、提供商计算相似度，相同时评分高的在前。
+    pub async fn get_similar_models(&self, model_id: Uuid, limit: usize) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
+        let url = format!("{}/api/v1/models/{}/similar", self.base_url, model_id);
+        let request = self.client.get(&url).query(&[("limit", limit)]);
+
+        match self.fetch_json::<Vec<DiscoveredModel>>(format!("GET {}?limit={}", url, limit), request, self.timeouts.search).await {
+            Ok(mut models) => {
+                models.retain(|m| m.id != model_id);
+                models.truncate(limit);
+                Ok(models)
+            }
+            Err(DiscoveryError::ApiError { status: 404 | 405 | 501, .. }) => self.rank_similar_locally(model_id, limit).await,
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 相似模型接口不可用时的本地排序
+    async fn rank_similar_locally(&self, model_id: Uuid, limit: usize) -> Result<Vec<DiscoveredModel>, DiscoveryError> {
+        let target = self.get_model_details(model_id).await?;
+        let requests = [
+            ModelSearchRequest { model_type: Some(target.model_type.clone()), page_size: Some(50), ..Default::default() },
+            ModelSearchRequest { provider: Some(target.provider.clone()), page_size: Some(50), ..Default::default() },
+        ];
+
+        let mut candidates: Vec<(f64, DiscoveredModel)> = Vec::new();
+        for request in requests {
+            for model in self.search_models(request).await?.models {
+                if model.id == target.id || candidates.iter().any(|(_, m)| m.id == model.id) {
+                    continue;
+                }
+                let score = similarity_score(&target, &model);
+                if score > 0.0 {
+                    candidates.push((score, model));
+                }
+            }
         }
 
-        let model: DiscoveredModel = response.json().await?;
-        Ok(model)
+        candidates.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.total_cmp(a_score).then_with(|| b.rating.total_cmp(&a.rating))
+        });
+        Ok(candidates.into_iter().take(limit).map(|(_, model)| model).collect())
     }
 
     /// 检查模型可用性
     pub async fn check_model_availability(&self, model_id: Uuid) -> Result<bool, DiscoveryError> {
+        self.ensure_online("availability check")?;
         let url = format!("{}/api/v1/models/{}/availability", self.base_url, model_id);
 
-        let response = self.client
-            .head(&url)
-            .timeout(self.timeout)
-            .send()
-            .await?;
+        let response = self.send(self.client.head(&url), self.timeouts.availability).await?;
 
         Ok(response.status().is_success())
     }
@@ -242,60 +523,77 @@ impl ModelDiscoveryClient {
     pub async fn get_category_stats(&self) -> Result<HashMap<ModelType, u64>, DiscoveryError> {
         let url = format!("{}/api/v1/models/categories/stats", self.base_url);
 
-        let response = self.client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(DiscoveryError::ApiError { status, message });
-        }
-
-        let stats: HashMap<ModelType, u64> = response.json().await?;
-        Ok(stats)
+        self.fetch_json(format!("GET {}", url), self.client.get(&url), self.timeouts.details).await
     }
 
     /// 获取所有可用的标签
     pub async fn get_available_tags(&self) -> Result<Vec<String>, DiscoveryError> {
         let url = format!("{}/api/v1/models/tags", self.base_url);
 
-        let response = self.client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await?;
+        self.fetch_json(format!("GET {}", url), self.client.get(&url), self.timeouts.details).await
+    }
+
+    /// 检查发现服务是否可达（请求提供商列表）
+    pub async fn ping(&self) -> Result<(), DiscoveryError> {
+        self.ensure_online("ping")?;
+        let url = format!("{}/api/v1/models/providers", self.base_url);
+        let response = self.send(self.client.get(&url), self.timeouts.availability).await?;
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
             let message = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
             return Err(DiscoveryError::ApiError { status, message });
         }
-
-        let tags: Vec<String> = response.json().await?;
-        Ok(tags)
+        Ok(())
     }
 
     /// 获取所有支持的提供商
     pub async fn get_providers(&self) -> Result<Vec<String>, DiscoveryError> {
         let url = format!("{}/api/v1/models/providers", self.base_url);
 
-        let response = self.client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await?;
+        self.fetch_json(format!("GET {}", url), self.client.get(&url), self.timeouts.details).await
+    }
+}
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let message = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(DiscoveryError::ApiError { status, message });
+/// 本地相似度：每个共同标签（不区分大小写）计 1 分，同类型计 2 分，同提供商计 1 分
+fn similarity_score(target: &DiscoveredModel, candidate: &DiscoveredModel) -> f64 {
+    let shared_tags = candidate.tags.iter()
+        .filter(|tag| target.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        .count();
+    let mut score = shared_tags as f64;
+    if candidate.model_type == target.model_type {
+        score += 2.0;
+    }
+    if candidate.provider.eq_ignore_ascii_case(&target.provider) {
+        score += 1.0;
+    }
+    score
+}
+
+impl DiscoveryError {
+    /// 将请求错误转换为发现错误，区分超时
+    fn from_request(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            DiscoveryError::TimeoutError
+        } else {
+            DiscoveryError::NetworkError(error)
+        }
+    }
+}
+
+impl DiscoveredModel {
+    /// 检查模型是否具备指定能力（忽略大小写）
+    pub fn has_capabilities(&self, caps: &[String], match_all: bool) -> bool {
+        if caps.is_empty() {
+            return true;
         }
 
-        let providers: Vec<String> = response.json().await?;
-        Ok(providers)
+        let has = |cap: &String| self.capabilities.iter().any(|c| c.eq_ignore_ascii_case(cap));
+        if match_all {
+            caps.iter().all(has)
+        } else {
+            caps.iter().any(has)
+        }
     }
 }
 
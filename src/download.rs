@@ -1,20 +1,56 @@
 // 模型下载和安装功能模块
 
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use sha2::{Sha256, Digest};
-use md5;
+use std::sync::Arc;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use crate::checksum::{digest_file, hash_file_into, strip_checksum_prefix, Checksummer, StreamingHasher};
+use crate::download_queue::{DownloadPriority, DownloadQueue};
 use crate::validation::ChecksumType;
 
+/// 每累计这么长时间的下载计算一次速度样本
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+/// 保留的速度样本数
+const SPEED_HISTORY_LEN: usize = 20;
+/// 平滑速度的 EMA 系数，越小越平稳
+const SPEED_SMOOTHING: f64 = 0.2;
+/// 向进度通道发送快照的最短间隔
+const PROGRESS_REPORT_INTERVAL: Duration = Duration::from_millis(250);
+/// 在 `.meta` 中记录已下载字节数的最短间隔
+const RESUME_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+/// 默认的传输中断后重试次数
+pub const DEFAULT_MAX_RETRIES: usize = 3;
+/// 第一次重试前的等待时间，之后每次翻倍
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+/// 默认下载后至少保留的可用磁盘空间（1GB）
+pub const DEFAULT_MIN_FREE_RESERVE: u64 = 1024 * 1024 * 1024;
+
 /// 模型下载管理器
+///
+/// 克隆的管理器共用同一个下载队列。
+#[derive(Clone)]
 pub struct ModelDownloadManager {
     download_dir: PathBuf,
     temp_dir: PathBuf,
-    max_concurrent_downloads: usize,
+    queue: Arc<DownloadQueue>,
+    mirror_region: Option<String>,
+    /// 离线模式下拒绝网络下载，只能从本地文件或缓存安装
+    offline: bool,
+    /// 下载完成后磁盘上至少保留的可用空间
+    min_free_reserve: u64,
+    /// 连接失败、超时或传输中断后的最大重试次数
+    max_retries: usize,
+    /// 所有下载共用的限速器，`None` 表示不限速
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// 进行中下载的取消令牌，`cancel_download` 据此中断传输
+    transfers: Arc<std::sync::Mutex<HashMap<Uuid, CancellationToken>>>,
     client: reqwest::Client,
 }
 
@@ -27,10 +63,212 @@ pub struct DownloadProgress {
     pub total_bytes: u64,
     pub downloaded_bytes: u64,
     pub progress_percent: f32,
+    /// 自开始以来的平均速度
     pub download_speed_bps: u64,
+    /// 最近一段时间的平滑速度，剩余时间据此估算
+    #[serde(default)]
+    pub current_speed_bps: u64,
     pub estimated_remaining_seconds: Option<u64>,
     pub started_at: DateTime<Utc>,
     pub error_message: Option<String>,
+    /// 实际完成下载的地址（主地址或某个镜像）
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// `total_bytes` 的来源
+    #[serde(default)]
+    pub total_source: TotalBytesSource,
+    /// 下载完成后计算出的实际校验和（十六进制）
+    #[serde(default)]
+    pub actual_checksum: Option<String>,
+    /// 下载成功但需要用户留意的情况
+    #[serde(default)]
+    pub warnings: Vec<DownloadWarning>,
+}
+
+impl DownloadProgress {
+    /// 刚开始下载、尚无进度的状态
+    fn downloading(model_id: Uuid, model_name: &str) -> Self {
+        Self {
+            model_id,
+            model_name: model_name.to_string(),
+            status: DownloadStatus::Downloading,
+            total_bytes: 0,
+            downloaded_bytes: 0,
+            progress_percent: 0.0,
+            download_speed_bps: 0,
+            current_speed_bps: 0,
+            estimated_remaining_seconds: None,
+            started_at: Utc::now(),
+            error_message: None,
+            source_url: None,
+            total_source: TotalBytesSource::Unknown,
+            actual_checksum: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 是否需要用户确认信任该文件（未经校验和比对）
+    pub fn needs_trust_confirmation(&self) -> bool {
+        self.warnings.contains(&DownloadWarning::UnverifiedChecksum)
+    }
+}
+
+/// 中断的下载的续传信息，保存在临时目录的 `<model_id>.meta` 中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PartialDownload {
+    model_name: String,
+    download_url: String,
+    /// 期望的总大小，未知时为 0
+    total_bytes: u64,
+    total_source: TotalBytesSource,
+    /// 不带前缀的十六进制校验和
+    expected_checksum: Option<String>,
+    /// 校验算法名称，见 `Checksummer::name`
+    checksum_algorithm: String,
+    /// 已确认写入临时文件的字节数；旧版本写入的记录中没有此项
+    #[serde(default)]
+    downloaded_bytes: Option<u64>,
+}
+
+impl PartialDownload {
+    /// 按记录的算法名称找回校验算法
+    fn checksummer(&self) -> Result<Arc<dyn Checksummer>, DownloadError> {
+        [ChecksumType::MD5, ChecksumType::SHA256, ChecksumType::SHA512]
+            .into_iter()
+            .map(|checksum_type| checksum_type.checksummer())
+            .find(|checksummer| checksummer.name() == self.checksum_algorithm)
+            .ok_or_else(|| DownloadError::ConfigError(format!("无法恢复使用 {} 校验的下载", self.checksum_algorithm)))
+    }
+}
+
+/// 下载速度记录
+///
+/// 按固定间隔把下载量折算为速度样本，保留最近的样本作为历史，并用指数移动平均
+/// 得到平滑速度，避免网络波动时剩余时间忽高忽低。
+#[derive(Debug, Clone, Default)]
+pub struct SpeedTracker {
+    history: VecDeque<u64>,
+    smoothed_bps: Option<f64>,
+    pending_bytes: u64,
+    pending_time: Duration,
+}
+
+impl SpeedTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录在 `elapsed` 时间内下载的 `bytes` 字节
+    pub fn record(&mut self, bytes: u64, elapsed: Duration) {
+        self.pending_bytes += bytes;
+        self.pending_time += elapsed;
+        if self.pending_time < SPEED_SAMPLE_INTERVAL {
+            return;
+        }
+
+        let rate = self.pending_bytes as f64 / self.pending_time.as_secs_f64();
+        self.pending_bytes = 0;
+        self.pending_time = Duration::ZERO;
+
+        if self.history.len() == SPEED_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(rate as u64);
+        self.smoothed_bps = Some(match self.smoothed_bps {
+            Some(previous) => SPEED_SMOOTHING * rate + (1.0 - SPEED_SMOOTHING) * previous,
+            None => rate,
+        });
+    }
+
+    /// 平滑后的速度（字节/秒），尚无样本时为 0
+    pub fn current_speed_bps(&self) -> u64 {
+        self.smoothed_bps.unwrap_or(0.0) as u64
+    }
+
+    /// 最近的速度样本（字节/秒），从旧到新
+    pub fn history(&self) -> impl Iterator<Item = u64> + '_ {
+        self.history.iter().copied()
+    }
+
+    /// 按平滑速度估算剩余秒数，尚无速度时返回 `None`
+    pub fn estimate_remaining_seconds(&self, remaining_bytes: u64) -> Option<u64> {
+        match self.current_speed_bps() {
+            0 => None,
+            speed => Some(remaining_bytes / speed),
+        }
+    }
+}
+
+/// 令牌桶限速器
+///
+/// 令牌按限速匀速补充，空闲时最多积攒一秒的量；取用超过余额时记为欠额，调用方等到
+/// 欠额补足再继续，因此长时间的平均速度不会超过限速。
+#[derive(Debug)]
+struct RateLimiter {
+    bytes_per_sec: u64,
+    /// 当前余额（可为负）和上次补充的时间
+    bucket: std::sync::Mutex<(f64, std::time::Instant)>,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec,
+            bucket: std::sync::Mutex::new((0.0, std::time::Instant::now())),
+        }
+    }
+
+    /// 取用 `bytes` 个令牌，余额不足时等待
+    async fn acquire(&self, bytes: u64) {
+        let rate = self.bytes_per_sec as f64;
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let (tokens, refilled_at) = &mut *bucket;
+            let now = std::time::Instant::now();
+            *tokens = (*tokens + (now - *refilled_at).as_secs_f64() * rate).min(rate);
+            *refilled_at = now;
+            *tokens -= bytes as f64;
+            if *tokens < 0.0 {
+                Duration::from_secs_f64(-*tokens / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// 进行中的下载结束时注销其取消令牌
+struct TransferGuard<'a> {
+    manager: &'a ModelDownloadManager,
+    model_id: Uuid,
+}
+
+impl Drop for TransferGuard<'_> {
+    fn drop(&mut self) {
+        self.manager.transfers.lock().unwrap().remove(&self.model_id);
+    }
+}
+
+/// 下载总大小的来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TotalBytesSource {
+    /// 服务器返回的 `content-length`
+    Header,
+    /// 服务器未返回大小时，使用模型已知的文件大小估算
+    Estimate,
+    /// 大小未知，`total_bytes` 为 0
+    #[default]
+    Unknown,
+}
+
+/// 下载完成时的警告
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownloadWarning {
+    /// 未提供期望校验和，只记录了实际校验和；首次使用前应由用户确认是否信任
+    UnverifiedChecksum,
 }
 
 /// 下载状态
@@ -55,6 +293,15 @@ pub struct InstallationConfig {
     pub install_dependencies: bool,
     pub enable_gpu: bool,
     pub custom_install_path: Option<PathBuf>,
+    /// 期望的文件大小，未指定时以源文件大小为准
+    #[serde(default)]
+    pub expected_file_size: Option<u64>,
+    /// 期望的校验和（可带 `sha256:` 前缀），未指定时以源文件校验和为准
+    #[serde(default)]
+    pub expected_checksum: Option<String>,
+    /// 安装后在 `downloads/` 缓存中保留原始下载文件，供之后重新安装
+    #[serde(default)]
+    pub keep_download: bool,
 }
 
 /// 模型安装信息
@@ -68,6 +315,31 @@ pub struct ModelInstallation {
     pub checksum: String,
     pub dependencies: Vec<String>,
     pub metadata: InstallationMetadata,
+    #[serde(default)]
+    pub verification: Option<InstallVerification>,
+    /// 保留在缓存中的原始下载文件
+    #[serde(default)]
+    pub cached_download: Option<PathBuf>,
+}
+
+/// 下载缓存记录，保存在缓存目录的 `cache.json` 中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedDownload {
+    pub path: PathBuf,
+    pub file_size: u64,
+    /// SHA256
+    pub checksum: String,
+    pub cached_at: DateTime<Utc>,
+}
+
+/// 安装后校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallVerification {
+    pub expected_size: u64,
+    pub actual_size: u64,
+    /// 是否校验了校验和（仅在 `auto_verify` 开启时）
+    pub checksum_verified: bool,
+    pub verified_at: DateTime<Utc>,
 }
 
 /// 安装元数据
@@ -89,6 +361,8 @@ pub enum DownloadError {
     IoError(#[from] std::io::Error),
     #[error("校验失败: 期望 {expected}, 实际 {actual}")]
     ChecksumMismatch { expected: String, actual: String },
+    #[error("无效的校验和: {0}")]
+    MalformedChecksum(String),
     #[error("磁盘空间不足: 需要 {required} bytes, 可用 {available} bytes")]
     InsufficientSpace { required: u64, available: u64 },
     #[error("权限不足: {0}")]
@@ -97,6 +371,14 @@ pub enum DownloadError {
     ModelAlreadyExists(String),
     #[error("无效的URL: {0}")]
     InvalidUrl(String),
+    #[error("响应内容类型不是模型文件: {0}")]
+    UnexpectedContentType(String),
+    #[error("下载缓存不可用: {0}")]
+    CacheUnavailable(String),
+    #[error("离线模式下无法下载: {0}")]
+    Offline(String),
+    #[error("下载已取消: {0}")]
+    Cancelled(String),
     #[error("安装失败: {0}")]
     InstallationFailed(String),
     #[error("配置错误: {0}")]
@@ -121,106 +403,719 @@ impl ModelDownloadManager {
         Ok(Self {
             download_dir,
             temp_dir,
-            max_concurrent_downloads: 3,
+            queue: Arc::new(DownloadQueue::default()),
+            mirror_region: None,
+            offline: false,
+            min_free_reserve: DEFAULT_MIN_FREE_RESERVE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            rate_limiter: None,
+            transfers: Arc::default(),
             client,
         })
     }
 
+    /// 设置离线模式：拒绝网络下载，本地安装和 `reinstall_from_cache` 不受影响
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
     /// 设置最大并发下载数
     pub fn with_max_concurrent(mut self, max: usize) -> Self {
-        self.max_concurrent_downloads = max;
+        self.queue = Arc::new(DownloadQueue::new(max));
+        self
+    }
+
+    /// 使用给定的下载队列，多个管理器共用同一队列时共享并发名额和排队顺序
+    pub fn with_queue(mut self, queue: Arc<DownloadQueue>) -> Self {
+        self.queue = queue;
+        self
+    }
+
+    /// 设置连接失败、超时或传输中断后的最大重试次数（默认 3），传入 0 表示不重试
+    ///
+    /// 重试间隔从 1 秒起每次翻倍；已写入的部分会保留，服务器支持时只请求剩余内容。
+    /// HTTP 错误状态（如 404）不会重试。
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// 设置优先使用的镜像区域，主机名包含该字符串的地址会被最先尝试（如 `modelscope`）
+    pub fn with_mirror_region(mut self, region: impl Into<String>) -> Self {
+        self.mirror_region = Some(region.into());
         self
     }
 
+    /// 设置下载完成后磁盘上至少保留的可用空间（默认 1GB），传入 0 表示允许写满磁盘
+    pub fn with_min_free_reserve(mut self, bytes: u64) -> Self {
+        self.min_free_reserve = bytes;
+        self
+    }
+
+    /// 下载完成后磁盘上至少保留的可用空间
+    pub fn min_free_reserve(&self) -> u64 {
+        self.min_free_reserve
+    }
+
+    /// 限制下载速度（字节/秒），传入 0 表示不限速
+    ///
+    /// 限额由同一管理器（及其克隆）上同时进行的所有下载共享。
+    pub fn with_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.rate_limiter = (bytes_per_sec > 0).then(|| Arc::new(RateLimiter::new(bytes_per_sec)));
+        self
+    }
+
+    /// 当前的下载限速（字节/秒），不限速时为 `None`
+    pub fn rate_limit(&self) -> Option<u64> {
+        self.rate_limiter.as_ref().map(|limiter| limiter.bytes_per_sec)
+    }
+
     /// 获取下载目录
     pub fn download_dir(&self) -> &Path {
         &self.download_dir
     }
 
+    /// 下载队列，可查看、调整顺序或取消等待中的任务
+    pub fn queue(&self) -> &Arc<DownloadQueue> {
+        &self.queue
+    }
+
+    /// 正在进行（已占用并发名额）的下载数
+    pub fn active_download_count(&self) -> usize {
+        self.queue.active_count()
+    }
+
+    /// 按队列位置、并发上限和测得的速度估算下载的开始和完成时间
+    ///
+    /// 模型不在队列中或尚未测得下载速度时返回 `None`。
+    pub fn estimated_start_and_finish(&self, model_id: Uuid) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.queue.estimated_start_and_finish(model_id)
+    }
+
     /// 开始下载模型
+    ///
+    /// `expected_checksum` 为 `None` 时不做比对，只记录实际校验和并附带
+    /// `DownloadWarning::UnverifiedChecksum`。有多个候选地址时使用
+    /// `download_model_from_mirrors`，失败时会依次换用下一个镜像。
     pub async fn download_model(
         &self,
         model_id: Uuid,
         model_name: String,
         download_url: String,
-        expected_checksum: String,
+        expected_checksum: impl Into<Option<String>>,
         checksum_type: ChecksumType,
     ) -> Result<DownloadProgress, DownloadError> {
-        // 验证URL
-        let url = reqwest::Url::parse(&download_url)
-            .map_err(|_| DownloadError::InvalidUrl(download_url.clone()))?;
+        // 接收端直接丢弃，进度快照发送失败时忽略
+        let (tx, _) = mpsc::channel(1);
+        self.download_model_with_progress(model_id, model_name, download_url, expected_checksum, checksum_type, tx).await
+    }
 
-        // 检查磁盘空间
-        let temp_file_path = self.temp_dir.join(format!("{}.tmp", model_id));
-        self.check_disk_space(&temp_file_path, &download_url).await?;
+    /// 开始下载模型，并通过 `tx` 实时报告进度
+    ///
+    /// 下载过程中每秒最多发送约 4 次进度快照（百分比、速度、剩余时间）；通道已满时跳过
+    /// 该次快照，不会拖慢下载。结束时再发送一次最终快照：成功为 `Completed`，被
+    /// `cancel_download` 取消为 `Cancelled`，其他错误为 `Failed`，后两者带有 `error_message`。
+    /// 最终快照不等待通道空位，调用方可以在本方法返回后再读取；接收端被丢弃不影响下载本身。
+    pub async fn download_model_with_progress(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_url: String,
+        expected_checksum: impl Into<Option<String>>,
+        checksum_type: ChecksumType,
+        tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        // 下载前解析期望校验和，格式错误时无需浪费带宽
+        let (checksummer, expected_checksum) = resolve_expected_checksum(expected_checksum.into(), checksum_type)?;
 
-        // 创建下载进度
-        let mut progress = DownloadProgress {
+        let result = self.queued_download(
             model_id,
-            model_name: model_name.clone(),
-            status: DownloadStatus::Downloading,
-            total_bytes: 0,
-            downloaded_bytes: 0,
-            progress_percent: 0.0,
-            download_speed_bps: 0,
-            estimated_remaining_seconds: None,
-            started_at: Utc::now(),
-            error_message: None,
+            &model_name,
+            &[download_url],
+            expected_checksum.as_deref(),
+            checksummer.as_ref(),
+            DownloadPriority::Normal,
+            None,
+            Some(&tx),
+        ).await;
+        let last = match &result {
+            Ok(progress) => progress.clone(),
+            Err(e) => {
+                let mut failed = DownloadProgress::downloading(model_id, &model_name);
+                failed.status = match e {
+                    DownloadError::Cancelled(_) => DownloadStatus::Cancelled,
+                    _ => DownloadStatus::Failed,
+                };
+                failed.error_message = Some(e.to_string());
+                failed
+            }
         };
+        // 调用方可能在本方法返回后才开始读取，通道已满时在这里等待会永远挂起；
+        // 改由后台任务在通道空出后送达最终快照
+        if let Err(mpsc::error::TrySendError::Full(last)) = tx.try_send(last) {
+            tokio::spawn(async move {
+                let _ = tx.send(last).await;
+            });
+        }
+        result
+    }
+
+    /// 把下载加入队列并立即返回状态为 `Queued` 的进度
+    ///
+    /// 下载在后台进行，空出并发名额时开始；进度快照和最终结果通过 `tx` 发送，
+    /// 出错时最后一个快照的状态为 `Failed`（取消时为 `Cancelled`）并带有 `error_message`。
+    /// 离线模式和格式错误的校验和在入队前直接返回错误。须在 tokio 运行时中调用。
+    pub fn queue_download(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_url: String,
+        expected_checksum: impl Into<Option<String>>,
+        checksum_type: ChecksumType,
+        tx: mpsc::Sender<DownloadProgress>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        if self.offline {
+            return Err(DownloadError::Offline(model_name));
+        }
+        let expected_checksum = expected_checksum.into();
+        resolve_expected_checksum(expected_checksum.clone(), checksum_type.clone())?;
+
+        let mut queued = DownloadProgress::downloading(model_id, &model_name);
+        queued.status = DownloadStatus::Queued;
+
+        let manager = self.clone();
+        tokio::spawn(async move {
+            // 结果已作为最终快照发送
+            let _ = manager
+                .download_model_with_progress(model_id, model_name, download_url, expected_checksum, checksum_type, tx)
+                .await;
+        });
+        Ok(queued)
+    }
+
+    /// 使用自定义校验算法下载模型
+    ///
+    /// `expected_checksum` 可带与算法名一致的前缀（如 `blake3:...`）。
+    pub async fn download_model_with_checksummer(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_url: String,
+        expected_checksum: impl Into<Option<String>>,
+        checksummer: Arc<dyn Checksummer>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let expected_checksum = expected_checksum
+            .into()
+            .map(|expected| strip_checksum_prefix(checksummer.as_ref(), &expected).to_string());
+        self.queued_download(
+            model_id,
+            &model_name,
+            &[download_url],
+            expected_checksum.as_deref(),
+            checksummer.as_ref(),
+            DownloadPriority::Normal,
+            None,
+            None,
+        ).await
+    }
+
+    /// 下载已知文件大小的模型
+    ///
+    /// 服务器未返回 `content-length` 时以 `expected_size`（如发现服务或模型记录中的文件大小）
+    /// 作为下载总大小，用于计算进度和检查磁盘空间。
+    pub async fn download_model_with_expected_size(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_url: String,
+        expected_checksum: impl Into<Option<String>>,
+        checksum_type: ChecksumType,
+        expected_size: Option<u64>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let (checksummer, expected_checksum) = resolve_expected_checksum(expected_checksum.into(), checksum_type)?;
+        self.queued_download(
+            model_id,
+            &model_name,
+            &[download_url],
+            expected_checksum.as_deref(),
+            checksummer.as_ref(),
+            DownloadPriority::Normal,
+            expected_size.filter(|size| *size > 0),
+            None,
+        ).await
+    }
+
+    /// 从多个镜像下载模型，按顺序尝试直到成功
+    ///
+    /// 连接失败、HTTP 错误或校验和不匹配时切换到下一个地址；
+    /// 配置了镜像区域时，匹配该区域的地址优先。成功的地址记录在 `source_url` 中；
+    /// 全部失败时返回最后一个地址的错误，HTTP 错误状态也作为 `NetworkError` 返回。
+    pub async fn download_model_from_mirrors(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_urls: Vec<String>,
+        expected_checksum: impl Into<Option<String>>,
+        checksum_type: ChecksumType,
+    ) -> Result<DownloadProgress, DownloadError> {
+        self.download_model_with_priority(
+            model_id,
+            model_name,
+            download_urls,
+            expected_checksum,
+            checksum_type,
+            DownloadPriority::Normal,
+        ).await
+    }
+
+    /// 以指定优先级排队下载模型
+    ///
+    /// 并发名额已满时在队列中等待，名额空出时高优先级的任务先开始。
+    pub async fn download_model_with_priority(
+        &self,
+        model_id: Uuid,
+        model_name: String,
+        download_urls: Vec<String>,
+        expected_checksum: impl Into<Option<String>>,
+        checksum_type: ChecksumType,
+        priority: DownloadPriority,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let (checksummer, expected_checksum) = resolve_expected_checksum(expected_checksum.into(), checksum_type)?;
+        self.queued_download(
+            model_id,
+            &model_name,
+            &download_urls,
+            expected_checksum.as_deref(),
+            checksummer.as_ref(),
+            priority,
+            None,
+            None,
+        ).await
+    }
+
+    /// 占用队列名额后依次尝试各下载地址
+    #[allow(clippy::too_many_arguments)]
+    async fn queued_download(
+        &self,
+        model_id: Uuid,
+        model_name: &str,
+        download_urls: &[String],
+        expected_checksum: Option<&str>,
+        checksummer: &dyn Checksummer,
+        priority: DownloadPriority,
+        expected_size: Option<u64>,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        if self.offline {
+            return Err(DownloadError::Offline(model_name.to_string()));
+        }
+        let _permit = self.queue.acquire_with_size(model_id, model_name, priority, expected_size.unwrap_or(0)).await?;
+        let _transfer = self.track_transfer(model_id);
+        self.download_from_mirrors(model_id, model_name, download_urls, expected_checksum, checksummer, expected_size, progress_tx).await
+    }
+
+    /// 依次尝试各下载地址
+    ///
+    /// 有多个地址时连接失败（含 DNS 解析失败）直接换下一个地址，不在原地退避重试；
+    /// 传输中断等其他临时故障仍按 `max_retries` 在当前地址续传。
+    #[allow(clippy::too_many_arguments)]
+    async fn download_from_mirrors(
+        &self,
+        model_id: Uuid,
+        model_name: &str,
+        download_urls: &[String],
+        expected_checksum: Option<&str>,
+        checksummer: &dyn Checksummer,
+        expected_size: Option<u64>,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let mut last_error = None;
+        let retry_connect = download_urls.len() == 1;
+        for url in self.order_mirrors(download_urls) {
+            match self.download_from(model_id, model_name, url, expected_checksum, checksummer, expected_size, progress_tx, retry_connect).await {
+                Ok(mut progress) => {
+                    progress.source_url = Some(url.clone());
+                    return Ok(progress);
+                }
+                Err(e) if e.is_mirror_failure() => last_error = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| DownloadError::ConfigError("没有可用的下载地址".to_string())))
+    }
+
+    /// 登记进行中的下载，调用方须已占用队列名额，因此同一模型不会同时有两个传输
+    fn track_transfer(&self, model_id: Uuid) -> TransferGuard<'_> {
+        self.transfers.lock().unwrap().insert(model_id, CancellationToken::new());
+        TransferGuard { manager: self, model_id }
+    }
+
+    /// 下载的取消令牌，未登记时返回不会被触发的令牌
+    fn cancellation(&self, model_id: Uuid) -> CancellationToken {
+        self.transfers.lock().unwrap().get(&model_id).cloned().unwrap_or_default()
+    }
+
+    /// 按镜像区域偏好排序，其余地址保持原有顺序
+    fn order_mirrors<'a>(&self, download_urls: &'a [String]) -> Vec<&'a String> {
+        let mut ordered: Vec<&String> = download_urls.iter().collect();
+        if let Some(region) = &self.mirror_region {
+            let region = region.to_lowercase();
+            ordered.sort_by_key(|url| {
+                let host = reqwest::Url::parse(url)
+                    .ok()
+                    .and_then(|u| u.host_str().map(str::to_lowercase))
+                    .unwrap_or_default();
+                !host.contains(&region)
+            });
+        }
+        ordered
+    }
+
+    /// 从单个地址下载并校验，临时网络故障时按指数退避重试
+    ///
+    /// `expected_size` 是服务器未返回 `content-length` 时使用的估算总大小。
+    /// `retry_connect` 为 false 时连接失败立即返回，交给调用方换用其他地址。
+    #[allow(clippy::too_many_arguments)]
+    async fn download_from(
+        &self,
+        model_id: Uuid,
+        model_name: &str,
+        download_url: &str,
+        expected_checksum: Option<&str>,
+        checksummer: &dyn Checksummer,
+        expected_size: Option<u64>,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+        retry_connect: bool,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let mut result = self.fetch_from(model_id, model_name, download_url, expected_checksum, checksummer, expected_size, progress_tx).await;
+        let cancel = self.cancellation(model_id);
+        let mut retries = 0;
+        while let Err(e) = &result {
+            if !e.is_transient() || retries >= self.max_retries || (!retry_connect && e.is_connect_failure()) {
+                break;
+            }
+            tokio::select! {
+                _ = cancel.cancelled() => return Err(DownloadError::Cancelled(model_id.to_string())),
+                _ = tokio::time::sleep(RETRY_BASE_DELAY.saturating_mul(1 << retries.min(16))) => {}
+            }
+            retries += 1;
+
+            // 上一次已写入的内容还在，能续传时只请求剩余部分
+            result = match self.read_partial(model_id).await {
+                Ok(partial) if partial.download_url == download_url => {
+                    self.resume_partial(model_id, partial, checksummer, progress_tx).await
+                }
+                _ => self.fetch_from(model_id, model_name, download_url, expected_checksum, checksummer, expected_size, progress_tx).await,
+            };
+        }
+        result
+    }
+
+    /// 从单个地址完整下载一次并校验
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_from(
+        &self,
+        model_id: Uuid,
+        model_name: &str,
+        download_url: &str,
+        expected_checksum: Option<&str>,
+        checksummer: &dyn Checksummer,
+        expected_size: Option<u64>,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        let model_name = model_name.to_string();
+        // 验证URL
+        let url = reqwest::Url::parse(download_url)
+            .map_err(|_| DownloadError::InvalidUrl(download_url.to_string()))?;
+
+        // 检查临时目录所在磁盘的空间
+        let temp_file_path = self.temp_file_path(model_id);
+        self.check_disk_space(&self.temp_dir, download_url, expected_size).await?;
 
         // 开始下载
-        let response = self.client.get(url).send().await?;
+        let mut progress = DownloadProgress::downloading(model_id, &model_name);
+        // HTTP 错误状态作为网络错误返回，不会重试
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        check_content_type(&response)?;
 
-        if !response.status().is_success() {
-            return Err(DownloadError::InvalidUrl(
-                format!("HTTP error: {}", response.status())
-            ));
+        match (response.content_length(), expected_size) {
+            (Some(length), _) => {
+                progress.total_bytes = length;
+                progress.total_source = TotalBytesSource::Header;
+            }
+            (None, Some(estimate)) => {
+                progress.total_bytes = estimate;
+                progress.total_source = TotalBytesSource::Estimate;
+            }
+            (None, None) => {}
         }
 
-        progress.total_bytes = response.content_length().unwrap_or(0);
+        // 记录续传所需的信息，下载中断后可由 `resume_download` 继续
+        let mut partial = PartialDownload {
+            model_name: model_name.clone(),
+            download_url: download_url.to_string(),
+            total_bytes: progress.total_bytes,
+            total_source: progress.total_source,
+            expected_checksum: expected_checksum.map(str::to_string),
+            checksum_algorithm: checksummer.name().to_string(),
+            downloaded_bytes: Some(0),
+        };
+        tokio::fs::write(self.meta_file_path(model_id), serde_json::to_vec(&partial)?).await?;
 
         // 下载文件
-        let mut file = tokio::fs::File::create(&temp_file_path).await?;
-        let mut downloaded = 0u64;
+        let file = tokio::fs::File::create(&temp_file_path).await?;
+        let streamed = self.receive_body(response, file, &temp_file_path, checksummer.streaming(), &mut progress, &mut partial, progress_tx).await?;
+        self.finish_download(&temp_file_path, expected_checksum, checksummer, streamed, &mut progress).await?;
+        Ok(progress)
+    }
+
+    /// 把响应体追加写入临时文件并更新进度
+    ///
+    /// `progress.downloaded_bytes` 为文件中已有的字节数，续传时从这里继续计数。
+    /// 每块内容写入的同时送入 `hasher`，返回其最终摘要；`hasher` 须已包含文件中已有的内容。
+    /// 传入 `progress_tx` 时按 `PROGRESS_REPORT_INTERVAL` 节流发送进度快照。
+    /// 已写入的字节数定期、在连接中断时和结束时记入 `partial`，供续传时核对临时文件。
+    #[allow(clippy::too_many_arguments)]
+    async fn receive_body(
+        &self,
+        response: reqwest::Response,
+        mut file: tokio::fs::File,
+        temp_file_path: &Path,
+        mut hasher: Option<Box<dyn StreamingHasher>>,
+        progress: &mut DownloadProgress,
+        partial: &mut PartialDownload,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+    ) -> Result<Option<String>, DownloadError> {
+        let offset = progress.downloaded_bytes;
+        let mut downloaded = offset;
         let start_time = std::time::Instant::now();
+        let mut last_chunk_at = start_time;
+        let mut last_report_at: Option<std::time::Instant> = None;
+        let mut last_checkpoint_at = start_time;
+        let mut speed = SpeedTracker::new();
 
+        let cancel = self.cancellation(progress.model_id);
         let mut stream = response.bytes_stream();
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
+        loop {
+            // 等待数据时也能立即响应取消
+            let chunk = tokio::select! {
+                _ = cancel.cancelled() => return Err(self.abandon_cancelled(file, temp_file_path, progress.model_id).await),
+                chunk = stream.next() => chunk,
+            };
+            let Some(chunk) = chunk else { break };
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    self.checkpoint(&mut file, partial, progress.model_id, downloaded).await?;
+                    return Err(e.into());
+                }
+            };
+            // 响应头缺失或不准确时，再根据开头的内容判断一次
+            if downloaded == 0 && looks_like_html(&chunk) {
+                drop(file);
+                let _ = tokio::fs::remove_file(temp_file_path).await;
+                let _ = tokio::fs::remove_file(self.meta_file_path(progress.model_id)).await;
+                return Err(DownloadError::UnexpectedContentType("text/html (sniffed)".to_string()));
+            }
+            // 先按限速等待再计入进度，速度统计反映的就是限速后的实际速度
+            if let Some(limiter) = &self.rate_limiter {
+                tokio::select! {
+                    _ = cancel.cancelled() => return Err(self.abandon_cancelled(file, temp_file_path, progress.model_id).await),
+                    _ = limiter.acquire(chunk.len() as u64) => {}
+                }
+            }
             file.write_all(&chunk).await?;
+            if let Some(hasher) = hasher.as_mut() {
+                hasher.update(&chunk);
+            }
             downloaded += chunk.len() as u64;
 
             // 更新进度
             progress.downloaded_bytes = downloaded;
+            // 估算的总大小可能偏小，进度不超过 100%
             progress.progress_percent = if progress.total_bytes > 0 {
-                (downloaded as f32 / progress.total_bytes as f32) * 100.0
+                ((downloaded as f32 / progress.total_bytes as f32) * 100.0).clamp(0.0, 100.0)
             } else {
                 0.0
             };
 
-            let elapsed = start_time.elapsed().as_secs();
-            if elapsed > 0 {
-                progress.download_speed_bps = downloaded / elapsed;
-                if progress.download_speed_bps > 0 {
-                    let remaining_bytes = progress.total_bytes.saturating_sub(downloaded);
-                    progress.estimated_remaining_seconds = Some(remaining_bytes / progress.download_speed_bps);
+            let now = std::time::Instant::now();
+            speed.record(chunk.len() as u64, now - last_chunk_at);
+            last_chunk_at = now;
+            progress.current_speed_bps = speed.current_speed_bps();
+
+            let elapsed = start_time.elapsed().as_secs_f64();
+            if elapsed > 0.0 {
+                progress.download_speed_bps = ((downloaded - offset) as f64 / elapsed) as u64;
+            }
+            let remaining_bytes = progress.total_bytes.saturating_sub(downloaded);
+            if let Some(eta) = speed.estimate_remaining_seconds(remaining_bytes) {
+                progress.estimated_remaining_seconds = Some(eta);
+            }
+            self.queue.record_progress(progress.model_id, downloaded, progress.total_bytes, progress.current_speed_bps);
+
+            if let Some(tx) = progress_tx {
+                if last_report_at.is_none_or(|at| now - at >= PROGRESS_REPORT_INTERVAL) {
+                    // 通道已满时跳过这次快照，界面稍后会收到更新的
+                    let _ = tx.try_send(progress.clone());
+                    last_report_at = Some(now);
                 }
             }
+            if now - last_checkpoint_at >= RESUME_CHECKPOINT_INTERVAL {
+                self.checkpoint(&mut file, partial, progress.model_id, downloaded).await?;
+                last_checkpoint_at = now;
+            }
         }
 
-        file.flush().await?;
+        self.checkpoint(&mut file, partial, progress.model_id, downloaded).await?;
+        Ok(hasher.map(|hasher| hasher.finalize()))
+    }
+
+    /// 传输被取消：关闭并删除临时文件和续传信息
+    async fn abandon_cancelled(&self, file: tokio::fs::File, temp_file_path: &Path, model_id: Uuid) -> DownloadError {
         drop(file);
+        let _ = tokio::fs::remove_file(temp_file_path).await;
+        let _ = tokio::fs::remove_file(self.meta_file_path(model_id)).await;
+        DownloadError::Cancelled(model_id.to_string())
+    }
+
+    /// 把已写入的内容刷到磁盘，再在 `.meta` 中记下已下载的字节数
+    async fn checkpoint(
+        &self,
+        file: &mut tokio::fs::File,
+        partial: &mut PartialDownload,
+        model_id: Uuid,
+        downloaded: u64,
+    ) -> Result<(), DownloadError> {
+        file.flush().await?;
+        partial.downloaded_bytes = Some(downloaded);
+        tokio::fs::write(self.meta_file_path(model_id), serde_json::to_vec(partial)?).await?;
+        Ok(())
+    }
+
+    /// 核对临时文件与 `.meta` 中记录的已下载字节数
+    ///
+    /// 记录每秒才更新一次，崩溃后文件通常比记录长：截掉记录之后未确认的部分，续传从记录处
+    /// 继续。文件比记录短说明已确认的内容丢失或被改动，清空临时文件并把记录归零，续传时
+    /// 从头下载。返回是否改动了临时文件。
+    async fn reconcile_partial(&self, model_id: Uuid, partial: &mut PartialDownload) -> Result<bool, DownloadError> {
+        let temp_file_path = self.temp_file_path(model_id);
+        let on_disk = match tokio::fs::metadata(&temp_file_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        match partial.downloaded_bytes {
+            Some(recorded) if recorded < on_disk => {
+                let file = tokio::fs::OpenOptions::new().write(true).open(&temp_file_path).await?;
+                file.set_len(recorded).await?;
+                Ok(true)
+            }
+            Some(recorded) if recorded > on_disk => {
+                tokio::fs::File::create(&temp_file_path).await?;
+                partial.downloaded_bytes = Some(0);
+                tokio::fs::write(self.meta_file_path(model_id), serde_json::to_vec(partial)?).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// 启动时检查临时目录中未完成的下载
+    ///
+    /// 比记录长的临时文件截到记录的长度，比记录短的被清空（之后的 `resume_download` 从头
+    /// 下载）；没有续传信息的临时文件无法续传，直接删除。正在队列中的下载不受影响。
+    /// 返回截断、清空或删除的文件数。
+    pub async fn reconcile_partial_downloads(&self) -> Result<usize, DownloadError> {
+        let queued: Vec<Uuid> = self.queue.list().into_iter().map(|d| d.model_id).collect();
+        let mut discarded = 0;
+
+        let mut entries = tokio::fs::read_dir(&self.temp_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("tmp") {
+                continue;
+            }
+            let Some(model_id) = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| Uuid::parse_str(stem).ok()) else {
+                continue;
+            };
+            if queued.contains(&model_id) {
+                continue;
+            }
+
+            let partial = match tokio::fs::read(self.meta_file_path(model_id)).await {
+                Ok(content) => serde_json::from_slice::<PartialDownload>(&content).ok(),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            match partial {
+                Some(mut partial) => {
+                    if self.reconcile_partial(model_id, &mut partial).await? {
+                        discarded += 1;
+                    }
+                }
+                None => {
+                    tokio::fs::remove_file(&path).await?;
+                    let _ = tokio::fs::remove_file(self.meta_file_path(model_id)).await;
+                    discarded += 1;
+                }
+            }
+        }
+        Ok(discarded)
+    }
 
-        // 验证校验和
+    /// 校验整个临时文件并移动到最终位置
+    ///
+    /// `streamed` 是下载时边写边算出的摘要，有则直接使用，不再重新读取文件。
+    async fn finish_download(
+        &self,
+        temp_file_path: &Path,
+        expected_checksum: Option<&str>,
+        checksummer: &dyn Checksummer,
+        streamed: Option<String>,
+        progress: &mut DownloadProgress,
+    ) -> Result<(), DownloadError> {
         progress.status = DownloadStatus::Verifying;
-        self.verify_checksum(&temp_file_path, &expected_checksum, checksum_type).await?;
+        let actual_checksum = match streamed {
+            Some(actual) => actual,
+            None => digest_file(checksummer, temp_file_path).await?,
+        };
+
+        // 验证校验和；没有期望值时只记录实际校验和，交由用户决定是否信任
+        match expected_checksum {
+            Some(expected) if !actual_checksum.eq_ignore_ascii_case(expected) => {
+                // 内容已完整但不对，续传无法修复，丢弃临时文件和续传信息
+                let _ = tokio::fs::remove_file(temp_file_path).await;
+                let _ = tokio::fs::remove_file(self.meta_file_path(progress.model_id)).await;
+                return Err(DownloadError::ChecksumMismatch {
+                    expected: expected.to_string(),
+                    actual: actual_checksum,
+                });
+            }
+            Some(_) => {}
+            None => progress.warnings.push(DownloadWarning::UnverifiedChecksum),
+        }
+        progress.actual_checksum = Some(actual_checksum);
 
         // 移动文件到最终位置
-        let final_path = self.download_dir.join(&model_name);
-        tokio::fs::rename(&temp_file_path, &final_path).await?;
+        let final_path = self.download_dir.join(&progress.model_name);
+        tokio::fs::rename(temp_file_path, &final_path).await?;
+        let _ = tokio::fs::remove_file(self.meta_file_path(progress.model_id)).await;
 
         progress.status = DownloadStatus::Completed;
-        Ok(progress)
+        Ok(())
+    }
+
+    /// 下载中的临时文件
+    fn temp_file_path(&self, model_id: Uuid) -> PathBuf {
+        self.temp_dir.join(format!("{}.tmp", model_id))
+    }
+
+    /// 记录续传信息的 `.meta` 文件
+    fn meta_file_path(&self, model_id: Uuid) -> PathBuf {
+        self.temp_dir.join(format!("{}.meta", model_id))
     }
 
     /// 安装模型
@@ -230,11 +1125,10 @@ impl ModelDownloadManager {
         model_path: PathBuf,
         config: InstallationConfig,
     ) -> Result<ModelInstallation, DownloadError> {
-        // 确定安装路径
-        let install_path = config.custom_install_path
-            .unwrap_or_else(|| self.download_dir.join("installed").join(model_id.to_string()));
+        let install_path = self.install_path(model_id, &config);
 
-        // 创建安装目录
+        // 创建安装目录（记录是否为新建，回滚时只删除本次创建的内容）
+        let created_install_dir = !install_path.exists();
         fs::create_dir_all(&install_path)?;
 
         // 复制或移动模型文件
@@ -253,15 +1147,30 @@ impl ModelDownloadManager {
             tokio::fs::copy(&model_path, &target_path).await?;
         }
 
-        // 获取文件大小
-        let metadata = tokio::fs::metadata(&target_path).await?;
-        let file_size = metadata.len();
+        // 安装后校验，失败时回滚
+        let (verification, checksum) = match self.verify_installed_file(&model_path, &target_path, &config).await {
+            Ok(result) => result,
+            Err(e) => {
+                Self::rollback_install(created_install_dir, &install_path, &target_path).await;
+                return Err(match e {
+                    DownloadError::InstallationFailed(_) => e,
+                    other => DownloadError::InstallationFailed(format!("安装校验失败: {}", other)),
+                });
+            }
+        };
+        let file_size = verification.actual_size;
 
-        // 计算校验和
-        let checksum = if config.auto_verify {
-            self.calculate_checksum(&target_path, ChecksumType::SHA256).await?
+        // 保留原始下载文件
+        let cached_download = if config.keep_download {
+            match self.cache_download(model_id, &model_path).await {
+                Ok(cached) => Some(cached.path),
+                Err(e) => {
+                    Self::rollback_install(created_install_dir, &install_path, &target_path).await;
+                    return Err(DownloadError::InstallationFailed(format!("保留下载文件失败: {}", e)));
+                }
+            }
         } else {
-            String::new()
+            None
         };
 
         // 创建配置文件
@@ -271,7 +1180,9 @@ impl ModelDownloadManager {
             "installed_at": Utc::now(),
             "version": "1.0.0",
             "file_size": file_size,
-            "checksum": checksum
+            "checksum": checksum,
+            "verification": verification,
+            "cached_download": cached_download
         });
         tokio::fs::write(&config_path, serde_json::to_string_pretty(&model_config)?).await?;
 
@@ -301,11 +1212,130 @@ impl ModelDownloadManager {
                     vec![]
                 },
             },
+            verification: Some(verification),
+            cached_download,
         };
 
         Ok(installation)
     }
 
+    /// 模型的安装目录：优先使用配置中的自定义路径
+    pub fn install_path(&self, model_id: Uuid, config: &InstallationConfig) -> PathBuf {
+        config.custom_install_path.clone()
+            .unwrap_or_else(|| self.download_dir.join("installed").join(model_id.to_string()))
+    }
+
+    /// 从下载缓存重新安装模型，不重新下载
+    ///
+    /// 缓存文件缺失或校验和与缓存时记录的不一致时返回 `CacheUnavailable`，调用方应重新下载。
+    pub async fn reinstall_from_cache(&self, model_id: Uuid) -> Result<ModelInstallation, DownloadError> {
+        let record_path = self.download_cache_dir(model_id).join("cache.json");
+        let record: CachedDownload = match tokio::fs::read_to_string(&record_path).await {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(_) => return Err(DownloadError::CacheUnavailable(format!("模型 {} 没有缓存的下载文件", model_id))),
+        };
+
+        if !record.path.is_file() {
+            return Err(DownloadError::CacheUnavailable(format!("缓存文件不存在: {}", record.path.display())));
+        }
+        let actual = self.calculate_checksum(&record.path, ChecksumType::SHA256).await?;
+        if actual != record.checksum {
+            return Err(DownloadError::CacheUnavailable(format!(
+                "缓存文件校验和不符: 期望 {}, 实际 {}", record.checksum, actual
+            )));
+        }
+
+        let config = InstallationConfig {
+            expected_file_size: Some(record.file_size),
+            expected_checksum: Some(format!("sha256:{}", record.checksum)),
+            keep_download: true,
+            ..Default::default()
+        };
+        self.install_model(model_id, record.path, config).await
+    }
+
+    /// 模型的下载缓存目录
+    fn download_cache_dir(&self, model_id: Uuid) -> PathBuf {
+        self.download_dir.join("downloads").join(model_id.to_string())
+    }
+
+    /// 将下载文件复制到缓存目录并记录其校验和
+    async fn cache_download(&self, model_id: Uuid, model_path: &Path) -> Result<CachedDownload, DownloadError> {
+        let cache_dir = self.download_cache_dir(model_id);
+        tokio::fs::create_dir_all(&cache_dir).await?;
+
+        let file_name = model_path.file_name()
+            .ok_or_else(|| DownloadError::ConfigError("无效的模型文件路径".to_string()))?;
+        let cached_path = cache_dir.join(file_name);
+        // 从缓存重新安装时源文件已在缓存中
+        if cached_path != model_path {
+            tokio::fs::copy(model_path, &cached_path).await?;
+        }
+
+        let record = CachedDownload {
+            file_size: tokio::fs::metadata(&cached_path).await?.len(),
+            checksum: self.calculate_checksum(&cached_path, ChecksumType::SHA256).await?,
+            path: cached_path,
+            cached_at: Utc::now(),
+        };
+        tokio::fs::write(cache_dir.join("cache.json"), serde_json::to_string_pretty(&record)?).await?;
+        Ok(record)
+    }
+
+    /// 撤销未完成的安装，只删除本次创建的内容
+    async fn rollback_install(created_install_dir: bool, install_path: &Path, target_path: &Path) {
+        if created_install_dir {
+            let _ = tokio::fs::remove_dir_all(install_path).await;
+        } else {
+            let _ = tokio::fs::remove_file(target_path).await;
+        }
+    }
+
+    /// 校验安装后的文件：可读、大小一致，开启 `auto_verify` 时校验和一致
+    ///
+    /// 返回校验结果和目标文件的 SHA256（未开启 `auto_verify` 时为空）。
+    async fn verify_installed_file(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+        config: &InstallationConfig,
+    ) -> Result<(InstallVerification, String), DownloadError> {
+        let actual_size = tokio::fs::metadata(target_path).await?.len();
+        let expected_size = match config.expected_file_size {
+            Some(size) => size,
+            None => tokio::fs::metadata(source_path).await?.len(),
+        };
+        if actual_size != expected_size {
+            return Err(DownloadError::InstallationFailed(format!(
+                "安装校验失败: 文件大小不符: 期望 {} bytes, 实际 {} bytes", expected_size, actual_size
+            )));
+        }
+
+        let mut checksum = String::new();
+        if config.auto_verify {
+            checksum = self.calculate_checksum(target_path, ChecksumType::SHA256).await?;
+            match &config.expected_checksum {
+                Some(expected) => {
+                    let (checksum_type, expected) = parse_expected_checksum(expected, ChecksumType::SHA256)?;
+                    self.verify_checksum(target_path, &expected, checksum_type.checksummer().as_ref()).await?;
+                }
+                None => {
+                    let source_checksum = self.calculate_checksum(source_path, ChecksumType::SHA256).await?;
+                    if source_checksum != checksum {
+                        return Err(DownloadError::ChecksumMismatch { expected: source_checksum, actual: checksum });
+                    }
+                }
+            }
+        }
+
+        Ok((InstallVerification {
+            expected_size,
+            actual_size,
+            checksum_verified: config.auto_verify,
+            verified_at: Utc::now(),
+        }, checksum))
+    }
+
     /// 暂停下载
     pub async fn pause_download(&self, _model_id: Uuid) -> Result<(), DownloadError> {
         // 实现下载暂停逻辑
@@ -313,19 +1343,125 @@ impl ModelDownloadManager {
         Ok(())
     }
 
-    /// 恢复下载
-    pub async fn resume_download(&self, _model_id: Uuid) -> Result<(), DownloadError> {
-        // 实现下载恢复逻辑
-        // 支持断点续传
-        Ok(())
+    /// 恢复中断的下载（断点续传）
+    ///
+    /// 从 `temp/` 下已有的 `.tmp` 文件末尾继续，以 `Range` 请求剩余部分并追加写入；
+    /// 服务器不支持范围请求（返回 200 而不是 206），或 206 的 `Content-Range` 不是从断点
+    /// 开始时从头重新下载。完成后对整个文件重新计算校验和，不匹配时丢弃临时文件和续传信息。
+    /// 下载地址、总大小和期望校验和保存在同名的 `.meta` 文件中，进程重启后也能恢复；
+    /// `.tmp` 比其中记录的已下载字节数长时截去多出的部分，短时从头下载。
+    pub async fn resume_download(&self, model_id: Uuid) -> Result<DownloadProgress, DownloadError> {
+        let partial = self.read_partial(model_id).await?;
+        if self.offline {
+            return Err(DownloadError::Offline(partial.model_name));
+        }
+        let checksummer = partial.checksummer()?;
+        let _permit = self.queue
+            .acquire_with_size(model_id, &partial.model_name, DownloadPriority::Normal, partial.total_bytes)
+            .await?;
+        let _transfer = self.track_transfer(model_id);
+        self.resume_partial(model_id, partial, checksummer.as_ref(), None).await
+    }
+
+    /// 读取中断的下载的续传信息
+    async fn read_partial(&self, model_id: Uuid) -> Result<PartialDownload, DownloadError> {
+        match tokio::fs::read(self.meta_file_path(model_id)).await {
+            Ok(content) => Ok(serde_json::from_slice(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(DownloadError::ConfigError(format!("没有可恢复的下载: {}", model_id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 从临时文件末尾继续下载并校验，调用方已占用队列名额
+    async fn resume_partial(
+        &self,
+        model_id: Uuid,
+        mut partial: PartialDownload,
+        checksummer: &dyn Checksummer,
+        progress_tx: Option<&mpsc::Sender<DownloadProgress>>,
+    ) -> Result<DownloadProgress, DownloadError> {
+        // 大小与记录不符的临时文件不可信，不能据此判断已下载完成
+        self.reconcile_partial(model_id, &mut partial).await?;
+        let temp_file_path = self.temp_file_path(model_id);
+        let offset = match tokio::fs::metadata(&temp_file_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut progress = DownloadProgress::downloading(model_id, &partial.model_name);
+        progress.total_bytes = partial.total_bytes;
+        progress.total_source = partial.total_source;
+        progress.source_url = Some(partial.download_url.clone());
+
+        // 已知大小的文件已经下完，只差校验和移动
+        let complete = partial.total_source == TotalBytesSource::Header && offset > 0 && offset >= partial.total_bytes;
+        let streamed = if complete {
+            progress.downloaded_bytes = offset;
+            progress.progress_percent = 100.0;
+            None
+        } else {
+            let mut request = self.client.get(&partial.download_url);
+            if offset > 0 {
+                request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+            }
+            let mut response = request.send().await?;
+            let mut resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT && offset > 0;
+            if resumed && content_range_start(&response) != Some(offset) {
+                // 返回的片段不是从断点开始，无法拼接，改为重新下载整个文件
+                response = self.client.get(&partial.download_url).send().await?;
+                resumed = false;
+            }
+            let response = response.error_for_status()?;
+            check_content_type(&response)?;
+
+            let mut hasher = checksummer.streaming();
+            let file = if resumed {
+                progress.downloaded_bytes = offset;
+                // 已下载的部分只读一遍，其余在接收时计算
+                if let Some(hasher) = hasher.as_mut() {
+                    hash_file_into(hasher.as_mut(), &temp_file_path).await?;
+                }
+                if progress.total_bytes == 0 {
+                    if let Some(remaining) = response.content_length() {
+                        progress.total_bytes = offset + remaining;
+                        progress.total_source = TotalBytesSource::Header;
+                    }
+                }
+                tokio::fs::OpenOptions::new().append(true).open(&temp_file_path).await?
+            } else {
+                // 服务器忽略了 Range 或片段位置不对，返回的是完整文件
+                if let Some(length) = response.content_length() {
+                    progress.total_bytes = length;
+                    progress.total_source = TotalBytesSource::Header;
+                }
+                tokio::fs::File::create(&temp_file_path).await?
+            };
+            self.receive_body(response, file, &temp_file_path, hasher, &mut progress, &mut partial, progress_tx).await?
+        };
+
+        self.finish_download(&temp_file_path, partial.expected_checksum.as_deref(), checksummer, streamed, &mut progress).await?;
+        Ok(progress)
     }
 
     /// 取消下载
+    ///
+    /// 进行中的传输会尽快停止并删除已写入的内容，该下载返回 `DownloadError::Cancelled`，
+    /// 进度通道最后收到状态为 `Cancelled` 的快照。
     pub async fn cancel_download(&self, model_id: Uuid) -> Result<(), DownloadError> {
-        // 清理临时文件
-        let temp_file_path = self.temp_dir.join(format!("{}.tmp", model_id));
-        if temp_file_path.exists() {
-            tokio::fs::remove_file(&temp_file_path).await?;
+        // 仍在排队的任务直接移出队列
+        self.queue.cancel(model_id);
+        if let Some(cancel) = self.transfers.lock().unwrap().get(&model_id) {
+            cancel.cancel();
+        }
+
+        // 清理临时文件和续传信息
+        for path in [self.temp_file_path(model_id), self.meta_file_path(model_id)] {
+            if path.exists() {
+                tokio::fs::remove_file(&path).await?;
+            }
         }
         Ok(())
     }
@@ -366,13 +1502,21 @@ impl ModelDownloadManager {
     }
 
     /// 检查磁盘空间
-    async fn check_disk_space(&self, file_path: &Path, download_url: &str) -> Result<(), DownloadError> {
-        // 获取文件大小（通过HEAD请求）
+    ///
+    /// 下载后仍需保留 `min_free_reserve`，空间不足时 `required` 包含该预留量。
+    async fn check_disk_space(&self, target_dir: &Path, download_url: &str, expected_size: Option<u64>) -> Result<(), DownloadError> {
+        // 获取文件大小（通过HEAD请求），服务器未返回时使用估算大小
         let response = self.client.head(download_url).send().await?;
-        let required_size = response.content_length().unwrap_or(0);
+        // HEAD 响应没有响应体，`content_length()` 总是 0，需直接读取响应头
+        let file_size = response.headers().get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|length| length.to_str().ok())
+            .and_then(|length| length.parse::<u64>().ok())
+            .or(expected_size)
+            .unwrap_or(0);
+        let required_size = file_size.saturating_add(self.min_free_reserve);
 
         // 检查可用磁盘空间
-        let available_space = self.get_available_disk_space(file_path)?;
+        let available_space = self.get_available_disk_space(target_dir)?;
 
         if required_size > available_space {
             return Err(DownloadError::InsufficientSpace {
@@ -384,21 +1528,23 @@ impl ModelDownloadManager {
         Ok(())
     }
 
-    /// 获取可用磁盘空间
-    fn get_available_disk_space(&self, _path: &Path) -> Result<u64, DownloadError> {
-        // 简化实现，实际应该使用系统API
-        // 这里返回一个大致的可用空间
-        Ok(10_000_000_000) // 10GB
+    /// 获取写入目录所在磁盘的可用空间
+    ///
+    /// 无法查询时返回错误，而不是假设空间足够。
+    fn get_available_disk_space(&self, dir: &Path) -> Result<u64, DownloadError> {
+        available_disk_space(dir).ok_or_else(|| {
+            DownloadError::IoError(std::io::Error::other(format!("无法查询 {} 所在磁盘的可用空间", dir.display())))
+        })
     }
 
-    /// 验证校验和
+    /// 验证校验和，返回实际校验和
     async fn verify_checksum(
         &self,
         file_path: &Path,
         expected: &str,
-        checksum_type: ChecksumType,
-    ) -> Result<(), DownloadError> {
-        let actual = self.calculate_checksum(file_path, checksum_type).await?;
+        checksummer: &dyn Checksummer,
+    ) -> Result<String, DownloadError> {
+        let actual = digest_file(checksummer, file_path).await?;
 
         if actual.to_lowercase() != expected.to_lowercase() {
             return Err(DownloadError::ChecksumMismatch {
@@ -407,7 +1553,7 @@ impl ModelDownloadManager {
             });
         }
 
-        Ok(())
+        Ok(actual)
     }
 
     /// 计算文件校验和
@@ -416,27 +1562,150 @@ impl ModelDownloadManager {
         file_path: &Path,
         checksum_type: ChecksumType,
     ) -> Result<String, DownloadError> {
-        let content = tokio::fs::read(file_path).await?;
+        Ok(digest_file(checksum_type.checksummer().as_ref(), file_path).await?)
+    }
+}
 
-        let hash = match checksum_type {
-            ChecksumType::MD5 => {
-                let digest = md5::compute(&content);
-                format!("{:x}", digest)
-            }
-            ChecksumType::SHA256 => {
-                let mut hasher = Sha256::new();
-                hasher.update(&content);
-                format!("{:x}", hasher.finalize())
-            }
-            ChecksumType::SHA512 => {
-                use sha2::Sha512;
-                let mut hasher = Sha512::new();
-                hasher.update(&content);
-                format!("{:x}", hasher.finalize())
-            }
+impl DownloadError {
+    /// 是否是值得原地重试的临时网络故障（连接失败、超时、传输中断），HTTP 错误状态不算
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            DownloadError::NetworkError(e)
+                if e.is_connect() || e.is_timeout() || e.is_request() || e.is_body() || e.is_decode()
+        )
+    }
+
+    /// 是否是连接失败（含 DNS 解析失败），换一个地址往往比等待重试更快
+    pub fn is_connect_failure(&self) -> bool {
+        matches!(self, DownloadError::NetworkError(e) if e.is_connect())
+    }
+
+    /// 是否应换用下一个镜像重试（连接失败、HTTP 错误、内容类型错误、校验和不匹配）
+    pub fn is_mirror_failure(&self) -> bool {
+        matches!(
+            self,
+            DownloadError::NetworkError(_)
+                | DownloadError::InvalidUrl(_)
+                | DownloadError::UnexpectedContentType(_)
+                | DownloadError::ChecksumMismatch { .. }
+        )
+    }
+}
+
+/// 模型应为二进制文件，错误页常被标成 HTML/JSON 返回
+fn check_content_type(response: &reqwest::Response) -> Result<(), DownloadError> {
+    if let Some(content_type) = response.headers().get(reqwest::header::CONTENT_TYPE) {
+        let content_type = content_type.to_str().unwrap_or_default().to_lowercase();
+        if is_non_model_content_type(&content_type) {
+            return Err(DownloadError::UnexpectedContentType(content_type));
+        }
+    }
+    Ok(())
+}
+
+/// 206 响应 `Content-Range` 的起始位置，如 `bytes 10-22/23` 为 10；缺失或无法解析时为 `None`
+fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+    let value = response.headers().get(reqwest::header::CONTENT_RANGE)?.to_str().ok()?;
+    let range = value.trim().strip_prefix("bytes")?.trim_start();
+    range.split_once('-')?.0.trim().parse().ok()
+}
+
+/// 明显不是模型文件的内容类型
+fn is_non_model_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    matches!(mime, "text/html" | "application/xhtml+xml" | "application/json")
+}
+
+/// 内容开头是否像 HTML 页面
+fn looks_like_html(bytes: &[u8]) -> bool {
+    let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let head = &bytes[start..bytes.len().min(start + 16)];
+    let head = String::from_utf8_lossy(head).to_lowercase();
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+/// 查询路径所在磁盘的可用空间，路径不存在时使用最近的已存在上级目录
+///
+/// Unix 上使用 `statvfs`，Windows 上使用 `GetDiskFreeSpaceExW`；其他平台或查询失败时返回 None。
+pub fn available_disk_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: c_path is a valid NUL-terminated string and stat is a properly sized out-parameter
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn GetDiskFreeSpaceExW(
+                directory_name: *const u16,
+                free_bytes_available_to_caller: *mut u64,
+                total_number_of_bytes: *mut u64,
+                total_number_of_free_bytes: *mut u64,
+            ) -> i32;
+        }
+
+        let wide: Vec<u16> = existing.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+        let mut available = 0u64;
+        // SAFETY: wide is NUL-terminated; the total out-parameters may be null
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut())
         };
+        (ok != 0).then_some(available)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = existing;
+        None
+    }
+}
+
+/// 解析期望校验和
+///
+/// 支持 `sha256:` 等算法前缀；未声明前缀时按长度推断，无法推断时使用 `fallback`。
+/// 十六进制串与算法长度不符（如截断的示例值）时返回 `MalformedChecksum`。
+pub fn parse_expected_checksum(
+    expected: &str,
+    fallback: ChecksumType,
+) -> Result<(ChecksumType, String), DownloadError> {
+    let (checksum_type, hex) = ChecksumType::resolve(expected, fallback);
 
-        Ok(hash)
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(DownloadError::MalformedChecksum(format!("{} 不是十六进制字符串", expected)));
+    }
+    if hex.len() != checksum_type.hex_len() {
+        return Err(DownloadError::MalformedChecksum(format!(
+            "{} 长度为 {}，{:?} 需要 {} 位",
+            expected, hex.len(), checksum_type, checksum_type.hex_len()
+        )));
+    }
+
+    Ok((checksum_type, hex.to_lowercase()))
+}
+
+/// 解析可选的期望校验和，返回使用的校验算法和去掉前缀的十六进制串
+fn resolve_expected_checksum(
+    expected: Option<String>,
+    fallback: ChecksumType,
+) -> Result<(Arc<dyn Checksummer>, Option<String>), DownloadError> {
+    match expected {
+        Some(expected) => {
+            let (checksum_type, hex) = parse_expected_checksum(&expected, fallback)?;
+            Ok((checksum_type.checksummer(), Some(hex)))
+        }
+        None => Ok((fallback.checksummer(), None)),
     }
 }
 
@@ -449,6 +1718,9 @@ impl Default for InstallationConfig {
             install_dependencies: true,
             enable_gpu: false,
             custom_install_path: None,
+            expected_file_size: None,
+            expected_checksum: None,
+            keep_download: false,
         }
     }
 }
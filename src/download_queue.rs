@@ -0,0 +1,376 @@
+// 带优先级的下载队列
+
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use uuid::Uuid;
+use crate::download::DownloadError;
+
+/// 下载优先级，高优先级的任务在空出名额时先开始
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DownloadPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// 队列中任务的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QueueState {
+    /// 等待空出名额
+    Waiting,
+    /// 已占用名额，正在下载
+    Active,
+}
+
+/// 队列中的一个下载任务
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedDownload {
+    pub model_id: Uuid,
+    pub model_name: String,
+    pub priority: DownloadPriority,
+    pub state: QueueState,
+    pub queued_at: DateTime<Utc>,
+    /// 文件总大小，未知时为 0
+    #[serde(default)]
+    pub total_bytes: u64,
+    /// 已下载的字节数
+    #[serde(default)]
+    pub downloaded_bytes: u64,
+    /// 最近的下载速度（字节/秒）
+    #[serde(default)]
+    pub speed_bps: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    download: QueuedDownload,
+    /// 同优先级内的先后顺序，越小越靠前
+    order: i64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    waiting: Vec<Entry>,
+    active: Vec<QueuedDownload>,
+    next_order: i64,
+    /// 最近一次测得的总吞吐量（字节/秒），没有进行中的下载时用于估算
+    last_throughput_bps: u64,
+}
+
+impl Inner {
+    /// 按开始顺序排列等待中的任务
+    fn sort_waiting(&mut self) {
+        self.waiting.sort_by_key(|e| (std::cmp::Reverse(e.download.priority), e.order));
+    }
+
+    fn waiting_index(&self, model_id: Uuid) -> Option<usize> {
+        self.waiting.iter().position(|e| e.download.model_id == model_id)
+    }
+
+    /// 当前总吞吐量：进行中下载的速度之和，尚无速度时取最近一次测得的值
+    fn throughput_bps(&self) -> Option<u64> {
+        let current: u64 = self.active.iter().map(|d| d.speed_bps).sum();
+        [current, self.last_throughput_bps].into_iter().find(|&bps| bps > 0)
+    }
+}
+
+/// 下载队列，限制同时进行的下载数量
+///
+/// 名额空出时按优先级、再按入队顺序选出下一个任务。
+#[derive(Debug)]
+pub struct DownloadQueue {
+    max_active: usize,
+    inner: Mutex<Inner>,
+    changed: Notify,
+}
+
+/// 下载名额，释放时让出给下一个任务
+#[derive(Debug)]
+pub struct DownloadPermit {
+    queue: Arc<DownloadQueue>,
+    model_id: Uuid,
+}
+
+/// 等待中的 `acquire` 被丢弃时把任务移出队列，避免堵住后面的任务
+struct WaitGuard<'a> {
+    queue: &'a DownloadQueue,
+    model_id: Uuid,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        let mut inner = self.queue.inner.lock().unwrap();
+        if let Some(index) = inner.waiting_index(self.model_id) {
+            inner.waiting.remove(index);
+            drop(inner);
+            self.queue.changed.notify_waiters();
+        }
+    }
+}
+
+impl Drop for DownloadPermit {
+    fn drop(&mut self) {
+        self.queue.inner.lock().unwrap().active.retain(|d| d.model_id != self.model_id);
+        self.queue.changed.notify_waiters();
+    }
+}
+
+impl DownloadQueue {
+    /// 创建最多同时进行 `max_active` 个下载的队列
+    pub fn new(max_active: usize) -> Self {
+        Self {
+            max_active: max_active.max(1),
+            inner: Mutex::new(Inner::default()),
+            changed: Notify::new(),
+        }
+    }
+
+    /// 最大并发下载数
+    pub fn max_active(&self) -> usize {
+        self.max_active
+    }
+
+    /// 已占用名额的任务数
+    pub fn active_count(&self) -> usize {
+        self.inner.lock().unwrap().active.len()
+    }
+
+    /// 入队并等待名额
+    ///
+    /// 等待期间被 `cancel` 时返回 `DownloadError::Cancelled`。
+    pub async fn acquire(
+        self: &Arc<Self>,
+        model_id: Uuid,
+        model_name: impl Into<String>,
+        priority: DownloadPriority,
+    ) -> Result<DownloadPermit, DownloadError> {
+        self.acquire_with_size(model_id, model_name, priority, 0).await
+    }
+
+    /// 入队并等待名额，`total_bytes` 用于估算排在后面的任务何时开始
+    pub async fn acquire_with_size(
+        self: &Arc<Self>,
+        model_id: Uuid,
+        model_name: impl Into<String>,
+        priority: DownloadPriority,
+        total_bytes: u64,
+    ) -> Result<DownloadPermit, DownloadError> {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            let queued = inner.waiting_index(model_id).is_some()
+                || inner.active.iter().any(|d| d.model_id == model_id);
+            if queued {
+                return Err(DownloadError::ModelAlreadyExists(format!("{} 已在下载队列中", model_id)));
+            }
+
+            let order = inner.next_order;
+            inner.next_order += 1;
+            inner.waiting.push(Entry {
+                download: QueuedDownload {
+                    model_id,
+                    model_name: model_name.into(),
+                    priority,
+                    state: QueueState::Waiting,
+                    queued_at: Utc::now(),
+                    total_bytes,
+                    downloaded_bytes: 0,
+                    speed_bps: 0,
+                },
+                order,
+            });
+            inner.sort_waiting();
+        }
+        let _guard = WaitGuard { queue: self, model_id };
+
+        loop {
+            // 先注册通知再检查状态，避免漏掉两者之间的变化
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            {
+                let mut inner = self.inner.lock().unwrap();
+                match inner.waiting_index(model_id) {
+                    None => return Err(DownloadError::Cancelled(model_id.to_string())),
+                    Some(0) if inner.active.len() < self.max_active => {
+                        let mut download = inner.waiting.remove(0).download;
+                        download.state = QueueState::Active;
+                        inner.active.push(download);
+                        // 可能还有空余名额，唤醒下一个
+                        self.changed.notify_waiters();
+                        return Ok(DownloadPermit {
+                            queue: Arc::clone(self),
+                            model_id,
+                        });
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            changed.await;
+        }
+    }
+
+    /// 当前队列：进行中的任务在前，其后是按开始顺序排列的等待任务
+    pub fn list(&self) -> Vec<QueuedDownload> {
+        let inner = self.inner.lock().unwrap();
+        inner.active.iter()
+            .cloned()
+            .chain(inner.waiting.iter().map(|e| e.download.clone()))
+            .collect()
+    }
+
+    /// 更新进行中任务的进度和速度，任务不在进行中时忽略
+    pub fn record_progress(&self, model_id: Uuid, downloaded_bytes: u64, total_bytes: u64, speed_bps: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(download) = inner.active.iter_mut().find(|d| d.model_id == model_id) else {
+            return;
+        };
+        download.downloaded_bytes = downloaded_bytes;
+        if total_bytes > 0 {
+            download.total_bytes = total_bytes;
+        }
+        download.speed_bps = speed_bps;
+
+        let throughput: u64 = inner.active.iter().map(|d| d.speed_bps).sum();
+        if throughput > 0 {
+            inner.last_throughput_bps = throughput;
+        }
+    }
+
+    /// 估算任务的开始和完成时间
+    ///
+    /// 按队列顺序模拟：最多 `max_active` 个任务同时下载，平分测得的总吞吐量。
+    /// 任务不在队列中或尚未测得速度时返回 `None`；大小未知的任务按 0 字节计。
+    pub fn estimated_start_and_finish(&self, model_id: Uuid) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let (jobs, throughput) = {
+            let inner = self.inner.lock().unwrap();
+            let throughput = inner.throughput_bps()?;
+            let jobs: Vec<(Uuid, u64)> = inner.active.iter()
+                .map(|d| (d.model_id, d.total_bytes.saturating_sub(d.downloaded_bytes)))
+                .chain(inner.waiting.iter().map(|e| (e.download.model_id, e.download.total_bytes)))
+                .collect();
+            (jobs, throughput)
+        };
+
+        let (start, finish) = simulate_schedule(&jobs, self.max_active, throughput as f64)
+            .into_iter()
+            .find(|(id, _, _)| *id == model_id)
+            .map(|(_, start, finish)| (start, finish))?;
+        let now = Utc::now();
+        let at = |seconds: f64| now + chrono::Duration::milliseconds((seconds * 1000.0) as i64);
+        Some((at(start), at(finish)))
+    }
+
+    /// 将等待中的任务移到队首，必要时提升到当前最高优先级
+    ///
+    /// 任务不在等待中时返回 `false`。
+    pub fn move_to_front(&self, model_id: Uuid) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(index) = inner.waiting_index(model_id) else {
+            return false;
+        };
+
+        let top_priority = inner.waiting.iter().map(|e| e.download.priority).max().unwrap_or_default();
+        let first_order = inner.waiting.iter().map(|e| e.order).min().unwrap_or(0);
+        let entry = &mut inner.waiting[index];
+        entry.download.priority = top_priority;
+        entry.order = first_order - 1;
+        inner.sort_waiting();
+        drop(inner);
+
+        self.changed.notify_waiters();
+        true
+    }
+
+    /// 修改等待中任务的优先级，任务不在等待中时返回 `false`
+    pub fn set_priority(&self, model_id: Uuid, priority: DownloadPriority) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(index) = inner.waiting_index(model_id) else {
+            return false;
+        };
+        inner.waiting[index].download.priority = priority;
+        inner.sort_waiting();
+        drop(inner);
+
+        self.changed.notify_waiters();
+        true
+    }
+
+    /// 取消等待中的任务，已开始的下载不受影响
+    ///
+    /// 任务不在等待中时返回 `false`。
+    pub fn cancel(&self, model_id: Uuid) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(index) = inner.waiting_index(model_id) else {
+            return false;
+        };
+        inner.waiting.remove(index);
+        drop(inner);
+
+        self.changed.notify_waiters();
+        true
+    }
+}
+
+/// 按顺序模拟下载，返回每个任务的 (id, 开始秒数, 完成秒数)
+///
+/// 前 `max_active` 个未完成的任务同时进行并平分 `throughput`，完成一个后下一个补上。
+fn simulate_schedule(jobs: &[(Uuid, u64)], max_active: usize, throughput: f64) -> Vec<(Uuid, f64, f64)> {
+    let mut pending: Vec<(Uuid, f64, Option<f64>)> = jobs.iter().map(|&(id, bytes)| (id, bytes as f64, None)).collect();
+    let mut schedule = Vec::with_capacity(jobs.len());
+    let mut clock = 0.0;
+
+    while !pending.is_empty() {
+        let running = pending.len().min(max_active);
+        for job in &mut pending[..running] {
+            job.2.get_or_insert(clock);
+        }
+
+        // 推进到下一个任务完成
+        let share = throughput / running as f64;
+        let step = pending[..running].iter().map(|job| job.1 / share).fold(f64::INFINITY, f64::min);
+        clock += step;
+        for job in &mut pending[..running] {
+            job.1 -= share * step;
+        }
+
+        // 剩余不足半字节即视为完成
+        for index in (0..running).rev() {
+            if pending[index].1 < 0.5 {
+                let (id, _, start) = pending.remove(index);
+                schedule.push((id, start.unwrap_or(clock), clock));
+            }
+        }
+    }
+    schedule
+}
+
+/// 以“约 2 分钟后开始，约 8 分钟后完成”的形式描述预计时间
+pub fn format_queue_eta(start: DateTime<Utc>, finish: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let finish_text = format!("约 {}后完成", format_wait(finish - now));
+    if start <= now {
+        finish_text
+    } else {
+        format!("约 {}后开始，{}", format_wait(start - now), finish_text)
+    }
+}
+
+fn format_wait(wait: chrono::Duration) -> String {
+    let minutes = (wait.num_seconds().max(0) + 59) / 60;
+    match minutes {
+        0 | 1 => "1 分钟".to_string(),
+        m if m < 60 => format!("{} 分钟", m),
+        m if m % 60 == 0 => format!("{} 小时", m / 60),
+        m => format!("{} 小时 {} 分钟", m / 60, m % 60),
+    }
+}
+
+impl Default for DownloadQueue {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
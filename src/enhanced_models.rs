@@ -1,19 +1,48 @@
+use std::collections::BTreeSet;
 use dioxus::prelude::*;
 use crate::app_state::AppState;
-use burncloud_service_models::ModelStatus;
+use crate::components::StatCard;
+use crate::integrated_service::license_label;
+use crate::model_table::{ModelTable, TableSort};
+use crate::page_states::EmptyState;
+use crate::search_bar::SearchBar;
+use crate::i18n::Locale;
+use crate::model_type_display::{group_by_model_type, model_type_display, model_type_icon};
+use burncloud_service_models::ModelType;
 
 /// 增强版模型管理组件 - 使用 AppState 获取真实数据
 #[component]
 pub fn EnhancedModelManagement(app_state: AppState) -> Element {
-    let mut search_term = use_signal(|| String::new());
+    let search_term = use_signal(|| String::new());
+    // 按模型类型分组显示，默认平铺
+    let mut group_by_type = use_signal(|| false);
+    // 以表格代替卡片显示，两个表格共用同一排序
+    let mut table_view = use_signal(|| false);
+    let table_sort = use_signal(TableSort::default);
+    // 按许可证筛选，空字符串表示全部
+    let mut license_filter = use_signal(String::new);
 
     // 从 AppState 获取数据
-    let (filtered_installed, filtered_available) = if search_term.read().is_empty() {
+    let (mut filtered_installed, mut filtered_available) = if search_term.read().is_empty() {
         (app_state.installed_models.iter().collect::<Vec<_>>(),
          app_state.available_models.iter().collect::<Vec<_>>())
     } else {
         app_state.search_models(&search_term.read())
     };
+    let licenses: BTreeSet<&str> = app_state.installed_models.iter().map(|m| &m.model)
+        .chain(app_state.available_models.iter().map(|m| &m.model))
+        .map(|model| license_label(model.license.as_deref()))
+        .collect();
+    if !license_filter.read().is_empty() {
+        let license = license_filter.read();
+        filtered_installed.retain(|m| license_label(m.model.license.as_deref()) == license.as_str());
+        filtered_available.retain(|m| license_label(m.model.license.as_deref()) == license.as_str());
+    }
+    let is_filtered = !search_term.read().is_empty() || !license_filter.read().is_empty();
+
+    let installed_groups = group_by_model_type(filtered_installed.iter().copied(), |m| &m.model.model_type);
+    let available_groups = group_by_model_type(filtered_available.iter().copied(), |m| &m.model.model_type);
+    let offline = app_state.service.is_offline();
 
     // 获取统计信息
     let stats = app_state.get_stats();
@@ -46,13 +75,37 @@ pub fn EnhancedModelManagement(app_state: AppState) -> Element {
                         }
                     }
                 }
-                div { class: "mt-lg",
-                    input {
+                div { class: "mt-lg flex items-center gap-md",
+                    SearchBar { value: search_term, result_count: filtered_installed.len() + filtered_available.len() }
+                    label { class: "flex items-center gap-sm text-secondary",
+                        input {
+                            r#type: "checkbox",
+                            checked: group_by_type(),
+                            onchange: move |_| group_by_type.toggle(),
+                        }
+                        "按类型分组"
+                    }
+                    select {
                         class: "input",
-                        style: "max-width: 400px;",
-                        placeholder: "搜索模型...",
-                        value: "{search_term}",
-                        oninput: move |evt| search_term.set(evt.value())
+                        style: "max-width: 200px;",
+                        value: "{license_filter}",
+                        onchange: move |evt| license_filter.set(evt.value()),
+                        option { value: "", "全部许可证" }
+                        for license in licenses {
+                            option { key: "{license}", value: "{license}", "{license}" }
+                        }
+                    }
+                    div { class: "flex gap-sm",
+                        button {
+                            class: if table_view() { "btn btn-secondary" } else { "btn btn-primary" },
+                            onclick: move |_| table_view.set(false),
+                            "🗂️ 卡片"
+                        }
+                        button {
+                            class: if table_view() { "btn btn-primary" } else { "btn btn-secondary" },
+                            onclick: move |_| table_view.set(true),
+                            "📋 表格"
+                        }
                     }
                 }
             }
@@ -93,10 +146,22 @@ pub fn EnhancedModelManagement(app_state: AppState) -> Element {
                         "已安装模型 ({filtered_installed.len()})"
                     }
                     if filtered_installed.is_empty() {
-                        div { class: "empty-state",
-                            p { "没有找到已安装的模型" }
-                            if !search_term.read().is_empty() {
-                                p { class: "text-secondary", "尝试调整搜索条件" }
+                        EmptyState {
+                            icon: "📦",
+                            title: "没有找到已安装的模型",
+                            message: if is_filtered { "尝试调整搜索条件".to_string() } else { String::new() },
+                        }
+                    } else if table_view() {
+                        ModelTable {
+                            installed: filtered_installed.iter().map(|m| (*m).clone()).collect::<Vec<_>>(),
+                            sort: table_sort,
+                        }
+                    } else if group_by_type() {
+                        for (model_type, models) in installed_groups {
+                            TypeGroup { key: "{model_type:?}", model_type, count: models.len(),
+                                for model in models {
+                                    crate::models::InstalledModelCard { model: model.clone() }
+                                }
                             }
                         }
                     } else {
@@ -114,16 +179,29 @@ pub fn EnhancedModelManagement(app_state: AppState) -> Element {
                         "可下载模型 ({filtered_available.len()})"
                     }
                     if filtered_available.is_empty() {
-                        div { class: "empty-state",
-                            p { "没有找到可下载的模型" }
-                            if !search_term.read().is_empty() {
-                                p { class: "text-secondary", "尝试调整搜索条件" }
+                        EmptyState {
+                            icon: "🌐",
+                            title: "没有找到可下载的模型",
+                            message: if is_filtered { "尝试调整搜索条件".to_string() } else { String::new() },
+                        }
+                    } else if table_view() {
+                        ModelTable {
+                            available: filtered_available.iter().map(|m| (*m).clone()).collect::<Vec<_>>(),
+                            offline,
+                            sort: table_sort,
+                        }
+                    } else if group_by_type() {
+                        for (model_type, models) in available_groups {
+                            TypeGroup { key: "{model_type:?}", model_type, count: models.len(),
+                                for model in models {
+                                    crate::models::AvailableModelCard { model: model.clone(), offline }
+                                }
                             }
                         }
                     } else {
                         div { class: "grid gap-lg", style: "grid-template-columns: repeat(auto-fill, minmax(400px, 1fr));",
                             for model in filtered_available.iter() {
-                                crate::models::AvailableModelCard { model: (*model).clone() }
+                                crate::models::AvailableModelCard { model: (*model).clone(), offline }
                             }
                         }
                     }
@@ -172,23 +250,25 @@ pub fn EnhancedModelManagement(app_state: AppState) -> Element {
     }
 }
 
-/// 统计卡片组件
+/// 可折叠的模型类型分组，标题显示类型图标、名称和数量
 #[component]
-fn StatCard(title: String, value: String, icon: String, color: String) -> Element {
-    let color_class = match color.as_str() {
-        "blue" => "stat-card-blue",
-        "green" => "stat-card-green",
-        "red" => "stat-card-red",
-        "purple" => "stat-card-purple",
-        _ => "stat-card-default"
-    };
+fn TypeGroup(model_type: ModelType, count: usize, children: Element) -> Element {
+    let mut expanded = use_signal(|| true);
+    let icon = model_type_icon(&model_type);
+    let name = model_type_display(&model_type, Locale::default());
+    let arrow = if expanded() { "▾" } else { "▸" };
 
     rsx! {
-        div { class: "stat-card {color_class}",
-            div { class: "stat-icon", "{icon}" }
-            div { class: "stat-content",
-                div { class: "stat-value", "{value}" }
-                div { class: "stat-title", "{title}" }
+        div { class: "type-group mb-lg",
+            button {
+                class: "btn btn-secondary type-group-header mb-md",
+                onclick: move |_| expanded.toggle(),
+                "{arrow} {icon} {name} ({count})"
+            }
+            if expanded() {
+                div { class: "grid gap-lg", style: "grid-template-columns: repeat(auto-fill, minmax(400px, 1fr));",
+                    {children}
+                }
             }
         }
     }
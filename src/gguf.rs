@@ -0,0 +1,228 @@
+// GGUF 文件头和元数据解析
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// GGUF 文件开头的魔术字节
+pub const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+/// 数组最多嵌套的层数，防止构造的文件用深层嵌套耗尽栈
+const MAX_ARRAY_DEPTH: usize = 8;
+
+/// GGUF 文件头中的信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct GgufMetadata {
+    pub version: u32,
+    pub tensor_count: u64,
+    /// 标量元数据（如 `general.architecture`），数组值只跳过不保存
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+impl GgufMetadata {
+    /// 模型架构，取自 `general.architecture`
+    pub fn architecture(&self) -> Option<&str> {
+        self.metadata.get("general.architecture")?.as_str()
+    }
+}
+
+/// GGUF 解析错误
+#[derive(Debug, thiserror::Error)]
+pub enum GgufError {
+    #[error("I/O错误: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("不是 GGUF 文件")]
+    InvalidMagic,
+    #[error("不支持的 GGUF 版本: {0}")]
+    UnsupportedVersion(u32),
+    #[error("文件头不完整: {0}")]
+    Truncated(String),
+    #[error("未知的元数据类型: {0}")]
+    InvalidValueType(u32),
+    #[error("数组嵌套过深: {0}")]
+    NestingTooDeep(String),
+}
+
+/// 读取 GGUF 文件头和元数据
+///
+/// 支持 GGUF v2 和 v3；长度字段超过剩余文件大小时视为文件头不完整，不会按其分配内存。
+pub fn parse_gguf_metadata(path: &Path) -> Result<GgufMetadata, GgufError> {
+    let file = File::open(path)?;
+    let remaining = file.metadata()?.len();
+    let mut reader = GgufReader { inner: BufReader::new(file), remaining };
+
+    if reader.bytes::<4>("magic")? != *GGUF_MAGIC {
+        return Err(GgufError::InvalidMagic);
+    }
+    let version = u32::from_le_bytes(reader.bytes("version")?);
+    if !(2..=3).contains(&version) {
+        return Err(GgufError::UnsupportedVersion(version));
+    }
+    let tensor_count = reader.u64("tensor_count")?;
+    let kv_count = reader.u64("metadata_kv_count")?;
+
+    let mut metadata = BTreeMap::new();
+    for _ in 0..kv_count {
+        let key = reader.string("metadata key")?;
+        let value_type = u32::from_le_bytes(reader.bytes("metadata type")?);
+        if let Some(value) = reader.value(value_type, &key, 0)? {
+            metadata.insert(key, value);
+        }
+    }
+
+    Ok(GgufMetadata { version, tensor_count, metadata })
+}
+
+/// 在阻塞线程池中读取 GGUF 文件头，避免在异步任务中做同步文件读取
+pub async fn read_gguf_metadata(path: &Path) -> Result<GgufMetadata, GgufError> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || parse_gguf_metadata(&path))
+        .await
+        .map_err(|e| GgufError::Io(std::io::Error::other(e)))?
+}
+
+/// 记录剩余字节数的读取器，用于检查长度字段
+struct GgufReader<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R: Read> GgufReader<R> {
+    fn bytes<const N: usize>(&mut self, field: &str) -> Result<[u8; N], GgufError> {
+        self.reserve(N as u64, field)?;
+        let mut buf = [0u8; N];
+        self.inner.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn u64(&mut self, field: &str) -> Result<u64, GgufError> {
+        Ok(u64::from_le_bytes(self.bytes(field)?))
+    }
+
+    fn reserve(&mut self, len: u64, field: &str) -> Result<(), GgufError> {
+        if len > self.remaining {
+            return Err(GgufError::Truncated(field.to_string()));
+        }
+        self.remaining -= len;
+        Ok(())
+    }
+
+    fn string(&mut self, field: &str) -> Result<String, GgufError> {
+        let len = self.u64(field)?;
+        self.reserve(len, field)?;
+        let mut buf = vec![0u8; len as usize];
+        self.inner.read_exact(&mut buf)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    /// 读取一个元数据值，数组返回 `None`；`depth` 是当前所在数组的嵌套层数
+    fn value(&mut self, value_type: u32, key: &str, depth: usize) -> Result<Option<serde_json::Value>, GgufError> {
+        use serde_json::json;
+        let value = match value_type {
+            0 => json!(self.bytes::<1>(key)?[0]),
+            1 => json!(i8::from_le_bytes(self.bytes(key)?)),
+            2 => json!(u16::from_le_bytes(self.bytes(key)?)),
+            3 => json!(i16::from_le_bytes(self.bytes(key)?)),
+            4 => json!(u32::from_le_bytes(self.bytes(key)?)),
+            5 => json!(i32::from_le_bytes(self.bytes(key)?)),
+            6 => json!(f32::from_le_bytes(self.bytes(key)?)),
+            7 => json!(self.bytes::<1>(key)?[0] != 0),
+            8 => json!(self.string(key)?),
+            9 => {
+                if depth >= MAX_ARRAY_DEPTH {
+                    return Err(GgufError::NestingTooDeep(key.to_string()));
+                }
+                let item_type = u32::from_le_bytes(self.bytes(key)?);
+                let len = self.u64(key)?;
+                // 每个元素至少占一个字节
+                if len > self.remaining {
+                    return Err(GgufError::Truncated(key.to_string()));
+                }
+                for _ in 0..len {
+                    self.value(item_type, key, depth + 1)?;
+                }
+                return Ok(None);
+            }
+            10 => json!(self.u64(key)?),
+            11 => json!(i64::from_le_bytes(self.bytes(key)?)),
+            12 => json!(f64::from_le_bytes(self.bytes(key)?)),
+            other => return Err(GgufError::InvalidValueType(other)),
+        };
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gguf_string(buf: &mut Vec<u8>, value: &str) {
+        buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        buf.extend_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn test_parse_header_and_scalar_metadata() {
+        let mut header = GGUF_MAGIC.to_vec();
+        header.extend_from_slice(&3u32.to_le_bytes());
+        header.extend_from_slice(&291u64.to_le_bytes());
+        header.extend_from_slice(&3u64.to_le_bytes());
+        gguf_string(&mut header, "general.architecture");
+        header.extend_from_slice(&8u32.to_le_bytes());
+        gguf_string(&mut header, "llama");
+        gguf_string(&mut header, "tokenizer.ggml.tokens");
+        header.extend_from_slice(&9u32.to_le_bytes());
+        header.extend_from_slice(&8u32.to_le_bytes());
+        header.extend_from_slice(&2u64.to_le_bytes());
+        gguf_string(&mut header, "<s>");
+        gguf_string(&mut header, "</s>");
+        gguf_string(&mut header, "llama.context_length");
+        header.extend_from_slice(&4u32.to_le_bytes());
+        header.extend_from_slice(&4096u32.to_le_bytes());
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("model.gguf");
+        std::fs::write(&path, &header).unwrap();
+
+        let metadata = parse_gguf_metadata(&path).unwrap();
+        assert_eq!(metadata.version, 3);
+        assert_eq!(metadata.tensor_count, 291);
+        assert_eq!(metadata.architecture(), Some("llama"));
+        assert_eq!(metadata.metadata["llama.context_length"], 4096);
+        assert!(!metadata.metadata.contains_key("tokenizer.ggml.tokens"));
+
+        // 声明的字符串长度超出文件大小
+        std::fs::write(&path, &header[..header.len() - 20]).unwrap();
+        assert!(matches!(parse_gguf_metadata(&path), Err(GgufError::Truncated(_))));
+    }
+
+    #[test]
+    fn test_deeply_nested_arrays_are_rejected() {
+        let nested_header = |depth: usize| {
+            let mut header = GGUF_MAGIC.to_vec();
+            header.extend_from_slice(&3u32.to_le_bytes());
+            header.extend_from_slice(&0u64.to_le_bytes());
+            header.extend_from_slice(&1u64.to_le_bytes());
+            gguf_string(&mut header, "nested");
+            header.extend_from_slice(&9u32.to_le_bytes());
+            // 每层是只含一个元素的数组，最内层是一个 u32
+            for level in 0..depth {
+                let item_type: u32 = if level + 1 == depth { 4 } else { 9 };
+                header.extend_from_slice(&item_type.to_le_bytes());
+                header.extend_from_slice(&1u64.to_le_bytes());
+            }
+            header.extend_from_slice(&7u32.to_le_bytes());
+            header
+        };
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("nested.gguf");
+        std::fs::write(&path, nested_header(MAX_ARRAY_DEPTH)).unwrap();
+        assert!(parse_gguf_metadata(&path).is_ok());
+
+        // 构造的深层嵌套返回错误而不是栈溢出
+        std::fs::write(&path, nested_header(100_000)).unwrap();
+        assert!(matches!(parse_gguf_metadata(&path), Err(GgufError::NestingTooDeep(_))));
+    }
+}
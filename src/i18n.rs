@@ -0,0 +1,171 @@
+// 界面文案的多语言支持
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    Zh,
+    En,
+}
+
+impl Locale {
+    /// 从 `LC_ALL` / `LC_MESSAGES` / `LANG` 读取系统语言
+    pub fn system() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|tag| Self::from_tag(&tag))
+            .unwrap_or(Locale::En)
+    }
+
+    /// 解析语言标签，如 `zh_CN.UTF-8`、`en-US`；非中文一律视为英文
+    pub fn from_tag(tag: &str) -> Self {
+        if tag.trim().to_lowercase().starts_with("zh") {
+            Locale::Zh
+        } else {
+            Locale::En
+        }
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+/// 文案表：(键, 中文, 英文)
+static MESSAGES: &[(&str, &str, &str)] = &[
+    // 模型类型
+    ("type.chat", "对话模型", "Conversational"),
+    ("type.code", "代码生成", "Code Generation"),
+    ("type.text", "文本生成", "Text Generation"),
+    ("type.embedding", "文本嵌入", "Text Embedding"),
+    ("type.image", "图像处理", "Image Processing"),
+    ("type.image_generation", "图像生成", "Image Generation"),
+    ("type.audio", "音频处理", "Audio Processing"),
+    ("type.speech", "语音处理", "Speech Processing"),
+    ("type.video", "视频处理", "Video Processing"),
+    ("type.multimodal", "多模态", "Multimodal"),
+    ("type.other", "其他", "Other"),
+    // 模型状态
+    ("status.running", "运行中", "Running"),
+    ("status.stopped", "已停止", "Stopped"),
+    ("status.starting", "启动中", "Starting"),
+    ("status.stopping", "停止中", "Stopping"),
+    ("status.error", "错误", "Error"),
+    ("status.unknown", "未知", "Unknown"),
+    // 错误提示
+    ("error.not_found", "未找到请求的模型。", "The requested model was not found."),
+    ("error.conflict", "同名模型已存在。", "A model with this name already exists."),
+    ("error.invalid_input", "输入无效", "Invalid input"),
+    ("error.unauthorized", "你没有执行此操作的权限。", "You don't have permission to perform this action."),
+    ("error.unexpected", "发生意外错误，请重试。", "An unexpected error occurred. Please try again."),
+    ("error.init_failed", "模型服务初始化失败，请检查配置。", "Failed to initialize the model service. Please check your configuration."),
+    ("error.resource_not_found", "资源不存在", "Resource not found"),
+    ("error.io", "发生文件系统错误。", "A file system error occurred."),
+    ("error.database", "发生数据库错误，请重试。", "A database error occurred. Please try again."),
+    ("error.schema_missing", "模型数据库尚未初始化。", "The model database has not been set up yet."),
+    ("error.schema_newer", "模型数据库由更新的版本创建，请升级应用。", "The model database was created by a newer version. Please update the application."),
+    ("error.serialization", "无法读取已保存的数据。", "Stored data could not be read."),
+    ("error.invalid_manifest", "模型清单无效", "The manifest is invalid"),
+    ("error.discovery", "无法获取模型目录，请检查网络连接。", "Could not fetch the model catalog. Please check your connection."),
+    ("error.cancelled", "操作已取消。", "The operation was cancelled."),
+    ("error.dependency_cycle", "模型依赖存在循环", "Model dependencies form a cycle"),
+    // 模型卡片
+    ("card.property", "属性", "Property"),
+    ("card.value", "值", "Value"),
+    ("card.name", "名称", "Name"),
+    ("card.version", "版本", "Version"),
+    ("card.type", "类型", "Type"),
+    ("card.provider", "提供商", "Provider"),
+    ("card.license", "许可证", "License"),
+    ("card.license_unknown", "未注明", "Not specified"),
+    ("card.size", "大小", "Size"),
+    ("card.context_length", "上下文长度", "Context length"),
+    ("card.languages", "语言", "Languages"),
+    ("card.tags", "标签", "Tags"),
+    ("card.installation", "安装状态", "Installation"),
+    ("card.status", "状态", "Status"),
+    ("card.usage_count", "使用次数", "Usage count"),
+    ("card.last_used", "最近使用", "Last used"),
+    ("card.never_used", "从未使用", "Never"),
+    ("card.runtime_config", "运行配置", "Runtime configuration"),
+    ("card.install_source", "安装来源", "Installed from"),
+    // 安装来源
+    ("source.discovery", "模型目录", "Model catalog"),
+    ("source.local_file", "本地文件", "Local file"),
+    ("source.ollama", "Ollama", "Ollama"),
+    ("source.manifest", "模型清单", "Manifest"),
+    ("source.manual", "手动添加", "Added manually"),
+    // 模型能力
+    ("capability.vision", "视觉", "Vision"),
+    ("capability.function_calling", "函数调用", "Function calling"),
+    ("capability.json_mode", "JSON 模式", "JSON mode"),
+    // 模型表格
+    ("table.name", "名称", "Name"),
+    ("table.type", "类型", "Type"),
+    ("table.size", "大小", "Size"),
+    ("table.provider", "提供商", "Provider"),
+    ("table.license", "许可证", "License"),
+    ("table.rating", "评分", "Rating"),
+    ("table.status_actions", "状态 / 操作", "Status / Actions"),
+    ("table.available", "可下载", "Available"),
+    ("table.download", "下载", "Download"),
+    // 下载队列
+    ("download.active", "下载中", "Downloading"),
+    ("download.waiting", "排队中", "Queued"),
+    ("download.estimating", "正在估算剩余时间", "Estimating time remaining"),
+    // 确认弹窗
+    ("confirm.cancel", "取消", "Cancel"),
+    ("confirm.ok", "确认", "Confirm"),
+    // 页面状态
+    ("state.load_failed", "数据加载失败", "Failed to load data"),
+    ("state.retry", "重试", "Retry"),
+    // 通知
+    ("notify.load_failed", "数据加载失败", "Failed to load data"),
+    ("notify.init_failed", "应用初始化失败", "Failed to initialize the application"),
+    ("notify.available_failed", "可用模型加载失败，仅显示已安装模型", "Available models could not be loaded; showing installed models only"),
+];
+
+/// 查找文案，未知的键原样返回
+pub fn t(key: &'static str, locale: Locale) -> &'static str {
+    MESSAGES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, zh, en)| match locale {
+            Locale::Zh => *zh,
+            Locale::En => *en,
+        })
+        .unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_known_key_resolves_in_both_locales() {
+        assert_eq!(t("status.running", Locale::Zh), "运行中");
+        assert_eq!(t("status.running", Locale::En), "Running");
+        assert_eq!(t("no.such.key", Locale::En), "no.such.key");
+    }
+
+    #[test]
+    fn test_table_has_unique_complete_entries() {
+        let mut keys = HashSet::new();
+        for (key, zh, en) in MESSAGES {
+            assert!(keys.insert(*key), "duplicate key {}", key);
+            assert!(!zh.is_empty() && !en.is_empty(), "missing text for {}", key);
+        }
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("zh_CN.UTF-8"), Locale::Zh);
+        assert_eq!(Locale::from_tag("zh-Hant"), Locale::Zh);
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Locale::En);
+        assert_eq!(Locale::from_tag("C"), Locale::En);
+    }
+}
@@ -1,11 +1,39 @@
 use burncloud_service_models::{
-    Model, InstalledModel, CreateModelRequest, UpdateModelRequest, ModelFilter,
-    ModelsService, ServiceError, ModelType, ModelStatus, SizeCategory
+    Model, InstalledModel, AvailableModel, CreateModelRequest, UpdateModelRequest, ModelFilter,
+    ModelsService, ServiceError, ModelType, ModelStatus, SizeCategory, RuntimeConfig
 };
 use burncloud_database::Database;
+use async_trait::async_trait;
+use crate::catalog_export::{write_catalog, write_catalog_ndjson, CatalogFormat};
+use crate::data_service::{installed_model_matches, ModelDataSource, ResourceOverview, SystemRequirements};
+use crate::diagnostics::{is_error_line, redact_home, redact_secrets, DiagnosticLogEntry, DiagnosticReport, HostCapabilities, DIAGNOSTIC_LOG_LINES};
+use crate::discovery::{DiscoveredModel, DiscoveryError, ModelDiscoveryClient, ModelSearchRequest};
+use crate::checksum::digest_file;
+use crate::download::{available_disk_space, parse_expected_checksum, DownloadError, ModelDownloadManager};
+use crate::download_queue::DownloadQueue;
+use crate::validation::{ChecksumType, ModelValidator, ValidationConfig, ValidatorError};
+use crate::manifest::{format_manifest_issues, parse_manifest, ManifestIssue};
+use crate::i18n::{t, Locale};
+use crate::model_config::{default_runtime_config, ModelConfigExt};
+use crate::model_capabilities::{Capability, ModelCapabilitiesExt};
+use crate::model_type_display::{model_type_display, SortedTypeCounts};
+use crate::model_logs::{ModelLogBuffer, ModelLogEntry};
+use crate::notification_sink::{emit_all, NotificationSink};
+use crate::state::Notification;
+use crate::preflight::{PreflightReport, detect_host_memory_bytes, model_requirements};
+use crate::schema::{check_supported, migrate_client_schema, missing_client_tables, read_schema_version, sql_quote};
 use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use futures_util::TryStreamExt;
+use tokio_util::sync::CancellationToken;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+pub use crate::schema::CURRENT_SCHEMA_VERSION;
 
 /// Client-level service that integrates with the complete database backend
 ///
@@ -14,6 +42,85 @@ use std::collections::HashMap;
 #[derive(Clone)]
 pub struct IntegratedModelService {
     service: Arc<ModelsService>,
+    database: Arc<Database>,
+    config: Arc<IntegratedServiceConfig>,
+    logs: Arc<ModelLogBuffer>,
+    idempotency_keys: Arc<tokio::sync::Mutex<HashMap<String, (Uuid, Instant)>>>,
+    maintenance: Arc<tokio::sync::Mutex<()>>,
+    /// Per-model locks that serialize mutations of the same model
+    model_locks: Arc<std::sync::Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>>>,
+    /// Catalog entries fetched by `get_model_details`, keyed by local model id
+    discovery_details: Arc<std::sync::Mutex<HashMap<Uuid, DiscoveredModel>>>,
+    /// Holds the trusted keys that manifest signatures are checked against
+    manifest_validator: Option<Arc<ModelValidator>>,
+    /// Shares one statistics aggregation between concurrent `get_statistics` calls
+    statistics_loads: Arc<SingleFlight<(), ClientModelStats>>,
+    /// Shares one catalog fetch between concurrent detail requests for the same model
+    discovery_fetches: Arc<SingleFlight<Uuid, Option<DiscoveredModel>>>,
+    /// Runtime configs loaded by `prewarm_model`, used once by the next start
+    prewarmed_configs: Arc<std::sync::Mutex<HashMap<Uuid, Option<RuntimeConfig>>>>,
+    /// Receive alerts about failed starts, failed validations and model errors
+    notification_sinks: Vec<Arc<dyn NotificationSink>>,
+    /// Stops the background checksum scrub started by `start_scrub`
+    scrub: Arc<std::sync::Mutex<Option<CancellationToken>>>,
+    /// Shared by every manager from `download_manager`, so the UI sees all queued downloads
+    download_queue: Arc<DownloadQueue>,
+}
+
+/// Read size used when pulling model files into the page cache
+const PREWARM_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+/// File extensions picked up by `scan_and_register`
+const LOCAL_MODEL_EXTENSIONS: &[&str] = &["gguf", "ggml", "safetensors", "pt", "pth", "onnx", "bin"];
+
+/// Pause between models during a checksum scrub, so a pass doesn't saturate the disk
+const SCRUB_MODEL_PAUSE: Duration = Duration::from_secs(5);
+
+/// How long an idempotency key maps to the model it created
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Most buckets a growth time series returns
+pub const MAX_TIMESERIES_BUCKETS: usize = 1000;
+
+/// License label used for models that don't declare one
+pub const UNKNOWN_LICENSE: &str = "Unknown";
+
+/// A model's license for display and grouping; blank or missing licenses become [`UNKNOWN_LICENSE`]
+pub fn license_label(license: Option<&str>) -> &str {
+    license.map(str::trim).filter(|license| !license.is_empty()).unwrap_or(UNKNOWN_LICENSE)
+}
+
+/// Configuration for [`IntegratedModelService`]
+#[derive(Debug, Clone)]
+pub struct IntegratedServiceConfig {
+    /// Database file path; `None` uses `$HOME/burncloud/models.db`
+    pub database_path: Option<String>,
+    /// Base directory for installs when no explicit path is given
+    pub default_install_dir: PathBuf,
+    /// Never make outbound requests: discovery serves cached results only and
+    /// downloads are refused, while local installs keep working
+    pub offline: bool,
+    /// Refuse to import manifests without a valid signature from a trusted key
+    pub require_signed_manifests: bool,
+    /// Near-duplicate tags merged by `normalize_tags`, alias -> canonical tag;
+    /// both sides are compared case-insensitively
+    pub tag_aliases: HashMap<String, String>,
+    /// Severity overrides for the cross-field rules in `validate_create_request`;
+    /// rules not listed use [`ConsistencyRule::default_severity`]
+    pub consistency_rules: HashMap<ConsistencyRule, RuleSeverity>,
+}
+
+impl Default for IntegratedServiceConfig {
+    fn default() -> Self {
+        Self {
+            database_path: None,
+            default_install_dir: PathBuf::from(format!("{}/burncloud/models", home_dir())),
+            offline: false,
+            require_signed_manifests: false,
+            tag_aliases: HashMap::new(),
+            consistency_rules: HashMap::new(),
+        }
+    }
 }
 
 impl IntegratedModelService {
@@ -21,12 +128,21 @@ impl IntegratedModelService {
     ///
     /// This initializes the complete database stack and provides a client interface.
     pub async fn new(database_path: Option<String>) -> Result<Self, ClientError> {
-        let db_path = database_path.unwrap_or_else(|| {
-            let home = std::env::var("HOME")
-                .or_else(|_| std::env::var("USERPROFILE"))
-                .unwrap_or_else(|_| ".".to_string());
-            format!("{}/burncloud/models.db", home)
-        });
+        Self::with_config(IntegratedServiceConfig {
+            database_path,
+            ..Default::default()
+        }).await
+    }
+
+    /// Create a new integrated model service from an explicit configuration
+    ///
+    /// Safe to call repeatedly against the same database. A database without
+    /// any client schema is bootstrapped at the current version; one created by
+    /// an older client is migrated forward before use, and one created by a
+    /// newer client is rejected with `UnsupportedSchemaVersion`. Use `open` to
+    /// attach without changing the schema.
+    pub async fn with_config(config: IntegratedServiceConfig) -> Result<Self, ClientError> {
+        let db_path = config.database_path.clone().unwrap_or_else(default_database_path);
 
         // Ensure directory exists
         if let Some(parent) = std::path::Path::new(&db_path).parent() {
@@ -34,24 +150,258 @@ impl IntegratedModelService {
                 .map_err(|e| ClientError::InitializationFailed(format!("Failed to create directory: {}", e)))?;
         }
 
-        // Initialize database core
-        let mut database = Database::new(&db_path);
-        database.initialize().await
-            .map_err(|e| ClientError::InitializationFailed(format!("Database initialization failed: {}", e)))?;
+        let database = connect_database(&db_path).await?;
+        let version = read_schema_version(&database).await?;
+        check_supported(version)?;
+        if version < CURRENT_SCHEMA_VERSION {
+            migrate_client_schema(&database, version).await?;
+        }
+
+        Self::from_database(database, config).await
+    }
+
+    /// Attach to an existing, already-initialized database
+    ///
+    /// Unlike `new`, this never creates the client schema and fails with
+    /// `SchemaMissing` if the file doesn't exist or wasn't set up by this client.
+    pub async fn open(database_path: impl AsRef<std::path::Path>) -> Result<Self, ClientError> {
+        let path = database_path.as_ref();
+        if !path.is_file() {
+            return Err(ClientError::SchemaMissing(format!("No database found at {}", path.display())));
+        }
+
+        let db_path = path.to_string_lossy().to_string();
+        let database = connect_database(&db_path).await?;
+
+        let version = read_schema_version(&database).await?;
+        if version == 0 {
+            return Err(ClientError::SchemaMissing(format!("Database at {} has no client schema", db_path)));
+        }
+        check_supported(version)?;
+
+        let missing = missing_client_tables(&database).await?;
+        if version == CURRENT_SCHEMA_VERSION && !missing.is_empty() {
+            return Err(ClientError::SchemaMissing(format!(
+                "Database at {} is missing tables: {}", db_path, missing.join(", ")
+            )));
+        }
+
+        Self::from_database(database, IntegratedServiceConfig {
+            database_path: Some(db_path),
+            ..Default::default()
+        }).await
+    }
 
+    /// Build the service layer on top of a connected database
+    async fn from_database(database: Database, config: IntegratedServiceConfig) -> Result<Self, ClientError> {
         let database = Arc::new(database);
 
         // Initialize service layer
-        let service = Arc::new(ModelsService::new(database).await
+        let service = Arc::new(ModelsService::new(database.clone()).await
             .map_err(|e| ClientError::InitializationFailed(format!("Service initialization failed: {}", e)))?);
 
-        Ok(Self { service })
+        Ok(Self {
+            service,
+            database,
+            config: Arc::new(config),
+            logs: Arc::new(ModelLogBuffer::default()),
+            idempotency_keys: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            maintenance: Arc::new(tokio::sync::Mutex::new(())),
+            model_locks: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            discovery_details: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            manifest_validator: None,
+            statistics_loads: Arc::new(SingleFlight::default()),
+            discovery_fetches: Arc::new(SingleFlight::default()),
+            prewarmed_configs: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            notification_sinks: Vec::new(),
+            scrub: Arc::new(std::sync::Mutex::new(None)),
+            download_queue: Arc::new(DownloadQueue::default()),
+        })
+    }
+
+    /// Forward alerts to `sink` in addition to any sinks already registered
+    pub fn with_notification_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.notification_sinks.push(sink);
+        self
+    }
+
+    /// Verify manifest signatures against `validator`'s trusted keys
+    pub fn with_manifest_validator(mut self, validator: ModelValidator) -> Self {
+        self.manifest_validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// Get the schema version recorded in the database
+    pub async fn schema_version(&self) -> Result<u32, ClientError> {
+        read_schema_version(&self.database).await
+    }
+
+    /// Check whether the database was created by an older client and needs `migrate`
+    pub async fn needs_migration(&self) -> Result<bool, ClientError> {
+        Ok(self.schema_version().await? < CURRENT_SCHEMA_VERSION)
+    }
+
+    /// Apply forward migrations up to `CURRENT_SCHEMA_VERSION`
+    ///
+    /// Returns the version the database is at afterwards. Calling this on an
+    /// up-to-date database is a no-op.
+    pub async fn migrate(&self) -> Result<u32, ClientError> {
+        let version = self.schema_version().await?;
+        if version == CURRENT_SCHEMA_VERSION {
+            return Ok(version);
+        }
+        migrate_client_schema(&self.database, version).await
+    }
+
+    /// Reclaim free space and refresh query planner statistics
+    ///
+    /// Runs `VACUUM` followed by `ANALYZE`; the reported sizes bracket the `VACUUM`. Only one maintenance run happens at a
+    /// time, and SQLite refuses to vacuum inside an open transaction, which is
+    /// reported as `OperationNotAllowed`.
+    pub async fn compact(&self) -> Result<CompactReport, ClientError> {
+        let _guard = self.maintenance.try_lock()
+            .map_err(|_| ClientError::OperationNotAllowed("Database maintenance is already running".to_string()))?;
+
+        let started = Instant::now();
+        let size_before = self.database_size_bytes().await?;
+
+        self.database.execute_query("VACUUM").await.map_err(|e| {
+            let message = e.to_string();
+            if message.contains("within a transaction") {
+                ClientError::OperationNotAllowed(format!("Cannot compact during a transaction: {}", message))
+            } else {
+                ClientError::DatabaseError(message)
+            }
+        })?;
+        let size_after = self.database_size_bytes().await?;
+
+        // Statistics tables are small; they're left out of the reclaimed figure
+        self.database.execute_query("ANALYZE").await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(CompactReport {
+            size_before,
+            size_after,
+            bytes_reclaimed: size_before as i64 - size_after as i64,
+            duration: started.elapsed(),
+        })
+    }
+
+    /// Current database size as reported by SQLite
+    async fn database_size_bytes(&self) -> Result<u64, ClientError> {
+        let (page_count,): (i64,) = self.database.fetch_one("PRAGMA page_count").await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        let (page_size,): (i64,) = self.database.fetch_one("PRAGMA page_size").await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok((page_count.max(0) as u64) * (page_size.max(0) as u64))
+    }
+
+    /// Get the service configuration
+    pub fn config(&self) -> &IntegratedServiceConfig {
+        &self.config
+    }
+
+    /// The database connection the service was opened with
+    pub fn database(&self) -> Arc<Database> {
+        Arc::clone(&self.database)
+    }
+
+    /// Whether the service was configured to run without network access
+    pub fn is_offline(&self) -> bool {
+        self.config.offline
+    }
+
+    /// Create a discovery client that honors the offline setting
+    pub fn discovery_client(&self, base_url: impl Into<String>) -> Result<ModelDiscoveryClient, DiscoveryError> {
+        Ok(ModelDiscoveryClient::new(base_url.into())?.with_offline(self.config.offline))
+    }
+
+    /// Create a download manager that honors the offline setting and uses the service's download queue
+    pub fn download_manager(&self, download_dir: PathBuf) -> Result<ModelDownloadManager, DownloadError> {
+        Ok(ModelDownloadManager::new(download_dir)?
+            .with_offline(self.config.offline)
+            .with_queue(Arc::clone(&self.download_queue)))
+    }
+
+    /// Downloads started through managers from `download_manager`
+    pub fn download_queue(&self) -> &Arc<DownloadQueue> {
+        &self.download_queue
+    }
+
+    /// Estimate when a queued download starts and finishes, see [`DownloadQueue::estimated_start_and_finish`]
+    pub fn estimated_start_and_finish(&self, model_id: Uuid) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        self.download_queue.estimated_start_and_finish(model_id)
     }
 
     /// Create a new model
+    ///
+    /// The request goes through `validate_create_request` first; rules at
+    /// [`RuleSeverity::Warn`] don't block the create and are noted in the model's log.
     pub async fn create_model(&self, request: CreateModelRequest) -> Result<Model, ClientError> {
-        self.service.create_model(request).await
-            .map_err(ClientError::ServiceError)
+        self.create_model_installing_at(request, None).await
+    }
+
+    /// Create a model that the caller installs at `pending_install` right afterwards,
+    /// so its file path already counts as covered by an install record
+    async fn create_model_installing_at(
+        &self,
+        request: CreateModelRequest,
+        pending_install: Option<&str>,
+    ) -> Result<Model, ClientError> {
+        let issues = self.check_create_request(&request, pending_install).await?;
+        let model = self.create_model_unchecked(request).await?;
+        for issue in issues {
+            self.append_model_log(model.id, format!("Created with a warning: {}", issue.message));
+        }
+        Ok(model)
+    }
+
+    /// Create a model whose request the caller validated its own way
+    ///
+    /// Used by `import_manifest`: manifest entries are checked as a whole before
+    /// anything is written and carry no file size yet.
+    async fn create_model_unchecked(&self, request: CreateModelRequest) -> Result<Model, ClientError> {
+        let history = self.begin_history(
+            AuditOperation::Create, None, Some(ModelEventKind::Created), serde_json::json!({ "name": request.name }),
+        ).await?;
+        match self.service.create_model(request).await {
+            Ok(model) => {
+                self.finish_history(history.for_model(model.id), true).await;
+                Ok(model)
+            }
+            Err(e) => {
+                self.finish_history(history, false).await;
+                Err(ClientError::ServiceError(e))
+            }
+        }
+    }
+
+    /// Create a model, deduplicating retries that carry the same idempotency key
+    ///
+    /// If the key was used within the last few minutes and its model still exists,
+    /// that model is returned instead of attempting another create. Keys are kept
+    /// in memory only. Without a key this behaves like `create_model`.
+    pub async fn create_model_idempotent(
+        &self,
+        request: CreateModelRequest,
+        idempotency_key: Option<String>,
+    ) -> Result<Model, ClientError> {
+        let Some(key) = idempotency_key else {
+            return self.create_model(request).await;
+        };
+
+        // Held across the create so concurrent retries with the same key can't both insert
+        let mut keys = self.idempotency_keys.lock().await;
+        keys.retain(|_, (_, created_at)| created_at.elapsed() < IDEMPOTENCY_KEY_TTL);
+
+        if let Some((model_id, _)) = keys.get(&key) {
+            if let Some(model) = self.get_model(*model_id).await? {
+                return Ok(model);
+            }
+        }
+
+        let model = self.create_model(request).await?;
+        keys.insert(key, (model.id, Instant::now()));
+        Ok(model)
     }
 
     /// Get a model by ID
@@ -67,6 +417,60 @@ impl IntegratedModelService {
             .map_err(ClientError::ServiceError)
     }
 
+    /// List models that support every capability in `required`
+    ///
+    /// Capabilities are derived client-side (see [`ModelCapabilitiesExt`]), so this
+    /// filters the result of `filter`; its `limit`/`offset` apply before the
+    /// capability check and a page may come back shorter than `limit`.
+    pub async fn list_models_with_capabilities(
+        &self,
+        filter: Option<ModelFilter>,
+        required: &[Capability],
+    ) -> Result<Vec<Model>, ClientError> {
+        let mut models = self.list_models(filter).await?;
+        models.retain(|model| model.capabilities().has_all(required));
+        Ok(models)
+    }
+
+    /// List one page of models for infinite scroll
+    ///
+    /// Models come oldest first by `(updated_at, id)`, starting after `cursor` (or
+    /// from the beginning). The returned cursor continues with the next page and is
+    /// `None` once the last page was returned. Unlike offset paging, models added
+    /// between two loads never shift rows that were already shown; they appear on a
+    /// later page instead.
+    pub async fn list_models_after(&self, cursor: Option<Cursor>, limit: u32) -> Result<(Vec<Model>, Option<Cursor>), ClientError> {
+        if limit == 0 {
+            return Err(ClientError::ValidationFailed("Page limit must be at least 1".to_string()));
+        }
+        let limit = limit as usize;
+        // One extra row tells whether another page follows
+        let after = cursor.map(|cursor| format!(
+            " WHERE (updated_at, id) > ({}, {})",
+            sql_quote(&cursor.updated_at.to_rfc3339()),
+            sql_quote(&cursor.id.to_string()),
+        ));
+        let sql = format!(
+            "SELECT id FROM models{} ORDER BY updated_at, id LIMIT {}",
+            after.unwrap_or_default(),
+            limit + 1,
+        );
+        let rows: Vec<(String,)> = self.database.fetch_all(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        let has_more = rows.len() > limit;
+        let mut models = Vec::with_capacity(limit);
+        for (id,) in rows.into_iter().take(limit) {
+            let Ok(id) = Uuid::parse_str(&id) else { continue };
+            // Models deleted since the query are left out
+            if let Some(model) = self.get_model(id).await? {
+                models.push(model);
+            }
+        }
+        let next = if has_more { models.last().map(Cursor::after) } else { None };
+        Ok((models, next))
+    }
+
     /// Search models by query string
     pub async fn search_models(&self, query: &str, limit: Option<u32>) -> Result<Vec<Model>, ClientError> {
         let filter = ModelFilter {
@@ -80,14 +484,660 @@ impl IntegratedModelService {
 
     /// Update a model
     pub async fn update_model(&self, id: Uuid, request: UpdateModelRequest) -> Result<Model, ClientError> {
-        self.service.update_model(id, request).await
-            .map_err(ClientError::ServiceError)
+        let _guard = self.lock_model(id).await;
+        let history = self.begin_history(AuditOperation::Update, Some(id), None, update_details(&request)).await?;
+        let result = self.service.update_model(id, request).await
+            .map_err(ClientError::ServiceError);
+        self.finish_history(history, result.is_ok()).await;
+        result
+    }
+
+    /// Wait for exclusive access to mutate a model
+    ///
+    /// Mutations of the same model run one at a time while different models proceed
+    /// in parallel; reads never take the lock. The lock is not reentrant, so methods
+    /// that hold it call the private helpers rather than other public mutators.
+    async fn lock_model(&self, model_id: Uuid) -> tokio::sync::OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.model_locks.lock().unwrap();
+            // Forget locks that nobody holds or waits on
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            Arc::clone(locks.entry(model_id).or_default())
+        };
+        lock.lock_owned().await
+    }
+
+    /// Rename a model, moving its install directory along with it
+    ///
+    /// The name is trimmed and must be unique (case-insensitive). The display name
+    /// follows when it still matched the old name. An installation is only moved when
+    /// its path is the default one derived from the old name; custom paths stay put.
+    /// Running models cannot be renamed.
+    pub async fn rename_model(&self, id: Uuid, new_name: &str) -> Result<Model, ClientError> {
+        let new_name = normalize_model_name(new_name)?;
+        let _guard = self.lock_model(id).await;
+        let model = self.get_model(id).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", id)))?;
+        if model.name == new_name {
+            return Ok(model);
+        }
+
+        let taken = self.list_models(None).await?
+            .into_iter()
+            .any(|m| m.id != id && m.name.eq_ignore_ascii_case(&new_name));
+        if taken {
+            return Err(ClientError::ServiceError(ServiceError::Conflict(new_name)));
+        }
+
+        let installed = self.find_installed_model(id).await?;
+        if let Some(installed) = &installed {
+            if Self::can_stop_model(installed) || installed.status == ModelStatus::Stopping {
+                return Err(ClientError::OperationNotAllowed(format!(
+                    "Model {} cannot be renamed while {}", model.name, installed.status
+                )));
+            }
+        }
+
+        // Only move directories we derived from the name ourselves
+        let old_path = self.default_install_path(&model.name);
+        let new_path = self.default_install_path(&new_name);
+        let moved_dir = matches!(
+            &installed,
+            Some(installed) if Path::new(&installed.install_path) == old_path && old_path != new_path
+        );
+        if moved_dir && new_path.exists() {
+            return Err(ClientError::OperationNotAllowed(format!(
+                "Install directory {} already exists", new_path.display()
+            )));
+        }
+
+        let history = self.begin_history(AuditOperation::Update, Some(id), None, serde_json::json!({
+            "renamed_from": model.name,
+            "renamed_to": new_name,
+        })).await?;
+        if moved_dir && old_path.exists() {
+            if let Err(e) = std::fs::rename(&old_path, &new_path) {
+                self.finish_history(history, false).await;
+                return Err(e.into());
+            }
+        }
+
+        let id_sql = sql_quote(&id.to_string());
+        let result = async {
+            if moved_dir {
+                let sql = format!(
+                    "UPDATE installed_models SET install_path = {} WHERE model_id = {}",
+                    sql_quote(&new_path.to_string_lossy()),
+                    id_sql
+                );
+                self.database.execute_query(&sql).await
+                    .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            }
+            let sql = format!("UPDATE models SET name = {} WHERE id = {}", sql_quote(&new_name), id_sql);
+            self.database.execute_query(&sql).await
+                .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+            if model.display_name == model.name {
+                let request = UpdateModelRequest {
+                    display_name: Some(new_name.clone()),
+                    ..Default::default()
+                };
+                self.service.update_model(id, request).await
+                    .map_err(ClientError::ServiceError)?;
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            // Best effort: put the record and directory back the way they were
+            let _ = self.database.execute_query(&format!(
+                "UPDATE models SET name = {} WHERE id = {}", sql_quote(&model.name), id_sql
+            )).await;
+            if moved_dir {
+                if let Some(installed) = &installed {
+                    let _ = self.database.execute_query(&format!(
+                        "UPDATE installed_models SET install_path = {} WHERE model_id = {}",
+                        sql_quote(&installed.install_path),
+                        id_sql
+                    )).await;
+                }
+                if new_path.exists() {
+                    let _ = std::fs::rename(&new_path, &old_path);
+                }
+            }
+            self.finish_history(history, false).await;
+            return Err(e);
+        }
+
+        self.finish_history(history, true).await;
+        self.append_model_log(id, format!("Renamed from {} to {}", model.name, new_name));
+        self.get_model(id).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", id)))
     }
 
     /// Delete a model
     pub async fn delete_model(&self, id: Uuid) -> Result<bool, ClientError> {
-        self.service.delete_model(id).await
-            .map_err(ClientError::ServiceError)
+        let _guard = self.lock_model(id).await;
+        self.delete_unlocked(id).await
+    }
+
+    /// Delete a model; the caller holds its lock
+    async fn delete_unlocked(&self, id: Uuid) -> Result<bool, ClientError> {
+        let history = self.begin_history(
+            AuditOperation::Delete, Some(id), Some(ModelEventKind::Deleted), serde_json::json!({}),
+        ).await?;
+        let deleted = self.service.delete_model(id).await
+            .map_err(ClientError::ServiceError);
+        self.finish_history(history, matches!(deleted, Ok(true))).await;
+        if deleted? {
+            self.prewarmed_configs.lock().unwrap().remove(&id);
+            for table in ["model_install_sources", "discovery_sync", "model_validations"] {
+                let sql = format!("DELETE FROM {} WHERE model_id = {}", table, sql_quote(&id.to_string()));
+                self.database.execute_query(&sql).await
+                    .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Create or update models from a declarative manifest (TOML or YAML)
+    ///
+    /// The whole manifest is validated before anything is written; any problem
+    /// rejects the import with every issue found. Models are matched by name:
+    /// existing ones get their download URL and tags updated, the rest are created.
+    ///
+    /// `signature` is a detached Ed25519 signature (hex) over the manifest text. When
+    /// given, it must verify against the manifest validator's trusted keys; without
+    /// one the manifest is rejected if `require_signed_manifests` is set.
+    pub async fn import_manifest(&self, toml_or_yaml: &str, signature: Option<&str>) -> Result<ImportReport, ClientError> {
+        self.verify_manifest_signature(toml_or_yaml, signature)?;
+        let entries = parse_manifest(toml_or_yaml).map_err(ClientError::InvalidManifest)?;
+
+        let existing: HashMap<String, Uuid> = self.list_models(None).await?
+            .into_iter()
+            .map(|m| (m.name, m.id))
+            .collect();
+
+        let mut report = ImportReport::default();
+        for entry in entries {
+            match existing.get(&entry.name) {
+                Some(&id) => {
+                    let request = UpdateModelRequest {
+                        download_url: entry.download_url,
+                        tags: Some(entry.tags),
+                        ..Default::default()
+                    };
+                    self.update_model(id, request).await?;
+                    report.updated.push(id);
+                }
+                None => {
+                    let model = self.create_model_unchecked(entry.to_create_request()).await?;
+                    self.record_install_source(model.id, InstallSource::Manifest, true).await?;
+                    report.created.push(model.id);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Check a manifest's detached signature against the signing policy
+    fn verify_manifest_signature(&self, manifest: &str, signature: Option<&str>) -> Result<(), ClientError> {
+        let Some(signature) = signature else {
+            if self.config.require_signed_manifests {
+                return Err(ClientError::ValidationFailed("Manifest must be signed by a trusted key".to_string()));
+            }
+            return Ok(());
+        };
+
+        let validator = self.manifest_validator.as_ref()
+            .ok_or_else(|| ClientError::ValidationFailed("No trusted keys configured for manifest signatures".to_string()))?;
+        validator.verify_detached_signature(manifest.as_bytes(), signature)
+            .map_err(|e| ClientError::ValidationFailed(format!("Manifest signature rejected: {}", e)))
+    }
+
+    /// Export every model to `path` as a JSON array or CSV
+    ///
+    /// `on_progress(written, total)` is called after each model. Cancelling through
+    /// `cancel` returns `ClientError::Cancelled` and leaves `path` untouched.
+    pub async fn export_catalog(
+        &self,
+        path: &Path,
+        format: CatalogFormat,
+        on_progress: impl FnMut(usize, usize),
+        cancel: &CancellationToken,
+    ) -> Result<usize, ClientError> {
+        let models = self.list_models(None).await?;
+        write_catalog(&models, path, format, on_progress, cancel).await
+    }
+
+    /// Export every model to `path` as NDJSON, one model per line
+    ///
+    /// Lines are flushed as they are written, so after a cancel (`ClientError::Cancelled`)
+    /// the file holds the models exported so far and still parses line by line.
+    pub async fn export_catalog_ndjson(
+        &self,
+        path: &Path,
+        on_progress: impl FnMut(usize, usize),
+        cancel: &CancellationToken,
+    ) -> Result<usize, ClientError> {
+        let models = self.list_models(None).await?;
+        write_catalog_ndjson(&models, path, on_progress, cancel).await
+    }
+
+    /// Delete several models at once
+    ///
+    /// Installed models are skipped unless `force` is set, in which case they are
+    /// uninstalled first. The service layer doesn't expose transactions, so each
+    /// model is deleted independently and the report says what happened to each;
+    /// a model that fails is listed in `failed` and the rest of the batch goes on.
+    pub async fn delete_models(&self, ids: &[Uuid], force: bool) -> Result<DeleteReport, ClientError> {
+        let installed: HashSet<Uuid> = self.get_installed_models().await?
+            .into_iter()
+            .map(|m| m.model.id)
+            .collect();
+
+        let mut report = DeleteReport::default();
+        for &id in ids {
+            let is_installed = installed.contains(&id);
+            if is_installed && !force {
+                report.skipped_installed.push(id);
+                continue;
+            }
+
+            let result = async {
+                if is_installed {
+                    self.uninstall_model(id).await?;
+                }
+                self.delete_model(id).await
+            }
+            .await;
+            match result {
+                Ok(true) => report.deleted.push(id),
+                Ok(false) => report.not_found.push(id),
+                Err(e) => report.failed.push((id, e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Stop, uninstall and delete a model in one go
+    ///
+    /// Files are only removed when the install path lives under the default install
+    /// directory; anything else was placed by the caller and is left alone. The files
+    /// are moved aside first and only removed once the records are gone, so a failure
+    /// at any step puts the model back the way it was.
+    pub async fn force_delete(&self, model_id: Uuid) -> Result<(), ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        if self.get_model(model_id).await?.is_none() {
+            return Err(ClientError::ResourceNotFound(format!("Model {}", model_id)));
+        }
+        let installed = self.find_installed_model(model_id).await?;
+        let was_running = installed.as_ref().is_some_and(Self::can_stop_model);
+
+        self.append_model_log(model_id, "Force delete requested");
+        if was_running {
+            self.stop_unlocked(model_id).await?;
+        }
+
+        let staged_files = match &installed {
+            Some(installed) => self.stage_install_files(&installed.install_path)?,
+            None => None,
+        };
+
+        let result = async {
+            if installed.is_some() {
+                self.uninstall_unlocked(model_id).await?;
+            }
+            if !self.delete_unlocked(model_id).await? {
+                return Err(ClientError::ResourceNotFound(format!("Model {}", model_id)));
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            self.append_model_log(model_id, format!("Force delete failed, rolling back: {}", e));
+            self.rollback_force_delete(model_id, installed.as_ref(), was_running, staged_files).await;
+            return Err(e);
+        }
+
+        if let Some((_, staged)) = staged_files {
+            let removed = if staged.is_dir() {
+                std::fs::remove_dir_all(&staged)
+            } else {
+                std::fs::remove_file(&staged)
+            };
+            if let Err(e) = removed {
+                self.append_model_log(model_id, format!("Failed to remove model files: {}", e));
+            }
+        }
+
+        self.append_model_log(model_id, "Model deleted");
+        Ok(())
+    }
+
+    /// Move an install path aside so it can be restored if a delete fails
+    ///
+    /// Returns the original and staged paths, or `None` if there is nothing to remove.
+    fn stage_install_files(&self, install_path: &str) -> Result<Option<(PathBuf, PathBuf)>, ClientError> {
+        let original = PathBuf::from(install_path);
+        if !original.exists() || !original.starts_with(&self.config.default_install_dir) {
+            return Ok(None);
+        }
+
+        let mut staged = original.clone().into_os_string();
+        staged.push(format!(".deleting-{}", Uuid::new_v4()));
+        let staged = PathBuf::from(staged);
+        std::fs::rename(&original, &staged)?;
+        Ok(Some((original, staged)))
+    }
+
+    /// Best-effort restore of a model after `force_delete` failed part way
+    async fn rollback_force_delete(
+        &self,
+        model_id: Uuid,
+        installed: Option<&InstalledModel>,
+        was_running: bool,
+        staged_files: Option<(PathBuf, PathBuf)>,
+    ) {
+        if let Some((original, staged)) = staged_files {
+            if let Err(e) = std::fs::rename(&staged, &original) {
+                self.append_model_log(model_id, format!("Failed to restore model files: {}", e));
+            }
+        }
+
+        if let Some(installed) = installed {
+            match self.find_installed_model(model_id).await {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    if let Err(e) = self.service.install_model(model_id, installed.install_path.clone()).await {
+                        self.append_model_log(model_id, format!("Failed to restore installation: {}", e));
+                        return;
+                    }
+                }
+                Err(e) => {
+                    self.append_model_log(model_id, format!("Failed to check installation before restoring it: {}", e));
+                    return;
+                }
+            }
+        }
+
+        if was_running {
+            if let Err(e) = self.start_unlocked(model_id).await {
+                self.append_model_log(model_id, format!("Failed to restart model: {}", e));
+            }
+        }
+    }
+
+    /// Remove a model's installation record, keeping the model in the catalog
+    pub async fn uninstall_model(&self, model_id: Uuid) -> Result<bool, ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        self.uninstall_unlocked(model_id).await
+    }
+
+    /// Remove a model's installation record; the caller holds its lock
+    async fn uninstall_unlocked(&self, model_id: Uuid) -> Result<bool, ClientError> {
+        let history = self.begin_history(
+            AuditOperation::Uninstall, Some(model_id), Some(ModelEventKind::Uninstalled), serde_json::json!({}),
+        ).await?;
+        let uninstalled = self.service.uninstall_model(model_id).await
+            .map_err(ClientError::ServiceError);
+        self.finish_history(history, matches!(uninstalled, Ok(true))).await;
+        uninstalled
+    }
+
+    /// Report which discovered models are already known locally
+    ///
+    /// Models are matched by name (case-insensitive) against the local catalog.
+    /// The returned statuses are parallel to `discovered`.
+    pub async fn annotate_availability(&self, discovered: &[DiscoveredModel]) -> Result<Vec<LocalStatus>, ClientError> {
+        let registered: HashSet<String> = self.list_models(None).await?
+            .into_iter()
+            .map(|m| m.name.to_lowercase())
+            .collect();
+        let installed: HashSet<String> = self.get_installed_models().await?
+            .into_iter()
+            .map(|m| m.model.name.to_lowercase())
+            .collect();
+
+        Ok(discovered
+            .iter()
+            .map(|d| {
+                let name = d.name.to_lowercase();
+                if installed.contains(&name) {
+                    LocalStatus::Installed
+                } else if registered.contains(&name) {
+                    LocalStatus::Registered
+                } else {
+                    LocalStatus::NotPresent
+                }
+            })
+            .collect())
+    }
+
+    /// Pull the discovery catalog matching `filter` into the local database
+    ///
+    /// Every page of results is fetched. Models are matched by name (case-insensitive):
+    /// unknown ones are created and recorded as discovery installs, known ones get their
+    /// download URL, checksum and rating refreshed when they differ. Models pulled in by
+    /// an earlier sync that this feed no longer lists are flagged stale rather than
+    /// deleted, so a narrower `filter` flags everything outside it; they are cleared
+    /// again once a later sync lists them.
+    pub async fn sync_from_discovery(
+        &self,
+        client: &ModelDiscoveryClient,
+        filter: ModelSearchRequest,
+    ) -> Result<SyncReport, ClientError> {
+        let existing: HashMap<String, Model> = self.list_models(None).await?
+            .into_iter()
+            .map(|m| (m.name.to_lowercase(), m))
+            .collect();
+
+        // Cached details may be outdated once the catalog is re-read
+        self.discovery_details.lock().unwrap().clear();
+
+        let mut report = SyncReport::default();
+        let mut seen_names = HashSet::new();
+        let mut seen = HashSet::new();
+        let mut pages = std::pin::pin!(client.search_models_paginated(filter));
+        while let Some(page) = pages.try_next().await? {
+            for discovered in page {
+                let name = discovered.name.to_lowercase();
+                if !seen_names.insert(name.clone()) {
+                    continue;
+                }
+
+                let id = match existing.get(&name) {
+                    Some(model) => {
+                        if let Some(request) = discovery_update_request(model, &discovered) {
+                            self.update_model(model.id, request).await?;
+                            report.updated.push(model.id);
+                        }
+                        model.id
+                    }
+                    None => {
+                        let model = self.create_model(discovery_create_request(&discovered)).await?;
+                        let request = UpdateModelRequest {
+                            checksum: (!discovered.checksum.is_empty()).then(|| discovered.checksum.clone()),
+                            rating: Some(discovered.rating),
+                            ..Default::default()
+                        };
+                        self.update_model(model.id, request).await?;
+                        self.record_install_source(model.id, InstallSource::Discovery, false).await?;
+                        report.added.push(model.id);
+                        model.id
+                    }
+                };
+
+                let sql = format!(
+                    "INSERT INTO discovery_sync (model_id, last_seen_at, stale, discovery_id) VALUES ({}, {}, 0, {}) \
+                     ON CONFLICT(model_id) DO UPDATE SET last_seen_at = excluded.last_seen_at, stale = 0, \
+                     discovery_id = excluded.discovery_id",
+                    sql_quote(&id.to_string()),
+                    sql_quote(&Utc::now().to_rfc3339()),
+                    sql_quote(&discovered.id.to_string()),
+                );
+                self.database.execute_query(&sql).await
+                    .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+                seen.insert(id);
+            }
+        }
+
+        let synced: Vec<(String,)> = self.database.fetch_all("SELECT model_id FROM discovery_sync").await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        for (model_id,) in synced {
+            let Ok(id) = Uuid::parse_str(&model_id) else { continue };
+            if seen.contains(&id) {
+                continue;
+            }
+            let sql = format!("UPDATE discovery_sync SET stale = 1 WHERE model_id = {}", sql_quote(&model_id));
+            self.database.execute_query(&sql).await
+                .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            report.stale.push(id);
+        }
+
+        Ok(report)
+    }
+
+    /// Models flagged stale by the last discovery sync
+    pub async fn get_stale_models(&self) -> Result<Vec<Model>, ClientError> {
+        let rows: Vec<(String,)> = self.database.fetch_all("SELECT model_id FROM discovery_sync WHERE stale = 1").await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        let ids: HashSet<String> = rows.into_iter().map(|(id,)| id).collect();
+
+        Ok(self.list_models(None).await?
+            .into_iter()
+            .filter(|model| ids.contains(&model.id.to_string()))
+            .collect())
+    }
+
+    /// Load a model's local record, enriched with its discovery catalog entry
+    ///
+    /// Installed models are fully local and returned without catalog data. For other
+    /// models pulled in by `sync_from_discovery`, the catalog details (capabilities,
+    /// requirements, repository URL) are fetched from `discovery` once and cached until
+    /// the next sync. If the catalog can't be reached the local record is returned alone.
+    pub async fn get_model_details(
+        &self,
+        id: Uuid,
+        discovery: Option<&ModelDiscoveryClient>,
+    ) -> Result<MergedModelDetails, ClientError> {
+        let model = self.get_model(id).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", id)))?;
+        let installed = self.find_installed_model(id).await?;
+
+        let discovered = match (discovery, &installed) {
+            (Some(client), None) => self.fetch_discovery_details(id, client).await?,
+            _ => None,
+        };
+
+        Ok(MergedModelDetails::merge(model, installed, discovered))
+    }
+
+    /// Cached or freshly fetched catalog entry for a synced model
+    async fn fetch_discovery_details(
+        &self,
+        id: Uuid,
+        client: &ModelDiscoveryClient,
+    ) -> Result<Option<DiscoveredModel>, ClientError> {
+        if let Some(cached) = self.discovery_details.lock().unwrap().get(&id) {
+            return Ok(Some(cached.clone()));
+        }
+        self.discovery_fetches.run(id, || self.load_discovery_details(id, client)).await
+    }
+
+    /// Fetch a synced model's catalog entry and cache it
+    async fn load_discovery_details(
+        &self,
+        id: Uuid,
+        client: &ModelDiscoveryClient,
+    ) -> Result<Option<DiscoveredModel>, ClientError> {
+        let sql = format!("SELECT discovery_id FROM discovery_sync WHERE model_id = {}", sql_quote(&id.to_string()));
+        let row: Option<(Option<String>,)> = self.database.fetch_optional(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        let Some(discovery_id) = row.and_then(|(discovery_id,)| discovery_id).and_then(|id| Uuid::parse_str(&id).ok()) else {
+            return Ok(None);
+        };
+
+        match client.get_model_details(discovery_id).await {
+            Ok(details) => {
+                self.discovery_details.lock().unwrap().insert(id, details.clone());
+                Ok(Some(details))
+            }
+            Err(e) => {
+                self.append_model_log(id, format!("Failed to fetch discovery details: {}", e));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Reorder discovery results using what the user has installed locally
+    ///
+    /// Each result scores its server position (first = 1.0, falling linearly towards 0)
+    /// plus `RERANK_PROVIDER_WEIGHT` times how strongly the user favors its provider and
+    /// `RERANK_TAG_WEIGHT` times its best-favored tag, minus `RERANK_UNINSTALLED_PENALTY`
+    /// if the user installed and later uninstalled a model by that name. Favor comes from
+    /// installed models, each counting `1 + ln(1 + usage_count) + rating / 5`, normalized
+    /// so the top provider or tag scores 1.0. Ties keep server order.
+    ///
+    /// If local data can't be read the results are returned in server order.
+    pub async fn rerank_discovered(&self, discovered: Vec<DiscoveredModel>) -> Vec<DiscoveredModel> {
+        let preferences = match self.local_preferences().await {
+            Ok(preferences) => preferences,
+            Err(_) => return discovered,
+        };
+
+        let total = discovered.len().max(1) as f64;
+        let mut scored: Vec<(f64, DiscoveredModel)> = discovered
+            .into_iter()
+            .enumerate()
+            .map(|(index, model)| (1.0 - index as f64 / total + preferences.boost(&model), model))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.into_iter().map(|(_, model)| model).collect()
+    }
+
+    /// Collect provider/tag favor and uninstall history for `rerank_discovered`
+    async fn local_preferences(&self) -> Result<LocalPreferences, ClientError> {
+        let installed = self.get_installed_models().await?;
+        let mut preferences = LocalPreferences::default();
+        for model in &installed {
+            let weight = 1.0
+                + (model.usage_count as f64).ln_1p()
+                + model.model.rating.map_or(0.0, |rating| rating as f64 / 5.0);
+            *preferences.providers.entry(model.model.provider.to_lowercase()).or_default() += weight;
+            for tag in &model.model.tags {
+                *preferences.tags.entry(tag.to_lowercase()).or_default() += weight;
+            }
+        }
+        normalize_affinity(&mut preferences.providers);
+        normalize_affinity(&mut preferences.tags);
+
+        // Models the user uninstalled and hasn't installed again; catalog entries that
+        // were never installed (e.g. from a manifest or discovery sync) say nothing
+        let sql = format!(
+            "SELECT DISTINCT model_id FROM model_events WHERE state = 'committed' AND kind = {}",
+            sql_quote(ModelEventKind::Uninstalled.as_str()),
+        );
+        let rows: Vec<(String,)> = self.database.fetch_all(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        let installed_ids: HashSet<String> = installed.iter().map(|m| m.model.id.to_string()).collect();
+        let uninstalled_ids: HashSet<String> = rows.into_iter()
+            .map(|(id,)| id)
+            .filter(|id| !installed_ids.contains(id))
+            .collect();
+        if !uninstalled_ids.is_empty() {
+            preferences.uninstalled = self.list_models(None).await?
+                .into_iter()
+                .filter(|m| uninstalled_ids.contains(&m.id.to_string()))
+                .map(|m| m.name.to_lowercase())
+                .collect();
+        }
+
+        Ok(preferences)
     }
 
     /// Get all installed models
@@ -97,170 +1147,2432 @@ impl IntegratedModelService {
     }
 
     /// Install a model
-    pub async fn install_model(&self, model_id: Uuid, install_path: String) -> Result<InstalledModel, ClientError> {
-        self.service.install_model(model_id, install_path).await
-            .map_err(ClientError::ServiceError)
-    }
+    ///
+    /// Pass `None` to install under `default_install_dir/{name}`, or an explicit path to override it.
+    pub async fn install_model(
+        &self,
+        model_id: Uuid,
+        install_path: impl Into<Option<String>>,
+    ) -> Result<InstalledModel, ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        let install_path = match install_path.into() {
+            Some(path) => path,
+            None => {
+                let model = self.get_model(model_id).await?
+                    .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", model_id)))?;
+                self.create_default_install_dir(&model.name)?
+            }
+        };
 
-    /// Update model status
-    pub async fn update_model_status(&self, model_id: Uuid, status: ModelStatus) -> Result<(), ClientError> {
-        self.service.update_model_status(model_id, status).await
-            .map_err(ClientError::ServiceError)
+        let history = self.begin_history(
+            AuditOperation::Install, Some(model_id), Some(ModelEventKind::Installed), install_details(&install_path),
+        ).await?;
+        let installed = self.service.install_model(model_id, install_path).await
+            .map_err(ClientError::ServiceError);
+        self.finish_history(history, installed.is_ok()).await;
+        let installed = installed?;
+        self.record_install_source(model_id, InstallSource::Manual, false).await?;
+        self.seed_default_runtime_config(model_id, &installed.model.model_type).await?;
+        Ok(installed)
     }
 
-    /// Get models filtered by type
-    pub async fn get_models_by_type(&self, model_type: ModelType) -> Result<Vec<Model>, ClientError> {
-        let filter = ModelFilter {
-            model_type: Some(model_type),
-            ..Default::default()
+    /// Register a model and install it as one unit
+    ///
+    /// The service layer doesn't expose database transactions, so if any part of the
+    /// install fails everything it wrote is removed again and the error returned: the
+    /// registration, installation, install source, seeded runtime config and a default
+    /// install directory created for it. `install_path` works as in `install_model`.
+    /// Its history is written as pending upfront and only committed once both steps succeeded.
+    ///
+    /// The rollback is not atomic either. A step of it that fails is reported through
+    /// the notification sinks and the model's log, next to the install error that is
+    /// returned, and whatever it couldn't remove keeps its history. If the process dies
+    /// between registering the model and rolling it back, the registration is left
+    /// behind as an orphan.
+    pub async fn register_and_install(
+        &self,
+        request: CreateModelRequest,
+        install_path: impl Into<Option<String>>,
+    ) -> Result<InstalledModel, ClientError> {
+        let install_path = install_path.into();
+        let pending_install = install_path.clone()
+            .unwrap_or_else(|| self.default_install_path(&request.name).to_string_lossy().to_string());
+        let issues = self.check_create_request(&request, Some(&pending_install)).await?;
+        let created = self.begin_history(
+            AuditOperation::Create, None, Some(ModelEventKind::Created), serde_json::json!({ "name": request.name }),
+        ).await?;
+        let installed_history = match self.begin_history(
+            AuditOperation::Install, None, Some(ModelEventKind::Installed), install_details(&pending_install),
+        ).await {
+            Ok(history) => history,
+            Err(e) => {
+                self.finish_history(created, false).await;
+                return Err(e);
+            }
         };
-        self.service.list_models(filter).await
-            .map_err(ClientError::ServiceError)
+        let model = match self.service.create_model(request).await {
+            Ok(model) => model,
+            Err(e) => {
+                self.finish_history(created, false).await;
+                self.finish_history(installed_history, false).await;
+                return Err(ClientError::ServiceError(e));
+            }
+        };
+        let _guard = self.lock_model(model.id).await;
+        for issue in issues {
+            self.append_model_log(model.id, format!("Created with a warning: {}", issue.message));
+        }
+
+        let mut created_dir = None;
+        let mut install_done = false;
+        let result = async {
+            let install_path = match install_path {
+                Some(path) => path,
+                None => {
+                    let default_dir = self.default_install_path(&model.name);
+                    if !default_dir.exists() {
+                        created_dir = Some(default_dir);
+                    }
+                    self.create_default_install_dir(&model.name)?
+                }
+            };
+            let installed = self.service.install_model(model.id, install_path).await
+                .map_err(ClientError::ServiceError)?;
+            install_done = true;
+            self.record_install_source(model.id, InstallSource::Manual, false).await?;
+            self.seed_default_runtime_config(model.id, &model.model_type).await?;
+            Ok(installed)
+        }
+        .await;
+
+        match result {
+            Ok(installed) => {
+                self.finish_history(created.for_model(model.id), true).await;
+                self.finish_history(installed_history.for_model(model.id), true).await;
+                Ok(installed)
+            }
+            Err(e) => {
+                self.append_model_log(model.id, format!("Install failed, removing registration: {}", e));
+                let mut rollback_failures = Vec::new();
+                let uninstalled = match self.service.uninstall_model(model.id).await {
+                    Ok(_) => true,
+                    Err(err) => {
+                        rollback_failures.push(format!("uninstall: {}", err));
+                        false
+                    }
+                };
+                let deleted = match self.service.delete_model(model.id).await {
+                    Ok(_) => true,
+                    Err(err) => {
+                        rollback_failures.push(format!("delete registration: {}", err));
+                        false
+                    }
+                };
+                for table in ["model_install_sources", "model_runtime_configs"] {
+                    let sql = format!("DELETE FROM {} WHERE model_id = {}", table, sql_quote(&model.id.to_string()));
+                    if let Err(err) = self.database.execute_query(&sql).await {
+                        rollback_failures.push(format!("clear {}: {}", table, err));
+                    }
+                }
+                if let Some(dir) = created_dir {
+                    if let Err(err) = std::fs::remove_dir_all(&dir) {
+                        rollback_failures.push(format!("remove {}: {}", dir.display(), err));
+                    }
+                }
+
+                // Whatever survived the rollback keeps its history
+                self.finish_history(created.for_model(model.id), !deleted).await;
+                self.finish_history(installed_history.for_model(model.id), install_done && !uninstalled).await;
+                if !rollback_failures.is_empty() {
+                    let failures = rollback_failures.join("; ");
+                    self.append_model_log(model.id, format!("Rollback incomplete: {}", failures));
+                    emit_all(&self.notification_sinks, &Notification::warning(
+                        "Rollback incomplete".to_string(),
+                        format!("Install of {} failed and could not be fully undone: {}", model.name, failures),
+                    ));
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Create the default install directory for `model_name` and return it
+    fn create_default_install_dir(&self, model_name: &str) -> Result<String, ClientError> {
+        let path = self.default_install_path(model_name);
+        std::fs::create_dir_all(&path)?;
+        Ok(path.to_string_lossy().to_string())
     }
 
-    /// Get models filtered by provider
-    pub async fn get_models_by_provider(&self, provider: &str) -> Result<Vec<Model>, ClientError> {
-        let filter = ModelFilter {
-            provider: Some(provider.to_string()),
-            ..Default::default()
-        };
-        self.service.list_models(filter).await
-            .map_err(ClientError::ServiceError)
+    /// Register every model file found directly inside `dir` and install it in place
+    ///
+    /// Files whose name matches a model already in the catalog are skipped.
+    /// Registered models are recorded with `InstallSource::LocalFile`.
+    pub async fn scan_and_register(&self, dir: impl AsRef<std::path::Path>) -> Result<Vec<InstalledModel>, ClientError> {
+        let known: HashSet<String> = self.list_models(None).await?
+            .into_iter()
+            .map(|m| m.name.to_lowercase())
+            .collect();
+
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir.as_ref())? {
+            let path = entry?.path();
+            if path.is_file() && is_local_model_file(&path) {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut registered = Vec::new();
+        for path in files {
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if known.contains(&name.to_lowercase()) {
+                continue;
+            }
+
+            let request = CreateModelRequest {
+                display_name: name.clone(),
+                name,
+                version: "local".to_string(),
+                model_type: ModelType::Other,
+                provider: "Local".to_string(),
+                file_size: std::fs::metadata(&path)?.len(),
+                description: None,
+                license: None,
+                tags: vec!["local".to_string()],
+                languages: vec![],
+                file_path: Some(path.to_string_lossy().to_string()),
+                download_url: None,
+                config: HashMap::new(),
+                is_official: false,
+            };
+            registered.push(self.register_in_place(request, InstallSource::LocalFile).await?);
+        }
+
+        Ok(registered)
+    }
+
+    /// Register the models pulled by Ollama and install them in place
+    ///
+    /// `models_dir` is Ollama's model store (usually `~/.ollama/models`). Each manifest
+    /// under `manifests/` names a weights blob in `blobs/`; the model is registered as
+    /// `name:tag` and recorded with `InstallSource::Ollama`. Tags whose name is already
+    /// in the catalog and manifests whose weights blob is missing are skipped.
+    pub async fn import_ollama_models(&self, models_dir: impl AsRef<Path>) -> Result<Vec<InstalledModel>, ClientError> {
+        let models_dir = models_dir.as_ref();
+        let mut known: HashSet<String> = self.list_models(None).await?
+            .into_iter()
+            .map(|m| m.name.to_lowercase())
+            .collect();
+
+        let mut registered = Vec::new();
+        for (name, tag, manifest_path) in find_ollama_manifests(&models_dir.join("manifests"))? {
+            let model_name = format!("{}:{}", name, tag);
+            if known.contains(&model_name.to_lowercase()) {
+                continue;
+            }
+            let Some(weights) = read_ollama_weights(&manifest_path) else {
+                continue;
+            };
+            let blob = models_dir.join("blobs").join(weights.digest.replace(':', "-"));
+            if !blob.is_file() {
+                continue;
+            }
+
+            let request = CreateModelRequest {
+                display_name: model_name.clone(),
+                name: model_name.clone(),
+                version: tag,
+                model_type: ModelType::Other,
+                provider: "Ollama".to_string(),
+                file_size: weights.size,
+                description: None,
+                license: None,
+                tags: vec!["ollama".to_string()],
+                languages: vec![],
+                file_path: Some(blob.to_string_lossy().to_string()),
+                download_url: None,
+                config: HashMap::new(),
+                is_official: false,
+            };
+            registered.push(self.register_in_place(request, InstallSource::Ollama).await?);
+            known.insert(model_name.to_lowercase());
+        }
+
+        Ok(registered)
+    }
+
+    /// Create a model for the file at `request.file_path` and install it where it is
+    async fn register_in_place(&self, request: CreateModelRequest, source: InstallSource) -> Result<InstalledModel, ClientError> {
+        let path = request.file_path.clone().unwrap_or_default();
+        let model = self.create_model_installing_at(request, Some(&path)).await?;
+
+        self.record_install_source(model.id, source, true).await?;
+        let history = self.begin_history(
+            AuditOperation::Install, Some(model.id), Some(ModelEventKind::Installed), install_details(&path),
+        ).await?;
+        let installed = self.service.install_model(model.id, path).await
+            .map_err(ClientError::ServiceError);
+        self.finish_history(history, installed.is_ok()).await;
+        let installed = installed?;
+        self.seed_default_runtime_config(model.id, &model.model_type).await?;
+        Ok(installed)
+    }
+
+    /// Compute and store SHA-256 checksums for installed models that lack one
+    ///
+    /// Files are read in chunks, so large models are not loaded into memory. Models
+    /// whose file can't be found on disk are skipped and noted in their log.
+    /// Returns how many checksums were stored.
+    pub async fn backfill_checksums(&self) -> Result<usize, ClientError> {
+        let checksummer = ChecksumType::SHA256.checksummer();
+        let mut backfilled = 0;
+
+        for installed in self.get_installed_models().await? {
+            let model_id = installed.model.id;
+            if installed.model.checksum.as_deref().is_some_and(|checksum| !checksum.is_empty()) {
+                continue;
+            }
+            let Some(path) = installed_model_file(&installed) else {
+                self.append_model_log(model_id, "Checksum backfill skipped: model file not found");
+                continue;
+            };
+
+            let checksum = digest_file(checksummer.as_ref(), &path).await?;
+            let _guard = self.lock_model(model_id).await;
+            let history = self.begin_history(AuditOperation::Update, Some(model_id), None, serde_json::json!({
+                "checksum_backfilled": checksum,
+            })).await?;
+            let result = self.service.update_model(model_id, UpdateModelRequest {
+                checksum: Some(checksum),
+                ..Default::default()
+            }).await;
+            self.finish_history(history, result.is_ok()).await;
+            result.map_err(ClientError::ServiceError)?;
+            backfilled += 1;
+        }
+
+        Ok(backfilled)
+    }
+
+    /// Point an installed model at a new file, e.g. a newer version downloaded by hand
+    ///
+    /// The file must be a non-empty model file that passes validation. Its SHA-256
+    /// checksum is stored and the installation's `install_path` and the model's
+    /// `file_size` are updated in place, so usage stats and runtime config are kept.
+    /// Running models cannot have their file replaced.
+    pub async fn replace_model_file(&self, id: Uuid, new_path: impl AsRef<Path>) -> Result<(), ClientError> {
+        let new_path = new_path.as_ref();
+        let _guard = self.lock_model(id).await;
+        let installed = self.get_installed_model(id).await?;
+        if Self::can_stop_model(&installed) || installed.status == ModelStatus::Stopping {
+            return Err(ClientError::OperationNotAllowed(format!(
+                "Model {} cannot have its file replaced while {}", installed.model.name, installed.status
+            )));
+        }
+
+        if !new_path.is_file() {
+            return Err(ClientError::ResourceNotFound(format!("Model file {}", new_path.display())));
+        }
+        if !is_local_model_file(new_path) {
+            return Err(ClientError::ValidationFailed(format!("{} is not a model file", new_path.display())));
+        }
+        let file_size = std::fs::metadata(new_path)?.len();
+        if file_size == 0 {
+            return Err(ClientError::ValidationFailed(format!("{} is empty", new_path.display())));
+        }
+
+        let validator = ModelValidator::new(std::env::temp_dir().join("burncloud-validation"))
+            .map_err(|e| ClientError::ValidationFailed(e.to_string()))?;
+        let result = validator.validate_model(new_path, Some(id), ValidationConfig::default()).await
+            .map_err(|e| ClientError::ValidationFailed(e.to_string()))?;
+        if !result.is_valid {
+            let problems: Vec<String> = result.errors.into_iter().map(|e| e.message).collect();
+            return Err(ClientError::ValidationFailed(problems.join("; ")));
+        }
+        let checksum = digest_file(ChecksumType::SHA256.checksummer().as_ref(), new_path).await?;
+
+        let id_sql = sql_quote(&id.to_string());
+        let new_path_str = new_path.to_string_lossy().to_string();
+        let history = self.begin_history(AuditOperation::Update, Some(id), None, serde_json::json!({
+            "replaced_file": installed.install_path,
+            "install_path": new_path_str,
+            "file_size": file_size,
+            "checksum": checksum,
+        })).await?;
+        let result = async {
+            let sql = format!(
+                "UPDATE installed_models SET install_path = {} WHERE model_id = {}",
+                sql_quote(&new_path_str),
+                id_sql
+            );
+            self.database.execute_query(&sql).await
+                .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            let sql = format!("UPDATE models SET file_size = {} WHERE id = {}", file_size, id_sql);
+            self.database.execute_query(&sql).await
+                .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            self.service.update_model(id, UpdateModelRequest {
+                checksum: Some(checksum.clone()),
+                file_path: Some(new_path_str.clone()),
+                ..Default::default()
+            }).await
+                .map_err(ClientError::ServiceError)?;
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            // Best effort: point the records back at the old file
+            let _ = self.database.execute_query(&format!(
+                "UPDATE installed_models SET install_path = {} WHERE model_id = {}",
+                sql_quote(&installed.install_path),
+                id_sql
+            )).await;
+            let _ = self.database.execute_query(&format!(
+                "UPDATE models SET file_size = {} WHERE id = {}", installed.model.file_size, id_sql
+            )).await;
+            self.finish_history(history, false).await;
+            return Err(e);
+        }
+        self.finish_history(history, true).await;
+
+        // Anything prewarmed belonged to the old file
+        self.prewarmed_configs.lock().unwrap().remove(&id);
+        self.record_validation(id, true).await?;
+        self.append_model_log(id, format!("Model file replaced with {}", new_path.display()));
+        Ok(())
+    }
+
+    /// Record where a model was registered from, replacing any earlier record
+    pub async fn set_install_source(&self, model_id: Uuid, source: InstallSource) -> Result<(), ClientError> {
+        if self.get_model(model_id).await?.is_none() {
+            return Err(ClientError::ResourceNotFound(format!("Model {}", model_id)));
+        }
+        self.record_install_source(model_id, source, true).await
+    }
+
+    /// Get where a model was registered from, if recorded
+    pub async fn get_install_source(&self, model_id: Uuid) -> Result<Option<InstallSource>, ClientError> {
+        let sql = format!(
+            "SELECT source FROM model_install_sources WHERE model_id = {}",
+            sql_quote(&model_id.to_string()),
+        );
+        let row: Option<(String,)> = self.database.fetch_optional(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(row.and_then(|(source,)| InstallSource::parse(&source)))
+    }
+
+    /// Get the recorded install source of every model that has one
+    pub async fn get_install_sources(&self) -> Result<HashMap<Uuid, InstallSource>, ClientError> {
+        let rows: Vec<(String, String)> = self.database
+            .fetch_all("SELECT model_id, source FROM model_install_sources")
+            .await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(rows.into_iter()
+            .filter_map(|(model_id, source)| Some((Uuid::parse_str(&model_id).ok()?, InstallSource::parse(&source)?)))
+            .collect())
+    }
+
+    /// Get installed models registered from the given source
+    pub async fn get_installed_models_by_source(&self, source: InstallSource) -> Result<Vec<InstalledModel>, ClientError> {
+        let sql = format!(
+            "SELECT model_id FROM model_install_sources WHERE source = {}",
+            sql_quote(source.as_str()),
+        );
+        let rows: Vec<(String,)> = self.database.fetch_all(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        let ids: HashSet<String> = rows.into_iter().map(|(id,)| id).collect();
+
+        Ok(self.get_installed_models().await?
+            .into_iter()
+            .filter(|installed| ids.contains(&installed.model.id.to_string()))
+            .collect())
+    }
+
+    /// Store a model's install source; with `replace` unset an existing record is kept
+    async fn record_install_source(&self, model_id: Uuid, source: InstallSource, replace: bool) -> Result<(), ClientError> {
+        let on_conflict = if replace {
+            "DO UPDATE SET source = excluded.source, recorded_at = excluded.recorded_at"
+        } else {
+            "DO NOTHING"
+        };
+        let sql = format!(
+            "INSERT INTO model_install_sources (model_id, source, recorded_at) VALUES ({}, {}, {}) \
+             ON CONFLICT(model_id) {}",
+            sql_quote(&model_id.to_string()),
+            sql_quote(source.as_str()),
+            sql_quote(&chrono::Utc::now().to_rfc3339()),
+            on_conflict,
+        );
+        self.database.execute_query(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Write the history of a mutation before running it
+    ///
+    /// The service layer doesn't expose database transactions, so the audit log
+    /// entry (and the model event, for lifecycle changes) is inserted as pending
+    /// first and the mutation only runs once that succeeded: an operation whose
+    /// history can't be written fails without changing anything. Pass the result
+    /// to `finish_history` once the mutation returned. `model_id` may be `None`
+    /// when the model doesn't exist yet; set it with `PendingHistory::for_model`.
+    async fn begin_history(
+        &self,
+        operation: AuditOperation,
+        model_id: Option<Uuid>,
+        event: Option<ModelEventKind>,
+        details: serde_json::Value,
+    ) -> Result<PendingHistory, ClientError> {
+        let now = Utc::now().to_rfc3339();
+        let sql = format!(
+            "INSERT INTO audit_log (timestamp, operation, model_id, details, state) VALUES ({}, {}, {}, {}, 'pending')",
+            sql_quote(&now),
+            sql_quote(operation.as_str()),
+            model_id.map(|id| sql_quote(&id.to_string())).unwrap_or_else(|| "NULL".to_string()),
+            sql_quote(&details.to_string()),
+        );
+        let audit_id = self.database.execute_query(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?
+            .last_insert_rowid();
+        let mut pending = PendingHistory { audit_id, operation, model_id, event_id: None };
+
+        if let Some(kind) = event {
+            let sql = format!(
+                "INSERT INTO model_events (model_id, kind, occurred_at, state) VALUES ({}, {}, {}, 'pending')",
+                sql_quote(&model_id.map(|id| id.to_string()).unwrap_or_default()),
+                sql_quote(kind.as_str()),
+                sql_quote(&now),
+            );
+            match self.database.execute_query(&sql).await {
+                Ok(result) => pending.event_id = Some(result.last_insert_rowid()),
+                Err(e) => {
+                    self.finish_history(pending, false).await;
+                    return Err(ClientError::DatabaseError(e.to_string()));
+                }
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Commit the history written by `begin_history`, or drop it if the mutation didn't happen
+    ///
+    /// By now the outcome of the mutation is settled, so a failed write doesn't fail
+    /// the operation. The entry is left pending instead of being lost: `audit_trail`
+    /// still lists it, and the notification sinks are told.
+    async fn finish_history(&self, pending: PendingHistory, committed: bool) {
+        let mut statements = Vec::new();
+        if committed {
+            let model_id = pending.model_id
+                .map(|id| sql_quote(&id.to_string()))
+                .unwrap_or_else(|| "NULL".to_string());
+            statements.push(format!(
+                "UPDATE audit_log SET state = 'committed', model_id = {} WHERE id = {}",
+                model_id, pending.audit_id
+            ));
+            if let Some(event_id) = pending.event_id {
+                statements.push(format!(
+                    "UPDATE model_events SET state = 'committed', model_id = {} WHERE id = {}",
+                    model_id, event_id
+                ));
+            }
+        } else {
+            statements.push(format!("DELETE FROM audit_log WHERE id = {}", pending.audit_id));
+            if let Some(event_id) = pending.event_id {
+                statements.push(format!("DELETE FROM model_events WHERE id = {}", event_id));
+            }
+        }
+
+        for sql in statements {
+            if let Err(e) = self.database.execute_query(&sql).await {
+                let subject = pending.model_id.map(|id| format!(" for model {}", id)).unwrap_or_default();
+                let outcome = if committed { "committed" } else { "dropped" };
+                emit_all(&self.notification_sinks, &Notification::warning(
+                    "History left pending".to_string(),
+                    format!("Could not mark the history of '{}'{} as {}: {}", pending.operation.as_str(), subject, outcome, e),
+                ));
+                return;
+            }
+        }
+    }
+
+    /// The most recent `limit` audit entries, oldest first
+    ///
+    /// Pass a `model_id` to only see operations on that model. Entries whose
+    /// operation never confirmed its outcome are included with `pending` set.
+    pub async fn audit_trail(&self, model_id: Option<Uuid>, limit: usize) -> Result<Vec<AuditEntry>, ClientError> {
+        let filter = model_id
+            .map(|id| format!("WHERE model_id = {}", sql_quote(&id.to_string())))
+            .unwrap_or_default();
+        let sql = format!(
+            "SELECT timestamp, operation, model_id, details, state FROM audit_log {} ORDER BY id DESC LIMIT {}",
+            filter, limit
+        );
+        let rows: Vec<(String, String, Option<String>, String, String)> = self.database
+            .fetch_all(&sql)
+            .await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        let mut entries: Vec<AuditEntry> = rows
+            .into_iter()
+            .filter_map(|(timestamp, operation, model_id, details, state)| {
+                Some(AuditEntry {
+                    timestamp: DateTime::parse_from_rfc3339(&timestamp).ok()?.with_timezone(&Utc),
+                    operation: AuditOperation::parse(&operation)?,
+                    model_id: model_id.and_then(|id| Uuid::parse_str(&id).ok()),
+                    details: serde_json::from_str(&details).unwrap_or(serde_json::Value::Null),
+                    pending: state == "pending",
+                })
+            })
+            .collect();
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// Get audit log entries at or after `since`, oldest first
+    pub async fn get_model_events(&self, since: DateTime<Utc>) -> Result<Vec<ModelEvent>, ClientError> {
+        // RFC 3339 text may carry any offset, so the indexed text comparison only
+        // narrows the scan with a day of slack; the exact cut happens after parsing
+        let lower = since.checked_sub_signed(chrono::Duration::days(1)).unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let sql = format!(
+            "SELECT model_id, kind, occurred_at FROM model_events WHERE state = 'committed' AND occurred_at >= {} ORDER BY id",
+            sql_quote(&lower.to_rfc3339()),
+        );
+        let rows: Vec<(String, String, String)> = self.database
+            .fetch_all(&sql)
+            .await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        let mut events: Vec<ModelEvent> = rows
+            .into_iter()
+            .filter_map(|(model_id, kind, occurred_at)| {
+                Some(ModelEvent {
+                    model_id: Uuid::parse_str(&model_id).ok()?,
+                    kind: ModelEventKind::parse(&kind)?,
+                    occurred_at: DateTime::parse_from_rfc3339(&occurred_at).ok()?.with_timezone(&Utc),
+                })
+            })
+            .filter(|event| event.occurred_at >= since)
+            .collect();
+        events.sort_by_key(|event| event.occurred_at);
+        Ok(events)
+    }
+
+    /// Number of models in the catalog at the end of each bucket from `since` until now
+    ///
+    /// Each entry is a bucket's start and the cumulative model count when it closed
+    /// (the current bucket counts up to now). At most the latest
+    /// [`MAX_TIMESERIES_BUCKETS`] buckets are returned, so an early `since` is clamped.
+    /// Creations and deletions come from the audit log; models created before the log
+    /// existed fall back to their `created_at`.
+    pub async fn growth_timeseries(
+        &self,
+        bucket: Granularity,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, usize)>, ClientError> {
+        let current = self.list_models(None).await?
+            .into_iter()
+            .map(|model| (model.id, model.created_at))
+            .collect();
+        self.cumulative_timeseries(bucket, since, current, ModelEventKind::Created, &[ModelEventKind::Deleted]).await
+    }
+
+    /// Number of installed models at the end of each bucket from `since` until now
+    ///
+    /// Same buckets and clamping as `growth_timeseries`. Installs and uninstalls come
+    /// from the audit log, and deleting a model ends its install; installs made before
+    /// the log existed fall back to their `installed_at`.
+    pub async fn install_growth_timeseries(
+        &self,
+        bucket: Granularity,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, usize)>, ClientError> {
+        let current = self.get_installed_models().await?
+            .into_iter()
+            .map(|installed| (installed.model.id, installed.installed_at))
+            .collect();
+        self.cumulative_timeseries(
+            bucket,
+            since,
+            current,
+            ModelEventKind::Installed,
+            &[ModelEventKind::Uninstalled, ModelEventKind::Deleted],
+        ).await
+    }
+
+    /// Replay the audit log over `current` (members now, with their fallback start time)
+    ///
+    /// Only events from the first bucket on are read. A member whose first event in
+    /// that range is a removal was already counted when the range began.
+    async fn cumulative_timeseries(
+        &self,
+        bucket: Granularity,
+        since: DateTime<Utc>,
+        current: HashMap<Uuid, DateTime<Utc>>,
+        added: ModelEventKind,
+        removed: &[ModelEventKind],
+    ) -> Result<Vec<(DateTime<Utc>, usize)>, ClientError> {
+        let now = Utc::now();
+        let earliest = now
+            .checked_sub_signed(bucket.min_length() * MAX_TIMESERIES_BUCKETS as i32)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let first = bucket.floor(since.max(earliest));
+
+        let mut timelines: HashMap<Uuid, Timeline> = HashMap::new();
+        for event in self.get_model_events(first).await? {
+            let is_added = event.kind == added;
+            if !is_added && !removed.contains(&event.kind) {
+                continue;
+            }
+            timelines
+                .entry(event.model_id)
+                .or_insert_with(|| (!is_added, Vec::new()))
+                .1
+                .push((event.occurred_at, is_added));
+        }
+
+        // Members quiet in the range either joined before it (logged earlier) or
+        // before the log existed, in which case their own timestamp says when
+        let quiet: Vec<(Uuid, DateTime<Utc>)> = current
+            .into_iter()
+            .filter(|(id, _)| !timelines.contains_key(id))
+            .collect();
+        if !quiet.is_empty() {
+            let upper = first.checked_add_signed(chrono::Duration::days(1)).unwrap_or(first);
+            let sql = format!(
+                "SELECT DISTINCT model_id FROM model_events WHERE state = 'committed' AND kind = {} AND occurred_at < {}",
+                sql_quote(added.as_str()),
+                sql_quote(&upper.to_rfc3339()),
+            );
+            let rows: Vec<(String,)> = self.database
+                .fetch_all(&sql)
+                .await
+                .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+            let logged: HashSet<String> = rows.into_iter().map(|(id,)| id).collect();
+            for (id, started_at) in quiet {
+                let timeline = if started_at < first || logged.contains(&id.to_string()) {
+                    (true, Vec::new())
+                } else {
+                    (false, vec![(started_at, true)])
+                };
+                timelines.insert(id, timeline);
+            }
+        }
+
+        let mut series = Vec::new();
+        let mut start = first;
+        while start <= now {
+            let end = bucket.next(start).min(now);
+            let count = timelines
+                .values()
+                .filter(|(counted_before, changes)| {
+                    changes
+                        .iter()
+                        .take_while(|(at, _)| *at <= end)
+                        .last()
+                        .map_or(*counted_before, |(_, is_added)| *is_added)
+                })
+                .count();
+            series.push((start, count));
+            start = bucket.next(start);
+        }
+        Ok(series)
+    }
+
+    /// Check a model against host resources before installing it
+    ///
+    /// Warnings in the report are advisory and never block `install_model`.
+    pub async fn preflight_install(&self, model_id: Uuid) -> Result<PreflightReport, ClientError> {
+        let model = self.get_model(model_id).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", model_id)))?;
+        Ok(PreflightReport::for_model(&model, detect_host_memory_bytes()))
+    }
+
+    /// Derive the default install directory for a model name
+    pub fn default_install_path(&self, model_name: &str) -> PathBuf {
+        self.config.default_install_dir.join(sanitize_path_component(model_name))
+    }
+
+    /// Update model status
+    pub async fn update_model_status(&self, model_id: Uuid, status: ModelStatus) -> Result<(), ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        self.set_status(model_id, status).await
+    }
+
+    /// Update model status; the caller holds the model's lock
+    async fn set_status(&self, model_id: Uuid, status: ModelStatus) -> Result<(), ClientError> {
+        let history = self.begin_history(
+            AuditOperation::StatusChange, Some(model_id), None, serde_json::json!({ "status": status.to_string() }),
+        ).await?;
+        let result = self.service.update_model_status(model_id, status).await;
+        self.finish_history(history, result.is_ok()).await;
+        result.map_err(ClientError::ServiceError)?;
+        if status == ModelStatus::Error {
+            emit_all(&self.notification_sinks, &Notification::error(
+                "Model error".to_string(),
+                format!("Model {} entered the error state", model_id),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start an installed model
+    ///
+    /// Returns the saved runtime config the model is launched with, if one was stored.
+    pub async fn start_model(&self, model_id: Uuid) -> Result<Option<RuntimeConfig>, ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        self.start_unlocked(model_id).await.inspect_err(|e| {
+            emit_all(&self.notification_sinks, &Notification::error(
+                "Model failed to start".to_string(),
+                format!("Model {}: {}", model_id, e),
+            ));
+        })
+    }
+
+    /// Start a model; the caller holds its lock
+    async fn start_unlocked(&self, model_id: Uuid) -> Result<Option<RuntimeConfig>, ClientError> {
+        let installed = self.get_installed_model(model_id).await?;
+        if !Self::can_start_model(&installed) {
+            let reason = format!("Model {} cannot be started while {}", installed.model.name, installed.status);
+            self.append_model_log(model_id, &reason);
+            return Err(ClientError::OperationNotAllowed(reason));
+        }
+
+        let prewarmed = self.prewarmed_configs.lock().unwrap().remove(&model_id);
+        let runtime_config = match prewarmed {
+            Some(runtime_config) => runtime_config,
+            None => self.get_runtime_config(model_id).await
+                .inspect_err(|e| self.append_model_log(model_id, format!("Failed to load runtime config: {}", e)))?,
+        };
+
+        self.append_model_log(model_id, "Starting model");
+        self.set_status(model_id, ModelStatus::Starting).await?;
+        self.set_status(model_id, ModelStatus::Running).await?;
+        self.append_model_log(model_id, "Model running");
+
+        Ok(runtime_config)
+    }
+
+    /// Get a model ready to start without the cold-start stall
+    ///
+    /// Validates the installed files (recording `last_validated_at`), reads them
+    /// once so they sit in the OS page cache, and loads the saved runtime config
+    /// for the next `start_model`. The model's status is left alone. Cancelling
+    /// through `cancel` returns `ClientError::Cancelled`; files that fail
+    /// validation return `ValidationFailed`.
+    pub async fn prewarm_model(&self, model_id: Uuid, cancel: &CancellationToken) -> Result<(), ClientError> {
+        let installed = self.get_installed_model(model_id).await?;
+        let files = model_files(Path::new(&installed.install_path))?;
+        if files.is_empty() {
+            return Err(ClientError::ResourceNotFound(format!("No model files in {}", installed.install_path)));
+        }
+        self.append_model_log(model_id, "Prewarming model");
+        let cancelled = || ClientError::Cancelled(format!("Prewarm of {} cancelled", installed.model.name));
+
+        let validator = ModelValidator::new(std::env::temp_dir().join("burncloud-validation"))
+            .map_err(|e| ClientError::ValidationFailed(e.to_string()))?;
+        // The source was already trusted at install time; this only checks the files are intact
+        let config = ValidationConfig::default();
+        let mut problems = Vec::new();
+        for file in &files {
+            let result = match validator.validate_model_with_progress(file, Some(model_id), config.clone(), None, cancel).await {
+                Ok(result) => result,
+                Err(ValidatorError::Cancelled) => return Err(cancelled()),
+                Err(e) => return Err(ClientError::ValidationFailed(e.to_string())),
+            };
+            if !result.is_valid {
+                problems.extend(result.errors.into_iter().map(|e| e.message));
+            }
+        }
+        self.record_validation(model_id, problems.is_empty()).await?;
+        if !problems.is_empty() {
+            self.append_model_log(model_id, format!("Prewarm validation failed: {}", problems.join("; ")));
+            emit_all(&self.notification_sinks, &Notification::error(
+                "Model validation failed".to_string(),
+                format!("{}: {}", installed.model.name, problems.join("; ")),
+            ));
+            return Err(ClientError::ValidationFailed(problems.join("; ")));
+        }
+
+        for file in &files {
+            if !touch_into_page_cache(file, cancel).await? {
+                return Err(cancelled());
+            }
+        }
+
+        let runtime_config = self.get_runtime_config(model_id).await?;
+        self.prewarmed_configs.lock().unwrap().insert(model_id, runtime_config);
+        self.append_model_log(model_id, "Model prewarmed");
+        Ok(())
+    }
+
+    /// Store the outcome of validating a model's files
+    async fn record_validation(&self, model_id: Uuid, is_valid: bool) -> Result<(), ClientError> {
+        let sql = format!(
+            "INSERT INTO model_validations (model_id, validated_at, is_valid) VALUES ({}, {}, {}) \
+             ON CONFLICT(model_id) DO UPDATE SET validated_at = excluded.validated_at, is_valid = excluded.is_valid",
+            sql_quote(&model_id.to_string()),
+            sql_quote(&Utc::now().to_rfc3339()),
+            is_valid as i32,
+        );
+        self.database.execute_query(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// When a model's files were last validated, if ever
+    pub async fn last_validated_at(&self, model_id: Uuid) -> Result<Option<DateTime<Utc>>, ClientError> {
+        let sql = format!(
+            "SELECT validated_at FROM model_validations WHERE model_id = {}",
+            sql_quote(&model_id.to_string()),
+        );
+        let row: Option<(String,)> = self.database.fetch_optional(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        Ok(row.and_then(|(at,)| DateTime::parse_from_rfc3339(&at).ok()).map(|at| at.with_timezone(&Utc)))
+    }
+
+    /// Check an installed model's file against its stored checksum
+    ///
+    /// Records `last_validated_at` either way. A missing file or a checksum mismatch
+    /// is noted in the model's log, moves the model to `Error` and returns `false`.
+    /// Models without a stored checksum only need their file to be present.
+    pub async fn verify_installed_model(&self, model_id: Uuid) -> Result<bool, ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        let installed = self.get_installed_model(model_id).await?;
+        self.verify_unlocked(installed).await
+    }
+
+    /// Like `verify_installed_model`, but leaves a model that is in use alone
+    ///
+    /// The status is read under the model's lock, so a start that happened after the
+    /// caller listed the models is seen. Returns `None` when the model was skipped.
+    async fn verify_idle_model(&self, model_id: Uuid) -> Result<Option<bool>, ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        let installed = self.get_installed_model(model_id).await?;
+        if Self::can_stop_model(&installed) || installed.status == ModelStatus::Stopping {
+            return Ok(None);
+        }
+        self.verify_unlocked(installed).await.map(Some)
+    }
+
+    /// Verify a model's file; the caller holds its lock
+    async fn verify_unlocked(&self, installed: InstalledModel) -> Result<bool, ClientError> {
+        let model_id = installed.model.id;
+        let problem = match installed_model_file(&installed) {
+            None => Some("model file not found".to_string()),
+            Some(path) => match installed.model.checksum.as_deref().filter(|checksum| !checksum.is_empty()) {
+                None => None,
+                Some(expected) => {
+                    let (checksum_type, expected) = parse_expected_checksum(expected, ChecksumType::SHA256)
+                        .map_err(|e| ClientError::ValidationFailed(e.to_string()))?;
+                    let actual = digest_file(checksum_type.checksummer().as_ref(), &path).await?;
+                    (!actual.eq_ignore_ascii_case(&expected))
+                        .then(|| format!("checksum mismatch: expected {}, got {}", expected, actual))
+                }
+            },
+        };
+
+        self.record_validation(model_id, problem.is_none()).await?;
+        let Some(problem) = problem else {
+            return Ok(true);
+        };
+        self.append_model_log(model_id, format!("Integrity check failed: {}", problem));
+        if installed.status != ModelStatus::Error {
+            self.set_status(model_id, ModelStatus::Error).await?;
+        }
+        Ok(false)
+    }
+
+    /// Periodically verify every installed model's checksum in the background
+    ///
+    /// Every `interval` (the first pass after one interval), each installed model
+    /// that isn't running is checked with `verify_installed_model`, pausing between
+    /// models to keep disk load low. Corrupted models move to `Error`, which alerts
+    /// the notification sinks. Starting again replaces a running scrub. Must be
+    /// called from within a tokio runtime.
+    pub fn start_scrub(&self, interval: Duration) {
+        self.start_scrub_with_clock(interval, Arc::new(TokioClock));
+    }
+
+    /// Like `start_scrub`, waiting on `clock` instead of the tokio timer
+    pub fn start_scrub_with_clock(&self, interval: Duration, clock: Arc<dyn ScrubClock>) {
+        let cancel = CancellationToken::new();
+        if let Some(previous) = self.scrub.lock().unwrap().replace(cancel.clone()) {
+            previous.cancel();
+        }
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = clock.sleep(interval) => {}
+                }
+                service.scrub_pass(clock.as_ref(), &cancel).await;
+            }
+        });
+    }
+
+    /// Stop the background scrub, if one is running
+    pub fn stop_scrub(&self) {
+        if let Some(cancel) = self.scrub.lock().unwrap().take() {
+            cancel.cancel();
+        }
+    }
+
+    /// Verify each idle installed model once, stopping early when cancelled
+    async fn scrub_pass(&self, clock: &dyn ScrubClock, cancel: &CancellationToken) {
+        let installed = match self.get_installed_models().await {
+            Ok(installed) => installed,
+            Err(e) => {
+                emit_all(&self.notification_sinks, &Notification::warning(
+                    "Checksum scrub skipped".to_string(),
+                    format!("Could not list installed models: {}", e),
+                ));
+                return;
+            }
+        };
+
+        for (index, model) in installed.iter().enumerate() {
+            if index > 0 {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = clock.sleep(SCRUB_MODEL_PAUSE) => {}
+                }
+            }
+            // Files in use are checked on a later pass
+            if let Err(e) = self.verify_idle_model(model.model.id).await {
+                self.append_model_log(model.model.id, format!("Integrity check could not run: {}", e));
+            }
+        }
+    }
+
+    /// Stop a running model
+    pub async fn stop_model(&self, model_id: Uuid) -> Result<(), ClientError> {
+        let _guard = self.lock_model(model_id).await;
+        self.stop_unlocked(model_id).await
+    }
+
+    /// Stop a model; the caller holds its lock
+    async fn stop_unlocked(&self, model_id: Uuid) -> Result<(), ClientError> {
+        let installed = self.get_installed_model(model_id).await?;
+        if !Self::can_stop_model(&installed) {
+            let reason = format!("Model {} cannot be stopped while {}", installed.model.name, installed.status);
+            self.append_model_log(model_id, &reason);
+            return Err(ClientError::OperationNotAllowed(reason));
+        }
+
+        self.append_model_log(model_id, "Stopping model");
+        self.set_status(model_id, ModelStatus::Stopping).await?;
+        self.set_status(model_id, ModelStatus::Stopped).await?;
+        self.append_model_log(model_id, "Model stopped");
+        Ok(())
+    }
+
+    /// Record a runtime log line for a model
+    ///
+    /// Logs are kept in memory only, capped at `MODEL_LOG_CAPACITY` lines per model.
+    pub fn append_model_log(&self, model_id: Uuid, line: impl Into<String>) {
+        self.logs.append(model_id, line);
+    }
+
+    /// Collect a diagnostic report for bug reports
+    ///
+    /// Log lines and paths are redacted, so the serialized report can be shared as-is.
+    pub async fn diagnostics(&self) -> Result<DiagnosticReport, ClientError> {
+        let models = self.list_models(None).await?;
+        let installed = self.get_installed_models().await?;
+        let database_path = self.config.database_path.clone().unwrap_or_else(default_database_path);
+        let database_size_bytes = std::fs::metadata(&database_path)
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len());
+
+        let recent_errors = self.logs.recent_all(DIAGNOSTIC_LOG_LINES)
+            .into_iter()
+            .filter(|(_, entry)| is_error_line(&entry.line))
+            .map(|(model_id, entry)| DiagnosticLogEntry {
+                model_id,
+                timestamp: entry.timestamp,
+                line: redact_secrets(&entry.line),
+            })
+            .collect();
+
+        Ok(DiagnosticReport {
+            generated_at: Utc::now(),
+            client_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: self.schema_version().await?,
+            model_count: models.len(),
+            installed_count: installed.len(),
+            running_count: installed.iter().filter(|m| m.status == ModelStatus::Running).count(),
+            database_path: redact_home(&database_path, &home_dir()),
+            database_size_bytes,
+            free_disk_space_bytes: available_disk_space(&self.config.default_install_dir),
+            offline: self.config.offline,
+            recent_errors,
+            host: HostCapabilities::detect(),
+        })
+    }
+
+    /// Get a model's most recent log lines, oldest first; `None` returns everything buffered
+    pub fn get_model_logs(&self, model_id: Uuid, last_n: Option<usize>) -> Vec<ModelLogEntry> {
+        self.logs.recent(model_id, last_n)
+    }
+
+    /// Get the installed record for a model
+    async fn get_installed_model(&self, model_id: Uuid) -> Result<InstalledModel, ClientError> {
+        self.find_installed_model(model_id).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Installed model {}", model_id)))
+    }
+
+    /// Get the installed record for a model, `None` if it isn't installed
+    async fn find_installed_model(&self, model_id: Uuid) -> Result<Option<InstalledModel>, ClientError> {
+        Ok(self.get_installed_models().await?
+            .into_iter()
+            .find(|installed| installed.model.id == model_id))
+    }
+
+    /// Summarise disk, port and process usage of installed models
+    ///
+    /// Computed with aggregate queries against the service tables, so large
+    /// installs don't have to be loaded into memory.
+    pub async fn resource_overview(&self) -> Result<ResourceOverview, ClientError> {
+        let (total_disk_usage,): (i64,) = self.database.fetch_one(
+            "SELECT COALESCE(SUM(m.file_size), 0) FROM installed_models i JOIN models m ON m.id = i.model_id",
+        ).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        let ports: Vec<(i64,)> = self.database.fetch_all(
+            "SELECT port FROM installed_models WHERE port IS NOT NULL ORDER BY port",
+        ).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        let processes: Vec<(i64,)> = self.database.fetch_all(
+            "SELECT process_id FROM installed_models WHERE process_id IS NOT NULL ORDER BY process_id",
+        ).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        Ok(ResourceOverview {
+            total_disk_usage_bytes: total_disk_usage.max(0) as u64,
+            ports_in_use: ports.into_iter().filter_map(|(port,)| u16::try_from(port).ok()).collect(),
+            active_processes: processes.into_iter().filter_map(|(pid,)| u32::try_from(pid).ok()).collect(),
+        })
+    }
+
+    /// Save the user's runtime config for a model
+    ///
+    /// Stored separately from the model's baked-in `config`, replacing any previous value.
+    pub async fn save_runtime_config(&self, model_id: Uuid, runtime_config: RuntimeConfig) -> Result<(), ClientError> {
+        if self.get_model(model_id).await?.is_none() {
+            return Err(ClientError::ResourceNotFound(format!("Model {}", model_id)));
+        }
+
+        let json = serde_json::to_string(&runtime_config)?;
+        let sql = format!(
+            "INSERT INTO model_runtime_configs (model_id, config, updated_at) VALUES ({}, {}, {}) \
+             ON CONFLICT(model_id) DO UPDATE SET config = excluded.config, updated_at = excluded.updated_at",
+            sql_quote(&model_id.to_string()),
+            sql_quote(&json),
+            sql_quote(&chrono::Utc::now().to_rfc3339()),
+        );
+        self.database.execute_query(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        // A prewarmed copy would now be stale
+        self.prewarmed_configs.lock().unwrap().remove(&model_id);
+
+        Ok(())
+    }
+
+    /// Save the default runtime config for `model_type` unless the model already has one
+    ///
+    /// Gives freshly installed models a baseline to tune from; see `default_runtime_config`.
+    async fn seed_default_runtime_config(&self, model_id: Uuid, model_type: &ModelType) -> Result<(), ClientError> {
+        let Some(defaults) = default_runtime_config(model_type) else {
+            return Ok(());
+        };
+        if self.get_runtime_config(model_id).await?.is_some() {
+            return Ok(());
+        }
+        self.save_runtime_config(model_id, defaults).await
+    }
+
+    /// Get the saved runtime config for a model, if any
+    pub async fn get_runtime_config(&self, model_id: Uuid) -> Result<Option<RuntimeConfig>, ClientError> {
+        let sql = format!(
+            "SELECT config FROM model_runtime_configs WHERE model_id = {}",
+            sql_quote(&model_id.to_string()),
+        );
+        let row: Option<(String,)> = self.database.fetch_optional(&sql).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+        match row {
+            Some((json,)) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Copy a model's config and saved runtime config onto another model
+    ///
+    /// Both configs are checked against the target's type first, so nothing is
+    /// written when they don't apply. The target keeps its own runtime config when
+    /// the source has none saved.
+    pub async fn copy_config(&self, from: Uuid, to: Uuid) -> Result<(), ClientError> {
+        let source = self.get_model(from).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", from)))?;
+        let _guard = self.lock_model(to).await;
+        let target = self.get_model(to).await?
+            .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", to)))?;
+
+        let runtime_config = self.get_runtime_config(from).await?;
+        let configs = std::iter::once(source.model_config())
+            .chain(runtime_config.iter().map(ModelConfigExt::model_config));
+        for config in configs {
+            config.validate_for(&target.model_type).map_err(|reason| {
+                ClientError::ValidationFailed(format!("Config of {} does not fit {}: {}", source.name, target.name, reason))
+            })?;
+        }
+
+        let history = self.begin_history(AuditOperation::Update, Some(to), None, serde_json::json!({
+            "config_copied_from": from,
+            "runtime_config_copied": runtime_config.is_some(),
+        })).await?;
+        let result = self.service.update_model(to, UpdateModelRequest {
+            config: Some(source.config),
+            ..Default::default()
+        }).await;
+        self.finish_history(history, result.is_ok()).await;
+        result.map_err(ClientError::ServiceError)?;
+        if let Some(runtime_config) = runtime_config {
+            self.save_runtime_config(to, runtime_config).await?;
+        }
+        Ok(())
+    }
+
+    /// Lowercase, merge and deduplicate the tags of every model
+    ///
+    /// Tags are trimmed and lowercased, aliases from `tag_aliases` are replaced by
+    /// their canonical tag, and repeats are dropped keeping the first occurrence.
+    /// Returns how many models changed.
+    pub async fn normalize_tags(&self) -> Result<usize, ClientError> {
+        let aliases: HashMap<String, String> = self.config.tag_aliases
+            .iter()
+            .map(|(alias, canonical)| (alias.trim().to_lowercase(), canonical.trim().to_lowercase()))
+            .collect();
+
+        let mut changed = 0;
+        for model in self.list_models(None).await? {
+            let tags = normalize_tag_list(&model.tags, &aliases);
+            if tags == model.tags {
+                continue;
+            }
+            self.update_model(model.id, UpdateModelRequest {
+                tags: Some(tags),
+                ..Default::default()
+            }).await?;
+            changed += 1;
+        }
+        Ok(changed)
+    }
+
+    /// Suggest existing tags for autocomplete
+    ///
+    /// Tags starting with `prefix` come first, then tags merely containing it;
+    /// within each group the most used tags come first. Matching ignores case.
+    pub async fn suggest_tags(&self, prefix: &str, limit: usize) -> Result<Vec<String>, ClientError> {
+        let prefix = prefix.trim().to_lowercase();
+        let mut usage: HashMap<String, usize> = HashMap::new();
+        for model in self.list_models(None).await? {
+            for tag in model.tags {
+                *usage.entry(tag).or_default() += 1;
+            }
+        }
+
+        let mut matches: Vec<(bool, usize, String)> = usage
+            .into_iter()
+            .filter_map(|(tag, count)| {
+                let lower = tag.to_lowercase();
+                if lower.starts_with(&prefix) {
+                    Some((true, count, tag))
+                } else if lower.contains(&prefix) {
+                    Some((false, count, tag))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)).then_with(|| a.2.cmp(&b.2)));
+        Ok(matches.into_iter().take(limit).map(|(_, _, tag)| tag).collect())
+    }
+
+    /// Get models filtered by type
+    pub async fn get_models_by_type(&self, model_type: ModelType) -> Result<Vec<Model>, ClientError> {
+        let filter = ModelFilter {
+            model_type: Some(model_type),
+            ..Default::default()
+        };
+        self.service.list_models(filter).await
+            .map_err(ClientError::ServiceError)
+    }
+
+    /// Get models filtered by provider
+    pub async fn get_models_by_provider(&self, provider: &str) -> Result<Vec<Model>, ClientError> {
+        let filter = ModelFilter {
+            provider: Some(provider.to_string()),
+            ..Default::default()
+        };
+        self.service.list_models(filter).await
+            .map_err(ClientError::ServiceError)
+    }
+
+    /// Installed models carrying `license`, compared case-insensitively
+    ///
+    /// Models without a license are listed under [`UNKNOWN_LICENSE`].
+    pub async fn get_models_by_license(&self, license: &str) -> Result<Vec<InstalledModel>, ClientError> {
+        let license = license.trim();
+        Ok(self.get_installed_models().await?
+            .into_iter()
+            .filter(|installed| license_label(installed.model.license.as_deref()).eq_ignore_ascii_case(license))
+            .collect())
+    }
+
+    /// Number of installed models per license, with missing licenses counted as [`UNKNOWN_LICENSE`]
+    ///
+    /// Licenses are matched case-insensitively like `get_models_by_license`; each group
+    /// is keyed by the spelling that sorts first, so `Apache-2.0` and `apache-2.0` are
+    /// counted together under `Apache-2.0`.
+    pub async fn license_summary(&self) -> Result<HashMap<String, usize>, ClientError> {
+        // Lowercased license -> (spelling used as key, count)
+        let mut groups: HashMap<String, (String, usize)> = HashMap::new();
+        for installed in self.get_installed_models().await? {
+            let label = license_label(installed.model.license.as_deref());
+            let (spelling, count) = groups
+                .entry(label.to_lowercase())
+                .or_insert_with(|| (label.to_string(), 0));
+            if label < spelling.as_str() {
+                *spelling = label.to_string();
+            }
+            *count += 1;
+        }
+        Ok(groups.into_values().collect())
+    }
+
+    /// Get official models only
+    pub async fn get_official_models(&self) -> Result<Vec<Model>, ClientError> {
+        let filter = ModelFilter {
+            is_official: Some(true),
+            ..Default::default()
+        };
+        self.service.list_models(filter).await
+            .map_err(ClientError::ServiceError)
+    }
+
+    /// Get service statistics
+    ///
+    /// Concurrent calls share a single aggregation query.
+    pub async fn get_statistics(&self) -> Result<ClientModelStats, ClientError> {
+        self.statistics_loads.run((), || self.load_statistics()).await
+    }
+
+    async fn load_statistics(&self) -> Result<ClientModelStats, ClientError> {
+        let stats = self.service.get_model_stats().await
+            .map_err(ClientError::ServiceError)?;
+
+        Ok(ClientModelStats {
+            total_models: stats.total_models,
+            installed_count: stats.installed_count,
+            official_count: stats.official_count,
+            running_count: stats.running_count,
+            total_size_bytes: stats.total_size_bytes,
+            models_by_type: stats.models_by_type,
+        })
+    }
+
+    /// Get models grouped by size category
+    pub async fn get_models_by_size(&self) -> Result<HashMap<SizeCategory, Vec<Model>>, ClientError> {
+        let models = self.list_models(None).await?;
+        let mut grouped = HashMap::new();
+
+        for model in models {
+            grouped.entry(model.size_category)
+                .or_insert_with(Vec::new)
+                .push(model);
+        }
+
+        Ok(grouped)
+    }
+
+    /// Pick which of the candidate models to install within a disk budget
+    ///
+    /// The chosen set never exceeds `budget_bytes` in total `file_size` and is the
+    /// best one for `objective`; see [`Objective`]. Duplicate ids are ignored and the
+    /// result keeps the order of `candidate_ids`. Unknown ids are an error.
+    pub async fn select_within_budget(
+        &self,
+        candidate_ids: &[Uuid],
+        budget_bytes: u64,
+        objective: Objective,
+    ) -> Result<Vec<Uuid>, ClientError> {
+        let mut seen = HashSet::new();
+        let mut candidates = Vec::new();
+        for &id in candidate_ids {
+            if !seen.insert(id) {
+                continue;
+            }
+            let model = self.get_model(id).await?
+                .ok_or_else(|| ClientError::ResourceNotFound(format!("Model {}", id)))?;
+            candidates.push(model);
+        }
+
+        let chosen: HashSet<Uuid> = match objective {
+            Objective::MaxCount => smallest_first_within_budget(&candidates, budget_bytes),
+            Objective::MaxRating => best_rated_within_budget(&candidates, budget_bytes),
+            Objective::MaxTypeCoverage => widest_coverage_within_budget(&candidates, budget_bytes),
+        };
+        Ok(candidates.iter().map(|model| model.id).filter(|id| chosen.contains(id)).collect())
+    }
+
+    /// Get recently updated models
+    pub async fn get_recent_models(&self, limit: u32) -> Result<Vec<Model>, ClientError> {
+        let filter = ModelFilter {
+            limit: Some(limit),
+            ..Default::default()
+        };
+        self.service.list_models(filter).await
+            .map_err(ClientError::ServiceError)
+    }
+
+    /// Validate model data before creation
+    ///
+    /// Besides the required fields, cross-field rules flag contradictory combinations.
+    /// Broken rules at [`RuleSeverity::Reject`] fail validation; those at
+    /// [`RuleSeverity::Warn`] are returned for the caller to show.
+    pub async fn validate_create_request(&self, request: &CreateModelRequest) -> Result<Vec<ConsistencyIssue>, ClientError> {
+        self.check_create_request(request, None).await
+    }
+
+    /// `validate_create_request`, treating `pending_install` as an install record
+    async fn check_create_request(
+        &self,
+        request: &CreateModelRequest,
+        pending_install: Option<&str>,
+    ) -> Result<Vec<ConsistencyIssue>, ClientError> {
+        if request.name.is_empty() {
+            return Err(ClientError::ValidationFailed("Model name cannot be empty".to_string()));
+        }
+
+        if request.display_name.is_empty() {
+            return Err(ClientError::ValidationFailed("Display name cannot be empty".to_string()));
+        }
+
+        if request.file_size == 0 {
+            return Err(ClientError::ValidationFailed("File size must be greater than 0".to_string()));
+        }
+
+        if request.provider.is_empty() {
+            return Err(ClientError::ValidationFailed("Provider cannot be empty".to_string()));
+        }
+
+        let install_paths: Vec<String> = match &request.file_path {
+            Some(_) => self.get_installed_models().await?
+                .into_iter()
+                .map(|installed| installed.install_path)
+                .chain(pending_install.map(str::to_string))
+                .collect(),
+            None => Vec::new(),
+        };
+        let mut issues = Vec::new();
+        for (rule, message) in consistency_violations(request, &self.config.default_install_dir, &install_paths) {
+            let severity = self.config.consistency_rules.get(&rule).copied().unwrap_or(rule.default_severity());
+            if severity != RuleSeverity::Ignore {
+                issues.push(ConsistencyIssue { rule, severity, message });
+            }
+        }
+        let rejected: Vec<&str> = issues.iter()
+            .filter(|issue| issue.severity == RuleSeverity::Reject)
+            .map(|issue| issue.message.as_str())
+            .collect();
+        if !rejected.is_empty() {
+            return Err(ClientError::ValidationFailed(rejected.join("; ")));
+        }
+
+        Ok(issues)
+    }
+
+    /// Format file size for display
+    pub fn format_file_size(bytes: u64) -> String {
+        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+        const THRESHOLD: f64 = 1024.0;
+
+        if bytes == 0 {
+            return "0 B".to_string();
+        }
+
+        let size = bytes as f64;
+        let unit_index = (size.ln() / THRESHOLD.ln()).floor() as usize;
+        let unit_index = unit_index.min(UNITS.len() - 1);
+
+        let value = size / THRESHOLD.powi(unit_index as i32);
+
+        if unit_index == 0 {
+            format!("{} {}", bytes, UNITS[unit_index])
+        } else {
+            format!("{:.1} {}", value, UNITS[unit_index])
+        }
+    }
+
+    /// Get model type display name
+    pub fn get_model_type_display_name(model_type: &ModelType) -> &'static str {
+        model_type_display(model_type, Locale::En)
+    }
+
+    /// Get status color for UI
+    pub fn get_status_color(status: &ModelStatus) -> &'static str {
+        match status {
+            ModelStatus::Running => "#10B981", // green
+            ModelStatus::Starting => "#F59E0B", // yellow
+            ModelStatus::Stopping => "#F59E0B", // yellow
+            ModelStatus::Stopped => "#6B7280", // gray
+            ModelStatus::Error => "#EF4444", // red
+        }
+    }
+
+    /// Check if model can be started
+    pub fn can_start_model(model: &InstalledModel) -> bool {
+        matches!(model.status, ModelStatus::Stopped | ModelStatus::Error)
+    }
+
+    /// Check if model can be stopped
+    pub fn can_stop_model(model: &InstalledModel) -> bool {
+        matches!(model.status, ModelStatus::Running | ModelStatus::Starting)
+    }
+}
+
+/// Connect to the database core
+async fn connect_database(db_path: &str) -> Result<Database, ClientError> {
+    let mut database = Database::new(db_path);
+    database.initialize().await
+        .map_err(|e| ClientError::InitializationFailed(format!("Database initialization failed: {}", e)))?;
+    Ok(database)
+}
+
+/// Deduplicates concurrent loads of the same key
+///
+/// Callers arriving while a load for their key is running wait for it and share its
+/// result instead of starting another. Nothing is kept once the load has finished, so
+/// later calls always load fresh data. If the load fails, each waiter retries in turn.
+struct SingleFlight<K, T> {
+    in_flight: std::sync::Mutex<HashMap<K, Arc<tokio::sync::OnceCell<T>>>>,
+    #[cfg(test)]
+    loads: std::sync::atomic::AtomicUsize,
+}
+
+impl<K, T> Default for SingleFlight<K, T> {
+    fn default() -> Self {
+        Self {
+            in_flight: std::sync::Mutex::new(HashMap::new()),
+            #[cfg(test)]
+            loads: std::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+}
+
+impl<K: Eq + std::hash::Hash + Clone, T: Clone> SingleFlight<K, T> {
+    async fn run<E, F, Fut>(&self, key: K, load: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+    {
+        let cell = self.in_flight.lock().unwrap().entry(key.clone()).or_default().clone();
+        let result = cell.get_or_try_init(|| {
+            #[cfg(test)]
+            self.loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            load()
+        }).await.cloned();
+
+        // The first caller to finish retires the flight; a newer one under the same key is left alone
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            in_flight.remove(&key);
+        }
+        result
+    }
+}
+
+/// Database used when the config doesn't name one
+fn default_database_path() -> String {
+    format!("{}/burncloud/models.db", home_dir())
+}
+
+/// Resolve the user's home directory, falling back to the current directory
+fn home_dir() -> String {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string())
+}
+
+/// Weight of provider favor in `rerank_discovered`
+const RERANK_PROVIDER_WEIGHT: f64 = 0.6;
+/// Weight of tag favor in `rerank_discovered`
+const RERANK_TAG_WEIGHT: f64 = 0.3;
+/// Score removed from models the user uninstalled before
+const RERANK_UNINSTALLED_PENALTY: f64 = 0.8;
+
+/// Local signals used to rerank discovery results
+#[derive(Debug, Default)]
+struct LocalPreferences {
+    /// Lowercased provider -> favor in 0..=1
+    providers: HashMap<String, f64>,
+    /// Lowercased tag -> favor in 0..=1
+    tags: HashMap<String, f64>,
+    /// Lowercased names of models the user uninstalled
+    uninstalled: HashSet<String>,
+}
+
+impl LocalPreferences {
+    /// Score added to a discovered model on top of its server position
+    fn boost(&self, model: &DiscoveredModel) -> f64 {
+        let provider = self.providers.get(&model.provider.to_lowercase()).copied().unwrap_or(0.0);
+        let tag = model.tags
+            .iter()
+            .filter_map(|tag| self.tags.get(&tag.to_lowercase()).copied())
+            .fold(0.0, f64::max);
+        let penalty = if self.uninstalled.contains(&model.name.to_lowercase()) {
+            RERANK_UNINSTALLED_PENALTY
+        } else {
+            0.0
+        };
+        RERANK_PROVIDER_WEIGHT * provider + RERANK_TAG_WEIGHT * tag - penalty
+    }
+}
+
+/// Canonical form of a tag list for `normalize_tags`
+///
+/// `aliases` must already be lowercased.
+fn normalize_tag_list(tags: &[String], aliases: &HashMap<String, String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        let tag = aliases.get(&tag).cloned().unwrap_or(tag);
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Take the smallest models first, which fits the most models into the budget
+fn smallest_first_within_budget(models: &[Model], budget: u64) -> HashSet<Uuid> {
+    let mut by_size: Vec<&Model> = models.iter().collect();
+    by_size.sort_by_key(|model| model.file_size);
+    let mut used = 0u64;
+    let mut chosen = HashSet::new();
+    for model in by_size {
+        match used.checked_add(model.file_size) {
+            Some(total) if total <= budget => {
+                used = total;
+                chosen.insert(model.id);
+            }
+            _ => break,
+        }
+    }
+    chosen
+}
+
+/// 0/1 knapsack maximizing the summed rating, in tenths of a star
+///
+/// Works over rating values rather than bytes, so the table stays small no matter
+/// how large the budget is. Ties go to the lighter selection.
+fn best_rated_within_budget(models: &[Model], budget: u64) -> HashSet<Uuid> {
+    let values: Vec<usize> = models.iter()
+        .map(|model| (model.rating.unwrap_or(0.0).max(0.0) * 10.0).round() as usize)
+        .collect();
+    let total: usize = values.iter().sum();
+
+    // lightest[v]: the least total size of a selection worth exactly v
+    let mut lightest: Vec<Option<u64>> = vec![None; total + 1];
+    lightest[0] = Some(0);
+    let mut taken = vec![vec![false; total + 1]; models.len()];
+    for (i, model) in models.iter().enumerate() {
+        if values[i] == 0 {
+            continue;
+        }
+        for v in (values[i]..=total).rev() {
+            let Some(size) = lightest[v - values[i]].and_then(|size| size.checked_add(model.file_size)) else {
+                continue;
+            };
+            if size <= budget && lightest[v].is_none_or(|current| size < current) {
+                lightest[v] = Some(size);
+                taken[i][v] = true;
+            }
+        }
+    }
+
+    let mut v = lightest.iter().rposition(Option::is_some).unwrap_or(0);
+    let mut chosen = HashSet::new();
+    for i in (0..models.len()).rev() {
+        if taken[i][v] {
+            chosen.insert(models[i].id);
+            v -= values[i];
+        }
+    }
+    chosen
+}
+
+/// Cover as many model types as possible, then fill the rest smallest first
+///
+/// Each type is represented by its smallest model and the cheapest types are taken
+/// first, which covers the most types; leftover space goes to the most extra models.
+fn widest_coverage_within_budget(models: &[Model], budget: u64) -> HashSet<Uuid> {
+    let mut smallest_per_type: HashMap<&ModelType, &Model> = HashMap::new();
+    for model in models {
+        let entry = smallest_per_type.entry(&model.model_type).or_insert(model);
+        if model.file_size < entry.file_size {
+            *entry = model;
+        }
+    }
+    let mut representatives: Vec<&Model> = smallest_per_type.into_values().collect();
+    representatives.sort_by_key(|model| (model.file_size, model.id));
+
+    let mut used = 0u64;
+    let mut chosen = HashSet::new();
+    for model in representatives {
+        match used.checked_add(model.file_size) {
+            Some(total) if total <= budget => {
+                used = total;
+                chosen.insert(model.id);
+            }
+            _ => break,
+        }
+    }
+
+    let rest: Vec<Model> = models.iter().filter(|model| !chosen.contains(&model.id)).cloned().collect();
+    chosen.extend(smallest_first_within_budget(&rest, budget - used));
+    chosen
+}
+
+/// Cross-field rules broken by a create request, with messages naming both fields
+///
+/// `install_paths` are the paths of the current install records.
+fn consistency_violations(
+    request: &CreateModelRequest,
+    install_dir: &Path,
+    install_paths: &[String],
+) -> Vec<(ConsistencyRule, String)> {
+    let config_flag = |key: &str| request.config.get(key).and_then(serde_json::Value::as_bool);
+    let mut violations = Vec::new();
+
+    if request.model_type == ModelType::Embedding && config_flag("code_mode") == Some(true) {
+        violations.push((
+            ConsistencyRule::EmbeddingCodeMode,
+            "model_type Embedding conflicts with config.code_mode = true: embedding models don't generate code".to_string(),
+        ));
+    }
+    if let Some(file_path) = &request.file_path {
+        let path = Path::new(file_path);
+        let owned = install_paths.iter().any(|install_path| path.starts_with(install_path));
+        if path.starts_with(install_dir) && !owned {
+            violations.push((
+                ConsistencyRule::FilePathNotInstalled,
+                format!(
+                    "file_path {} is inside default_install_dir {} but no install record covers it",
+                    file_path,
+                    install_dir.display(),
+                ),
+            ));
+        }
+        if request.download_url.is_none() && !Path::new(file_path).exists() {
+            violations.push((
+                ConsistencyRule::UnavailableFile,
+                format!("file_path {} does not exist and download_url is missing, so the model can't be obtained", file_path),
+            ));
+        }
+    }
+    violations
+}
+
+/// Scale affinities so the largest becomes 1.0
+fn normalize_affinity(affinity: &mut HashMap<String, f64>) {
+    let max = affinity.values().copied().fold(0.0, f64::max);
+    if max > 0.0 {
+        affinity.values_mut().for_each(|value| *value /= max);
+    }
+}
+
+/// Trim a model name and reject ones that can't be stored or shown
+fn normalize_model_name(name: &str) -> Result<String, ClientError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ClientError::ValidationFailed("Model name cannot be empty".to_string()));
+    }
+    if name.chars().any(|c| c.is_control() || matches!(c, '/' | '\\')) {
+        return Err(ClientError::ValidationFailed(format!("Model name contains invalid characters: {:?}", name)));
+    }
+    Ok(name.to_string())
+}
+
+/// Locate an installed model's file on disk
+///
+/// The install path is used when it is a file. For an install directory, the single
+/// model file inside it is used, falling back to the model's registered `file_path`.
+fn installed_model_file(installed: &InstalledModel) -> Option<PathBuf> {
+    let install_path = Path::new(&installed.install_path);
+    if install_path.is_file() {
+        return Some(install_path.to_path_buf());
+    }
+
+    if let Ok(entries) = std::fs::read_dir(install_path) {
+        let model_files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && is_local_model_file(path))
+            .collect();
+        if let [file] = model_files.as_slice() {
+            return Some(file.clone());
+        }
+    }
+
+    installed.model.file_path.as_deref()
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+}
+
+/// Whether a path has one of the `LOCAL_MODEL_EXTENSIONS`
+fn is_local_model_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| LOCAL_MODEL_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Weights layer of an Ollama manifest
+#[derive(Debug, Deserialize)]
+struct OllamaLayer {
+    #[serde(rename = "mediaType")]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaManifest {
+    #[serde(default)]
+    layers: Vec<OllamaLayer>,
+}
+
+/// Media type of the layer that holds an Ollama model's weights
+const OLLAMA_WEIGHTS_MEDIA_TYPE: &str = "application/vnd.ollama.image.model";
+
+/// Manifests below `dir`, laid out as `<registry>/<namespace>/<name>/<tag>`,
+/// as `(name, tag, path)` sorted by path
+fn find_ollama_manifests(dir: &Path) -> std::io::Result<Vec<(String, String, PathBuf)>> {
+    let mut manifests = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let name = path.parent().and_then(|parent| parent.file_name()).and_then(|name| name.to_str());
+            let tag = path.file_name().and_then(|tag| tag.to_str());
+            if let (Some(name), Some(tag)) = (name, tag) {
+                manifests.push((name.to_string(), tag.to_string(), path.clone()));
+            }
+        }
+    }
+    manifests.sort_by(|a, b| a.2.cmp(&b.2));
+    Ok(manifests)
+}
+
+/// The weights layer of the manifest at `path`, if it can be read
+fn read_ollama_weights(path: &Path) -> Option<OllamaLayer> {
+    let manifest: OllamaManifest = serde_json::from_slice(&std::fs::read(path).ok()?).ok()?;
+    manifest.layers.into_iter().find(|layer| layer.media_type == OLLAMA_WEIGHTS_MEDIA_TYPE)
+}
+
+/// Make a model name safe to use as a single directory name
+fn sanitize_path_component(name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect();
+    let sanitized = sanitized.trim_matches('.');
+
+    if sanitized.is_empty() {
+        "model".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+/// Lets components written against `ModelDataSource` run on the database backend
+///
+/// Ports are managed by the service layer, so the `port` passed to `start_model` is not used.
+#[async_trait]
+impl ModelDataSource for IntegratedModelService {
+    async fn installed_models(&self) -> Result<Vec<InstalledModel>, ClientError> {
+        self.get_installed_models().await
+    }
+
+    async fn available_models(&self) -> Result<Vec<AvailableModel>, ClientError> {
+        Ok(self.list_models(None).await?
+            .into_iter()
+            .map(|model| AvailableModel {
+                model,
+                is_downloadable: true,
+                estimated_download_time: None,
+            })
+            .collect())
+    }
+
+    async fn install_model(&mut self, model_id: Uuid, install_path: String) -> Result<(), ClientError> {
+        IntegratedModelService::install_model(self, model_id, install_path).await.map(|_| ())
+    }
+
+    async fn uninstall_model(&mut self, model_id: Uuid) -> Result<(), ClientError> {
+        if IntegratedModelService::uninstall_model(self, model_id).await? {
+            Ok(())
+        } else {
+            Err(ClientError::ValidationFailed(format!("Model {} is not installed", model_id)))
+        }
+    }
+
+    async fn start_model(&mut self, model_id: Uuid, _port: u16) -> Result<(), ClientError> {
+        IntegratedModelService::start_model(self, model_id).await.map(|_| ())
+    }
+
+    async fn stop_model(&mut self, model_id: Uuid) -> Result<(), ClientError> {
+        IntegratedModelService::stop_model(self, model_id).await
+    }
+
+    async fn search_installed_models(&self, query: &str) -> Result<Vec<InstalledModel>, ClientError> {
+        let query_lower = query.to_lowercase();
+        Ok(self.get_installed_models().await?
+            .into_iter()
+            .filter(|model| installed_model_matches(model, &query_lower))
+            .collect())
+    }
+}
+
+/// Create request for a model first seen in the discovery feed
+///
+/// Hardware requirements go into the config under the keys `model_requirements` reads.
+fn discovery_create_request(discovered: &DiscoveredModel) -> CreateModelRequest {
+    let requirements = &discovered.requirements;
+    let mut config = HashMap::from([
+        ("min_memory_gb".to_string(), serde_json::json!(requirements.min_ram_gb)),
+        ("gpu_required".to_string(), serde_json::json!(requirements.gpu_required)),
+        ("cpu_cores".to_string(), serde_json::json!(requirements.cpu_cores)),
+    ]);
+    if !requirements.supported_platforms.is_empty() {
+        config.insert("supported_os".to_string(), serde_json::json!(requirements.supported_platforms));
+    }
+
+    CreateModelRequest {
+        name: discovered.name.clone(),
+        display_name: discovered.display_name.clone(),
+        version: discovered.version.clone(),
+        model_type: match discovered.model_type {
+            crate::discovery::ModelType::TextGeneration => ModelType::Text,
+            crate::discovery::ModelType::ChatCompletion => ModelType::Chat,
+            crate::discovery::ModelType::Embedding => ModelType::Embedding,
+            crate::discovery::ModelType::CodeGeneration => ModelType::Code,
+            crate::discovery::ModelType::ImageGeneration => ModelType::ImageGeneration,
+            crate::discovery::ModelType::Multimodal => ModelType::Multimodal,
+        },
+        provider: discovered.provider.clone(),
+        file_size: (discovered.size_gb * 1024.0 * 1024.0 * 1024.0) as u64,
+        description: (!discovered.description.is_empty()).then(|| discovered.description.clone()),
+        license: (!discovered.license.is_empty()).then(|| discovered.license.clone()),
+        tags: discovered.tags.clone(),
+        languages: vec![],
+        file_path: None,
+        download_url: Some(discovered.download_url.clone()),
+        config,
+        is_official: discovered.is_verified,
+    }
+}
+
+/// Fields of a local model that the discovery feed has changed, if any
+fn discovery_update_request(model: &Model, discovered: &DiscoveredModel) -> Option<UpdateModelRequest> {
+    let download_url = (model.download_url.as_deref() != Some(discovered.download_url.as_str()))
+        .then(|| discovered.download_url.clone());
+    let checksum = (!discovered.checksum.is_empty() && model.checksum.as_deref() != Some(discovered.checksum.as_str()))
+        .then(|| discovered.checksum.clone());
+    let rating = (model.rating != Some(discovered.rating)).then_some(discovered.rating);
+
+    if download_url.is_none() && checksum.is_none() && rating.is_none() {
+        return None;
+    }
+    Some(UpdateModelRequest {
+        download_url,
+        checksum,
+        rating,
+        ..Default::default()
+    })
+}
+
+/// Local availability of a discovered model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalStatus {
+    NotPresent,
+    Registered,
+    Installed,
+}
+
+impl LocalStatus {
+    /// Badge text for discovery results, if any
+    pub fn badge(&self) -> Option<&'static str> {
+        match self {
+            LocalStatus::NotPresent => None,
+            LocalStatus::Registered => Some("已注册"),
+            LocalStatus::Installed => Some("已安装"),
+        }
     }
+}
 
-    /// Get official models only
-    pub async fn get_official_models(&self) -> Result<Vec<Model>, ClientError> {
-        let filter = ModelFilter {
-            is_official: Some(true),
-            ..Default::default()
-        };
-        self.service.list_models(filter).await
-            .map_err(ClientError::ServiceError)
+/// Position in the model list for `list_models_after`
+///
+/// Models are ordered by `(updated_at, id)`; the cursor holds the key of the last
+/// model on a page. It serializes so the UI can keep it between loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Cursor {
+    updated_at: DateTime<Utc>,
+    id: Uuid,
+}
+
+impl Cursor {
+    /// The cursor just past `model`
+    pub fn after(model: &Model) -> Self {
+        Self { updated_at: model.updated_at, id: model.id }
     }
+}
 
-    /// Get service statistics
-    pub async fn get_statistics(&self) -> Result<ClientModelStats, ClientError> {
-        let stats = self.service.get_model_stats().await
-            .map_err(ClientError::ServiceError)?;
+/// Time source for the waits of a checksum scrub
+///
+/// Tests substitute a fake clock to run a scrub without waiting out its interval.
+#[async_trait]
+pub trait ScrubClock: Send + Sync {
+    async fn sleep(&self, duration: Duration);
+}
 
-        Ok(ClientModelStats {
-            total_models: stats.total_models,
-            installed_count: stats.installed_count,
-            official_count: stats.official_count,
-            running_count: stats.running_count,
-            total_size_bytes: stats.total_size_bytes,
-            models_by_type: stats.models_by_type,
-        })
+/// Waits on the tokio timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+#[async_trait]
+impl ScrubClock for TokioClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
     }
+}
 
-    /// Get models grouped by size category
-    pub async fn get_models_by_size(&self) -> Result<HashMap<SizeCategory, Vec<Model>>, ClientError> {
-        let models = self.list_models(None).await?;
-        let mut grouped = HashMap::new();
+/// What `select_within_budget` optimizes for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Objective {
+    /// Install as many models as possible
+    MaxCount,
+    /// Maximize the summed rating; unrated models count as zero
+    MaxRating,
+    /// Cover as many model types as possible, then as many models as possible
+    MaxTypeCoverage,
+}
 
-        for model in models {
-            grouped.entry(model.size_category)
-                .or_insert_with(Vec::new)
-                .push(model);
+/// Cross-field rule checked by `validate_create_request`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConsistencyRule {
+    /// An `Embedding` model with `config.code_mode = true`
+    EmbeddingCodeMode,
+    /// `file_path` lies inside `default_install_dir`, but no install record covers it,
+    /// e.g. a file left behind by an uninstalled model
+    FilePathNotInstalled,
+    /// `file_path` doesn't exist and there is no `download_url` to fetch it from
+    UnavailableFile,
+}
+
+impl ConsistencyRule {
+    /// Severity used when the service config doesn't override it
+    pub fn default_severity(&self) -> RuleSeverity {
+        match self {
+            ConsistencyRule::EmbeddingCodeMode => RuleSeverity::Warn,
+            ConsistencyRule::FilePathNotInstalled => RuleSeverity::Reject,
+            ConsistencyRule::UnavailableFile => RuleSeverity::Warn,
         }
+    }
+}
 
-        Ok(grouped)
+/// How a broken [`ConsistencyRule`] is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSeverity {
+    Ignore,
+    Warn,
+    Reject,
+}
+
+/// A broken cross-field rule reported by `validate_create_request`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsistencyIssue {
+    pub rule: ConsistencyRule,
+    pub severity: RuleSeverity,
+    pub message: String,
+}
+
+/// Where a model was registered from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstallSource {
+    Discovery,
+    LocalFile,
+    Ollama,
+    Manifest,
+    Manual,
+}
+
+impl InstallSource {
+    /// Value stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallSource::Discovery => "discovery",
+            InstallSource::LocalFile => "local_file",
+            InstallSource::Ollama => "ollama",
+            InstallSource::Manifest => "manifest",
+            InstallSource::Manual => "manual",
+        }
     }
 
-    /// Get recently updated models
-    pub async fn get_recent_models(&self, limit: u32) -> Result<Vec<Model>, ClientError> {
-        let filter = ModelFilter {
-            limit: Some(limit),
-            ..Default::default()
+    /// Name shown in the UI
+    pub fn label(&self, locale: Locale) -> &'static str {
+        let key = match self {
+            InstallSource::Discovery => "source.discovery",
+            InstallSource::LocalFile => "source.local_file",
+            InstallSource::Ollama => "source.ollama",
+            InstallSource::Manifest => "source.manifest",
+            InstallSource::Manual => "source.manual",
         };
-        self.service.list_models(filter).await
-            .map_err(ClientError::ServiceError)
+        t(key, locale)
     }
 
-    /// Validate model data before creation
-    pub fn validate_create_request(&self, request: &CreateModelRequest) -> Result<(), ClientError> {
-        if request.name.is_empty() {
-            return Err(ClientError::ValidationFailed("Model name cannot be empty".to_string()));
+    /// Parse a stored value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "discovery" => Some(InstallSource::Discovery),
+            "local_file" => Some(InstallSource::LocalFile),
+            "ollama" => Some(InstallSource::Ollama),
+            "manifest" => Some(InstallSource::Manifest),
+            "manual" => Some(InstallSource::Manual),
+            _ => None,
         }
+    }
+}
 
-        if request.display_name.is_empty() {
-            return Err(ClientError::ValidationFailed("Display name cannot be empty".to_string()));
+/// Kind of change recorded in the model audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModelEventKind {
+    Created,
+    Installed,
+    Uninstalled,
+    Deleted,
+}
+
+impl ModelEventKind {
+    /// Value stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelEventKind::Created => "created",
+            ModelEventKind::Installed => "installed",
+            ModelEventKind::Uninstalled => "uninstalled",
+            ModelEventKind::Deleted => "deleted",
         }
+    }
 
-        if request.file_size == 0 {
-            return Err(ClientError::ValidationFailed("File size must be greater than 0".to_string()));
+    /// Parse a stored value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "created" => Some(ModelEventKind::Created),
+            "installed" => Some(ModelEventKind::Installed),
+            "uninstalled" => Some(ModelEventKind::Uninstalled),
+            "deleted" => Some(ModelEventKind::Deleted),
+            _ => None,
         }
+    }
+}
 
-        if request.provider.is_empty() {
-            return Err(ClientError::ValidationFailed("Provider cannot be empty".to_string()));
+/// One entry in the model audit log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelEvent {
+    pub model_id: Uuid,
+    pub kind: ModelEventKind,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Kind of mutating operation recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AuditOperation {
+    Create,
+    Update,
+    Delete,
+    Install,
+    Uninstall,
+    StatusChange,
+}
+
+impl AuditOperation {
+    /// Value stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::Create => "create",
+            AuditOperation::Update => "update",
+            AuditOperation::Delete => "delete",
+            AuditOperation::Install => "install",
+            AuditOperation::Uninstall => "uninstall",
+            AuditOperation::StatusChange => "status_change",
         }
+    }
 
-        Ok(())
+    /// Parse a stored value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "create" => Some(AuditOperation::Create),
+            "update" => Some(AuditOperation::Update),
+            "delete" => Some(AuditOperation::Delete),
+            "install" => Some(AuditOperation::Install),
+            "uninstall" => Some(AuditOperation::Uninstall),
+            "status_change" => Some(AuditOperation::StatusChange),
+            _ => None,
+        }
     }
+}
 
-    /// Format file size for display
-    pub fn format_file_size(bytes: u64) -> String {
-        const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
-        const THRESHOLD: f64 = 1024.0;
+/// One entry in the audit log of mutating operations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub operation: AuditOperation,
+    /// `None` for operations that aren't about a single model
+    pub model_id: Option<Uuid>,
+    pub details: serde_json::Value,
+    /// Written before its operation ran, which never confirmed the outcome
+    pub pending: bool,
+}
 
-        if bytes == 0 {
-            return "0 B".to_string();
+/// History rows written ahead of a mutation, waiting for `finish_history`
+#[must_use]
+struct PendingHistory {
+    audit_id: i64,
+    operation: AuditOperation,
+    model_id: Option<Uuid>,
+    /// Row id of the model event, if the operation records one
+    event_id: Option<i64>,
+}
+
+impl PendingHistory {
+    /// Attach the id of a model created by the mutation
+    fn for_model(mut self, model_id: Uuid) -> Self {
+        self.model_id = Some(model_id);
+        self
+    }
+}
+
+/// Audit details for an update: the fields the request sets
+fn update_details(request: &UpdateModelRequest) -> serde_json::Value {
+    match serde_json::to_value(request) {
+        Ok(serde_json::Value::Object(mut fields)) => {
+            fields.retain(|_, value| !value.is_null());
+            serde_json::Value::Object(fields)
         }
+        _ => serde_json::json!({}),
+    }
+}
 
-        let size = bytes as f64;
-        let unit_index = (size.ln() / THRESHOLD.ln()).floor() as usize;
-        let unit_index = unit_index.min(UNITS.len() - 1);
+/// Audit details for an install
+fn install_details(install_path: &str) -> serde_json::Value {
+    serde_json::json!({ "install_path": install_path })
+}
 
-        let value = size / THRESHOLD.powi(unit_index as i32);
+/// Model files at an install path: the path itself, or the model files directly inside a directory
+fn model_files(install_path: &Path) -> Result<Vec<PathBuf>, ClientError> {
+    if install_path.is_file() {
+        return Ok(vec![install_path.to_path_buf()]);
+    }
+    if !install_path.is_dir() {
+        return Err(ClientError::ResourceNotFound(format!("Install path {}", install_path.display())));
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(install_path)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file() && path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| LOCAL_MODEL_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
 
-        if unit_index == 0 {
-            format!("{} {}", bytes, UNITS[unit_index])
-        } else {
-            format!("{:.1} {}", value, UNITS[unit_index])
+/// Read a file end to end so the OS keeps it in the page cache
+///
+/// Returns `false` if `cancel` fired before the whole file was read.
+async fn touch_into_page_cache(path: &Path, cancel: &CancellationToken) -> Result<bool, ClientError> {
+    use tokio::io::AsyncReadExt;
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut buf = vec![0u8; PREWARM_CHUNK_BYTES];
+    loop {
+        if cancel.is_cancelled() {
+            return Ok(false);
+        }
+        if file.read(&mut buf).await? == 0 {
+            return Ok(true);
         }
     }
+}
 
-    /// Get model type display name
-    pub fn get_model_type_display_name(model_type: &ModelType) -> &'static str {
-        match model_type {
-            ModelType::Chat => "Conversational",
-            ModelType::Code => "Code Generation",
-            ModelType::Text => "Text Generation",
-            ModelType::Embedding => "Text Embedding",
-            ModelType::Image => "Image Processing",
-            ModelType::ImageGeneration => "Image Generation",
-            ModelType::Audio => "Audio Processing",
-            ModelType::Speech => "Speech Processing",
-            ModelType::Video => "Video Processing",
-            ModelType::Multimodal => "Multimodal",
-            ModelType::Other => "Other",
-        }
+/// Whether a time series member counted before the first bucket, then its
+/// additions (`true`) and removals in order
+type Timeline = (bool, Vec<(DateTime<Utc>, bool)>);
+
+/// Bucket size for time series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Granularity {
+    Hour,
+    Day,
+    /// Weeks start on Monday
+    Week,
+    /// Calendar months
+    Month,
+}
+
+impl Granularity {
+    /// Start of the bucket containing `at`
+    pub fn floor(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let start = match self {
+            Granularity::Hour => {
+                return date.and_hms_opt(at.hour(), 0, 0).unwrap().and_utc();
+            }
+            Granularity::Day => date,
+            Granularity::Week => date - chrono::Days::new(date.weekday().num_days_from_monday() as u64),
+            Granularity::Month => date.with_day(1).unwrap(),
+        };
+        start.and_hms_opt(0, 0, 0).unwrap().and_utc()
     }
 
-    /// Get status color for UI
-    pub fn get_status_color(status: &ModelStatus) -> &'static str {
-        match status {
-            ModelStatus::Running => "#10B981", // green
-            ModelStatus::Starting => "#F59E0B", // yellow
-            ModelStatus::Stopping => "#F59E0B", // yellow
-            ModelStatus::Stopped => "#6B7280", // gray
-            ModelStatus::Error => "#EF4444", // red
+    /// Shortest length a bucket can have
+    fn min_length(&self) -> chrono::Duration {
+        match self {
+            Granularity::Hour => chrono::Duration::hours(1),
+            Granularity::Day => chrono::Duration::days(1),
+            Granularity::Week => chrono::Duration::weeks(1),
+            Granularity::Month => chrono::Duration::days(28),
         }
     }
 
-    /// Check if model can be started
-    pub fn can_start_model(model: &InstalledModel) -> bool {
-        matches!(model.status, ModelStatus::Stopped | ModelStatus::Error)
+    /// Start of the bucket after the one starting at `start`
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Granularity::Hour => start + chrono::Duration::hours(1),
+            Granularity::Day => start + chrono::Duration::days(1),
+            Granularity::Week => start + chrono::Duration::weeks(1),
+            Granularity::Month => start + chrono::Months::new(1),
+        }
     }
+}
 
-    /// Check if model can be stopped
-    pub fn can_stop_model(model: &InstalledModel) -> bool {
-        matches!(model.status, ModelStatus::Running | ModelStatus::Starting)
+/// Outcome of a `compact` run
+#[derive(Debug, Clone)]
+pub struct CompactReport {
+    pub size_before: u64,
+    pub size_after: u64,
+    /// Negative if the database grew, which VACUUM can occasionally do
+    pub bytes_reclaimed: i64,
+    pub duration: Duration,
+}
+
+/// Outcome of a bulk delete
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteReport {
+    pub deleted: Vec<Uuid>,
+    pub skipped_installed: Vec<Uuid>,
+    pub not_found: Vec<Uuid>,
+    /// Models whose delete failed, with the error
+    pub failed: Vec<(Uuid, String)>,
+}
+
+/// Outcome of a manifest import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub created: Vec<Uuid>,
+    pub updated: Vec<Uuid>,
+}
+
+/// A local model merged with its discovery catalog entry, for the details view
+#[derive(Debug, Clone)]
+pub struct MergedModelDetails {
+    pub model: Model,
+    /// Installation record, if the model is installed
+    pub installed: Option<InstalledModel>,
+    /// Catalog entry; `None` for installed models, models not synced from discovery,
+    /// or when the catalog couldn't be reached
+    pub discovered: Option<DiscoveredModel>,
+    /// Local description, falling back to the catalog's
+    pub description: Option<String>,
+    /// Catalog capabilities, empty without a catalog entry
+    pub capabilities: Vec<String>,
+    /// Catalog requirements, falling back to those derived from the local record
+    pub requirements: SystemRequirements,
+    pub repository_url: Option<String>,
+    pub documentation_url: Option<String>,
+}
+
+impl MergedModelDetails {
+    fn merge(model: Model, installed: Option<InstalledModel>, discovered: Option<DiscoveredModel>) -> Self {
+        let requirements = match &discovered {
+            Some(d) => SystemRequirements {
+                min_memory_gb: d.requirements.min_ram_gb as f32,
+                recommended_memory_gb: d.requirements.min_ram_gb as f32,
+                min_disk_space_gb: d.requirements.disk_space_gb as f32,
+                gpu_required: d.requirements.gpu_required,
+                cpu_cores: d.requirements.cpu_cores,
+                supported_os: (!d.requirements.supported_platforms.is_empty()).then(|| {
+                    d.requirements.supported_platforms.iter().map(|p| p.to_lowercase()).collect()
+                }),
+                supported_arch: None,
+            },
+            None => model_requirements(&model),
+        };
+        let description = model.description.clone()
+            .or_else(|| discovered.as_ref().map(|d| d.description.clone()).filter(|d| !d.is_empty()));
+
+        Self {
+            description,
+            capabilities: discovered.as_ref().map(|d| d.capabilities.clone()).unwrap_or_default(),
+            requirements,
+            repository_url: discovered.as_ref().and_then(|d| d.repository_url.clone()),
+            documentation_url: discovered.as_ref().and_then(|d| d.documentation_url.clone()),
+            model,
+            installed,
+            discovered,
+        }
     }
 }
 
+/// Outcome of a discovery sync
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Models created from the feed
+    pub added: Vec<Uuid>,
+    /// Existing models whose download URL, checksum or rating changed
+    pub updated: Vec<Uuid>,
+    /// Previously synced models the feed no longer lists
+    pub stale: Vec<Uuid>,
+}
+
 /// Client-level statistics
-#[derive(Debug, Clone)]
+///
+/// Serializes with `installation_rate` and `running_rate` alongside the stored
+/// fields; those are recomputed on load, not read back.
+#[derive(Debug, Clone, Deserialize)]
 pub struct ClientModelStats {
     pub total_models: usize,
     pub installed_count: usize,
@@ -303,6 +3615,21 @@ impl ClientModelStats {
     }
 }
 
+impl Serialize for ClientModelStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut stats = serializer.serialize_struct("ClientModelStats", 8)?;
+        stats.serialize_field("total_models", &self.total_models)?;
+        stats.serialize_field("installed_count", &self.installed_count)?;
+        stats.serialize_field("official_count", &self.official_count)?;
+        stats.serialize_field("running_count", &self.running_count)?;
+        stats.serialize_field("total_size_bytes", &self.total_size_bytes)?;
+        stats.serialize_field("models_by_type", &SortedTypeCounts(&self.models_by_type))?;
+        stats.serialize_field("installation_rate", &self.installation_rate())?;
+        stats.serialize_field("running_rate", &self.running_rate())?;
+        stats.end()
+    }
+}
+
 /// Client-level errors
 #[derive(Debug, thiserror::Error)]
 pub enum ClientError {
@@ -323,24 +3650,66 @@ pub enum ClientError {
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+
+    #[error("Schema missing: {0}")]
+    SchemaMissing(String),
+
+    #[error("Unsupported schema version {found} (this client supports up to {supported})")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+
+    #[error("Invalid manifest: {}", format_manifest_issues(.0))]
+    InvalidManifest(Vec<ManifestIssue>),
+
+    #[error("Discovery error: {0}")]
+    DiscoveryError(#[from] DiscoveryError),
+
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
+    #[error("Dependency cycle: {}", format_dependency_chain(.0))]
+    DependencyCycle(Vec<Uuid>),
+}
+
+/// `a -> b -> a` style rendering of a dependency chain
+fn format_dependency_chain(chain: &[Uuid]) -> String {
+    chain.iter().map(Uuid::to_string).collect::<Vec<_>>().join(" -> ")
 }
 
 impl ClientError {
-    /// Get user-friendly error message
+    /// Get user-friendly error message in the system locale
     pub fn user_message(&self) -> String {
+        self.user_message_in(Locale::default())
+    }
+
+    /// Get user-friendly error message in the given locale
+    pub fn user_message_in(&self, locale: Locale) -> String {
         match self {
             ClientError::ServiceError(se) => match se {
-                ServiceError::NotFound(_) => "The requested model was not found.".to_string(),
-                ServiceError::Conflict(_) => "A model with this name already exists.".to_string(),
-                ServiceError::Validation(msg) => format!("Invalid input: {}", msg),
-                ServiceError::Unauthorized(_) => "You don't have permission to perform this action.".to_string(),
-                _ => "An unexpected error occurred. Please try again.".to_string(),
+                ServiceError::NotFound(_) => t("error.not_found", locale).to_string(),
+                ServiceError::Conflict(_) => t("error.conflict", locale).to_string(),
+                ServiceError::Validation(msg) => format!("{}: {}", t("error.invalid_input", locale), msg),
+                ServiceError::Unauthorized(_) => t("error.unauthorized", locale).to_string(),
+                _ => t("error.unexpected", locale).to_string(),
             },
             ClientError::ValidationFailed(msg) => msg.clone(),
-            ClientError::InitializationFailed(_) => "Failed to initialize the model service. Please check your configuration.".to_string(),
+            ClientError::InitializationFailed(_) => t("error.init_failed", locale).to_string(),
             ClientError::OperationNotAllowed(msg) => msg.clone(),
-            ClientError::ResourceNotFound(msg) => format!("Resource not found: {}", msg),
-            ClientError::IoError(_) => "A file system error occurred.".to_string(),
+            ClientError::ResourceNotFound(msg) => format!("{}: {}", t("error.resource_not_found", locale), msg),
+            ClientError::IoError(_) => t("error.io", locale).to_string(),
+            ClientError::DatabaseError(_) => t("error.database", locale).to_string(),
+            ClientError::SchemaMissing(_) => t("error.schema_missing", locale).to_string(),
+            ClientError::UnsupportedSchemaVersion { .. } => t("error.schema_newer", locale).to_string(),
+            ClientError::SerializationError(_) => t("error.serialization", locale).to_string(),
+            ClientError::InvalidManifest(issues) => format!("{}: {}", t("error.invalid_manifest", locale), format_manifest_issues(issues)),
+            ClientError::DiscoveryError(_) => t("error.discovery", locale).to_string(),
+            ClientError::Cancelled(_) => t("error.cancelled", locale).to_string(),
+            ClientError::DependencyCycle(chain) => format!("{}: {}", t("error.dependency_cycle", locale), format_dependency_chain(chain)),
         }
     }
 
@@ -353,6 +3722,8 @@ impl ClientError {
                 _ => false,
             },
             ClientError::IoError(_) => true,
+            ClientError::DatabaseError(_) => true,
+            ClientError::DiscoveryError(de) => matches!(de, DiscoveryError::NetworkError(_) | DiscoveryError::TimeoutError),
             _ => false,
         }
     }
@@ -362,6 +3733,207 @@ impl ClientError {
 mod tests {
     use super::*;
 
+    /// Create a service backed by an in-memory database
+    async fn setup_service(install_dir: &Path) -> IntegratedModelService {
+        IntegratedModelService::with_config(IntegratedServiceConfig {
+            database_path: Some(":memory:".to_string()),
+            default_install_dir: install_dir.to_path_buf(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Create a minimal model request
+    fn model_request(name: &str, model_type: ModelType) -> CreateModelRequest {
+        CreateModelRequest {
+            name: name.to_string(),
+            display_name: format!("{} Display", name),
+            version: "1.0.0".to_string(),
+            model_type,
+            provider: "TestProvider".to_string(),
+            file_size: 1024,
+            description: None,
+            license: None,
+            tags: vec![],
+            languages: vec![],
+            file_path: None,
+            download_url: None,
+            config: HashMap::new(),
+            is_official: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_cold_statistics_share_one_query() {
+        let service = setup_service(&std::env::temp_dir()).await;
+        service.create_model(model_request("stats-model", ModelType::Chat)).await.unwrap();
+
+        let calls = (0..20).map(|_| service.get_statistics());
+        let results = futures_util::future::join_all(calls).await;
+        assert!(results.iter().all(|stats| stats.as_ref().unwrap().total_models == 1));
+        assert_eq!(service.statistics_loads.loads.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Nothing is cached once the flight lands, so a later call sees fresh data
+        service.get_statistics().await.unwrap();
+        assert_eq!(service.statistics_loads.loads.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(service.statistics_loads.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_growth_timeseries_buckets_by_day() {
+        let service = setup_service(&std::env::temp_dir()).await;
+
+        // Backdate each creation in the audit log
+        let now = Utc::now();
+        for (name, days_ago) in [("growth-a", 2), ("growth-b", 2), ("growth-c", 1)] {
+            let model = service.create_model(model_request(name, ModelType::Chat)).await.unwrap();
+            let occurred_at = now - chrono::Duration::days(days_ago);
+            service.database.execute_query(&format!(
+                "UPDATE model_events SET occurred_at = {} WHERE model_id = {}",
+                sql_quote(&occurred_at.to_rfc3339()),
+                sql_quote(&model.id.to_string()),
+            )).await.unwrap();
+        }
+
+        let series = service.growth_timeseries(Granularity::Day, now - chrono::Duration::days(3)).await.unwrap();
+        let counts: Vec<usize> = series.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![0, 2, 3, 3]);
+        assert_eq!(series[0].0, Granularity::Day.floor(now - chrono::Duration::days(3)));
+        assert!(series.windows(2).all(|w| w[1].0 - w[0].0 == chrono::Duration::days(1)));
+    }
+
+    #[tokio::test]
+    async fn test_install_growth_timeseries_counts_installed_models() {
+        let service = setup_service(&std::env::temp_dir()).await;
+
+        let now = Utc::now();
+        let mut ids = Vec::new();
+        for name in ["installs-a", "installs-b", "installs-c"] {
+            let model = service.create_model(model_request(name, ModelType::Chat)).await.unwrap();
+            service.install_model(model.id, None).await.unwrap();
+            ids.push(model.id);
+        }
+        // a: installed 3 days ago; b: installed 2 days ago, uninstalled yesterday; c: installed today
+        service.uninstall_model(ids[1]).await.unwrap();
+        for (id, kind, days_ago) in [
+            (ids[0], ModelEventKind::Installed, 3),
+            (ids[1], ModelEventKind::Installed, 2),
+            (ids[1], ModelEventKind::Uninstalled, 1),
+        ] {
+            service.database.execute_query(&format!(
+                "UPDATE model_events SET occurred_at = {} WHERE model_id = {} AND kind = {}",
+                sql_quote(&(now - chrono::Duration::days(days_ago)).to_rfc3339()),
+                sql_quote(&id.to_string()),
+                sql_quote(kind.as_str()),
+            )).await.unwrap();
+        }
+
+        let series = service.install_growth_timeseries(Granularity::Day, now - chrono::Duration::days(2)).await.unwrap();
+        let counts: Vec<usize> = series.iter().map(|(_, count)| *count).collect();
+        // a was installed before the range began, so it counts from the first bucket
+        assert_eq!(counts, vec![2, 1, 2]);
+
+        // The catalog series still counts uninstalled models, all created today
+        let catalog = service.growth_timeseries(Granularity::Day, now - chrono::Duration::days(2)).await.unwrap();
+        let counts: Vec<usize> = catalog.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![0, 0, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_purges_prewarmed_config_and_validations() {
+        let temp = tempfile::tempdir().unwrap();
+        let service = setup_service(&temp.path().join("models")).await;
+
+        let model_file = temp.path().join("prewarmed.gguf");
+        std::fs::write(&model_file, b"GGUF prewarmed weights").unwrap();
+        let model = service.create_model(model_request("prewarmed", ModelType::Chat)).await.unwrap();
+        service.install_model(model.id, model_file.to_string_lossy().to_string()).await.unwrap();
+        service.prewarm_model(model.id, &CancellationToken::new()).await.unwrap();
+        assert!(service.prewarmed_configs.lock().unwrap().contains_key(&model.id));
+        assert!(service.last_validated_at(model.id).await.unwrap().is_some());
+
+        service.force_delete(model.id).await.unwrap();
+
+        assert!(service.prewarmed_configs.lock().unwrap().is_empty());
+        let rows: Vec<(String,)> = service.database
+            .fetch_all("SELECT model_id FROM model_validations")
+            .await
+            .unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_register_and_install_rollback_removes_install_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let service = setup_service(temp.path()).await;
+
+        // Fails after the install directory was created and the install recorded
+        service.database.execute_query("DROP TABLE model_install_sources").await.unwrap();
+        let result = service.register_and_install(model_request("half-installed", ModelType::Chat), None).await;
+        assert!(matches!(result, Err(ClientError::DatabaseError(_))));
+
+        assert!(service.list_models(None).await.unwrap().is_empty());
+        assert!(service.get_installed_models().await.unwrap().is_empty());
+        assert!(!temp.path().join("half-installed").exists());
+        let rows: Vec<(String,)> = service.database
+            .fetch_all("SELECT model_id FROM model_runtime_configs")
+            .await
+            .unwrap();
+        assert!(rows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_growth_timeseries_clamps_bucket_count() {
+        let service = setup_service(&std::env::temp_dir()).await;
+
+        let series = service.growth_timeseries(Granularity::Hour, DateTime::<Utc>::MIN_UTC).await.unwrap();
+        assert!(series.len() <= MAX_TIMESERIES_BUCKETS + 1);
+        assert!(series.len() >= MAX_TIMESERIES_BUCKETS);
+        assert!(series.iter().all(|(_, count)| *count == 0));
+
+        // A range starting in the future is empty
+        let future = service.growth_timeseries(Granularity::Day, Utc::now() + chrono::Duration::days(2)).await.unwrap();
+        assert!(future.is_empty());
+    }
+
+    #[test]
+    fn test_stats_serialization_round_trip() {
+        let stats = ClientModelStats {
+            total_models: 4,
+            installed_count: 2,
+            official_count: 1,
+            running_count: 1,
+            total_size_bytes: 2048,
+            models_by_type: HashMap::from([(ModelType::Code, 1), (ModelType::Chat, 3)]),
+        };
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["installation_rate"], 50.0);
+        assert_eq!(json["running_rate"], 50.0);
+        assert!(serde_json::to_string(&stats).unwrap().contains(r#""models_by_type":{"Chat":3,"Code":1}"#));
+
+        let restored: ClientModelStats = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.installed_count, 2);
+        assert_eq!(restored.models_by_type, stats.models_by_type);
+
+        let app_stats = crate::AppStats {
+            total_installed: 2,
+            running_count: 1,
+            stopped_count: 1,
+            available_count: 3,
+            total_size_bytes: 2048,
+            models_by_type: stats.models_by_type.clone(),
+            installation_rate: 200.0 / 3.0,
+            running_rate: 50.0,
+        };
+        let json = serde_json::to_string(&app_stats).unwrap();
+        assert!(json.contains(r#""running_rate":50.0"#));
+        let restored: crate::AppStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.models_by_type, app_stats.models_by_type);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    }
+
     #[tokio::test]
     async fn test_service_initialization() {
         // Use in-memory database for testing
@@ -390,23 +3962,8 @@ mod tests {
         // Use in-memory database for testing
         let service = IntegratedModelService::new(Some(":memory:".to_string())).await.unwrap();
 
-        let invalid_request = CreateModelRequest {
-            name: "".to_string(), // Empty name should fail
-            display_name: "Test".to_string(),
-            version: "1.0.0".to_string(),
-            model_type: ModelType::Chat,
-            provider: "Test".to_string(),
-            file_size: 1024,
-            description: None,
-            license: None,
-            tags: vec![],
-            languages: vec![],
-            file_path: None,
-            download_url: None,
-            config: HashMap::new(),
-            is_official: false,
-        };
+        let invalid_request = model_request("", ModelType::Chat); // Empty name should fail
 
-        assert!(service.validate_create_request(&invalid_request).is_err());
+        assert!(service.validate_create_request(&invalid_request).await.is_err());
     }
 }
\ No newline at end of file
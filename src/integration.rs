@@ -1,27 +1,57 @@
 // 简化的模型管理集成示例（不依赖数据库）
 
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 use crate::{
     ModelDiscoveryClient, ModelSearchRequest, ModelDownloadManager, ModelValidator,
-    InstallationConfig, ValidationConfig, DiscoveredModel
+    InstallationConfig, ValidationConfig, DiscoveredModel, DownloadError, DownloadProgress,
+    PreflightReport, estimate_runtime_memory_bytes, detect_host_memory_bytes, available_disk_space, ClientError,
+    parse_expected_checksum, CheckStatus
 };
+use crate::notification_sink::{emit_all, NotificationSink};
+use crate::state::Notification;
+
+/// 下载目录中保存发现服务响应缓存的文件
+const DISCOVERY_CACHE_FILE: &str = "discovery_cache.json";
+
+/// 批量下载中单个模型的结果
+pub type BulkDownloadResult = Result<DownloadProgress, Box<dyn std::error::Error + Send + Sync>>;
+
+/// 各子系统的健康状态
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ManagementStatus {
+    pub discovery_reachable: bool,
+    pub download_dir_writable: bool,
+    /// 下载目录所在磁盘的可用空间，无法查询时为 None
+    pub free_space_bytes: Option<u64>,
+    pub installed_count: usize,
+}
 
 /// 简化的模型管理服务
 pub struct ModelManagementService {
     discovery_client: ModelDiscoveryClient,
     download_manager: ModelDownloadManager,
     validator: ModelValidator,
+    /// 下载失败、验证失败时推送通知
+    notification_sinks: Vec<Arc<dyn NotificationSink>>,
 }
 
 impl ModelManagementService {
     /// 创建新的模型管理服务
+    ///
+    /// 发现服务的响应缓存保存在下载目录的 `discovery_cache.json` 中，离线模式下重启后仍可使用。
     pub async fn new(
         discovery_base_url: String,
         download_dir: PathBuf,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         // 初始化发现客户端
-        let discovery_client = ModelDiscoveryClient::new(discovery_base_url)?;
+        let discovery_client = ModelDiscoveryClient::new(discovery_base_url)?
+            .with_cache_file(download_dir.join(DISCOVERY_CACHE_FILE));
 
         // 初始化下载管理器
         let download_manager = ModelDownloadManager::new(download_dir.clone())?;
@@ -34,9 +64,29 @@ impl ModelManagementService {
             discovery_client,
             download_manager,
             validator,
+            notification_sinks: Vec::new(),
         })
     }
 
+    /// 设置离线模式：发现服务只返回缓存的结果，下载管理器拒绝网络下载
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.discovery_client = self.discovery_client.with_offline(offline);
+        self.download_manager = self.download_manager.with_offline(offline);
+        self
+    }
+
+    /// 设置最大并发下载数
+    pub fn with_max_concurrent_downloads(mut self, max: usize) -> Self {
+        self.download_manager = self.download_manager.with_max_concurrent(max);
+        self
+    }
+
+    /// 添加通知输出，下载失败和验证失败会推送给它
+    pub fn with_notification_sink(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.notification_sinks.push(sink);
+        self
+    }
+
     /// 搜索并发现模型
     pub async fn discover_models(&self, query: &str) -> Result<Vec<DiscoveredModel>, Box<dyn std::error::Error>> {
         let search_request = ModelSearchRequest {
@@ -48,6 +98,43 @@ impl ModelManagementService {
         Ok(response.models)
     }
 
+    /// 安装 `root` 所需的顺序
+    ///
+    /// 按 `depends_on` 展开依赖并拓扑排序，依赖总在依赖它的模型之前，`root` 在最后；
+    /// 每个模型只出现一次。依赖成环时返回 `ClientError::DependencyCycle`，其中为环上的模型。
+    pub async fn install_plan(&self, root: Uuid) -> Result<Vec<Uuid>, ClientError> {
+        let mut plan = Vec::new();
+        let mut planned = HashSet::new();
+        // 正在展开的模型及其尚未处理的依赖
+        let mut stack = vec![(root, self.dependencies_of(root).await?.into_iter())];
+
+        while let Some((id, dependencies)) = stack.last_mut() {
+            let Some(dependency) = dependencies.next() else {
+                let id = *id;
+                stack.pop();
+                planned.insert(id);
+                plan.push(id);
+                continue;
+            };
+            if planned.contains(&dependency) {
+                continue;
+            }
+            if let Some(start) = stack.iter().position(|(id, _)| *id == dependency) {
+                let mut cycle: Vec<Uuid> = stack[start..].iter().map(|(id, _)| *id).collect();
+                cycle.push(dependency);
+                return Err(ClientError::DependencyCycle(cycle));
+            }
+            let dependencies = self.dependencies_of(dependency).await?;
+            stack.push((dependency, dependencies.into_iter()));
+        }
+        Ok(plan)
+    }
+
+    /// 模型在目录中声明的依赖
+    async fn dependencies_of(&self, model_id: Uuid) -> Result<Vec<Uuid>, ClientError> {
+        Ok(self.discovery_client.get_model_details(model_id).await?.depends_on)
+    }
+
     /// 简化的模型安装流程：发现 -> 下载 -> 验证 -> 安装
     pub async fn install_model_simple(
         &self,
@@ -75,15 +162,90 @@ impl ModelManagementService {
 
         println!("✅ 找到模型: {} v{}", discovered_model.name, discovered_model.version);
 
+        // 安装前预检（仅提示，不阻止安装）
+        let preflight = PreflightReport::for_memory(
+            &discovered_model.name,
+            estimate_runtime_memory_bytes((discovered_model.size_gb * 1024.0 * 1024.0 * 1024.0) as u64),
+            detect_host_memory_bytes(),
+        );
+        for warning in &preflight.warnings {
+            println!("⚠️ {}: {}", warning.message, warning.recommendation);
+        }
+
+        // 下载、验证、安装任一步失败时删除本次创建的文件，恢复到安装前的状态
+        let install_config = InstallationConfig::default();
+        let model_path = self.download_manager.download_dir().join(&discovered_model.name);
+        let install_path = self.download_manager.install_path(discovered_model.id, &install_config);
+        let created_model_file = !model_path.exists();
+        let created_install_dir = !install_path.exists();
+
+        match self.download_validate_install(&discovered_model, &model_path, install_config).await {
+            Ok(installation) => {
+                println!("🎉 模型安装完成!");
+                Ok(installation.install_path.to_string_lossy().to_string())
+            }
+            Err(e) => {
+                let _ = self.download_manager.cancel_download(discovered_model.id).await;
+                if created_model_file {
+                    let _ = tokio::fs::remove_file(&model_path).await;
+                }
+                if created_install_dir {
+                    let _ = tokio::fs::remove_dir_all(&install_path).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// 本地已有的文件是否与发现服务公布的校验和一致，一致时无需重新下载
+    ///
+    /// 按名称找到模型后从其详情中取校验和，并按公布的算法计算本地文件的摘要。
+    /// 模型未找到或没有公布校验和时返回错误；本地文件不存在或无法读取时返回 `false`。
+    pub async fn verify_local_against_remote(
+        &self,
+        local_path: &Path,
+        model_name: &str,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let search_request = ModelSearchRequest {
+            query: Some(model_name.to_string()),
+            ..Default::default()
+        };
+        let found = self.discovery_client.search_models(search_request).await?
+            .models
+            .into_iter()
+            .find(|m| m.name == model_name)
+            .ok_or("模型未找到")?;
+
+        let details = self.discovery_client.get_model_details(found.id).await?;
+        if details.checksum.trim().is_empty() {
+            return Err(format!("发现服务未提供 {} 的校验和", model_name).into());
+        }
+        let (checksum_type, expected) = parse_expected_checksum(&details.checksum, details.checksum_type)?;
+
+        let check = self.validator
+            .verify_file_checksum_with(local_path, &expected, checksum_type.checksummer().as_ref())
+            .await;
+        Ok(check.status == CheckStatus::Passed)
+    }
+
+    /// 下载 -> 验证 -> 安装，不做失败清理
+    async fn download_validate_install(
+        &self,
+        discovered_model: &DiscoveredModel,
+        model_path: &Path,
+        mut install_config: InstallationConfig,
+    ) -> Result<crate::ModelInstallation, Box<dyn std::error::Error>> {
         // 2. 下载模型
         println!("📥 开始下载模型...");
-        let download_progress = self.download_manager.download_model(
+        let download_progress = self.download_manager.download_model_with_expected_size(
             discovered_model.id,
             discovered_model.name.clone(),
-            discovered_model.download_url,
-            discovered_model.checksum.clone(),
+            discovered_model.download_url.clone(),
+            Some(discovered_model.checksum.clone()).filter(|checksum| !checksum.is_empty()),
             crate::validation::ChecksumType::SHA256,
-        ).await?;
+            Some((discovered_model.size_gb * 1024.0 * 1024.0 * 1024.0) as u64),
+        ).await
+            .inspect_err(|e| self.notify_download_failed(&discovered_model.name, e))?;
 
         match download_progress.status {
             crate::DownloadStatus::Completed => {
@@ -93,29 +255,120 @@ impl ModelManagementService {
                 return Err("下载失败".into());
             }
         }
+        if download_progress.needs_trust_confirmation() {
+            println!("⚠️ 发现服务未提供校验和，文件未经校验，请确认信任来源");
+        }
+        // 安装时以下载得到的校验和为准，确保安装的就是下载的文件
+        if let Some(actual) = &download_progress.actual_checksum {
+            install_config.expected_checksum = Some(format!("sha256:{}", actual));
+        }
 
         // 3. 验证模型
         println!("🔒 正在验证模型完整性...");
-        let model_path = self.download_manager.download_dir().join(&discovered_model.name);
-        let validation_config = ValidationConfig::default();
-        let validation_result = self.validator.validate_model(&model_path, Some(discovered_model.id), validation_config).await?;
+        let validation_config = ValidationConfig::default()
+            .for_provider(&discovered_model.provider, discovered_model.is_verified);
+        let validation_result = self.validator.validate_model(model_path, Some(discovered_model.id), validation_config).await?;
 
         if !validation_result.is_valid {
+            let reasons: Vec<&str> = validation_result.errors.iter().map(|e| e.message.as_str()).collect();
+            emit_all(&self.notification_sinks, &Notification::error(
+                "模型验证失败".to_string(),
+                format!("{}: {}", discovered_model.name, reasons.join("; ")),
+            ));
             return Err("模型验证失败".into());
         }
         println!("✅ 模型验证通过");
 
         // 4. 安装模型
         println!("📦 正在安装模型...");
-        let install_config = InstallationConfig::default();
         let installation = self.download_manager.install_model(
             discovered_model.id,
-            model_path,
-            install_config.clone(),
+            model_path.to_path_buf(),
+            install_config,
         ).await?;
+        Ok(installation)
+    }
+
+    /// 批量下载可用模型
+    ///
+    /// 同时进行的下载数不超过下载管理器的并发上限。每个模型开始前检查取消令牌和磁盘空间：
+    /// 取消后不再开始新的下载；剩余空间放不下下一个模型（并保留下载管理器的预留空间）时整批停止。
+    /// 已开始的下载不会被中断。
+    ///
+    /// 每个 id 恰好产生一个结果，已收到的结果数即整体进度；未开始的模型返回 `DownloadError::Cancelled`。
+    pub fn download_all_available(
+        &self,
+        ids: Vec<Uuid>,
+        cancel: CancellationToken,
+    ) -> impl Stream<Item = (Uuid, BulkDownloadResult)> + '_ {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let reserved = Arc::new(Mutex::new(0u64));
+        let max_active = self.download_manager.queue().max_active();
 
-        println!("🎉 模型安装完成!");
-        Ok(installation.install_path.to_string_lossy().to_string())
+        stream::iter(ids)
+            .map(move |model_id| {
+                let cancel = cancel.clone();
+                let stopped = Arc::clone(&stopped);
+                let reserved = Arc::clone(&reserved);
+                async move {
+                    let result = self.download_one(model_id, &cancel, &stopped, &reserved).await;
+                    (model_id, result)
+                }
+            })
+            .buffer_unordered(max_active)
+    }
+
+    /// 批量下载中的单个模型
+    ///
+    /// `reserved` 是进行中的下载预计还要占用的空间。
+    async fn download_one(
+        &self,
+        model_id: Uuid,
+        cancel: &CancellationToken,
+        stopped: &AtomicBool,
+        reserved: &Mutex<u64>,
+    ) -> BulkDownloadResult {
+        if cancel.is_cancelled() || stopped.load(Ordering::SeqCst) {
+            return Err(DownloadError::Cancelled(model_id.to_string()).into());
+        }
+
+        let model = self.discovery_client.get_model_details(model_id).await?;
+        let required = (model.size_gb * 1024.0 * 1024.0 * 1024.0) as u64;
+        {
+            // 查询详情期间可能已被取消
+            if cancel.is_cancelled() || stopped.load(Ordering::SeqCst) {
+                return Err(DownloadError::Cancelled(model_id.to_string()).into());
+            }
+            let mut reserved = reserved.lock().unwrap();
+            let available = available_disk_space(self.download_manager.download_dir()).unwrap_or(u64::MAX);
+            let needed = *reserved + required + self.download_manager.min_free_reserve();
+            if needed > available {
+                stopped.store(true, Ordering::SeqCst);
+                return Err(DownloadError::InsufficientSpace { required: needed, available }.into());
+            }
+            *reserved += required;
+        }
+
+        let result = self.download_manager.download_model_with_expected_size(
+            model_id,
+            model.name.clone(),
+            model.download_url,
+            Some(model.checksum).filter(|checksum| !checksum.is_empty()),
+            model.checksum_type,
+            Some(required),
+        ).await;
+        *reserved.lock().unwrap() -= required;
+        if let Err(e) = &result {
+            self.notify_download_failed(&model.name, e);
+        }
+        Ok(result?)
+    }
+
+    fn notify_download_failed(&self, model_name: &str, error: &DownloadError) {
+        emit_all(&self.notification_sinks, &Notification::error(
+            "模型下载失败".to_string(),
+            format!("{}: {}", model_name, error),
+        ));
     }
 
     /// 列出已安装的模型
@@ -124,6 +377,20 @@ impl ModelManagementService {
         Ok(installed)
     }
 
+    /// 汇总发现、下载、安装各子系统的状态，用于诊断面板
+    pub async fn status(&self) -> ManagementStatus {
+        let download_dir = self.download_manager.download_dir();
+
+        ManagementStatus {
+            discovery_reachable: self.discovery_client.ping().await.is_ok(),
+            download_dir_writable: is_dir_writable(download_dir).await,
+            free_space_bytes: available_disk_space(download_dir),
+            installed_count: self.download_manager.get_installed_models().await
+                .map(|installed| installed.len())
+                .unwrap_or(0),
+        }
+    }
+
     /// 卸载模型
     pub async fn uninstall_model(&self, model_id: Uuid) -> Result<(), Box<dyn std::error::Error>> {
         // 从文件系统删除
@@ -133,6 +400,18 @@ impl ModelManagementService {
     }
 }
 
+/// 通过写入并删除探测文件检查目录是否可写
+async fn is_dir_writable(dir: &std::path::Path) -> bool {
+    let probe = dir.join(format!(".write-probe-{}", Uuid::new_v4()));
+    match tokio::fs::write(&probe, b"").await {
+        Ok(()) => {
+            let _ = tokio::fs::remove_file(&probe).await;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +430,321 @@ mod tests {
         // 在演示模式下，这通常会因为网络错误而失败
         // 这是预期的行为
     }
-}
\ No newline at end of file
+
+    /// 构造一个指向 mock 服务器文件的发现结果
+    fn bulk_model(server_uri: &str, name: &str) -> DiscoveredModel {
+        use sha2::{Digest, Sha256};
+
+        DiscoveredModel {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            display_name: name.to_string(),
+            description: String::new(),
+            size_gb: 0.000001,
+            model_type: crate::discovery::ModelType::ChatCompletion,
+            provider: "Test".to_string(),
+            tags: vec![],
+            capabilities: vec![],
+            requirements: crate::ModelRequirements {
+                min_ram_gb: 1.0,
+                min_vram_gb: None,
+                gpu_required: false,
+                cpu_cores: 1,
+                disk_space_gb: 0.001,
+                supported_platforms: vec![],
+                cuda_version: None,
+                python_version: None,
+            },
+            download_url: format!("{}/files/{}", server_uri, name),
+            checksum: format!("{:x}", Sha256::digest(name.as_bytes())),
+            checksum_type: crate::ChecksumType::SHA256,
+            license: "MIT".to_string(),
+            rating: 0.0,
+            download_count: 0,
+            last_updated: chrono::Utc::now(),
+            is_featured: false,
+            is_verified: false,
+            repository_url: None,
+            documentation_url: None,
+            depends_on: vec![],
+        }
+    }
+
+    /// Serve each model's catalog details and return a service pointed at the mock server
+    async fn service_with_catalog(
+        server: &wiremock::MockServer,
+        models: &[DiscoveredModel],
+    ) -> (ModelManagementService, tempfile::TempDir) {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        for model in models {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/models/{}", model.id)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(model))
+                .mount(server)
+                .await;
+        }
+        let temp = tempfile::tempdir().unwrap();
+        let service = ModelManagementService::new(server.uri(), temp.path().to_path_buf()).await.unwrap();
+        (service, temp)
+    }
+
+    #[tokio::test]
+    async fn test_install_plan_orders_linear_chain() {
+        let server = wiremock::MockServer::start().await;
+        let embedding = bulk_model(&server.uri(), "embedding");
+        let mut reranker = bulk_model(&server.uri(), "reranker");
+        reranker.depends_on = vec![embedding.id];
+        let mut rag = bulk_model(&server.uri(), "rag-pipeline");
+        // The embedding model is reachable twice but planned once
+        rag.depends_on = vec![reranker.id, embedding.id];
+
+        let (service, _temp) = service_with_catalog(&server, &[embedding.clone(), reranker.clone(), rag.clone()]).await;
+        assert_eq!(service.install_plan(rag.id).await.unwrap(), vec![embedding.id, reranker.id, rag.id]);
+        assert_eq!(service.install_plan(embedding.id).await.unwrap(), vec![embedding.id]);
+    }
+
+    #[tokio::test]
+    async fn test_install_plan_detects_cycle() {
+        let server = wiremock::MockServer::start().await;
+        let mut a = bulk_model(&server.uri(), "cycle-a");
+        let mut b = bulk_model(&server.uri(), "cycle-b");
+        let mut c = bulk_model(&server.uri(), "cycle-c");
+        a.depends_on = vec![b.id];
+        b.depends_on = vec![c.id];
+        c.depends_on = vec![b.id];
+
+        let (service, _temp) = service_with_catalog(&server, &[a.clone(), b.clone(), c.clone()]).await;
+        match service.install_plan(a.id).await {
+            Err(ClientError::DependencyCycle(cycle)) => assert_eq!(cycle, vec![b.id, c.id, b.id]),
+            other => panic!("expected a dependency cycle, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_all_stops_after_cancel() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let models: Vec<DiscoveredModel> = ["bulk-a", "bulk-b", "bulk-c"]
+            .iter()
+            .map(|name| bulk_model(&server.uri(), name))
+            .collect();
+        for model in &models {
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/models/{}", model.id)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(model))
+                .mount(&server)
+                .await;
+            Mock::given(path(format!("/files/{}", model.name)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(model.name.as_bytes()))
+                .mount(&server)
+                .await;
+        }
+
+        let temp = tempfile::tempdir().unwrap();
+        let service = ModelManagementService::new(server.uri(), temp.path().to_path_buf())
+            .await
+            .unwrap()
+            .with_max_concurrent_downloads(1);
+
+        let cancel = CancellationToken::new();
+        let ids: Vec<Uuid> = models.iter().map(|m| m.id).collect();
+        let mut results = Box::pin(service.download_all_available(ids.clone(), cancel.clone()));
+
+        let (first_id, first) = results.next().await.unwrap();
+        assert_eq!(first_id, ids[0]);
+        assert!(matches!(first.unwrap().status, crate::DownloadStatus::Completed));
+        cancel.cancel();
+
+        let rest: Vec<(Uuid, BulkDownloadResult)> = results.collect().await;
+        assert_eq!(rest.len(), 2);
+        for (_, result) in rest {
+            let err = result.unwrap_err();
+            assert!(matches!(err.downcast_ref::<DownloadError>(), Some(DownloadError::Cancelled(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unverified_provider_model_installs_without_signature_store() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // bulk_model providers are unlisted and unverified
+        let model = bulk_model(&server.uri(), "community.gguf");
+        assert!(!model.is_verified);
+        Mock::given(method("POST"))
+            .and(path("/api/v1/models/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [model],
+                "total_count": 1,
+                "page": 1,
+                "page_size": 20,
+                "has_next": false,
+                "search_time_ms": 1,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/files/community.gguf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(model.name.as_bytes()))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::tempdir().unwrap();
+        let service = ModelManagementService::new(server.uri(), temp.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let install_path = service.install_model_simple("community.gguf", None).await.unwrap();
+        assert!(std::path::Path::new(&install_path).exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_validation_leaves_no_files() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // Suspicious extension makes the malware scan fail validation
+        let model = bulk_model(&server.uri(), "suspicious.exe");
+        Mock::given(method("POST"))
+            .and(path("/api/v1/models/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [model],
+                "total_count": 1,
+                "page": 1,
+                "page_size": 20,
+                "has_next": false,
+                "search_time_ms": 1,
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/files/suspicious.exe"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(model.name.as_bytes()))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::tempdir().unwrap();
+        let service = ModelManagementService::new(server.uri(), temp.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let err = service.install_model_simple("suspicious.exe", None).await.unwrap_err();
+        assert_eq!(err.to_string(), "模型验证失败");
+
+        assert!(!temp.path().join("suspicious.exe").exists());
+        assert!(!temp.path().join("installed").join(model.id.to_string()).exists());
+        let leftover_temp: Vec<_> = std::fs::read_dir(temp.path().join("temp"))
+            .map(|entries| entries.flatten().filter(|e| e.path().is_file()).collect())
+            .unwrap_or_default();
+        assert!(leftover_temp.is_empty(), "leftover temp files: {:?}", leftover_temp);
+    }
+
+    #[tokio::test]
+    async fn test_verify_local_against_remote_uses_discovery_checksum() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, ResponseTemplate};
+
+        let server = wiremock::MockServer::start().await;
+        let model = bulk_model(&server.uri(), "local-copy");
+        Mock::given(method("POST"))
+            .and(path("/api/v1/models/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [model],
+                "total_count": 1,
+                "page": 1,
+                "page_size": 20,
+                "has_next": false,
+                "search_time_ms": 1,
+            })))
+            .mount(&server)
+            .await;
+        let (service, temp) = service_with_catalog(&server, std::slice::from_ref(&model)).await;
+
+        // The advertised checksum is the SHA256 of the model name
+        let matching = temp.path().join("matching.gguf");
+        std::fs::write(&matching, model.name.as_bytes()).unwrap();
+        assert!(service.verify_local_against_remote(&matching, "local-copy").await.unwrap());
+
+        let stale = temp.path().join("stale.gguf");
+        std::fs::write(&stale, b"an older build").unwrap();
+        assert!(!service.verify_local_against_remote(&stale, "local-copy").await.unwrap());
+        assert!(!service.verify_local_against_remote(&temp.path().join("missing.gguf"), "local-copy").await.unwrap());
+
+        assert!(service.verify_local_against_remote(&matching, "not-in-catalog").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_aggregates_subsystems() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/models/providers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec!["Meta"]))
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::tempdir().unwrap();
+        let service = ModelManagementService::new(server.uri(), temp.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let status = service.status().await;
+        assert!(status.discovery_reachable);
+        assert!(status.download_dir_writable);
+        assert_eq!(status.installed_count, 0);
+        #[cfg(unix)]
+        assert!(status.free_space_bytes.unwrap() > 0);
+
+        // Unreachable discovery is reported, not an error
+        let offline = ModelManagementService::new("http://127.0.0.1:1".to_string(), temp.path().to_path_buf())
+            .await
+            .unwrap();
+        assert!(!offline.status().await.discovery_reachable);
+    }
+
+    #[tokio::test]
+    async fn test_offline_service_uses_cached_discovery_and_refuses_downloads() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/models/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "models": [],
+                "total_count": 0,
+                "page": 1,
+                "page_size": 20,
+                "has_next": false,
+                "search_time_ms": 1,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp = tempfile::tempdir().unwrap();
+        let online = ModelManagementService::new(server.uri(), temp.path().to_path_buf()).await.unwrap();
+        online.discover_models("qwen").await.unwrap();
+        drop(online);
+
+        // After a restart the cached search is still served, nothing else reaches the network
+        let offline = ModelManagementService::new(server.uri(), temp.path().to_path_buf())
+            .await
+            .unwrap()
+            .with_offline(true);
+        assert!(offline.discover_models("qwen").await.unwrap().is_empty());
+        let uncached = offline.discover_models("llama").await.unwrap_err();
+        assert!(uncached.downcast_ref::<crate::DiscoveryError>().is_some_and(|e| matches!(e, crate::DiscoveryError::Offline(_))));
+        let download = offline.download_manager
+            .download_model(Uuid::new_v4(), "m.gguf".to_string(), format!("{}/m.gguf", server.uri()), None, crate::ChecksumType::SHA256)
+            .await;
+        assert!(matches!(download, Err(DownloadError::Offline(_))));
+    }
+}
@@ -83,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("  Creating model {}: {}", i + 1, request.display_name);
 
         // Test validation
-        service.validate_create_request(&request)?;
+        service.validate_create_request(&request).await?;
 
         let model = service.create_model(request).await?;
         created_models.push(model);
@@ -1,6 +1,7 @@
 pub mod models;
 pub mod discovery;
 pub mod download;
+pub mod download_queue;
 pub mod validation;
 pub mod integration;
 pub mod data_service;
@@ -10,10 +11,32 @@ pub mod simple_models;
 pub mod integrated_service;
 pub mod app_state;
 pub mod model_stats;
+pub mod model_type_display;
+pub mod i18n;
+pub mod preflight;
+pub mod checksum;
+pub mod model_logs;
+pub mod model_config;
+pub mod manifest;
+pub mod page_states;
+pub mod search_bar;
+pub mod components;
+pub mod model_table;
+pub mod model_card;
+pub mod diagnostics;
+pub mod catalog_export;
+pub mod confirm;
+pub mod gguf;
+pub mod notification_sink;
+pub mod model_capabilities;
+mod schema;
+#[cfg(test)]
+mod render_test;
 
 pub use models::*;
 pub use discovery::*;
 pub use download::*;
+pub use download_queue::*;
 pub use validation::*;
 pub use integration::*;
 pub use data_service::*;
@@ -23,6 +46,24 @@ pub use simple_models::*;
 pub use integrated_service::*;
 pub use app_state::*;
 pub use model_stats::*;
+pub use model_type_display::*;
+pub use i18n::*;
+pub use preflight::*;
+pub use checksum::*;
+pub use model_logs::*;
+pub use model_config::*;
+pub use manifest::*;
+pub use page_states::*;
+pub use search_bar::*;
+pub use components::*;
+pub use model_table::*;
+pub use model_card::*;
+pub use diagnostics::*;
+pub use catalog_export::*;
+pub use confirm::*;
+pub use gguf::*;
+pub use notification_sink::*;
+pub use model_capabilities::*;
 
 // Re-export for convenience
 pub use burncloud_service_models;
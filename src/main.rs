@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
 use burncloud_client_models::{
     ModelManagement, SimpleModelManagement, EnhancedModelManagement, ModelStats,
-    app_state::AppState
+    app_state::{load_app_state, AppState}, t, IntegratedServiceConfig, Locale, WarningBanner,
 };
 
 fn main() {
@@ -24,25 +24,35 @@ fn App() -> Element {
     let mut loading = use_signal(|| true);
     let mut error_message = use_signal(|| None::<String>);
 
-    // 初始化应用状态
-    use_effect(move || {
-        spawn(async move {
-            match AppState::new().await {
-                Ok(mut state) => {
-                    // 加载数据
-                    if let Err(e) = state.load_data().await {
-                        error_message.set(Some(format!("数据加载失败: {}", e.user_message())));
-                    }
-                    app_state.set(Some(state));
-                    loading.set(false);
-                }
+    // 进行中的初始化任务，重试前先取消，避免反复重试留下多个任务
+    let mut init_task = use_signal(|| None::<Task>);
+
+    // 初始化应用状态并加载数据，失败后“重试”再次调用
+    let mut initialize = move || {
+        if let Some(task) = init_task.take() {
+            task.cancel();
+        }
+        loading.set(true);
+        error_message.set(None);
+        let current = app_state.peek().clone();
+        let task = spawn(async move {
+            let initialized = current.is_some();
+            match load_app_state(current, || AppState::initialize(IntegratedServiceConfig::default())).await {
+                Ok(Some(state)) => app_state.set(Some(state)),
+                Ok(None) => return,
                 Err(e) => {
-                    error_message.set(Some(format!("应用初始化失败: {}", e.user_message())));
-                    loading.set(false);
+                    let key = if initialized { "notify.load_failed" } else { "notify.init_failed" };
+                    error_message.set(Some(format!("{}: {}", t(key, Locale::default()), e.user_message())));
                 }
             }
+            loading.set(false);
+            init_task.set(None);
         });
-    });
+        init_task.set(Some(task));
+    };
+
+    // 初始化应用状态
+    use_effect(move || initialize());
 
     // 如果正在加载，显示加载界面
     if *loading.read() {
@@ -65,17 +75,16 @@ fn App() -> Element {
                 p { "{error}" }
                 button {
                     class: "btn btn-primary",
-                    onclick: move |_| {
-                        loading.set(true);
-                        error_message.set(None);
-                        // 重新尝试初始化
-                    },
+                    onclick: move |_| initialize(),
                     "重试"
                 }
             }
         };
     }
 
+    // 可用模型加载失败等非致命问题，不阻止进入界面
+    let warning = app_state.read().as_ref().and_then(|state| state.warning.clone());
+
     // 正常界面
     rsx! {
         style { {include_str!("../assets/styles.css")} }
@@ -115,6 +124,16 @@ fn App() -> Element {
 
             // 主内容区域
             main { class: "main-content",
+                if let Some(warning) = warning {
+                    WarningBanner {
+                        message: warning,
+                        on_dismiss: move |_| {
+                            if let Some(state) = app_state.write().as_mut() {
+                                state.warning = None;
+                            }
+                        },
+                    }
+                }
                 match current_view.read().as_str() {
                     "original" => rsx! {
                         div { class: "view-container",
@@ -0,0 +1,277 @@
+// 声明式模型清单（TOML / YAML）
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use serde::Deserialize;
+use burncloud_service_models::{CreateModelRequest, ModelType};
+
+/// 清单中的一个模型条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestModel {
+    pub name: String,
+    pub provider: String,
+    pub version: String,
+    pub model_type: ModelType,
+    pub download_url: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl ManifestModel {
+    /// 转换为创建请求
+    pub fn to_create_request(&self) -> CreateModelRequest {
+        CreateModelRequest {
+            name: self.name.clone(),
+            display_name: self.name.clone(),
+            version: self.version.clone(),
+            model_type: self.model_type,
+            provider: self.provider.clone(),
+            file_size: 0,
+            description: None,
+            license: None,
+            tags: self.tags.clone(),
+            languages: vec![],
+            file_path: None,
+            download_url: self.download_url.clone(),
+            config: HashMap::new(),
+            is_official: false,
+        }
+    }
+}
+
+/// 清单中的一处问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestIssue {
+    /// 条目序号（从 1 开始），整体解析失败时为 `None`
+    pub entry: Option<usize>,
+    /// 所在行号（从 1 开始），无法定位时为 `None`
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.entry, self.line) {
+            (Some(entry), Some(line)) => write!(f, "entry {} (line {}): {}", entry, line, self.message),
+            (Some(entry), None) => write!(f, "entry {}: {}", entry, self.message),
+            (None, Some(line)) => write!(f, "line {}: {}", line, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// 将问题列表拼成一行
+pub fn format_manifest_issues(issues: &[ManifestIssue]) -> String {
+    issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEntry {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    provider: String,
+    #[serde(default)]
+    version: String,
+    #[serde(rename = "type", default)]
+    model_type: String,
+    download_url: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlManifest {
+    #[serde(default)]
+    models: Vec<toml::Spanned<RawEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct YamlManifest {
+    #[serde(default)]
+    models: Vec<RawEntry>,
+}
+
+/// 解析并校验清单
+///
+/// 含 `[[models]]` 的文本按 TOML 解析，否则按 YAML 解析。所有条目都会被检查，
+/// 有任何问题时返回全部问题而不是第一个。
+pub fn parse_manifest(text: &str) -> Result<Vec<ManifestModel>, Vec<ManifestIssue>> {
+    let entries = if text.contains("[[models]]") {
+        parse_toml(text)?
+    } else {
+        parse_yaml(text)?
+    };
+
+    if entries.is_empty() {
+        return Err(vec![ManifestIssue {
+            entry: None,
+            line: None,
+            message: "manifest does not list any models".to_string(),
+        }]);
+    }
+
+    let mut models = Vec::with_capacity(entries.len());
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (index, (line, raw)) in entries.into_iter().enumerate() {
+        let mut issue = |message: String| issues.push(ManifestIssue {
+            entry: Some(index + 1),
+            line,
+            message,
+        });
+
+        let name = raw.name.trim().to_string();
+        if name.is_empty() {
+            issue("missing `name`".to_string());
+        } else if !seen.insert(name.clone()) {
+            issue(format!("duplicate model name `{}`", name));
+        }
+        if raw.provider.trim().is_empty() {
+            issue("missing `provider`".to_string());
+        }
+        if raw.version.trim().is_empty() {
+            issue("missing `version`".to_string());
+        }
+
+        let model_type = parse_model_type(&raw.model_type);
+        if model_type.is_none() {
+            issue(format!("unknown model type `{}`", raw.model_type));
+        }
+
+        let download_url = raw.download_url.map(|url| url.trim().to_string());
+        if let Some(url) = &download_url {
+            match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => issue(format!("invalid download_url `{}`", url)),
+            }
+        }
+
+        if let Some(model_type) = model_type {
+            models.push(ManifestModel {
+                name,
+                provider: raw.provider.trim().to_string(),
+                version: raw.version.trim().to_string(),
+                model_type,
+                download_url,
+                tags: raw.tags,
+            });
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(models)
+    } else {
+        Err(issues)
+    }
+}
+
+fn parse_toml(text: &str) -> Result<Vec<(Option<usize>, RawEntry)>, Vec<ManifestIssue>> {
+    let manifest: TomlManifest = toml::from_str(text).map_err(|e| {
+        vec![ManifestIssue {
+            entry: None,
+            line: e.span().map(|span| line_of(text, span.start)),
+            message: e.message().to_string(),
+        }]
+    })?;
+
+    Ok(manifest.models
+        .into_iter()
+        .map(|entry| (Some(line_of(text, entry.span().start)), entry.into_inner()))
+        .collect())
+}
+
+fn parse_yaml(text: &str) -> Result<Vec<(Option<usize>, RawEntry)>, Vec<ManifestIssue>> {
+    let manifest: YamlManifest = serde_yaml::from_str(text).map_err(|e| {
+        vec![ManifestIssue {
+            entry: None,
+            line: e.location().map(|location| location.line()),
+            message: e.to_string(),
+        }]
+    })?;
+
+    Ok(manifest.models.into_iter().map(|entry| (None, entry)).collect())
+}
+
+/// 字节偏移所在的行号（从 1 开始）
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+/// 解析模型类型，忽略大小写以及 `-`、`_`
+fn parse_model_type(value: &str) -> Option<ModelType> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase();
+
+    let model_type = match normalized.as_str() {
+        "chat" => ModelType::Chat,
+        "code" => ModelType::Code,
+        "text" => ModelType::Text,
+        "embedding" => ModelType::Embedding,
+        "image" => ModelType::Image,
+        "imagegeneration" => ModelType::ImageGeneration,
+        "audio" => ModelType::Audio,
+        "speech" => ModelType::Speech,
+        "video" => ModelType::Video,
+        "multimodal" => ModelType::Multimodal,
+        "other" => ModelType::Other,
+        _ => return None,
+    };
+    Some(model_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toml_manifest() {
+        let text = r#"
+[[models]]
+name = "qwen2-7b"
+provider = "Alibaba"
+version = "2.0"
+type = "chat"
+tags = ["qwen"]
+"#;
+        let models = parse_manifest(text).unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].model_type, ModelType::Chat);
+        assert_eq!(models[0].tags, vec!["qwen".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_yaml_manifest() {
+        let text = "models:\n  - name: bge-m3\n    provider: BAAI\n    version: \"1.0\"\n    type: Embedding\n";
+        let models = parse_manifest(text).unwrap();
+        assert_eq!(models[0].name, "bge-m3");
+        assert_eq!(models[0].model_type, ModelType::Embedding);
+    }
+
+    #[test]
+    fn test_issues_report_entry_and_line() {
+        let text = r#"[[models]]
+name = "ok"
+provider = "P"
+version = "1"
+type = "chat"
+
+[[models]]
+name = "ok"
+provider = "P"
+version = "1"
+type = "robot"
+download_url = "ftp://example.com/m.gguf"
+"#;
+        let issues = parse_manifest(text).unwrap_err();
+        assert_eq!(issues.len(), 3);
+        assert!(issues.iter().all(|i| i.entry == Some(2)));
+        assert!(issues.iter().all(|i| i.line == Some(7)));
+    }
+}
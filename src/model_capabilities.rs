@@ -0,0 +1,187 @@
+// 模型可选能力（视觉、函数调用、JSON 模式），界面据此决定是否开放对应功能
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use burncloud_service_models::{Model, ModelType};
+use crate::i18n::{t, Locale};
+
+/// 模型可能支持的可选能力
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Capability {
+    /// 理解图像输入
+    Vision,
+    /// 调用工具/函数
+    FunctionCalling,
+    /// 按 JSON 格式输出
+    JsonMode,
+}
+
+impl Capability {
+    /// 所有能力，按界面显示顺序排列
+    pub const ALL: [Capability; 3] = [Capability::Vision, Capability::FunctionCalling, Capability::JsonMode];
+
+    /// 显示名称
+    pub fn label(&self, locale: Locale) -> &'static str {
+        let key = match self {
+            Capability::Vision => "capability.vision",
+            Capability::FunctionCalling => "capability.function_calling",
+            Capability::JsonMode => "capability.json_mode",
+        };
+        t(key, locale)
+    }
+
+    /// 配置中显式开关该能力的键
+    fn config_key(&self) -> &'static str {
+        match self {
+            Capability::Vision => "vision",
+            Capability::FunctionCalling => "function_calling",
+            Capability::JsonMode => "json_mode",
+        }
+    }
+
+    /// 表示具备该能力的标签（忽略大小写）
+    fn tags(&self) -> &'static [&'static str] {
+        match self {
+            Capability::Vision => &["vision", "视觉"],
+            Capability::FunctionCalling => &["tools", "function-calling", "function_calling"],
+            Capability::JsonMode => &["json", "json-mode", "json_mode"],
+        }
+    }
+}
+
+/// 模型支持的可选能力
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelCapabilities {
+    pub vision: bool,
+    pub function_calling: bool,
+    pub json_mode: bool,
+}
+
+impl ModelCapabilities {
+    /// 是否支持某项能力
+    pub fn has(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Vision => self.vision,
+            Capability::FunctionCalling => self.function_calling,
+            Capability::JsonMode => self.json_mode,
+        }
+    }
+
+    /// 是否支持全部列出的能力
+    pub fn has_all(&self, capabilities: &[Capability]) -> bool {
+        capabilities.iter().all(|capability| self.has(*capability))
+    }
+
+    /// 支持的能力，按 `Capability::ALL` 的顺序
+    pub fn list(&self) -> Vec<Capability> {
+        Capability::ALL.into_iter().filter(|capability| self.has(*capability)).collect()
+    }
+
+    fn set(&mut self, capability: Capability, enabled: bool) {
+        match capability {
+            Capability::Vision => self.vision = enabled,
+            Capability::FunctionCalling => self.function_calling = enabled,
+            Capability::JsonMode => self.json_mode = enabled,
+        }
+    }
+}
+
+/// 从模型的类型、标签和配置推断可选能力
+pub trait ModelCapabilitiesExt {
+    /// 推断出的能力
+    ///
+    /// `Multimodal` 模型默认支持视觉；配置中有 `tools` 时支持函数调用；标签（如 `vision`、
+    /// `tools`、`json`）也算作声明。配置中的布尔开关（`vision`、`function_calling`、
+    /// `json_mode`）优先于其他推断，可用来关闭某项能力。
+    fn capabilities(&self) -> ModelCapabilities;
+}
+
+impl ModelCapabilitiesExt for Model {
+    fn capabilities(&self) -> ModelCapabilities {
+        let mut capabilities = ModelCapabilities {
+            vision: self.model_type == ModelType::Multimodal,
+            function_calling: self.config.get("tools").is_some_and(is_present),
+            json_mode: false,
+        };
+
+        for capability in Capability::ALL {
+            let tagged = self.tags.iter()
+                .any(|tag| capability.tags().iter().any(|known| tag.eq_ignore_ascii_case(known)));
+            if tagged {
+                capabilities.set(capability, true);
+            }
+            if let Some(enabled) = self.config.get(capability.config_key()).and_then(Value::as_bool) {
+                capabilities.set(capability, enabled);
+            }
+        }
+        capabilities
+    }
+}
+
+/// 配置值是否表示“有”：非 `null`、非 `false`、非空数组/对象
+fn is_present(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(enabled) => *enabled,
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn model(model_type: ModelType, tags: &[&str], config: HashMap<String, Value>) -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: uuid::Uuid::new_v4(),
+            name: "capable".to_string(),
+            display_name: "Capable".to_string(),
+            version: "1.0.0".to_string(),
+            model_type,
+            size_category: burncloud_service_models::SizeCategory::Small,
+            file_size: 1024,
+            provider: "TestProvider".to_string(),
+            license: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            languages: vec![],
+            file_path: None,
+            checksum: None,
+            download_url: None,
+            config,
+            rating: None,
+            download_count: 0,
+            is_official: false,
+            description: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_multimodal_model_supports_vision_and_chat_does_not() {
+        let multimodal = model(ModelType::Multimodal, &[], HashMap::new());
+        assert!(multimodal.capabilities().has(Capability::Vision));
+        assert_eq!(multimodal.capabilities().list(), vec![Capability::Vision]);
+
+        let chat = model(ModelType::Chat, &[], HashMap::new());
+        assert!(!chat.capabilities().has(Capability::Vision));
+        assert!(chat.capabilities().list().is_empty());
+    }
+
+    #[test]
+    fn test_config_and_tags_declare_capabilities() {
+        let config = HashMap::from([("tools".to_string(), json!(["search", "calculator"]))]);
+        let chat = model(ModelType::Chat, &["JSON"], config);
+        assert_eq!(chat.capabilities().list(), vec![Capability::FunctionCalling, Capability::JsonMode]);
+
+        // 空的工具列表不算；显式开关优先于模型类型
+        let config = HashMap::from([("tools".to_string(), json!([])), ("vision".to_string(), json!(false))]);
+        let multimodal = model(ModelType::Multimodal, &[], config);
+        assert!(multimodal.capabilities().list().is_empty());
+    }
+}
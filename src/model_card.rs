@@ -0,0 +1,247 @@
+// 可分享的 Markdown 模型卡片
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use dioxus::prelude::*;
+use burncloud_service_models::{InstalledModel, Model};
+use crate::i18n::{t, Locale};
+use crate::integrated_service::InstallSource;
+use crate::model_config::ModelConfigExt;
+use crate::model_type_display::{model_status_display, model_type_display, model_type_icon};
+
+/// 生成模型卡片的扩展
+pub trait ModelCardExt {
+    /// 以系统语言生成 README 风格的 Markdown 模型卡片
+    ///
+    /// 传入 `installed` 时附带安装状态和使用情况。
+    fn to_model_card_markdown(&self, installed: Option<&InstalledModel>) -> String {
+        self.to_model_card_markdown_in(installed, Locale::default())
+    }
+
+    /// 以指定语言生成 Markdown 模型卡片
+    fn to_model_card_markdown_in(&self, installed: Option<&InstalledModel>, locale: Locale) -> String;
+}
+
+impl ModelCardExt for Model {
+    fn to_model_card_markdown_in(&self, installed: Option<&InstalledModel>, locale: Locale) -> String {
+        let mut card = format!("# {}\n\n", self.display_name);
+        if let Some(description) = self.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            let _ = write!(card, "{}\n\n", description.trim());
+        }
+
+        let mut rows = vec![
+            (t("card.name", locale), format!("`{}`", self.name)),
+            (t("card.version", locale), self.version.clone()),
+            (t("card.type", locale), format!("{} {}", model_type_icon(&self.model_type), model_type_display(&self.model_type, locale))),
+            (t("card.provider", locale), self.provider.clone()),
+            (t("card.license", locale), self.license.clone().unwrap_or_else(|| t("card.license_unknown", locale).to_string())),
+            (t("card.size", locale), self.formatted_size()),
+        ];
+        if let Some(context_length) = self.model_config().context_length() {
+            rows.push((t("card.context_length", locale), format!("{} tokens", context_length)));
+        }
+        if !self.languages.is_empty() {
+            rows.push((t("card.languages", locale), self.languages.join(", ")));
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| format!("`{}`", tag)).collect();
+            rows.push((t("card.tags", locale), tags.join(" ")));
+        }
+
+        let _ = writeln!(card, "| {} | {} |", t("card.property", locale), t("card.value", locale));
+        card.push_str("| --- | --- |\n");
+        for (label, value) in rows {
+            let _ = writeln!(card, "| {} | {} |", label, value.replace('|', "\\|"));
+        }
+
+        if let Some(installed) = installed {
+            let last_used = installed.last_used
+                .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| t("card.never_used", locale).to_string());
+            let _ = write!(card, "\n## {}\n\n", t("card.installation", locale));
+            let _ = writeln!(card, "- {}: {}", t("card.status", locale), model_status_display(&installed.status, locale));
+            let _ = writeln!(card, "- {}: {}", t("card.usage_count", locale), installed.usage_count);
+            let _ = writeln!(card, "- {}: {}", t("card.last_used", locale), last_used);
+        }
+
+        // 按键排序，保证输出稳定
+        let config: BTreeMap<_, _> = self.config.iter().collect();
+        let config = serde_json::to_string_pretty(&config).unwrap_or_else(|_| "{}".to_string());
+        let _ = write!(card, "\n## {}\n\n```json\n{}\n```\n", t("card.runtime_config", locale), config);
+        card
+    }
+}
+
+/// 模型详情弹窗，显示模型卡片和安装来源，卡片可复制到剪贴板；`locale` 默认取系统语言
+#[component]
+pub fn ModelCardModal(
+    model: Model,
+    installed: Option<InstalledModel>,
+    install_source: Option<InstallSource>,
+    #[props(default)] locale: Locale,
+    on_close: EventHandler,
+) -> Element {
+    let markdown = model.to_model_card_markdown_in(installed.as_ref(), locale);
+    let mut copied = use_signal(|| false);
+
+    let copy = {
+        let markdown = markdown.clone();
+        move |_| {
+            let text = serde_json::to_string(&markdown).unwrap_or_default();
+            dioxus::document::eval(&format!("navigator.clipboard.writeText({})", text));
+            copied.set(true);
+        }
+    };
+
+    rsx! {
+        div { class: "modal-overlay",
+            style: "position: fixed; inset: 0; background: rgba(0, 0, 0, 0.4); display: flex; align-items: center; justify-content: center; z-index: 100;",
+            onclick: move |_| on_close.call(()),
+            div { class: "card p-lg",
+                style: "width: min(720px, 90vw); max-height: 80vh; overflow: auto;",
+                onclick: move |evt| evt.stop_propagation(),
+                div { class: "flex justify-between items-center mb-md",
+                    h2 { class: "text-title font-semibold m-0", "{model.display_name}" }
+                    div { class: "flex gap-sm",
+                        button { class: "btn btn-primary", onclick: copy,
+                            if copied() { "✅ 已复制" } else { "📋 复制模型卡片" }
+                        }
+                        button { class: "btn btn-secondary", title: "关闭", onclick: move |_| on_close.call(()), "×" }
+                    }
+                }
+                if let Some(source) = install_source {
+                    p { class: "text-secondary m-0 mb-md",
+                        "{t(\"card.install_source\", locale)}: {source.label(locale)}"
+                    }
+                }
+                pre { style: "white-space: pre-wrap; font-size: 13px;", "{markdown}" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IntegratedModelService, IntegratedServiceConfig};
+    use burncloud_service_models::{CreateModelRequest, ModelType};
+    use crate::render_test::render;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_model_card_markdown_snapshot() {
+        let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+            database_path: Some(":memory:".to_string()),
+            default_install_dir: std::env::temp_dir(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let model = service.create_model(CreateModelRequest {
+            name: "qwen2-7b-instruct".to_string(),
+            display_name: "Qwen2 7B Instruct".to_string(),
+            version: "2.0".to_string(),
+            model_type: ModelType::Chat,
+            provider: "Alibaba".to_string(),
+            file_size: 1024,
+            description: Some("Instruction-tuned chat model".to_string()),
+            license: Some("Apache-2.0".to_string()),
+            tags: vec!["qwen".to_string(), "instruct".to_string()],
+            languages: vec!["Chinese".to_string(), "English".to_string()],
+            file_path: None,
+            download_url: None,
+            config: HashMap::from([
+                ("context_length".to_string(), serde_json::json!(32768)),
+                ("temperature".to_string(), serde_json::json!(0.7)),
+            ]),
+            is_official: true,
+        }).await.unwrap();
+        let installed = service.install_model(model.id, None).await.unwrap();
+
+        let expected = format!("\
+# Qwen2 7B Instruct
+
+Instruction-tuned chat model
+
+| 属性 | 值 |
+| --- | --- |
+| 名称 | `qwen2-7b-instruct` |
+| 版本 | 2.0 |
+| 类型 | 💬 对话模型 |
+| 提供商 | Alibaba |
+| 许可证 | Apache-2.0 |
+| 大小 | {} |
+| 上下文长度 | 32768 tokens |
+| 语言 | Chinese, English |
+| 标签 | `qwen` `instruct` |
+
+## 安装状态
+
+- 状态: 已停止
+- 使用次数: 0
+- 最近使用: 从未使用
+
+## 运行配置
+
+```json
+{{
+  \"context_length\": 32768,
+  \"temperature\": 0.7
+}}
+```
+", model.formatted_size());
+        assert_eq!(model.to_model_card_markdown_in(Some(&installed), Locale::Zh), expected);
+
+        // 未安装时不输出安装状态
+        let uninstalled = model.to_model_card_markdown_in(None, Locale::En);
+        assert!(uninstalled.contains("| License | Apache-2.0 |"));
+        assert!(!uninstalled.contains("## Installation"));
+    }
+
+    fn ollama_model() -> Model {
+        let now = chrono::Utc::now();
+        Model {
+            id: uuid::Uuid::new_v4(),
+            name: "llama3:8b".to_string(),
+            display_name: "llama3:8b".to_string(),
+            version: "8b".to_string(),
+            model_type: ModelType::Other,
+            size_category: burncloud_service_models::SizeCategory::Small,
+            file_size: 1024,
+            provider: "Ollama".to_string(),
+            license: None,
+            tags: vec!["ollama".to_string()],
+            languages: vec![],
+            file_path: None,
+            checksum: None,
+            download_url: None,
+            config: HashMap::new(),
+            rating: None,
+            download_count: 0,
+            is_official: false,
+            description: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_modal_shows_install_source() {
+        let rendered = render(|| rsx! {
+            ModelCardModal {
+                model: ollama_model(),
+                install_source: InstallSource::Ollama,
+                locale: Locale::Zh,
+                on_close: |_| {},
+            }
+        });
+        assert!(rendered.contains("安装来源: Ollama"));
+
+        // 未记录来源时不显示
+        let unknown = render(|| rsx! {
+            ModelCardModal { model: ollama_model(), locale: Locale::Zh, on_close: |_| {} }
+        });
+        assert!(!unknown.contains("安装来源"));
+    }
+}
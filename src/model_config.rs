@@ -0,0 +1,317 @@
+// 模型配置的类型化访问
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use burncloud_service_models::{Model, ModelType, RuntimeConfig};
+
+const TEMPERATURE: &str = "temperature";
+const MAX_TOKENS: &str = "max_tokens";
+const TOP_P: &str = "top_p";
+const ENABLE_STREAMING: &str = "enable_streaming";
+const MIRROR_URLS: &str = "mirror_urls";
+const CONTEXT_LENGTH: &str = "context_length";
+const CODE_MODE: &str = "code_mode";
+const NORMALIZE: &str = "normalize";
+
+/// 只对生成文本的模型有意义的采样参数
+const SAMPLING_KEYS: &[&str] = &[TEMPERATURE, MAX_TOKENS, TOP_P, ENABLE_STREAMING];
+
+/// 模型配置表的类型化包装
+///
+/// 底层仍是 `HashMap<String, Value>`，未知键原样保留；setter 传入 `None` 时删除对应键。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ModelConfig(HashMap<String, Value>);
+
+impl ModelConfig {
+    /// 创建空配置
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 采样温度
+    pub fn temperature(&self) -> Option<f64> {
+        self.0.get(TEMPERATURE).and_then(Value::as_f64)
+    }
+
+    /// 设置采样温度
+    pub fn set_temperature(&mut self, value: Option<f64>) {
+        self.set(TEMPERATURE, value.map(Value::from));
+    }
+
+    /// 最大生成 token 数
+    pub fn max_tokens(&self) -> Option<u32> {
+        self.0.get(MAX_TOKENS)
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+    }
+
+    /// 设置最大生成 token 数
+    pub fn set_max_tokens(&mut self, value: Option<u32>) {
+        self.set(MAX_TOKENS, value.map(Value::from));
+    }
+
+    /// Top-p 采样阈值
+    pub fn top_p(&self) -> Option<f64> {
+        self.0.get(TOP_P).and_then(Value::as_f64)
+    }
+
+    /// 设置 Top-p 采样阈值
+    pub fn set_top_p(&mut self, value: Option<f64>) {
+        self.set(TOP_P, value.map(Value::from));
+    }
+
+    /// 是否启用流式输出
+    pub fn enable_streaming(&self) -> Option<bool> {
+        self.0.get(ENABLE_STREAMING).and_then(Value::as_bool)
+    }
+
+    /// 设置是否启用流式输出
+    pub fn set_enable_streaming(&mut self, value: Option<bool>) {
+        self.set(ENABLE_STREAMING, value.map(Value::from));
+    }
+
+    /// 上下文长度（token 数）
+    pub fn context_length(&self) -> Option<u32> {
+        self.0.get(CONTEXT_LENGTH)
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+    }
+
+    /// 除 `download_url` 外可用的下载镜像，按优先级排列
+    pub fn mirror_urls(&self) -> Vec<String> {
+        self.0.get(MIRROR_URLS)
+            .and_then(Value::as_array)
+            .map(|urls| urls.iter().filter_map(|u| u.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+
+    /// 设置下载镜像，传入空列表时删除该键
+    pub fn set_mirror_urls(&mut self, urls: Vec<String>) {
+        let value = (!urls.is_empty()).then(|| Value::from(urls));
+        self.set(MIRROR_URLS, value);
+    }
+
+    /// 检查配置是否适用于 `model_type`
+    ///
+    /// 已知键的值必须类型正确且在范围内；嵌入模型不生成文本，不接受采样参数。
+    /// 未知键不做检查。
+    pub fn validate_for(&self, model_type: &ModelType) -> Result<(), String> {
+        if *model_type == ModelType::Embedding {
+            if let Some(key) = SAMPLING_KEYS.iter().find(|key| self.0.contains_key(**key)) {
+                return Err(format!("{} does not apply to {:?} models", key, model_type));
+            }
+        }
+
+        if self.0.contains_key(TEMPERATURE) && self.temperature().filter(|t| (0.0..=2.0).contains(t)).is_none() {
+            return Err("temperature must be a number between 0 and 2".to_string());
+        }
+        if self.0.contains_key(TOP_P) && self.top_p().filter(|p| (0.0..=1.0).contains(p)).is_none() {
+            return Err("top_p must be a number between 0 and 1".to_string());
+        }
+        if self.0.contains_key(MAX_TOKENS) && self.max_tokens().filter(|n| *n > 0).is_none() {
+            return Err("max_tokens must be a positive integer".to_string());
+        }
+        if self.0.contains_key(ENABLE_STREAMING) && self.enable_streaming().is_none() {
+            return Err("enable_streaming must be a boolean".to_string());
+        }
+        Ok(())
+    }
+
+    /// 读取任意键的原始值
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// 底层配置表
+    pub fn as_map(&self) -> &HashMap<String, Value> {
+        &self.0
+    }
+
+    /// 取出底层配置表
+    pub fn into_map(self) -> HashMap<String, Value> {
+        self.0
+    }
+
+    fn set(&mut self, key: &str, value: Option<Value>) {
+        match value {
+            Some(value) => {
+                self.0.insert(key.to_string(), value);
+            }
+            None => {
+                self.0.remove(key);
+            }
+        }
+    }
+}
+
+impl From<HashMap<String, Value>> for ModelConfig {
+    fn from(map: HashMap<String, Value>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<ModelConfig> for HashMap<String, Value> {
+    fn from(config: ModelConfig) -> Self {
+        config.0
+    }
+}
+
+/// 为带配置表的类型提供类型化访问
+pub trait ModelConfigExt {
+    /// 读取类型化配置
+    fn model_config(&self) -> ModelConfig;
+
+    /// 写回类型化配置
+    fn set_model_config(&mut self, config: ModelConfig);
+}
+
+/// 模型的全部下载地址：`download_url` 在前，其后是配置中的镜像
+pub fn model_download_urls(model: &Model) -> Vec<String> {
+    let mut urls: Vec<String> = model.download_url.iter().cloned().collect();
+    for mirror in model.model_config().mirror_urls() {
+        if !urls.contains(&mirror) {
+            urls.push(mirror);
+        }
+    }
+    urls
+}
+
+impl ModelConfigExt for Model {
+    fn model_config(&self) -> ModelConfig {
+        ModelConfig::from(self.config.clone())
+    }
+
+    fn set_model_config(&mut self, config: ModelConfig) {
+        self.config = config.into_map();
+    }
+}
+
+/// `RuntimeConfig` 的类型化字段之外的参数存放在 `custom_params` 中
+impl ModelConfigExt for RuntimeConfig {
+    fn model_config(&self) -> ModelConfig {
+        ModelConfig::from(self.custom_params.clone())
+    }
+
+    fn set_model_config(&mut self, config: ModelConfig) {
+        self.custom_params = config.into_map();
+    }
+}
+
+/// 按模型类型给出的默认运行配置，安装时作为可编辑的起点
+///
+/// 对话和多模态模型：温度 0.7、top_p 0.9；代码模型：温度 0.1 并开启 `code_mode`；
+/// 嵌入模型：开启 `normalize`，不流式输出。其他类型没有通用的默认值，返回 `None`。
+pub fn default_runtime_config(model_type: &ModelType) -> Option<RuntimeConfig> {
+    let mut config = RuntimeConfig {
+        max_context_length: None,
+        temperature: None,
+        top_p: None,
+        top_k: None,
+        max_tokens: None,
+        stop_sequences: Vec::new(),
+        batch_size: None,
+        max_concurrent_requests: None,
+        gpu_device_ids: Vec::new(),
+        memory_limit_mb: None,
+        enable_streaming: true,
+        custom_params: HashMap::new(),
+    };
+    match model_type {
+        ModelType::Chat | ModelType::Multimodal => {
+            config.temperature = Some(0.7);
+            config.top_p = Some(0.9);
+        }
+        ModelType::Code => {
+            config.temperature = Some(0.1);
+            config.custom_params.insert(CODE_MODE.to_string(), Value::Bool(true));
+        }
+        ModelType::Embedding => {
+            config.enable_streaming = false;
+            config.custom_params.insert(NORMALIZE.to_string(), Value::Bool(true));
+        }
+        _ => return None,
+    }
+    Some(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_read_typed_fields() {
+        let mut map = HashMap::new();
+        map.insert("temperature".to_string(), json!(0.7));
+        map.insert("max_tokens".to_string(), json!(2048));
+        map.insert("top_p".to_string(), json!(0.9));
+        map.insert("enable_streaming".to_string(), json!(true));
+        map.insert("repeat_penalty".to_string(), json!(1.1));
+
+        let config = ModelConfig::from(map);
+        assert_eq!(config.temperature(), Some(0.7));
+        assert_eq!(config.max_tokens(), Some(2048));
+        assert_eq!(config.top_p(), Some(0.9));
+        assert_eq!(config.enable_streaming(), Some(true));
+        assert_eq!(config.get("repeat_penalty"), Some(&json!(1.1)));
+    }
+
+    #[test]
+    fn test_wrong_types_read_as_none() {
+        let mut map = HashMap::new();
+        map.insert("temperature".to_string(), json!("hot"));
+        map.insert("max_tokens".to_string(), json!(-1));
+        map.insert("enable_streaming".to_string(), json!("yes"));
+
+        let config = ModelConfig::from(map);
+        assert_eq!(config.temperature(), None);
+        assert_eq!(config.max_tokens(), None);
+        assert_eq!(config.enable_streaming(), None);
+    }
+
+    #[test]
+    fn test_write_typed_fields_keeps_map_in_sync() {
+        let mut config = ModelConfig::new();
+        config.set_temperature(Some(0.2));
+        config.set_max_tokens(Some(512));
+        config.set_top_p(Some(0.95));
+        config.set_enable_streaming(Some(false));
+
+        let map: HashMap<String, Value> = config.clone().into();
+        assert_eq!(map["temperature"], json!(0.2));
+        assert_eq!(map["max_tokens"], json!(512));
+        assert_eq!(map["top_p"], json!(0.95));
+        assert_eq!(map["enable_streaming"], json!(false));
+
+        config.set_mirror_urls(vec!["https://modelscope.cn/m.gguf".to_string()]);
+        assert_eq!(config.mirror_urls(), vec!["https://modelscope.cn/m.gguf".to_string()]);
+        config.set_mirror_urls(vec![]);
+        assert!(config.get("mirror_urls").is_none());
+
+        config.set_temperature(None);
+        assert!(config.get("temperature").is_none());
+        assert_eq!(config.as_map().len(), 3);
+    }
+
+    #[test]
+    fn test_validate_for_model_type() {
+        let mut config = ModelConfig::new();
+        config.set_temperature(Some(0.7));
+        config.set_max_tokens(Some(2048));
+        assert!(config.validate_for(&ModelType::Chat).is_ok());
+        assert!(config.validate_for(&ModelType::Embedding).is_err());
+
+        config.set_temperature(Some(3.5));
+        assert!(config.validate_for(&ModelType::Chat).is_err());
+
+        let mut map = HashMap::new();
+        map.insert("max_tokens".to_string(), json!("many"));
+        assert!(ModelConfig::from(map).validate_for(&ModelType::Text).is_err());
+
+        let mut map = HashMap::new();
+        map.insert("dimensions".to_string(), json!(768));
+        assert!(ModelConfig::from(map).validate_for(&ModelType::Embedding).is_ok());
+    }
+}
@@ -0,0 +1,82 @@
+// 模型运行日志缓冲区
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 每个模型保留的最大日志行数
+pub const MODEL_LOG_CAPACITY: usize = 500;
+
+/// 一条模型日志
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelLogEntry {
+    pub timestamp: DateTime<Utc>,
+    pub line: String,
+}
+
+/// 按模型划分的环形日志缓冲区，超出容量时丢弃最旧的行
+#[derive(Debug)]
+pub struct ModelLogBuffer {
+    capacity: usize,
+    logs: Mutex<HashMap<Uuid, VecDeque<ModelLogEntry>>>,
+}
+
+impl ModelLogBuffer {
+    /// 创建指定容量的缓冲区
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            logs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 追加一行日志
+    pub fn append(&self, model_id: Uuid, line: impl Into<String>) {
+        let mut logs = self.logs.lock().unwrap();
+        let buffer = logs.entry(model_id).or_default();
+        if buffer.len() == self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(ModelLogEntry {
+            timestamp: Utc::now(),
+            line: line.into(),
+        });
+    }
+
+    /// 获取最近的 `last_n` 行日志（按时间先后排列），`None` 返回全部
+    pub fn recent(&self, model_id: Uuid, last_n: Option<usize>) -> Vec<ModelLogEntry> {
+        let logs = self.logs.lock().unwrap();
+        let Some(buffer) = logs.get(&model_id) else {
+            return Vec::new();
+        };
+
+        let skip = last_n.map_or(0, |n| buffer.len().saturating_sub(n));
+        buffer.iter().skip(skip).cloned().collect()
+    }
+
+    /// 获取所有模型最近的 `last_n` 行日志及其所属模型，按时间先后排列
+    pub fn recent_all(&self, last_n: usize) -> Vec<(Uuid, ModelLogEntry)> {
+        let logs = self.logs.lock().unwrap();
+        let mut entries: Vec<(Uuid, ModelLogEntry)> = logs
+            .iter()
+            .flat_map(|(model_id, buffer)| buffer.iter().map(|entry| (*model_id, entry.clone())))
+            .collect();
+        entries.sort_by_key(|(_, entry)| entry.timestamp);
+
+        let skip = entries.len().saturating_sub(last_n);
+        entries.split_off(skip)
+    }
+
+    /// 清空某个模型的日志
+    pub fn clear(&self, model_id: Uuid) {
+        self.logs.lock().unwrap().remove(&model_id);
+    }
+}
+
+impl Default for ModelLogBuffer {
+    fn default() -> Self {
+        Self::new(MODEL_LOG_CAPACITY)
+    }
+}
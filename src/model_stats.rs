@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
 use crate::app_state::AppState;
+use crate::components::{StatCard, StatCardVariant};
+use crate::i18n::Locale;
+use crate::model_type_display::model_type_display;
 
 /// 模型统计组件 - 使用 AppState 展示数据统计
 #[component]
@@ -26,28 +29,32 @@ pub fn ModelStats(app_state: AppState) -> Element {
                         value: stats.total_installed.to_string(),
                         icon: "🧠".to_string(),
                         description: "已安装的模型总数".to_string(),
-                        color: "blue".to_string()
+                        color: "blue".to_string(),
+                        variant: StatCardVariant::Detailed
                     }
                     StatCard {
                         title: "运行中".to_string(),
                         value: stats.running_count.to_string(),
                         icon: "🟢".to_string(),
                         description: "当前正在运行的模型".to_string(),
-                        color: "green".to_string()
+                        color: "green".to_string(),
+                        variant: StatCardVariant::Detailed
                     }
                     StatCard {
                         title: "已停止".to_string(),
                         value: stats.stopped_count.to_string(),
                         icon: "🔴".to_string(),
                         description: "当前已停止的模型".to_string(),
-                        color: "red".to_string()
+                        color: "red".to_string(),
+                        variant: StatCardVariant::Detailed
                     }
                     StatCard {
                         title: "存储占用".to_string(),
                         value: stats.format_total_size(),
                         icon: "💾".to_string(),
                         description: "模型文件总大小".to_string(),
-                        color: "purple".to_string()
+                        color: "purple".to_string(),
+                        variant: StatCardVariant::Detailed
                     }
                 }
 
@@ -64,7 +71,7 @@ pub fn ModelStats(app_state: AppState) -> Element {
                                 div { class: "card p-md",
                                     div { class: "flex justify-between items-center",
                                         div {
-                                            div { class: "font-semibold", "{get_model_type_display_name(model_type)}" }
+                                            div { class: "font-semibold", "{model_type_display(model_type, Locale::default())}" }
                                             div { class: "text-sm text-secondary", "{model_type:?}" }
                                         }
                                         div { class: "text-xl font-bold text-primary", "{count}" }
@@ -156,44 +163,3 @@ pub fn ModelStats(app_state: AppState) -> Element {
         }
     }
 }
-
-/// 增强型统计卡片组件
-#[component]
-fn StatCard(title: String, value: String, icon: String, description: String, color: String) -> Element {
-    let color_class = match color.as_str() {
-        "blue" => "stat-card-blue",
-        "green" => "stat-card-green",
-        "red" => "stat-card-red",
-        "purple" => "stat-card-purple",
-        _ => "stat-card-default"
-    };
-
-    rsx! {
-        div { class: "stat-card {color_class}",
-            div { class: "stat-header",
-                div { class: "stat-icon", "{icon}" }
-                div { class: "stat-title", "{title}" }
-            }
-            div { class: "stat-value", "{value}" }
-            div { class: "stat-description", "{description}" }
-        }
-    }
-}
-
-/// 获取模型类型的显示名称
-fn get_model_type_display_name(model_type: &burncloud_service_models::ModelType) -> &'static str {
-    use burncloud_service_models::ModelType;
-    match model_type {
-        ModelType::Chat => "对话模型",
-        ModelType::Code => "代码生成",
-        ModelType::Text => "文本生成",
-        ModelType::Embedding => "文本嵌入",
-        ModelType::Image => "图像处理",
-        ModelType::ImageGeneration => "图像生成",
-        ModelType::Audio => "音频处理",
-        ModelType::Speech => "语音处理",
-        ModelType::Video => "视频处理",
-        ModelType::Multimodal => "多模态",
-        ModelType::Other => "其他",
-    }
-}
\ No newline at end of file
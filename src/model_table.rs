@@ -0,0 +1,311 @@
+// 模型表格视图，便于快速浏览大量模型
+
+use std::cmp::Ordering;
+use dioxus::prelude::*;
+use burncloud_service_models::{AvailableModel, InstalledModel, Model};
+use crate::i18n::{t, Locale};
+use crate::integrated_service::license_label;
+use crate::model_type_display::{model_status_class, model_status_display, model_type_display, model_type_icon, MODEL_TYPES};
+use crate::models::installed_action_button;
+
+/// 可排序的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Name,
+    Type,
+    Size,
+    Provider,
+    License,
+    Rating,
+}
+
+impl SortColumn {
+    /// 表头文字
+    pub fn label(&self, locale: Locale) -> &'static str {
+        let key = match self {
+            SortColumn::Name => "table.name",
+            SortColumn::Type => "table.type",
+            SortColumn::Size => "table.size",
+            SortColumn::Provider => "table.provider",
+            SortColumn::License => "table.license",
+            SortColumn::Rating => "table.rating",
+        };
+        t(key, locale)
+    }
+}
+
+/// 排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// 表格当前的排序方式，默认按名称升序
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: SortColumn,
+    pub direction: SortDirection,
+}
+
+impl Default for TableSort {
+    fn default() -> Self {
+        Self { column: SortColumn::Name, direction: SortDirection::Ascending }
+    }
+}
+
+impl TableSort {
+    /// 点击表头后的排序：再次点击当前列时反转方向，点击其他列时按该列升序
+    pub fn clicked(self, column: SortColumn) -> Self {
+        if self.column == column {
+            let direction = match self.direction {
+                SortDirection::Ascending => SortDirection::Descending,
+                SortDirection::Descending => SortDirection::Ascending,
+            };
+            Self { column, direction }
+        } else {
+            Self { column, direction: SortDirection::Ascending }
+        }
+    }
+
+    /// 按当前排序比较两个模型
+    pub fn compare(&self, a: &Model, b: &Model) -> Ordering {
+        let ordering = compare_by_column(a, b, self.column);
+        match self.direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    }
+}
+
+/// 按某一列升序比较两个模型
+///
+/// 名称、提供商和许可证不区分大小写，类型按 `MODEL_TYPES` 的顺序，没有评分的排在最前。
+pub fn compare_by_column(a: &Model, b: &Model, column: SortColumn) -> Ordering {
+    match column {
+        SortColumn::Name => a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()),
+        SortColumn::Type => type_position(a).cmp(&type_position(b)),
+        SortColumn::Size => a.file_size.cmp(&b.file_size),
+        SortColumn::Provider => a.provider.to_lowercase().cmp(&b.provider.to_lowercase()),
+        SortColumn::License => license_label(a.license.as_deref()).to_lowercase()
+            .cmp(&license_label(b.license.as_deref()).to_lowercase()),
+        SortColumn::Rating => a.rating.partial_cmp(&b.rating).unwrap_or(Ordering::Equal),
+    }
+}
+
+fn type_position(model: &Model) -> usize {
+    MODEL_TYPES.iter().position(|t| *t == model.model_type).unwrap_or(MODEL_TYPES.len())
+}
+
+/// 表格中的一行
+#[derive(Debug, Clone, PartialEq)]
+enum TableRow {
+    Installed(InstalledModel),
+    Available(AvailableModel),
+}
+
+impl TableRow {
+    fn model(&self) -> &Model {
+        match self {
+            TableRow::Installed(installed) => &installed.model,
+            TableRow::Available(available) => &available.model,
+        }
+    }
+}
+
+/// 模型表格
+///
+/// 已安装和可下载的模型一起排序；点击表头修改 `sort`。
+#[component]
+pub fn ModelTable(
+    #[props(default)] installed: Vec<InstalledModel>,
+    #[props(default)] available: Vec<AvailableModel>,
+    #[props(default)] offline: bool,
+    sort: Signal<TableSort>,
+    #[props(default)] locale: Locale,
+) -> Element {
+    let mut sort = sort;
+    let current = sort();
+    let mut rows: Vec<TableRow> = installed.into_iter().map(TableRow::Installed)
+        .chain(available.into_iter().map(TableRow::Available))
+        .collect();
+    rows.sort_by(|a, b| current.compare(a.model(), b.model()));
+
+    let status_header = t("table.status_actions", locale);
+    let columns = [SortColumn::Name, SortColumn::Type, SortColumn::Size, SortColumn::Provider, SortColumn::License, SortColumn::Rating];
+
+    rsx! {
+        table { class: "model-table", style: "width: 100%; border-collapse: collapse;",
+            thead {
+                tr {
+                    for column in columns {
+                        th {
+                            key: "{column:?}",
+                            style: "cursor: pointer; text-align: left; padding: 8px;",
+                            onclick: move |_| sort.set(sort().clicked(column)),
+                            "{column.label(locale)}"
+                            if current.column == column {
+                                if current.direction == SortDirection::Ascending { " ▲" } else { " ▼" }
+                            }
+                        }
+                    }
+                    th { style: "text-align: left; padding: 8px;", "{status_header}" }
+                }
+            }
+            tbody {
+                for row in rows {
+                    ModelTableRow { key: "{row.model().id}", row, offline, locale }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ModelTableRow(row: TableRow, offline: bool, locale: Locale) -> Element {
+    let model = row.model();
+    let type_icon = model_type_icon(&model.model_type);
+    let type_display = model_type_display(&model.model_type, locale);
+    let rating = model.rating.map(|r| format!("⭐{}", r)).unwrap_or_else(|| "-".to_string());
+    let license = license_label(model.license.as_deref());
+    let available_label = t("table.available", locale);
+    let download_label = t("table.download", locale);
+
+    rsx! {
+        tr { class: "model-table-row", style: "border-top: 1px solid #eee;",
+            td { style: "padding: 8px;", "{model.display_name}" }
+            td { style: "padding: 8px;", "{type_icon} {type_display}" }
+            td { style: "padding: 8px;", "{model.formatted_size()}" }
+            td { style: "padding: 8px;", "{model.provider}" }
+            td { style: "padding: 8px;", "{license}" }
+            td { style: "padding: 8px;", "{rating}" }
+            td { style: "padding: 8px;",
+                div { class: "flex items-center gap-md",
+                    match &row {
+                        TableRow::Installed(installed) => {
+                            let status_class = model_status_class(&installed.status);
+                            let status_text = model_status_display(&installed.status, locale);
+                            rsx! {
+                                span { class: "status-indicator {status_class}",
+                                    span { class: "status-dot" }
+                                    "{status_text}"
+                                }
+                                {installed_action_button(&installed.status)}
+                            }
+                        }
+                        TableRow::Available(_) => rsx! {
+                            span { class: "text-secondary", "{available_label}" }
+                            if !offline {
+                                button { class: "btn btn-primary", "{download_label}" }
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_test::render;
+    use crate::{IntegratedModelService, IntegratedServiceConfig};
+    use burncloud_service_models::{CreateModelRequest, ModelType, UpdateModelRequest};
+    use std::collections::HashMap;
+
+    /// 通过内存数据库创建一组便于比较的模型
+    async fn sample_models() -> Vec<Model> {
+        let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+            database_path: Some(":memory:".to_string()),
+            default_install_dir: std::env::temp_dir(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let specs = [
+            ("beta", ModelType::Embedding, 2048, "openai", Some(4.5)),
+            ("Alpha", ModelType::Code, 4096, "Meta", None),
+            ("gamma", ModelType::Chat, 1024, "Alibaba", Some(3.0)),
+        ];
+        let mut models = Vec::new();
+        for (name, model_type, file_size, provider, rating) in specs {
+            let model = service.create_model(CreateModelRequest {
+                name: name.to_string(),
+                display_name: name.to_string(),
+                version: "1.0.0".to_string(),
+                model_type,
+                provider: provider.to_string(),
+                file_size,
+                description: None,
+                license: None,
+                tags: vec![],
+                languages: vec![],
+                file_path: None,
+                download_url: None,
+                config: HashMap::new(),
+                is_official: false,
+            }).await.unwrap();
+            let model = match rating {
+                Some(rating) => service.update_model(model.id, UpdateModelRequest {
+                    rating: Some(rating),
+                    ..Default::default()
+                }).await.unwrap(),
+                None => model,
+            };
+            models.push(model);
+        }
+        models
+    }
+
+    fn sorted_names(models: &[Model], sort: TableSort) -> Vec<String> {
+        let mut models = models.to_vec();
+        models.sort_by(|a, b| sort.compare(a, b));
+        models.into_iter().map(|m| m.name).collect()
+    }
+
+    #[tokio::test]
+    async fn test_column_comparators() {
+        let models = sample_models().await;
+        let by = |column| sorted_names(&models, TableSort { column, direction: SortDirection::Ascending });
+
+        assert_eq!(by(SortColumn::Name), vec!["Alpha", "beta", "gamma"]);
+        assert_eq!(by(SortColumn::Type), vec!["gamma", "Alpha", "beta"]);
+        assert_eq!(by(SortColumn::Size), vec!["gamma", "beta", "Alpha"]);
+        assert_eq!(by(SortColumn::Provider), vec!["gamma", "Alpha", "beta"]);
+        assert_eq!(by(SortColumn::Rating), vec!["Alpha", "gamma", "beta"]);
+
+        let descending = TableSort { column: SortColumn::Size, direction: SortDirection::Descending };
+        assert_eq!(sorted_names(&models, descending), vec!["Alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn test_clicking_header_toggles_direction() {
+        let sort = TableSort::default().clicked(SortColumn::Name);
+        assert_eq!(sort, TableSort { column: SortColumn::Name, direction: SortDirection::Descending });
+        assert_eq!(sort.clicked(SortColumn::Name), TableSort::default());
+
+        let sort = sort.clicked(SortColumn::Size);
+        assert_eq!(sort, TableSort { column: SortColumn::Size, direction: SortDirection::Ascending });
+    }
+
+    #[test]
+    fn test_headers_follow_locale() {
+        assert_eq!(SortColumn::Rating.label(Locale::Zh), "评分");
+        assert_eq!(SortColumn::Rating.label(Locale::En), "Rating");
+
+        let english = render(|| rsx! {
+            ModelTable { sort: use_signal(TableSort::default), locale: Locale::En }
+        });
+        assert!(english.contains(SortColumn::Provider.label(Locale::En)));
+        assert!(english.contains(t("table.status_actions", Locale::En)));
+
+        let chinese = render(|| rsx! {
+            ModelTable { sort: use_signal(TableSort::default), locale: Locale::Zh }
+        });
+        assert!(chinese.contains(t("table.status_actions", Locale::Zh)));
+        assert!(!chinese.contains("Status / Actions"));
+    }
+}
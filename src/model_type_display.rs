@@ -0,0 +1,168 @@
+// 模型类型的图标与显示名称
+
+use std::collections::HashMap;
+use burncloud_service_models::{ModelStatus, ModelType};
+use serde::{Serialize, Serializer};
+use crate::i18n::{t, Locale};
+
+/// 所有模型类型，按界面中分组显示的顺序排列
+pub const MODEL_TYPES: [ModelType; 11] = [
+    ModelType::Chat,
+    ModelType::Code,
+    ModelType::Text,
+    ModelType::Embedding,
+    ModelType::Image,
+    ModelType::ImageGeneration,
+    ModelType::Audio,
+    ModelType::Speech,
+    ModelType::Video,
+    ModelType::Multimodal,
+    ModelType::Other,
+];
+
+/// 模型类型图标
+pub fn model_type_icon(model_type: &ModelType) -> &'static str {
+    match model_type {
+        ModelType::Chat => "💬",
+        ModelType::Code => "💻",
+        ModelType::Text => "📝",
+        ModelType::Embedding => "🔗",
+        ModelType::Image => "🖼️",
+        ModelType::ImageGeneration => "🎨",
+        ModelType::Audio => "🎵",
+        ModelType::Speech => "🎤",
+        ModelType::Video => "🎬",
+        ModelType::Multimodal => "🎭",
+        ModelType::Other => "📦",
+    }
+}
+
+/// 模型类型显示名称
+pub fn model_type_display(model_type: &ModelType, locale: Locale) -> &'static str {
+    let key = match model_type {
+        ModelType::Chat => "type.chat",
+        ModelType::Code => "type.code",
+        ModelType::Text => "type.text",
+        ModelType::Embedding => "type.embedding",
+        ModelType::Image => "type.image",
+        ModelType::ImageGeneration => "type.image_generation",
+        ModelType::Audio => "type.audio",
+        ModelType::Speech => "type.speech",
+        ModelType::Video => "type.video",
+        ModelType::Multimodal => "type.multimodal",
+        ModelType::Other => "type.other",
+    };
+    t(key, locale)
+}
+
+/// 模型状态显示名称
+pub fn model_status_display(status: &ModelStatus, locale: Locale) -> &'static str {
+    // 上游新增的状态显示为"未知"
+    #[allow(unreachable_patterns)]
+    let key = match status {
+        ModelStatus::Running => "status.running",
+        ModelStatus::Stopped => "status.stopped",
+        ModelStatus::Starting => "status.starting",
+        ModelStatus::Stopping => "status.stopping",
+        ModelStatus::Error => "status.error",
+        _ => "status.unknown",
+    };
+    t(key, locale)
+}
+
+/// 模型状态指示器的样式类
+pub fn model_status_class(status: &ModelStatus) -> &'static str {
+    #[allow(unreachable_patterns)]
+    match status {
+        ModelStatus::Running => "status-running",
+        ModelStatus::Stopped => "status-stopped",
+        ModelStatus::Starting => "status-starting",
+        ModelStatus::Stopping => "status-stopping",
+        ModelStatus::Error => "status-error",
+        _ => "status-unknown",
+    }
+}
+
+/// 按模型类型分组
+///
+/// 组按 `MODEL_TYPES` 的顺序排列，没有模型的类型不出现；组内保持输入顺序，
+/// 先排序再分组即可得到组内有序的结果。
+pub fn group_by_model_type<'a, T: 'a>(
+    models: impl IntoIterator<Item = &'a T>,
+    model_type: impl Fn(&T) -> &ModelType,
+) -> Vec<(ModelType, Vec<&'a T>)> {
+    let mut groups: Vec<(ModelType, Vec<&'a T>)> =
+        MODEL_TYPES.iter().map(|t| (*t, Vec::new())).collect();
+    for model in models {
+        if let Some((_, bucket)) = groups.iter_mut().find(|(t, _)| t == model_type(model)) {
+            bucket.push(model);
+        }
+    }
+    groups.retain(|(_, bucket)| !bucket.is_empty());
+    groups
+}
+
+/// 按类型名排序的类型计数，序列化结果与 `HashMap` 的遍历顺序无关
+pub(crate) struct SortedTypeCounts<'a>(pub &'a HashMap<ModelType, usize>);
+
+impl Serialize for SortedTypeCounts<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut counts: Vec<(&ModelType, &usize)> = self.0.iter().collect();
+        counts.sort_by_cached_key(|(model_type, _)| format!("{:?}", model_type));
+        serializer.collect_map(counts)
+    }
+}
+
+/// 供 `#[serde(serialize_with)]` 使用
+pub(crate) fn serialize_type_counts<S: Serializer>(
+    counts: &HashMap<ModelType, usize>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    SortedTypeCounts(counts).serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_type_has_icon_and_labels() {
+        for locale in [Locale::Zh, Locale::En] {
+            let labels: HashSet<&str> = MODEL_TYPES.iter().map(|t| model_type_display(t, locale)).collect();
+            assert_eq!(labels.len(), MODEL_TYPES.len(), "duplicate label for {:?}", locale);
+            assert!(labels.iter().all(|l| !l.is_empty()));
+        }
+
+        let icons: HashSet<&str> = MODEL_TYPES.iter().map(model_type_icon).collect();
+        assert_eq!(icons.len(), MODEL_TYPES.len());
+
+        assert_eq!(model_status_display(&ModelStatus::Running, Locale::Zh), "运行中");
+        assert_eq!(model_status_display(&ModelStatus::Running, Locale::En), "Running");
+    }
+
+    #[test]
+    fn test_group_by_model_type_buckets_in_type_order() {
+        let models = [
+            (ModelType::Code, "coder-b"),
+            (ModelType::Chat, "chat-a"),
+            (ModelType::Code, "coder-a"),
+            (ModelType::Embedding, "embed"),
+            (ModelType::Chat, "chat-b"),
+        ];
+
+        let groups = group_by_model_type(&models, |(model_type, _)| model_type);
+        let names: Vec<(ModelType, Vec<&str>)> = groups
+            .into_iter()
+            .map(|(model_type, bucket)| (model_type, bucket.iter().map(|(_, name)| *name).collect()))
+            .collect();
+
+        // 组按类型顺序排列，组内保持输入顺序，空组省略
+        assert_eq!(names, vec![
+            (ModelType::Chat, vec!["chat-a", "chat-b"]),
+            (ModelType::Code, vec!["coder-b", "coder-a"]),
+            (ModelType::Embedding, vec!["embed"]),
+        ]);
+        assert!(group_by_model_type(&[] as &[(ModelType, &str)], |(t, _)| t).is_empty());
+    }
+}
@@ -1,76 +1,109 @@
 use dioxus::prelude::*;
-use burncloud_service_models::{InstalledModel, AvailableModel, ModelStatus, ModelType};
-use crate::app_state::AppState;
+use burncloud_service_models::{InstalledModel, AvailableModel, ModelStatus};
+use crate::app_state::{load_app_state, AppState};
+use crate::integrated_service::{InstallSource, IntegratedServiceConfig};
+use std::collections::HashSet;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::data_service::SystemRequirements;
+use crate::preflight::{PreflightReport, combined_requirements, detect_host_memory_bytes};
+use crate::i18n::{t, Locale};
+use crate::model_type_display::{model_status_class, model_status_display, model_type_display, model_type_icon};
+use crate::model_card::ModelCardModal;
+use crate::confirm::{use_confirm, ConfirmRequest};
+use crate::page_states::{EmptyState, ErrorState, LoadingState, WarningBanner};
+use crate::search_bar::SearchBar;
+use crate::components::{CapabilityChips, DownloadEta};
+use crate::download_queue::{QueueState, QueuedDownload};
+use crate::model_capabilities::ModelCapabilitiesExt;
+
+/// 需要用户确认的破坏性操作
+#[derive(Debug, Clone, Copy)]
+enum DestructiveAction {
+    Uninstall,
+    ForceDelete,
+}
 
 #[component]
 pub fn ModelManagement() -> Element {
-    let mut search_term = use_signal(|| String::new());
+    let search_term = use_signal(|| String::new());
     let mut app_state = use_signal(|| None::<AppState>);
     let mut loading = use_signal(|| true);
     let mut error_message = use_signal(|| None::<String>);
+    // 勾选用于批量安装的模型
+    let mut selected = use_signal(HashSet::<Uuid>::new);
 
-    // 初始化应用状态并加载数据
-    use_effect(move || {
-        spawn(async move {
-            println!("🚀 ModelManagement: 开始初始化数据服务...");
-            match AppState::new().await {
-                Ok(mut state) => {
-                    println!("✅ ModelManagement: AppState 初始化成功");
-                    match state.load_data().await {
-                        Ok(_) => {
-                            println!("✅ ModelManagement: 数据加载成功");
-                            println!("📊 已安装模型数量: {}", state.installed_models.len());
-                            println!("📊 可用模型数量: {}", state.available_models.len());
-                            app_state.set(Some(state));
-                        }
-                        Err(e) => {
-                            let error_msg = format!("数据加载失败: {}", e);
-                            println!("❌ ModelManagement: {}", error_msg);
-                            error_message.set(Some(error_msg));
-                        }
-                    }
-                    loading.set(false);
+    // 进行中的加载任务，再次加载前先取消，避免反复重试留下多个任务
+    let mut load_task = use_signal(|| None::<Task>);
+    // 卸载、强制删除等操作共用的确认弹窗
+    let (open_confirm, confirm_dialog) = use_confirm();
+
+    // 加载数据：复用现有状态，使并发的刷新共享加载代数；尚无状态时重新初始化
+    let mut reload = move || {
+        if let Some(task) = load_task.take() {
+            task.cancel();
+        }
+        loading.set(true);
+        error_message.set(None);
+        let current = app_state.peek().clone();
+        let task = spawn(async move {
+            match load_app_state(current, || AppState::initialize(IntegratedServiceConfig::default())).await {
+                Ok(Some(state)) => {
+                    println!("✅ ModelManagement: 数据加载成功");
+                    println!("📊 已安装模型数量: {}", state.installed_models.len());
+                    println!("📊 可用模型数量: {}", state.available_models.len());
+                    app_state.set(Some(state));
                 }
+                // 已有更新的加载在进行，丢弃本次结果
+                Ok(None) => return,
                 Err(e) => {
-                    let error_msg = format!("应用初始化失败: {}", e);
+                    let error_msg = format!("数据加载失败: {}", e);
                     println!("❌ ModelManagement: {}", error_msg);
                     error_message.set(Some(error_msg));
-                    loading.set(false);
                 }
             }
+            loading.set(false);
+            load_task.set(None);
+        });
+        load_task.set(Some(task));
+    };
+
+    // 初始化应用状态并加载数据
+    use_effect(move || reload());
+
+    // 确认后执行破坏性操作，失败时以提示条显示，完成后刷新列表
+    let run_destructive = move |action: DestructiveAction, model_id: Uuid| {
+        let Some(service) = app_state.peek().as_ref().map(|state| state.service.clone()) else {
+            return;
+        };
+        let mut reload = reload;
+        spawn(async move {
+            let result = match action {
+                DestructiveAction::Uninstall => service.uninstall_model(model_id).await.map(|_| ()),
+                DestructiveAction::ForceDelete => service.force_delete(model_id).await,
+            };
+            if let Err(e) = result {
+                println!("❌ ModelManagement: 操作失败: {}", e);
+                if let Some(state) = app_state.write().as_mut() {
+                    state.warning = Some(format!("操作失败: {}", e));
+                }
+                return;
+            }
+            reload();
         });
-    });
+    };
 
     // 显示加载状态
     if *loading.read() {
         return rsx! {
-            div { class: "page-content",
-                style: "display: flex; justify-content: center; align-items: center; height: 400px; flex-direction: column;",
-                div { class: "loading-spinner", style: "font-size: 24px; margin-bottom: 16px;", "🔄" }
-                p { style: "color: #666; font-size: 16px;", "正在加载模型数据..." }
-                p { style: "color: #999; font-size: 14px;", "首次加载可能需要几秒钟" }
-            }
+            LoadingState { message: "正在加载模型数据...", hint: "首次加载可能需要几秒钟".to_string() }
         };
     }
 
     // 显示错误状态
     if let Some(error) = error_message.read().as_ref() {
         return rsx! {
-            div { class: "page-content",
-                style: "display: flex; justify-content: center; align-items: center; height: 400px; flex-direction: column;",
-                div { class: "error-icon", style: "font-size: 48px; margin-bottom: 16px;", "❌" }
-                h2 { style: "color: #e74c3c; margin-bottom: 8px;", "数据加载失败" }
-                p { style: "color: #666; margin-bottom: 16px;", "{error}" }
-                button {
-                    class: "btn btn-primary",
-                    onclick: move |_| {
-                        loading.set(true);
-                        error_message.set(None);
-                        // 重新加载数据
-                    },
-                    "重试"
-                }
-            }
+            ErrorState { message: "{error}", on_retry: move |_| reload() }
         };
     }
 
@@ -80,8 +113,27 @@ pub fn ModelManagement() -> Element {
 
     match state_option {
         Some(state) => {
-            let installed_models = &state.installed_models;
-            let available_models = &state.available_models;
+            let (installed_models, available_models) = if search_term.read().is_empty() {
+                (state.installed_models.iter().collect::<Vec<_>>(),
+                 state.available_models.iter().collect::<Vec<_>>())
+            } else {
+                state.search_models(&search_term.read())
+            };
+            let result_count = installed_models.len() + available_models.len();
+            // 离线模式下不提供下载
+            let offline = state.service.is_offline();
+            let selected_models: Vec<&AvailableModel> = state.available_models.iter()
+                .filter(|m| selected.read().contains(&m.model.id))
+                .collect();
+            let batch_requirements = (selected_models.len() > 1)
+                .then(|| combined_requirements(&selected_models));
+            // 下载队列及每个任务的预计开始、完成时间
+            let downloads: Vec<_> = state.service.download_queue().list().into_iter()
+                .map(|download| {
+                    let estimate = state.service.estimated_start_and_finish(download.model_id);
+                    (download, estimate)
+                })
+                .collect();
 
             rsx! {
                 div { class: "page-header",
@@ -97,24 +149,7 @@ pub fn ModelManagement() -> Element {
                         div { class: "flex gap-md",
                             button {
                                 class: "btn btn-secondary",
-                                onclick: move |_| {
-                                    loading.set(true);
-                                    error_message.set(None);
-                                    // 重新加载数据的逻辑
-                                    spawn(async move {
-                                        // 创建新的AppState实例并加载数据
-                                        match AppState::new().await {
-                                            Ok(mut new_state) => {
-                                                match new_state.load_data().await {
-                                                    Ok(_) => app_state.set(Some(new_state)),
-                                                    Err(e) => error_message.set(Some(format!("{}", e))),
-                                                }
-                                            }
-                                            Err(e) => error_message.set(Some(format!("{}", e))),
-                                        }
-                                        loading.set(false);
-                                    });
-                                },
+                                onclick: move |_| reload(),
                                 span { "🔄" }
                                 "刷新"
                             }
@@ -129,34 +164,72 @@ pub fn ModelManagement() -> Element {
                         }
                     }
                     div { class: "mt-lg",
-                        input {
-                            class: "input",
-                            style: "max-width: 400px;",
-                            placeholder: "搜索模型...",
-                            value: "{search_term}",
-                            oninput: move |evt| search_term.set(evt.value())
-                        }
+                        SearchBar { value: search_term, result_count }
                     }
                 }
 
                 div { class: "page-content",
+                    {confirm_dialog}
+                    // 可用模型加载失败时仍可管理已安装模型
+                    if let Some(warning) = state.warning.clone() {
+                        WarningBanner {
+                            message: warning,
+                            on_dismiss: move |_| {
+                                if let Some(state) = app_state.write().as_mut() {
+                                    state.warning = None;
+                                }
+                            },
+                        }
+                    }
+                    // 下载队列
+                    if !downloads.is_empty() {
+                        div { class: "mb-xxxl",
+                            h2 { class: "text-title font-semibold mb-lg",
+                                "下载队列 ({downloads.len()})"
+                            }
+                            div { class: "grid gap-lg",
+                                style: "grid-template-columns: 1fr;",
+                                for (download, estimate) in downloads {
+                                    DownloadQueueRow { download, estimate }
+                                }
+                            }
+                        }
+                    }
                     // 已安装模型
                     div { class: "mb-xxxl",
                         h2 { class: "text-title font-semibold mb-lg",
                             "已安装模型 ({installed_models.len()})"
                         }
                         if installed_models.is_empty() {
-                            div { class: "empty-state",
-                                style: "text-align: center; padding: 40px; color: #666;",
-                                div { style: "font-size: 48px; margin-bottom: 16px;", "📦" }
-                                h3 { style: "margin-bottom: 8px;", "暂无已安装模型" }
-                                p { "从下方的可安装模型列表中选择并安装模型" }
+                            EmptyState {
+                                icon: "📦",
+                                title: "暂无已安装模型",
+                                message: "从下方的可安装模型列表中选择并安装模型",
                             }
                         } else {
                             div { class: "grid gap-lg",
                                 style: "grid-template-columns: 1fr;",
                                 for installed_model in installed_models.iter() {
-                                    InstalledModelCard { model: installed_model.clone() }
+                                    InstalledModelCard {
+                                        model: (*installed_model).clone(),
+                                        install_source: state.install_sources.get(&installed_model.model.id).copied(),
+                                        on_uninstall: move |model: InstalledModel| {
+                                            let model_id = model.model.id;
+                                            open_confirm(ConfirmRequest::new(
+                                                "卸载模型",
+                                                format!("确定卸载「{}」吗？模型仍保留在目录中，可以重新安装。", model.model.display_name),
+                                                move || run_destructive(DestructiveAction::Uninstall, model_id),
+                                            ).danger());
+                                        },
+                                        on_delete: move |model: InstalledModel| {
+                                            let model_id = model.model.id;
+                                            open_confirm(ConfirmRequest::new(
+                                                "删除模型",
+                                                format!("确定删除「{}」吗？运行中的模型会先停止，安装文件和记录将一并删除，无法恢复。", model.model.display_name),
+                                                move || run_destructive(DestructiveAction::ForceDelete, model_id),
+                                            ).danger());
+                                        },
+                                    }
                                 }
                             }
                         }
@@ -167,18 +240,30 @@ pub fn ModelManagement() -> Element {
                         h2 { class: "text-title font-semibold mb-lg",
                             "可安装模型 ({available_models.len()})"
                         }
+                        if let Some(requirements) = batch_requirements {
+                            BatchRequirementsSummary { count: selected_models.len(), requirements }
+                        }
                         if available_models.is_empty() {
-                            div { class: "empty-state",
-                                style: "text-align: center; padding: 40px; color: #666;",
-                                div { style: "font-size: 48px; margin-bottom: 16px;", "🌐" }
-                                h3 { style: "margin-bottom: 8px;", "暂无可安装模型" }
-                                p { "请检查网络连接或稍后重试" }
+                            EmptyState {
+                                icon: "🌐",
+                                title: "暂无可安装模型",
+                                message: "请检查网络连接或稍后重试",
                             }
                         } else {
                             div { class: "grid gap-lg",
                                 style: "grid-template-columns: 1fr;",
                                 for available_model in available_models.iter() {
-                                    AvailableModelCard { model: available_model.clone() }
+                                    AvailableModelCard {
+                                        model: (*available_model).clone(),
+                                        offline,
+                                        selected: selected.read().contains(&available_model.model.id),
+                                        on_select: move |id: Uuid| {
+                                            let mut selected = selected.write();
+                                            if !selected.remove(&id) {
+                                                selected.insert(id);
+                                            }
+                                        },
+                                    }
                                 }
                             }
                         }
@@ -197,65 +282,25 @@ pub fn ModelManagement() -> Element {
     }
 }
 
+/// 已安装模型卡片；传入 `on_uninstall` 时显示卸载按钮，删除按钮调用 `on_delete`，
+/// `install_source` 显示在详情弹窗中
 #[component]
-pub fn InstalledModelCard(model: InstalledModel) -> Element {
-    let status_class = match model.status {
-        ModelStatus::Running => "status-running",
-        ModelStatus::Stopped => "status-stopped",
-        ModelStatus::Starting => "status-starting",
-        ModelStatus::Stopping => "status-stopping",
-        ModelStatus::Error => "status-error",
-        _ => "status-unknown",
-    };
+pub fn InstalledModelCard(
+    model: InstalledModel,
+    install_source: Option<InstallSource>,
+    on_uninstall: Option<EventHandler<InstalledModel>>,
+    on_delete: Option<EventHandler<InstalledModel>>,
+) -> Element {
+    let status_class = model_status_class(&model.status);
 
-    let status_text = match model.status {
-        ModelStatus::Running => "运行中",
-        ModelStatus::Stopped => "已停止",
-        ModelStatus::Starting => "启动中",
-        ModelStatus::Stopping => "停止中",
-        ModelStatus::Error => "错误",
-        _ => "未知",
-    };
+    let status_text = model_status_display(&model.status, Locale::default());
 
-    let type_icon = match model.model.model_type {
-        ModelType::Chat => "🧠",
-        ModelType::Code => "💻",
-        ModelType::Text => "📝",
-        ModelType::Embedding => "🔗",
-        ModelType::Multimodal => "🎭",
-        ModelType::Image => "🖼️",
-        ModelType::ImageGeneration => "🎨",
-        ModelType::Audio => "🎵",
-        ModelType::Speech => "🎤",
-        ModelType::Video => "🎬",
-        ModelType::Other => "📦",
-    };
+    let type_icon = model_type_icon(&model.model.model_type);
 
-    let type_display = match model.model.model_type {
-        ModelType::Chat => "💬对话专用",
-        ModelType::Code => "💻代码生成",
-        ModelType::Text => "📝文本生成",
-        ModelType::Embedding => "🔗嵌入模型",
-        ModelType::Multimodal => "🎭多模态",
-        ModelType::Image => "🖼️图像处理",
-        ModelType::ImageGeneration => "🎨图像生成",
-        ModelType::Audio => "🎵音频处理",
-        ModelType::Speech => "🎤语音模型",
-        ModelType::Video => "🎬视频处理",
-        ModelType::Other => "📦其他类型",
-    };
+    let type_display = model_type_display(&model.model.model_type, Locale::default());
 
-    let action_button = match model.status {
-        ModelStatus::Running => rsx! {
-            button { class: "btn btn-secondary", "停止" }
-        },
-        ModelStatus::Stopped => rsx! {
-            button { class: "btn btn-primary", "启动" }
-        },
-        _ => rsx! {
-            button { class: "btn btn-secondary", disabled: true, "{status_text}" }
-        },
-    };
+    let action_button = installed_action_button(&model.status);
+    let mut show_details = use_signal(|| false);
 
     rsx! {
         div { class: "card model-card",
@@ -268,6 +313,7 @@ pub fn InstalledModelCard(model: InstalledModel) -> Element {
                             {model.model.description.as_deref().unwrap_or(&model.model.name)}
                         }
                     }
+                    CapabilityChips { capabilities: model.model.capabilities() }
                 }
                 div { class: "flex items-center gap-md",
                     span { class: "status-indicator {status_class}",
@@ -276,8 +322,18 @@ pub fn InstalledModelCard(model: InstalledModel) -> Element {
                     }
                     div { class: "model-actions",
                         {action_button}
+                        button { class: "btn btn-subtle", onclick: move |_| show_details.set(true), "详情" }
                         button { class: "btn btn-subtle", "配置" }
-                        button { class: "btn btn-subtle", "删除" }
+                        if let Some(on_uninstall) = on_uninstall {
+                            button { class: "btn btn-subtle", onclick: {
+                                let model = model.clone();
+                                move |_| on_uninstall.call(model.clone())
+                            }, "卸载" }
+                        }
+                        button { class: "btn btn-subtle", onclick: {
+                            let model = model.clone();
+                            move |_| if let Some(on_delete) = on_delete { on_delete.call(model.clone()) }
+                        }, "删除" }
                     }
                 }
             }
@@ -311,39 +367,125 @@ pub fn InstalledModelCard(model: InstalledModel) -> Element {
                     div { class: "font-medium", "{type_display}" }
                 }
             }
+            if show_details() {
+                ModelCardModal {
+                    model: model.model.clone(),
+                    installed: model.clone(),
+                    install_source,
+                    on_close: move |_| show_details.set(false),
+                }
+            }
         }
     }
 }
 
+/// 已安装模型的启动/停止按钮，过渡状态下显示为禁用的状态名
+pub(crate) fn installed_action_button(status: &ModelStatus) -> Element {
+    let status_text = model_status_display(status, Locale::default());
+    match status {
+        ModelStatus::Running => rsx! {
+            button { class: "btn btn-secondary", "停止" }
+        },
+        ModelStatus::Stopped => rsx! {
+            button { class: "btn btn-primary", "启动" }
+        },
+        _ => rsx! {
+            button { class: "btn btn-secondary", disabled: true, "{status_text}" }
+        },
+    }
+}
+
+/// 批量安装所选模型时的合计系统需求
 #[component]
-pub fn AvailableModelCard(model: AvailableModel) -> Element {
-    let type_icon = match model.model.model_type {
-        ModelType::Chat => "🧠",
-        ModelType::Code => "💻",
-        ModelType::Text => "📝",
-        ModelType::Embedding => "🔗",
-        ModelType::Multimodal => "🎭",
-        ModelType::Image => "🖼️",
-        ModelType::ImageGeneration => "🎨",
-        ModelType::Audio => "🎵",
-        ModelType::Speech => "🎤",
-        ModelType::Video => "🎬",
-        ModelType::Other => "📦",
+pub fn BatchRequirementsSummary(count: usize, requirements: SystemRequirements) -> Element {
+    let platforms = |list: &Option<Vec<String>>| match list {
+        None => "不限".to_string(),
+        Some(list) if list.is_empty() => "无共同支持".to_string(),
+        Some(list) => list.join(", "),
     };
+    let os = platforms(&requirements.supported_os);
+    let arch = platforms(&requirements.supported_arch);
+
+    rsx! {
+        div { class: "card mb-lg",
+            div { class: "text-subtitle font-semibold", "已选择 {count} 个模型，合计需求" }
+            div { class: "model-details",
+                div {
+                    div { class: "metric-label", "内存" }
+                    div { class: "font-medium",
+                        "{requirements.min_memory_gb:.1} GB（推荐 {requirements.recommended_memory_gb:.1} GB）"
+                    }
+                }
+                div {
+                    div { class: "metric-label", "磁盘空间" }
+                    div { class: "font-medium", "{requirements.min_disk_space_gb:.1} GB" }
+                }
+                div {
+                    div { class: "metric-label", "GPU" }
+                    div { class: "font-medium", if requirements.gpu_required { "需要" } else { "不需要" } }
+                }
+                div {
+                    div { class: "metric-label", "操作系统" }
+                    div { class: "font-medium", "{os}" }
+                }
+                div {
+                    div { class: "metric-label", "架构" }
+                    div { class: "font-medium", "{arch}" }
+                }
+            }
+        }
+    }
+}
 
-    let type_display = match model.model.model_type {
-        ModelType::Chat => "💬对话专用",
-        ModelType::Code => "💻代码生成",
-        ModelType::Text => "📝文本生成",
-        ModelType::Embedding => "🔗嵌入模型",
-        ModelType::Multimodal => "🎭多模态",
-        ModelType::Image => "🖼️图像处理",
-        ModelType::ImageGeneration => "🎨图像生成",
-        ModelType::Audio => "🎵音频处理",
-        ModelType::Speech => "🎤语音模型",
-        ModelType::Video => "🎬视频处理",
-        ModelType::Other => "📦其他类型",
+/// 下载队列中的一行：模型名称、排队状态、进度和预计开始、完成时间
+#[component]
+pub fn DownloadQueueRow(
+    download: QueuedDownload,
+    estimate: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    #[props(default)] locale: Locale,
+) -> Element {
+    let state_text = match download.state {
+        QueueState::Active => t("download.active", locale),
+        QueueState::Waiting => t("download.waiting", locale),
     };
+    // 大小未知时不显示百分比
+    let percent = (download.total_bytes > 0)
+        .then(|| (download.downloaded_bytes.saturating_mul(100) / download.total_bytes).min(100));
+
+    rsx! {
+        div { class: "card download-row flex justify-between items-center",
+            div {
+                div { class: "text-subtitle font-semibold", "{download.model_name}" }
+                div { class: "text-caption text-secondary",
+                    "{state_text}"
+                    if let Some(percent) = percent {
+                        " · {percent}%"
+                    }
+                }
+            }
+            DownloadEta { estimate, locale }
+        }
+    }
+}
+
+/// 可安装模型卡片；传入 `on_select` 时显示批量安装的勾选框
+#[component]
+pub fn AvailableModelCard(
+    model: AvailableModel,
+    offline: bool,
+    #[props(default)] selected: bool,
+    on_select: Option<EventHandler<Uuid>>,
+) -> Element {
+    let type_icon = model_type_icon(&model.model.model_type);
+    let model_id = model.model.id;
+
+    let type_display = model_type_display(&model.model.model_type, Locale::default());
+
+    let mut show_details = use_signal(|| false);
+
+    // 安装前内存预检
+    let host_memory = use_hook(detect_host_memory_bytes);
+    let preflight = PreflightReport::for_model(&model.model, host_memory);
 
     // 检查是否为最新版本（最近7天内更新）
     let is_latest = {
@@ -356,6 +498,13 @@ pub fn AvailableModelCard(model: AvailableModel) -> Element {
         div { class: "card model-card",
             div { class: "model-header",
                 div { class: "model-title",
+                    if let Some(on_select) = on_select {
+                        input {
+                            r#type: "checkbox",
+                            checked: selected,
+                            onchange: move |_| on_select.call(model_id),
+                        }
+                    }
                     span { style: "font-size: 20px;", "{type_icon}" }
                     div {
                         div { class: "text-subtitle font-semibold", "{model.model.display_name}" }
@@ -363,16 +512,26 @@ pub fn AvailableModelCard(model: AvailableModel) -> Element {
                             {model.model.description.as_deref().unwrap_or(&model.model.name)}
                         }
                     }
+                    CapabilityChips { capabilities: model.model.capabilities() }
                     if is_latest {
                         span { class: "text-caption",
                             style: "background: linear-gradient(45deg, #ff6b6b, #feca57); color: white; padding: 2px 6px; border-radius: 4px; margin-left: 8px;",
                             "🔥最新版本"
                         }
                     }
+                    if preflight.exceeds_memory() {
+                        span { class: "text-caption",
+                            title: "{preflight.warnings[0].recommendation}",
+                            style: "background: #F59E0B; color: white; padding: 2px 6px; border-radius: 4px; margin-left: 8px;",
+                            "⚠️内存可能不足"
+                        }
+                    }
                 }
                 div { class: "model-actions",
-                    button { class: "btn btn-primary", "下载" }
-                    button { class: "btn btn-subtle", "详情" }
+                    if !offline {
+                        button { class: "btn btn-primary", "下载" }
+                    }
+                    button { class: "btn btn-subtle", onclick: move |_| show_details.set(true), "详情" }
                 }
             }
             div { class: "model-details",
@@ -403,6 +562,54 @@ pub fn AvailableModelCard(model: AvailableModel) -> Element {
                     div { class: "font-medium", "{model.model.provider}" }
                 }
             }
+            if show_details() {
+                ModelCardModal { model: model.model.clone(), on_close: move |_| show_details.set(false) }
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::download_queue::DownloadPriority;
+    use crate::render_test::render;
+
+    fn queued(state: QueueState, downloaded_bytes: u64) -> QueuedDownload {
+        QueuedDownload {
+            model_id: Uuid::new_v4(),
+            model_name: "qwen2.5-7b".to_string(),
+            priority: DownloadPriority::Normal,
+            state,
+            queued_at: Utc::now(),
+            total_bytes: 1000,
+            downloaded_bytes,
+            speed_bps: 0,
+        }
+    }
+
+    #[test]
+    fn test_download_queue_row_shows_eta() {
+        let rendered = render(|| {
+            let now = Utc::now();
+            let start = now + chrono::Duration::seconds(110);
+            let finish = now + chrono::Duration::seconds(470);
+            rsx! {
+                DownloadQueueRow { download: queued(QueueState::Waiting, 0), estimate: (start, finish), locale: Locale::Zh }
+                DownloadQueueRow { download: queued(QueueState::Active, 250), locale: Locale::Zh }
+            }
+        });
+        assert!(rendered.contains("qwen2.5-7b"));
+        assert!(rendered.contains(t("download.waiting", Locale::Zh)));
+        assert!(rendered.contains(t("download.active", Locale::Zh)));
+        assert!(rendered.contains("约 2 分钟后开始，约 8 分钟后完成"));
+        assert!(rendered.contains("25%"));
+        assert!(rendered.contains(t("download.estimating", Locale::Zh)));
+
+        let english = render(|| rsx! {
+            DownloadQueueRow { download: queued(QueueState::Waiting, 0), locale: Locale::En }
+        });
+        assert!(english.contains(t("download.waiting", Locale::En)));
+        assert!(english.contains(t("download.estimating", Locale::En)));
+    }
+}
@@ -0,0 +1,77 @@
+// 通知输出：界面之外也能收到下载失败、验证失败等提醒
+
+use std::sync::{Arc, Mutex};
+use crate::state::{Notification, NotificationState, NotificationType};
+
+/// 接收服务推送的通知
+///
+/// `emit` 在服务的调用路径上同步执行，耗时的输出（如网络请求）应放到后台进行。
+pub trait NotificationSink: Send + Sync {
+    fn emit(&self, notification: &Notification);
+}
+
+/// 界面使用的内存通知列表
+impl NotificationSink for Mutex<NotificationState> {
+    fn emit(&self, notification: &Notification) {
+        self.lock().unwrap().add_notification(notification.clone());
+    }
+}
+
+/// 把通知输出到标准输出，错误输出到标准错误
+#[derive(Debug, Clone, Default)]
+pub struct LogSink;
+
+impl NotificationSink for LogSink {
+    fn emit(&self, notification: &Notification) {
+        let line = format!("[{}] {}: {}", notification.created_at.to_rfc3339(), notification.title, notification.message);
+        match notification.notification_type {
+            NotificationType::Error => eprintln!("❌ {}", line),
+            NotificationType::Warning => println!("⚠️ {}", line),
+            NotificationType::Success => println!("✅ {}", line),
+            NotificationType::Info => println!("🔔 {}", line),
+        }
+    }
+}
+
+/// 以 JSON 形式 POST 到指定地址
+///
+/// 请求在后台发送，失败只打印日志，不影响触发通知的操作；没有 tokio 运行时时丢弃通知。
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into(), client: reqwest::Client::new() }
+    }
+
+    /// 接收通知的地址
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn emit(&self, notification: &Notification) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            eprintln!("❌ WebhookSink: 没有可用的运行时，丢弃通知 {}", notification.title);
+            return;
+        };
+        let request = self.client.post(&self.url).json(notification);
+        let url = self.url.clone();
+        runtime.spawn(async move {
+            if let Err(e) = request.send().await.and_then(|response| response.error_for_status()) {
+                eprintln!("❌ WebhookSink: 发送到 {} 失败: {}", url, e);
+            }
+        });
+    }
+}
+
+/// 把通知依次交给每个输出
+pub(crate) fn emit_all(sinks: &[Arc<dyn NotificationSink>], notification: &Notification) {
+    for sink in sinks {
+        sink.emit(notification);
+    }
+}
@@ -0,0 +1,135 @@
+// 页面的加载、空数据和错误状态组件
+
+use dioxus::prelude::*;
+use crate::i18n::{t, Locale};
+
+/// 加载中状态
+#[component]
+pub fn LoadingState(message: String, hint: Option<String>) -> Element {
+    rsx! {
+        div { class: "page-content",
+            style: "display: flex; justify-content: center; align-items: center; height: 400px; flex-direction: column;",
+            div { class: "loading-spinner", style: "font-size: 24px; margin-bottom: 16px;", "🔄" }
+            p { style: "color: #666; font-size: 16px;", "{message}" }
+            if let Some(hint) = hint {
+                p { style: "color: #999; font-size: 14px;", "{hint}" }
+            }
+        }
+    }
+}
+
+/// 列表为空时的占位，`message` 为空时只显示标题
+#[component]
+pub fn EmptyState(icon: String, title: String, #[props(default)] message: String) -> Element {
+    rsx! {
+        div { class: "empty-state",
+            style: "text-align: center; padding: 40px; color: #666;",
+            div { style: "font-size: 48px; margin-bottom: 16px;", "{icon}" }
+            h3 { style: "margin-bottom: 8px;", "{title}" }
+            if !message.is_empty() {
+                p { "{message}" }
+            }
+        }
+    }
+}
+
+/// 加载失败状态，点击重试时调用 `on_retry`；`locale` 默认取系统语言
+#[component]
+pub fn ErrorState(message: String, on_retry: EventHandler, #[props(default)] locale: Locale) -> Element {
+    let title = t("state.load_failed", locale);
+    let retry = t("state.retry", locale);
+
+    rsx! {
+        div { class: "page-content",
+            style: "display: flex; justify-content: center; align-items: center; height: 400px; flex-direction: column;",
+            div { class: "error-icon", style: "font-size: 48px; margin-bottom: 16px;", "❌" }
+            h2 { style: "color: #e74c3c; margin-bottom: 8px;", "{title}" }
+            p { style: "color: #666; margin-bottom: 16px;", "{message}" }
+            button {
+                class: "btn btn-primary",
+                onclick: move |_| on_retry.call(()),
+                "{retry}"
+            }
+        }
+    }
+}
+
+/// 不影响使用的警告提示条，设置 `on_dismiss` 时显示关闭按钮
+#[component]
+pub fn WarningBanner(message: String, on_dismiss: Option<EventHandler>) -> Element {
+    rsx! {
+        div { class: "warning-banner",
+            style: "display: flex; align-items: center; gap: 8px; padding: 12px 16px; margin-bottom: 16px; background: #fff8e1; border: 1px solid #f0c36d; border-radius: 8px; color: #8a6d3b;",
+            span { "⚠️" }
+            span { style: "flex: 1;", "{message}" }
+            if let Some(on_dismiss) = on_dismiss {
+                button {
+                    class: "btn btn-secondary",
+                    title: "关闭",
+                    onclick: move |_| on_dismiss.call(()),
+                    "×"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_test::render;
+
+    #[test]
+    fn test_loading_state_renders_message_and_hint() {
+        let rendered = render(|| rsx! {
+            LoadingState { message: "正在加载模型数据...", hint: "首次加载可能需要几秒钟".to_string() }
+        });
+        assert!(rendered.contains("正在加载模型数据..."));
+        assert!(rendered.contains("首次加载可能需要几秒钟"));
+
+        let without_hint = render(|| rsx! { LoadingState { message: "加载中" } });
+        assert!(without_hint.contains("加载中"));
+        assert!(!without_hint.contains("首次加载"));
+    }
+
+    #[test]
+    fn test_empty_state_renders_icon_title_and_message() {
+        let rendered = render(|| rsx! {
+            EmptyState { icon: "📦", title: "暂无已安装模型", message: "从下方的可安装模型列表中选择并安装模型" }
+        });
+        assert!(rendered.contains("📦"));
+        assert!(rendered.contains("暂无已安装模型"));
+        assert!(rendered.contains("从下方的可安装模型列表中选择并安装模型"));
+    }
+
+    #[test]
+    fn test_error_state_renders_message_and_retry() {
+        let rendered = render(|| rsx! {
+            ErrorState { message: "连接数据库失败", on_retry: |_| {}, locale: Locale::Zh }
+        });
+        assert!(rendered.contains("数据加载失败"));
+        assert!(rendered.contains("连接数据库失败"));
+        assert!(rendered.contains("重试"));
+        assert_eq!(rendered.listener_names(), vec!["click"]);
+
+        let english = render(|| rsx! {
+            ErrorState { message: "database is locked", on_retry: |_| {}, locale: Locale::En }
+        });
+        assert!(english.contains("Failed to load data"));
+        assert!(english.contains("Retry"));
+    }
+
+    #[test]
+    fn test_warning_banner_renders_message_and_optional_dismiss() {
+        let rendered = render(|| rsx! {
+            WarningBanner { message: "可用模型加载失败，仅显示已安装模型" }
+        });
+        assert!(rendered.contains("可用模型加载失败，仅显示已安装模型"));
+        assert!(rendered.listener_names().is_empty());
+
+        let dismissible = render(|| rsx! {
+            WarningBanner { message: "网络不可用", on_dismiss: |_| {} }
+        });
+        assert_eq!(dismissible.listener_names(), vec!["click"]);
+    }
+}
@@ -0,0 +1,204 @@
+// 安装前预检模块
+
+use serde::{Deserialize, Serialize};
+use burncloud_service_models::{AvailableModel, Model};
+use crate::data_service::SystemRequirements;
+use crate::validation::{ValidationWarning, WarningType};
+
+/// 运行时内存相对于模型文件大小的额外开销（KV 缓存、运行时缓冲区等），按文件大小的 20% 估算
+const RUNTIME_MEMORY_OVERHEAD_DIVISOR: u64 = 5;
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 模型资源估算扩展
+pub trait ModelResourceExt {
+    /// 估算模型加载运行所需的内存字节数
+    fn estimated_runtime_memory_bytes(&self) -> u64;
+}
+
+impl ModelResourceExt for Model {
+    fn estimated_runtime_memory_bytes(&self) -> u64 {
+        estimate_runtime_memory_bytes(self.file_size)
+    }
+}
+
+/// 根据模型文件大小估算运行时内存
+pub fn estimate_runtime_memory_bytes(file_size: u64) -> u64 {
+    file_size.saturating_add(file_size / RUNTIME_MEMORY_OVERHEAD_DIVISOR)
+}
+
+/// 安装前检查报告
+///
+/// 报告中的警告不会阻止安装，仅用于在用户确认前提示风险。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightReport {
+    pub model_name: String,
+    pub estimated_memory_bytes: u64,
+    pub host_memory_bytes: Option<u64>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl PreflightReport {
+    /// 针对模型和主机内存生成预检报告
+    pub fn for_model(model: &Model, host_memory_bytes: Option<u64>) -> Self {
+        Self::for_memory(&model.name, model.estimated_runtime_memory_bytes(), host_memory_bytes)
+    }
+
+    /// 根据估算内存和主机内存生成预检报告
+    pub fn for_memory(model_name: &str, estimated_memory_bytes: u64, host_memory_bytes: Option<u64>) -> Self {
+        let mut warnings = Vec::new();
+
+        if let Some(host) = host_memory_bytes {
+            if estimated_memory_bytes > host {
+                warnings.push(ValidationWarning {
+                    warning_type: WarningType::ResourceUsage,
+                    message: "model likely exceeds available memory".to_string(),
+                    recommendation: format!(
+                        "模型预计需要 {:.1} GB 内存，主机仅有 {:.1} GB，请选择更小或量化程度更高的版本",
+                        estimated_memory_bytes as f64 / GIB,
+                        host as f64 / GIB,
+                    ),
+                });
+            }
+        }
+
+        Self {
+            model_name: model_name.to_string(),
+            estimated_memory_bytes,
+            host_memory_bytes,
+            warnings,
+        }
+    }
+
+    /// 模型是否可能超出主机内存
+    pub fn exceeds_memory(&self) -> bool {
+        self.warnings.iter().any(|w| matches!(w.warning_type, WarningType::ResourceUsage))
+    }
+}
+
+/// 单个模型的系统需求
+///
+/// 优先读取模型配置中的 `min_memory_gb`、`recommended_memory_gb`、`gpu_required`、`cpu_cores`、
+/// `supported_os`、`supported_arch`；未配置的内存需求按文件大小估算，磁盘需求为文件大小。
+pub fn model_requirements(model: &Model) -> SystemRequirements {
+    let config = &model.config;
+    let number = |key: &str| config.get(key).and_then(|v| v.as_f64()).map(|v| v as f32);
+    let list = |key: &str| {
+        config.get(key).and_then(|v| v.as_array()).map(|values| {
+            values.iter().filter_map(|v| v.as_str()).map(str::to_lowercase).collect::<Vec<_>>()
+        })
+    };
+
+    let min_memory_gb = number("min_memory_gb")
+        .unwrap_or((estimate_runtime_memory_bytes(model.file_size) as f64 / GIB) as f32);
+    SystemRequirements {
+        min_memory_gb,
+        recommended_memory_gb: number("recommended_memory_gb").unwrap_or(min_memory_gb).max(min_memory_gb),
+        min_disk_space_gb: (model.file_size as f64 / GIB) as f32,
+        gpu_required: config.get("gpu_required").and_then(|v| v.as_bool()).unwrap_or(false),
+        cpu_cores: config.get("cpu_cores").and_then(|v| v.as_u64()).unwrap_or(1) as u32,
+        supported_os: list("supported_os"),
+        supported_arch: list("supported_arch"),
+    }
+}
+
+/// 同时运行多个模型所需的系统资源
+///
+/// 内存与 CPU 核数取最大值，磁盘空间相加，任一模型需要 GPU 即需要 GPU，
+/// 支持的系统和架构取交集（交集为空表示这些模型无法在同一平台上运行）。
+pub fn combined_requirements(models: &[&AvailableModel]) -> SystemRequirements {
+    let empty = SystemRequirements {
+        min_memory_gb: 0.0,
+        recommended_memory_gb: 0.0,
+        min_disk_space_gb: 0.0,
+        gpu_required: false,
+        cpu_cores: 0,
+        supported_os: None,
+        supported_arch: None,
+    };
+
+    models.iter()
+        .map(|available| model_requirements(&available.model))
+        .fold(empty, |total, next| SystemRequirements {
+            min_memory_gb: total.min_memory_gb.max(next.min_memory_gb),
+            recommended_memory_gb: total.recommended_memory_gb.max(next.recommended_memory_gb),
+            min_disk_space_gb: total.min_disk_space_gb + next.min_disk_space_gb,
+            gpu_required: total.gpu_required || next.gpu_required,
+            cpu_cores: total.cpu_cores.max(next.cpu_cores),
+            supported_os: intersect(total.supported_os, next.supported_os),
+            supported_arch: intersect(total.supported_arch, next.supported_arch),
+        })
+}
+
+/// 两个支持列表的交集，None 表示不限
+fn intersect(a: Option<Vec<String>>, b: Option<Vec<String>>) -> Option<Vec<String>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.into_iter().filter(|item| b.contains(item)).collect()),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// 检测主机物理内存总量，无法检测时返回 None
+pub fn detect_host_memory_bytes() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        parse_meminfo_total(&meminfo)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// 解析 /proc/meminfo 中的 MemTotal（单位 kB）
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_meminfo_total(meminfo: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find(|line| line.starts_with("MemTotal:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_large_model_on_small_host_warns() {
+        let report = PreflightReport::for_memory("qwen-32b", estimate_runtime_memory_bytes(32 * GB), Some(16 * GB));
+        assert!(report.exceeds_memory());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].message, "model likely exceeds available memory");
+    }
+
+    #[test]
+    fn test_small_model_or_unknown_host_passes() {
+        let small = PreflightReport::for_memory("qwen-1.5b", estimate_runtime_memory_bytes(2 * GB), Some(16 * GB));
+        assert!(!small.exceeds_memory());
+
+        let unknown_host = PreflightReport::for_memory("qwen-32b", estimate_runtime_memory_bytes(32 * GB), None);
+        assert!(!unknown_host.exceeds_memory());
+    }
+
+    #[test]
+    fn test_intersect_treats_none_as_unrestricted() {
+        let linux_mac = Some(vec!["linux".to_string(), "macos".to_string()]);
+        let linux = Some(vec!["linux".to_string()]);
+        assert_eq!(intersect(linux_mac.clone(), None), linux_mac);
+        assert_eq!(intersect(linux_mac, linux.clone()), linux);
+        assert_eq!(intersect(linux, Some(vec!["windows".to_string()])), Some(vec![]));
+        assert_eq!(intersect(None, None), None);
+    }
+
+    #[test]
+    fn test_parse_meminfo_total() {
+        let meminfo = "MemTotal:       16318480 kB\nMemFree:         1234567 kB\n";
+        assert_eq!(parse_meminfo_total(meminfo), Some(16318480 * 1024));
+    }
+}
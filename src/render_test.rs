@@ -0,0 +1,84 @@
+// 组件渲染测试用的工具：收集 VirtualDom 输出的文本和事件监听
+
+use dioxus::dioxus_core::{
+    AttributeValue, ElementId, Template, TemplateAttribute, TemplateNode, WriteMutations,
+};
+use dioxus::prelude::*;
+
+/// 收集渲染产生的文本、属性值和事件监听
+#[derive(Default)]
+pub(crate) struct RenderedText {
+    pub text: Vec<String>,
+    pub listeners: Vec<(&'static str, ElementId)>,
+}
+
+impl RenderedText {
+    fn collect(&mut self, node: &TemplateNode) {
+        match node {
+            TemplateNode::Element { attrs, children, .. } => {
+                for attr in attrs.iter() {
+                    if let TemplateAttribute::Static { value, .. } = attr {
+                        self.text.push(value.to_string());
+                    }
+                }
+                for child in children.iter() {
+                    self.collect(child);
+                }
+            }
+            TemplateNode::Text { text } => self.text.push(text.to_string()),
+            TemplateNode::Dynamic { .. } => {}
+        }
+    }
+
+    pub fn contains(&self, needle: &str) -> bool {
+        self.text.iter().any(|t| t.contains(needle))
+    }
+
+    /// 按注册顺序列出事件名
+    pub fn listener_names(&self) -> Vec<&'static str> {
+        self.listeners.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// 第一个注册了该事件的元素
+    pub fn listener(&self, name: &str) -> Option<ElementId> {
+        self.listeners.iter().find(|(n, _)| *n == name).map(|(_, id)| *id)
+    }
+}
+
+impl WriteMutations for RenderedText {
+    fn append_children(&mut self, _: ElementId, _: usize) {}
+    fn assign_node_id(&mut self, _: &'static [u8], _: ElementId) {}
+    fn create_placeholder(&mut self, _: ElementId) {}
+    fn create_text_node(&mut self, value: &str, _: ElementId) {
+        self.text.push(value.to_string());
+    }
+    fn load_template(&mut self, template: Template, index: usize, _: ElementId) {
+        self.collect(&template.roots[index]);
+    }
+    fn replace_node_with(&mut self, _: ElementId, _: usize) {}
+    fn replace_placeholder_with_nodes(&mut self, _: &'static [u8], _: usize) {}
+    fn insert_nodes_after(&mut self, _: ElementId, _: usize) {}
+    fn insert_nodes_before(&mut self, _: ElementId, _: usize) {}
+    fn set_attribute(&mut self, _: &'static str, _: Option<&'static str>, value: &AttributeValue, _: ElementId) {
+        if let AttributeValue::Text(value) = value {
+            self.text.push(value.clone());
+        }
+    }
+    fn set_node_text(&mut self, value: &str, _: ElementId) {
+        self.text.push(value.to_string());
+    }
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.listeners.push((name, id));
+    }
+    fn remove_event_listener(&mut self, _: &'static str, _: ElementId) {}
+    fn remove_node(&mut self, _: ElementId) {}
+    fn push_root(&mut self, _: ElementId) {}
+}
+
+/// 首次渲染组件
+pub(crate) fn render(app: fn() -> Element) -> RenderedText {
+    let mut dom = VirtualDom::new(app);
+    let mut rendered = RenderedText::default();
+    dom.rebuild(&mut rendered);
+    rendered
+}
@@ -0,0 +1,187 @@
+// Client-owned database schema
+//
+// Tables here live alongside the service-layer schema and are created with
+// `IF NOT EXISTS`, so bootstrapping is safe to run against an existing database.
+//
+// The layout is versioned through `client_meta`. Databases created before the
+// version was recorded are detected from the tables they contain.
+
+use burncloud_database::Database;
+use crate::ClientError;
+
+/// Schema version this build of the client reads and writes
+pub const CURRENT_SCHEMA_VERSION: u32 = 9;
+
+/// Per-model runtime configs saved by the user
+const RUNTIME_CONFIGS_TABLE: &str = "CREATE TABLE IF NOT EXISTS model_runtime_configs (
+    model_id TEXT PRIMARY KEY NOT NULL,
+    config TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+)";
+
+/// Key/value metadata about the client schema itself
+const CLIENT_META_TABLE: &str = "CREATE TABLE IF NOT EXISTS client_meta (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL
+)";
+
+/// Where each model was registered from
+const INSTALL_SOURCES_TABLE: &str = "CREATE TABLE IF NOT EXISTS model_install_sources (
+    model_id TEXT PRIMARY KEY NOT NULL,
+    source TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+)";
+
+/// Audit log of catalog changes, kept after the model itself is deleted
+const MODEL_EVENTS_TABLE: &str = "CREATE TABLE IF NOT EXISTS model_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    model_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    occurred_at TEXT NOT NULL
+)";
+
+const MODEL_EVENTS_INDEX: &str =
+    "CREATE INDEX IF NOT EXISTS idx_model_events_occurred_at ON model_events (occurred_at)";
+
+/// Models pulled in by a discovery sync, flagged stale once the feed stops listing them
+const DISCOVERY_SYNC_TABLE: &str = "CREATE TABLE IF NOT EXISTS discovery_sync (
+    model_id TEXT PRIMARY KEY NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    stale INTEGER NOT NULL DEFAULT 0
+)";
+
+/// Catalog id of each synced model, used to fetch its full discovery details
+const DISCOVERY_SYNC_ID_COLUMN: &str = "ALTER TABLE discovery_sync ADD COLUMN discovery_id TEXT";
+
+/// Append-only record of every mutating operation, with JSON details
+const AUDIT_LOG_TABLE: &str = "CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    model_id TEXT,
+    details TEXT NOT NULL
+)";
+
+const AUDIT_LOG_INDEX: &str =
+    "CREATE INDEX IF NOT EXISTS idx_audit_log_model_id ON audit_log (model_id)";
+
+/// Outcome of the most recent validation of each installed model's files
+const MODEL_VALIDATIONS_TABLE: &str = "CREATE TABLE IF NOT EXISTS model_validations (
+    model_id TEXT PRIMARY KEY NOT NULL,
+    validated_at TEXT NOT NULL,
+    is_valid INTEGER NOT NULL
+)";
+
+/// History rows are written as pending before their operation runs and committed after it
+const AUDIT_LOG_STATE_COLUMN: &str =
+    "ALTER TABLE audit_log ADD COLUMN state TEXT NOT NULL DEFAULT 'committed'";
+
+const MODEL_EVENTS_STATE_COLUMN: &str =
+    "ALTER TABLE model_events ADD COLUMN state TEXT NOT NULL DEFAULT 'committed'";
+
+/// Every client-owned table at the current version
+const CLIENT_TABLES: &[&str] = &[
+    "model_runtime_configs",
+    "client_meta",
+    "model_install_sources",
+    "model_events",
+    "discovery_sync",
+    "audit_log",
+    "model_validations",
+];
+
+/// Forward migrations, each bringing the schema up to its version
+const MIGRATIONS: &[(u32, &[&str])] = &[
+    (1, &[RUNTIME_CONFIGS_TABLE]),
+    (2, &[CLIENT_META_TABLE]),
+    (3, &[INSTALL_SOURCES_TABLE]),
+    (4, &[MODEL_EVENTS_TABLE, MODEL_EVENTS_INDEX]),
+    (5, &[DISCOVERY_SYNC_TABLE]),
+    (6, &[DISCOVERY_SYNC_ID_COLUMN]),
+    (7, &[AUDIT_LOG_TABLE, AUDIT_LOG_INDEX]),
+    (8, &[MODEL_VALIDATIONS_TABLE]),
+    (9, &[AUDIT_LOG_STATE_COLUMN, MODEL_EVENTS_STATE_COLUMN]),
+];
+
+/// Read the schema version of the database
+///
+/// Returns 0 for databases without any client schema, and 1 for databases
+/// created before the version was recorded.
+pub(crate) async fn read_schema_version(database: &Database) -> Result<u32, ClientError> {
+    let tables = existing_tables(database).await?;
+
+    if tables.iter().any(|t| t == "client_meta") {
+        let row: Option<(String,)> = database
+            .fetch_optional("SELECT value FROM client_meta WHERE key = 'schema_version'")
+            .await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+        if let Some((value,)) = row {
+            return value.parse::<u32>()
+                .map_err(|_| ClientError::DatabaseError(format!("Invalid schema version: {}", value)));
+        }
+    }
+
+    if tables.iter().any(|t| t == "model_runtime_configs") {
+        Ok(1)
+    } else {
+        Ok(0)
+    }
+}
+
+/// Fail if the database was written by a newer client
+pub(crate) fn check_supported(version: u32) -> Result<(), ClientError> {
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(ClientError::UnsupportedSchemaVersion {
+            found: version,
+            supported: CURRENT_SCHEMA_VERSION,
+        });
+    }
+    Ok(())
+}
+
+/// Apply every migration newer than `from` and record the resulting version
+pub(crate) async fn migrate_client_schema(database: &Database, from: u32) -> Result<u32, ClientError> {
+    check_supported(from)?;
+
+    for (version, statements) in MIGRATIONS.iter().filter(|(version, _)| *version > from) {
+        for ddl in statements.iter() {
+            database.execute_query(ddl).await
+                .map_err(|e| ClientError::DatabaseError(format!("Migration to version {} failed: {}", version, e)))?;
+        }
+    }
+
+    database.execute_query(&format!(
+        "INSERT INTO client_meta (key, value) VALUES ('schema_version', {}) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        sql_quote(&CURRENT_SCHEMA_VERSION.to_string())
+    )).await
+        .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+    Ok(CURRENT_SCHEMA_VERSION)
+}
+
+/// List client tables that are missing from the database
+pub(crate) async fn missing_client_tables(database: &Database) -> Result<Vec<&'static str>, ClientError> {
+    let tables = existing_tables(database).await?;
+
+    Ok(CLIENT_TABLES
+        .iter()
+        .copied()
+        .filter(|name| !tables.iter().any(|existing| existing == name))
+        .collect())
+}
+
+/// Names of all tables in the database
+async fn existing_tables(database: &Database) -> Result<Vec<String>, ClientError> {
+    let rows: Vec<(String,)> = database
+        .fetch_all("SELECT name FROM sqlite_master WHERE type = 'table'")
+        .await
+        .map_err(|e| ClientError::DatabaseError(e.to_string()))?;
+
+    Ok(rows.into_iter().map(|(name,)| name).collect())
+}
+
+/// Quote a value as an SQL string literal
+pub(crate) fn sql_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
@@ -0,0 +1,110 @@
+// 管理页面通用的搜索栏
+
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// 停止输入多久后才更新搜索词
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 带清除按钮和结果计数的搜索栏
+///
+/// 输入内容先保存在组件内，停止输入 300ms 后才写入 `value`，避免每次按键都重新过滤；
+/// 点击清除按钮立即清空 `value`。
+#[component]
+pub fn SearchBar(
+    value: Signal<String>,
+    #[props(default = "搜索模型...".to_string())] placeholder: String,
+    result_count: usize,
+) -> Element {
+    let mut value = value;
+    let mut draft = use_signal(|| value.peek().clone());
+    // 等待写入 `value` 的任务，新的输入到来时取消
+    let mut pending = use_signal(|| None::<Task>);
+
+    let mut cancel_pending = move || {
+        if let Some(task) = pending.take() {
+            task.cancel();
+        }
+    };
+
+    rsx! {
+        div { class: "search-box flex items-center gap-sm",
+            input {
+                class: "input",
+                style: "max-width: 400px;",
+                placeholder: "{placeholder}",
+                value: "{draft}",
+                oninput: move |evt| {
+                    draft.set(evt.value());
+                    cancel_pending();
+                    let task = spawn(async move {
+                        tokio::time::sleep(SEARCH_DEBOUNCE).await;
+                        value.set(draft.peek().clone());
+                        pending.set(None);
+                    });
+                    pending.set(Some(task));
+                }
+            }
+            if !draft.read().is_empty() {
+                button {
+                    class: "btn btn-secondary search-clear",
+                    title: "清除",
+                    onclick: move |_| {
+                        cancel_pending();
+                        draft.set(String::new());
+                        value.set(String::new());
+                    },
+                    "×"
+                }
+            }
+            span { class: "text-secondary search-result-count", "{result_count} 个结果" }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_test::{render, RenderedText};
+    use dioxus::html::{PlatformEventData, SerializedHtmlEventConverter, SerializedMouseData};
+    use std::any::Any;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_clear_button_empties_search_term() {
+        dioxus::html::set_event_converter(Box::new(SerializedHtmlEventConverter));
+
+        fn app() -> Element {
+            let value = use_signal(|| "llama".to_string());
+            rsx! {
+                SearchBar { value, result_count: 3 }
+                p { "搜索词: [{value}]" }
+            }
+        }
+
+        let mut dom = VirtualDom::new(app);
+        let mut rendered = RenderedText::default();
+        dom.rebuild(&mut rendered);
+        assert!(rendered.contains("3 个结果"));
+        assert!(rendered.contains("搜索词: [llama]"));
+
+        let clear = rendered.listener("click").expect("clear button is shown for a non-empty term");
+        let click: Rc<dyn Any> = Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default()));
+        dom.runtime().handle_event("click", Event::new(click, true), clear);
+
+        let mut updated = RenderedText::default();
+        dom.render_immediate(&mut updated);
+        assert!(updated.contains("搜索词: []"));
+    }
+
+    #[test]
+    fn test_empty_search_term_has_no_clear_button() {
+        let rendered = render(|| {
+            let value = use_signal(String::new);
+            rsx! { SearchBar { value, placeholder: "搜索已安装模型...", result_count: 0 } }
+        });
+        assert!(rendered.contains("搜索已安装模型..."));
+        assert!(rendered.contains("0 个结果"));
+        assert_eq!(rendered.listener_names(), vec!["input"]);
+    }
+}
@@ -1,10 +1,12 @@
 use dioxus::prelude::*;
 use crate::app_state::AppState;
+use crate::page_states::EmptyState;
+use crate::search_bar::SearchBar;
 
 /// 简化版模型管理组件 - 使用 AppState 获取真实数据
 #[component]
 pub fn SimpleModelManagement(app_state: AppState) -> Element {
-    let mut search_term = use_signal(|| String::new());
+    let search_term = use_signal(|| String::new());
 
     // 从 AppState 获取数据
     let (filtered_installed, filtered_available) = if search_term.read().is_empty() {
@@ -41,13 +43,7 @@ pub fn SimpleModelManagement(app_state: AppState) -> Element {
                 }
             }
             div { class: "mt-lg",
-                input {
-                    class: "input",
-                    style: "max-width: 400px;",
-                    placeholder: "搜索模型...",
-                    value: "{search_term}",
-                    oninput: move |evt| search_term.set(evt.value())
-                }
+                SearchBar { value: search_term, result_count: filtered_installed.len() + filtered_available.len() }
             }
         }
 
@@ -58,11 +54,10 @@ pub fn SimpleModelManagement(app_state: AppState) -> Element {
                     "已安装模型 ({filtered_installed.len()})"
                 }
                 if filtered_installed.is_empty() {
-                    div { class: "empty-state",
-                        p { "没有找到已安装的模型" }
-                        if !search_term.read().is_empty() {
-                            p { class: "text-secondary", "尝试调整搜索条件" }
-                        }
+                    EmptyState {
+                        icon: "📦",
+                        title: "没有找到已安装的模型",
+                        message: if search_term.read().is_empty() { String::new() } else { "尝试调整搜索条件".to_string() },
                     }
                 } else {
                     div { class: "grid gap-lg",
@@ -80,17 +75,16 @@ pub fn SimpleModelManagement(app_state: AppState) -> Element {
                     "可安装模型 ({filtered_available.len()})"
                 }
                 if filtered_available.is_empty() {
-                    div { class: "empty-state",
-                        p { "没有找到可安装的模型" }
-                        if !search_term.read().is_empty() {
-                            p { class: "text-secondary", "尝试调整搜索条件" }
-                        }
+                    EmptyState {
+                        icon: "🌐",
+                        title: "没有找到可安装的模型",
+                        message: if search_term.read().is_empty() { String::new() } else { "尝试调整搜索条件".to_string() },
                     }
                 } else {
                     div { class: "grid gap-lg",
                         style: "grid-template-columns: 1fr;",
                         for available_model in filtered_available {
-                            crate::models::AvailableModelCard { model: available_model.clone() }
+                            crate::models::AvailableModelCard { model: available_model.clone(), offline: app_state.service.is_offline() }
                         }
                     }
                 }
@@ -2,8 +2,10 @@ use dioxus::prelude::*;
 use burncloud_service_models::{InstalledModel, ModelStatus, ModelType, AvailableModel};
 use burncloud_database::Database;
 use crate::data_service::{ModelDataService, ModelUsageStats, ResourceOverview};
+use crate::integrated_service::{ClientError, IntegratedModelService, IntegratedServiceConfig};
 use uuid::Uuid;
 use std::sync::Arc;
+use serde::{Deserialize, Serialize};
 
 /// 全局应用状态管理
 #[derive(Clone)]
@@ -17,7 +19,18 @@ pub struct AppState {
 }
 
 impl AppState {
-    pub async fn new(database: Arc<Database>) -> Result<Self, Box<dyn std::error::Error>> {
+    /// 按配置打开数据库并创建状态
+    ///
+    /// 与 `app_state::AppState::initialize` 共用 `IntegratedModelService` 的数据库初始化流程，
+    /// 两种状态对同一配置看到的是同一份数据。
+    pub async fn initialize(config: IntegratedServiceConfig) -> Result<Self, ClientError> {
+        let service = IntegratedModelService::with_config(config).await?;
+        Self::with_database(service.database()).await
+            .map_err(|e| ClientError::DatabaseError(e.to_string()))
+    }
+
+    /// 使用已打开的数据库创建状态
+    pub async fn with_database(database: Arc<Database>) -> Result<Self, Box<dyn std::error::Error>> {
         let data_service = ModelDataService::new(database.clone()).await?;
 
         Ok(Self {
@@ -133,7 +146,7 @@ pub struct ModelActions {
 }
 
 /// 通知系统
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NotificationType {
     Success,
     Error,
@@ -141,7 +154,7 @@ pub enum NotificationType {
     Info,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Notification {
     pub id: Uuid,
     pub title: String,
@@ -5,12 +5,24 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use burncloud_service_models::Model;
+use std::sync::Arc;
 use sha2::{Sha256, Digest};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use crate::checksum::{digest_file_with_progress, strip_checksum_prefix, ChecksumProgress, Checksummer};
+use crate::gguf::read_gguf_metadata;
 
 /// 模型验证器
 pub struct ModelValidator {
     known_signatures: HashMap<String, ModelSignature>,
     temp_dir: PathBuf,
+    checksummer: Arc<dyn Checksummer>,
+    /// 各格式需要放在模型文件同目录下的配套文件
+    companion_files: HashMap<ModelFormat, Vec<String>>,
+    /// 可信的 Ed25519 公钥，用于验证分离签名
+    trusted_keys: Vec<VerifyingKey>,
 }
 
 /// 验证结果
@@ -48,6 +60,7 @@ pub enum CheckType {
     MalwareCheck,
     DigitalSignature,
     VersionCompatibility,
+    CompanionFiles,
 }
 
 /// 检查状态
@@ -124,7 +137,7 @@ pub struct ModelMetadata {
 }
 
 /// 模型格式
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ModelFormat {
     GGUF,
     GGML,
@@ -151,13 +164,90 @@ pub struct ModelSignature {
 }
 
 /// 校验和类型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ChecksumType {
     MD5,
     SHA256,
     SHA512,
 }
 
+impl ChecksumType {
+    /// 该算法摘要的十六进制长度
+    pub fn hex_len(&self) -> usize {
+        match self {
+            ChecksumType::MD5 => 32,
+            ChecksumType::SHA256 => 64,
+            ChecksumType::SHA512 => 128,
+        }
+    }
+
+    /// 根据十六进制串长度推断校验和类型（32→MD5，64→SHA256，128→SHA512）
+    pub fn infer_from(hex: &str) -> Option<Self> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        match hex.len() {
+            32 => Some(ChecksumType::MD5),
+            64 => Some(ChecksumType::SHA256),
+            128 => Some(ChecksumType::SHA512),
+            _ => None,
+        }
+    }
+
+    /// 拆分带算法前缀的校验和，如 `sha256:abc123...`
+    ///
+    /// 没有可识别的前缀时原样返回字符串。
+    pub fn split_prefixed(checksum: &str) -> (Option<Self>, &str) {
+        let checksum = checksum.trim();
+        if let Some((prefix, hex)) = checksum.split_once(':') {
+            let checksum_type = match prefix.to_ascii_lowercase().as_str() {
+                "md5" => Some(ChecksumType::MD5),
+                "sha256" => Some(ChecksumType::SHA256),
+                "sha512" => Some(ChecksumType::SHA512),
+                _ => None,
+            };
+            if checksum_type.is_some() {
+                return (checksum_type, hex);
+            }
+        }
+        (None, checksum)
+    }
+
+    /// 解析期望校验和，返回实际使用的类型和去掉前缀的十六进制串
+    ///
+    /// 优先使用前缀声明的类型，其次根据长度推断，都无法确定时使用调用方给出的 `fallback`。
+    pub fn resolve(expected: &str, fallback: ChecksumType) -> (ChecksumType, String) {
+        let (prefixed, hex) = Self::split_prefixed(expected);
+        let checksum_type = prefixed
+            .or_else(|| Self::infer_from(hex))
+            .unwrap_or(fallback);
+        (checksum_type, hex.to_string())
+    }
+}
+
+/// 模型提供商的信任级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TrustLevel {
+    /// 官方或已认证的提供商，未签名文件不再告警
+    Trusted,
+    /// 按配置本身验证
+    #[default]
+    Standard,
+    /// 来源不明的提供商，自动启用严格模式；加载了已知签名后未签名文件不通过
+    Unknown,
+}
+
+impl TrustLevel {
+    /// 未显式配置时的信任级别：官方/已认证为 `Trusted`，否则为 `Unknown`
+    pub fn from_verified(is_verified: bool) -> Self {
+        if is_verified {
+            TrustLevel::Trusted
+        } else {
+            TrustLevel::Unknown
+        }
+    }
+}
+
 /// 验证配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationConfig {
@@ -166,9 +256,41 @@ pub struct ValidationConfig {
     pub enable_format_validation: bool,
     pub enable_dependency_check: bool,
     pub enable_permission_check: bool,
+    /// 严格模式下高危错误会使验证失败；加载了已知签名时，未签名文件也不通过
     pub strict_mode: bool,
     pub timeout_seconds: u64,
     pub quarantine_suspicious_files: bool,
+    /// 按提供商名称显式配置的信任级别
+    #[serde(default)]
+    pub provider_trust: HashMap<String, TrustLevel>,
+    /// 本次验证的模型来源的信任级别，通常由 `for_provider` 设置
+    #[serde(default)]
+    pub trust_level: TrustLevel,
+    /// 允许的模型格式，设置后其他格式一律判为无效（即使关闭了格式验证）；`None` 表示不限制
+    #[serde(default)]
+    pub allowed_formats: Option<Vec<ModelFormat>>,
+}
+
+impl ValidationConfig {
+    /// 按模型提供商确定本次验证的信任级别
+    ///
+    /// `provider_trust` 中列出的提供商以配置为准，否则由 `is_verified` 推断。
+    pub fn for_provider(mut self, provider: &str, is_verified: bool) -> Self {
+        self.trust_level = self.provider_trust.get(provider)
+            .copied()
+            .unwrap_or_else(|| TrustLevel::from_verified(is_verified));
+        self
+    }
+
+    /// 按模型的提供商和是否官方确定信任级别
+    pub fn for_model(self, model: &Model) -> Self {
+        self.for_provider(&model.provider, model.is_official)
+    }
+
+    /// 是否按严格模式判定，来源不明的提供商总是严格验证
+    pub fn is_strict(&self) -> bool {
+        self.strict_mode || self.trust_level == TrustLevel::Unknown
+    }
 }
 
 /// 验证错误
@@ -188,6 +310,10 @@ pub enum ValidatorError {
     UnsupportedFormat(String),
     #[error("配置错误: {0}")]
     ConfigError(String),
+    #[error("签名无效: {0}")]
+    InvalidSignature(String),
+    #[error("验证已取消")]
+    Cancelled,
 }
 
 impl ModelValidator {
@@ -198,9 +324,76 @@ impl ModelValidator {
         Ok(Self {
             known_signatures: HashMap::new(),
             temp_dir,
+            checksummer: ChecksumType::SHA256.checksummer(),
+            companion_files: default_companion_files(),
+            trusted_keys: Vec::new(),
         })
     }
 
+    /// 添加可信的 Ed25519 公钥
+    pub fn with_trusted_key(mut self, public_key: [u8; 32]) -> Result<Self, ValidatorError> {
+        let key = VerifyingKey::from_bytes(&public_key)
+            .map_err(|e| ValidatorError::ConfigError(format!("无效的公钥: {}", e)))?;
+        self.trusted_keys.push(key);
+        Ok(self)
+    }
+
+    /// 是否配置了可信公钥
+    pub fn has_trusted_keys(&self) -> bool {
+        !self.trusted_keys.is_empty()
+    }
+
+    /// 是否加载了可用于核对模型文件签名的已知签名，没有时任何文件都无法证明已签名
+    ///
+    /// 可信公钥只用于核对清单的分离签名，不参与模型文件的签名检查。
+    fn can_check_signatures(&self) -> bool {
+        !self.known_signatures.is_empty()
+    }
+
+    /// 验证 `message` 的分离签名
+    ///
+    /// `signature_hex` 是 64 字节 Ed25519 签名的十六进制形式，首尾空白会被忽略；
+    /// 任一可信公钥验证通过即可。
+    pub fn verify_detached_signature(&self, message: &[u8], signature_hex: &str) -> Result<(), ValidatorError> {
+        if self.trusted_keys.is_empty() {
+            return Err(ValidatorError::ConfigError("未配置可信公钥".to_string()));
+        }
+
+        let bytes = decode_hex(signature_hex.trim())
+            .ok_or_else(|| ValidatorError::InvalidSignature("签名不是有效的十六进制".to_string()))?;
+        let signature = Signature::from_slice(&bytes)
+            .map_err(|_| ValidatorError::InvalidSignature(format!("签名长度应为 64 字节，实际为 {}", bytes.len())))?;
+
+        if self.trusted_keys.iter().any(|key| key.verify(message, &signature).is_ok()) {
+            Ok(())
+        } else {
+            Err(ValidatorError::InvalidSignature("签名与任何可信公钥都不匹配".to_string()))
+        }
+    }
+
+    /// 设置校验期望校验和时使用的算法（默认 SHA256）
+    pub fn with_checksummer(mut self, checksummer: Arc<dyn Checksummer>) -> Self {
+        self.checksummer = checksummer;
+        self
+    }
+
+    /// 设置某一格式需要的配套文件，传入空列表表示该格式无需配套文件
+    pub fn with_companion_files(mut self, format: ModelFormat, files: Vec<String>) -> Self {
+        self.companion_files.insert(format, files);
+        self
+    }
+
+    /// 使用当前算法校验文件是否与期望校验和一致
+    pub async fn verify_file_checksum(&self, path: &Path, expected: &str) -> ValidationCheck {
+        self.verify_file_checksum_with(path, expected, self.checksummer.as_ref()).await
+    }
+
+    /// 使用指定算法校验文件是否与期望校验和一致，适用于算法由外部（如发现服务）给出的情况
+    pub async fn verify_file_checksum_with(&self, path: &Path, expected: &str, checksummer: &dyn Checksummer) -> ValidationCheck {
+        let expected = strip_checksum_prefix(checksummer, expected);
+        self.checksum_check(path, expected, checksummer, None, &CancellationToken::new()).await
+    }
+
     /// 加载已知模型签名
     pub fn load_signatures(&mut self, signatures_file: &Path) -> Result<(), ValidatorError> {
         if signatures_file.exists() {
@@ -212,11 +405,41 @@ impl ModelValidator {
     }
 
     /// 验证模型文件
+    ///
+    /// 整个验证过程受 `config.timeout_seconds` 限制，超时返回 `TimeoutError`。
     pub async fn validate_model(
         &self,
         model_path: &Path,
         model_id: Option<Uuid>,
         config: ValidationConfig,
+    ) -> Result<ValidationResult, ValidatorError> {
+        self.validate_model_with_progress(model_path, model_id, config, None, &CancellationToken::new()).await
+    }
+
+    /// 验证模型文件，并报告校验和计算进度
+    ///
+    /// 校验和计算期间每读取一块向 `progress` 发送一次进度；`cancel` 触发后返回 `Cancelled`。
+    /// 与 `validate_model` 一样受 `config.timeout_seconds` 限制。
+    pub async fn validate_model_with_progress(
+        &self,
+        model_path: &Path,
+        model_id: Option<Uuid>,
+        config: ValidationConfig,
+        progress: Option<UnboundedSender<ChecksumProgress>>,
+        cancel: &CancellationToken,
+    ) -> Result<ValidationResult, ValidatorError> {
+        let timeout = std::time::Duration::from_secs(config.timeout_seconds);
+        with_timeout(timeout, self.run_validation(model_path, model_id, config, progress, cancel)).await
+    }
+
+    /// 执行各项验证检查
+    async fn run_validation(
+        &self,
+        model_path: &Path,
+        model_id: Option<Uuid>,
+        config: ValidationConfig,
+        progress: Option<UnboundedSender<ChecksumProgress>>,
+        cancel: &CancellationToken,
     ) -> Result<ValidationResult, ValidatorError> {
         let model_id = model_id.unwrap_or_else(|| Uuid::new_v4());
         let start_time = Utc::now();
@@ -266,7 +489,10 @@ impl ModelValidator {
 
         // 3. 校验和验证
         if config.enable_checksum_verification {
-            let checksum_check = self.verify_checksum(model_path, &metadata.checksum_sha256).await;
+            let checksum_check = self.verify_checksum(model_path, &self.expected_checksum(model_path, &metadata), progress, cancel).await;
+            if cancel.is_cancelled() {
+                return Err(ValidatorError::Cancelled);
+            }
             checks.push(checksum_check.clone());
             if checksum_check.status == CheckStatus::Failed {
                 errors.push(ValidationError {
@@ -279,16 +505,45 @@ impl ModelValidator {
         }
 
         // 4. 文件格式验证
-        if config.enable_format_validation {
-            let format_check = self.validate_file_format(model_path, &metadata).await;
+        if config.enable_format_validation || config.allowed_formats.is_some() {
+            let allowed = config.allowed_formats.as_deref();
+            let format_check = self.validate_file_format(model_path, &metadata, allowed).await;
             checks.push(format_check.clone());
-            if format_check.status == CheckStatus::Failed {
+            let disallowed = metadata.model_format.as_ref()
+                .filter(|format| allowed.is_some_and(|allowed| !allowed.contains(format)));
+            if let Some(format) = disallowed {
+                errors.push(ValidationError {
+                    error_type: ErrorType::InvalidFormat,
+                    message: format!("不允许的模型格式: {:?}", format),
+                    severity: ErrorSeverity::Critical,
+                    details: None,
+                });
+            } else if format_check.status == CheckStatus::Failed {
                 errors.push(ValidationError {
                     error_type: ErrorType::InvalidFormat,
                     message: "不支持的文件格式".to_string(),
                     severity: ErrorSeverity::Medium,
                     details: None,
                 });
+            } else if format_check.status == CheckStatus::Warning && metadata.model_format == Some(ModelFormat::GGUF) {
+                warnings.push(ValidationWarning {
+                    warning_type: WarningType::CompatibilityIssue,
+                    message: format_check.message.clone(),
+                    recommendation: "文件可能已损坏或未下载完整，建议重新下载".to_string(),
+                });
+            }
+        }
+
+        // 4.1 配套文件检查
+        if config.enable_format_validation {
+            let companion_check = self.check_companion_files(model_path, &metadata);
+            checks.push(companion_check.clone());
+            if companion_check.status == CheckStatus::Warning {
+                warnings.push(ValidationWarning {
+                    warning_type: WarningType::CompatibilityIssue,
+                    message: companion_check.message.clone(),
+                    recommendation: "请将配套的分词器和配置文件放在模型文件所在目录".to_string(),
+                });
             }
         }
 
@@ -333,16 +588,29 @@ impl ModelValidator {
         }
 
         // 8. 数字签名验证
+        let strict = config.is_strict();
         let signature_check = self.verify_digital_signature(model_path).await;
         checks.push(signature_check.clone());
-        if signature_check.status == CheckStatus::Failed && config.strict_mode {
+        if signature_check.status == CheckStatus::Failed && strict {
             errors.push(ValidationError {
                 error_type: ErrorType::SecurityRisk,
                 message: "数字签名验证失败".to_string(),
                 severity: ErrorSeverity::High,
                 details: None,
             });
-        } else if signature_check.status == CheckStatus::Warning {
+        } else if signature_check.status == CheckStatus::Warning
+            && strict
+            && config.trust_level != TrustLevel::Trusted
+            && self.can_check_signatures()
+        {
+            // 只在确实能核对签名时拒绝未签名文件，否则所有文件都会被拒绝；可信的提供商不检查
+            errors.push(ValidationError {
+                error_type: ErrorType::SecurityRisk,
+                message: "严格模式下不接受未签名的文件".to_string(),
+                severity: ErrorSeverity::High,
+                details: None,
+            });
+        } else if signature_check.status == CheckStatus::Warning && config.trust_level != TrustLevel::Trusted {
             warnings.push(ValidationWarning {
                 warning_type: WarningType::SecurityConcern,
                 message: "文件未签名或签名无法验证".to_string(),
@@ -353,7 +621,7 @@ impl ModelValidator {
         // 判断是否有效
         let has_critical_errors = errors.iter().any(|e| e.severity == ErrorSeverity::Critical);
         let has_high_errors = errors.iter().any(|e| e.severity == ErrorSeverity::High);
-        let is_valid = !has_critical_errors && (!config.strict_mode || !has_high_errors);
+        let is_valid = !has_critical_errors && (!strict || !has_high_errors);
 
         Ok(ValidationResult {
             model_id,
@@ -378,6 +646,7 @@ impl ModelValidator {
             strict_mode: false,
             timeout_seconds: 30,
             quarantine_suspicious_files: false,
+            ..Default::default()
         };
 
         let result = self.validate_model(model_path, None, config).await?;
@@ -432,9 +701,48 @@ impl ModelValidator {
         })
     }
 
+    /// 文件的期望校验和：优先取已知签名中的记录，没有时为提取元数据时算出的 SHA256
+    fn expected_checksum(&self, path: &Path, metadata: &ModelMetadata) -> String {
+        let file_name = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        match self.known_signatures.get(file_name) {
+            Some(signature) => signature.expected_checksum.clone(),
+            None => format!("sha256:{}", metadata.checksum_sha256),
+        }
+    }
+
     /// 验证校验和
-    async fn verify_checksum(&self, path: &Path, expected: &str) -> ValidationCheck {
-        match self.calculate_sha256(path).await {
+    ///
+    /// 期望值带内置算法前缀（如 `sha256:`）时使用该算法，否则使用 `with_checksummer` 设置的算法。
+    async fn verify_checksum(
+        &self,
+        path: &Path,
+        expected: &str,
+        progress: Option<UnboundedSender<ChecksumProgress>>,
+        cancel: &CancellationToken,
+    ) -> ValidationCheck {
+        match ChecksumType::split_prefixed(expected) {
+            (Some(checksum_type), hex) => {
+                self.checksum_check(path, hex, checksum_type.checksummer().as_ref(), progress, cancel).await
+            }
+            (None, _) => {
+                let expected = strip_checksum_prefix(self.checksummer.as_ref(), expected);
+                self.checksum_check(path, expected, self.checksummer.as_ref(), progress, cancel).await
+            }
+        }
+    }
+
+    /// 计算摘要并生成校验和检查项
+    async fn checksum_check(
+        &self,
+        path: &Path,
+        expected: &str,
+        checksummer: &dyn Checksummer,
+        progress: Option<UnboundedSender<ChecksumProgress>>,
+        cancel: &CancellationToken,
+    ) -> ValidationCheck {
+        match digest_file_with_progress(checksummer, path, progress, cancel).await {
             Ok(actual) => {
                 if actual.to_lowercase() == expected.to_lowercase() {
                     ValidationCheck {
@@ -442,6 +750,7 @@ impl ModelValidator {
                         status: CheckStatus::Passed,
                         message: "校验和匹配".to_string(),
                         details: Some(serde_json::json!({
+                            "algorithm": checksummer.name(),
                             "expected": expected,
                             "actual": actual
                         })),
@@ -452,6 +761,7 @@ impl ModelValidator {
                         status: CheckStatus::Failed,
                         message: "校验和不匹配".to_string(),
                         details: Some(serde_json::json!({
+                            "algorithm": checksummer.name(),
                             "expected": expected,
                             "actual": actual
                         })),
@@ -468,8 +778,20 @@ impl ModelValidator {
     }
 
     /// 验证文件格式
-    async fn validate_file_format(&self, _path: &Path, metadata: &ModelMetadata) -> ValidationCheck {
+    ///
+    /// 指定 `allowed` 时，不在列表中的格式判为失败。GGUF 文件头无法解析时只给出警告，
+    /// 文件仍按扩展名或魔术字节识别为 GGUF，可以继续使用。
+    async fn validate_file_format(&self, path: &Path, metadata: &ModelMetadata, allowed: Option<&[ModelFormat]>) -> ValidationCheck {
         match &metadata.model_format {
+            Some(format) if allowed.is_some_and(|allowed| !allowed.contains(format)) => ValidationCheck {
+                check_type: CheckType::FileFormat,
+                status: CheckStatus::Failed,
+                message: format!("不允许的格式: {:?}", format),
+                details: Some(serde_json::json!({
+                    "format": format,
+                    "allowed": allowed,
+                })),
+            },
             Some(format) => match format {
                 ModelFormat::Unknown(_) => ValidationCheck {
                     check_type: CheckType::FileFormat,
@@ -477,6 +799,27 @@ impl ModelValidator {
                     message: "未知文件格式".to_string(),
                     details: None,
                 },
+                ModelFormat::GGUF => match read_gguf_metadata(path).await {
+                    Ok(gguf) => ValidationCheck {
+                        check_type: CheckType::FileFormat,
+                        status: CheckStatus::Passed,
+                        message: format!("支持的格式: {:?}", format),
+                        details: Some(serde_json::json!({
+                            "gguf_version": gguf.version,
+                            "tensor_count": gguf.tensor_count,
+                            "architecture": gguf.architecture(),
+                        })),
+                    },
+                    Err(e) => ValidationCheck {
+                        check_type: CheckType::FileFormat,
+                        status: CheckStatus::Warning,
+                        message: "GGUF 文件头无法读取".to_string(),
+                        details: Some(serde_json::json!({
+                            "format": format,
+                            "parse_error": e.to_string(),
+                        })),
+                    },
+                },
                 _ => ValidationCheck {
                     check_type: CheckType::FileFormat,
                     status: CheckStatus::Passed,
@@ -562,6 +905,35 @@ impl ModelValidator {
         }
     }
 
+    /// 检查模型文件同目录下是否有该格式所需的配套文件
+    fn check_companion_files(&self, path: &Path, metadata: &ModelMetadata) -> ValidationCheck {
+        let expected = metadata.model_format.as_ref()
+            .and_then(|format| self.companion_files.get(format))
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let missing: Vec<&String> = expected.iter()
+            .filter(|name| !dir.join(name).is_file())
+            .collect();
+
+        if missing.is_empty() {
+            ValidationCheck {
+                check_type: CheckType::CompanionFiles,
+                status: CheckStatus::Passed,
+                message: "配套文件齐全".to_string(),
+                details: None,
+            }
+        } else {
+            let names: Vec<&str> = missing.iter().map(|name| name.as_str()).collect();
+            ValidationCheck {
+                check_type: CheckType::CompanionFiles,
+                status: CheckStatus::Warning,
+                message: format!("缺少配套文件: {}", names.join(", ")),
+                details: Some(serde_json::json!({ "missing": names })),
+            }
+        }
+    }
+
     /// 验证数字签名
     async fn verify_digital_signature(&self, path: &Path) -> ValidationCheck {
         // 简化实现：检查是否有已知签名
@@ -586,14 +958,6 @@ impl ModelValidator {
         }
     }
 
-    /// 计算SHA256校验和
-    async fn calculate_sha256(&self, path: &Path) -> Result<String, ValidatorError> {
-        let content = tokio::fs::read(path).await?;
-        let mut hasher = Sha256::new();
-        hasher.update(&content);
-        Ok(format!("{:x}", hasher.finalize()))
-    }
-
     /// 检测文件类型
     fn detect_file_type(&self, path: &Path) -> Result<String, ValidatorError> {
         let extension = path.extension()
@@ -629,6 +993,40 @@ impl ModelValidator {
     }
 }
 
+/// 默认的配套文件：SafeTensors 与 PyTorch 权重需要分词器和模型配置，GGUF 等格式自包含
+fn default_companion_files() -> HashMap<ModelFormat, Vec<String>> {
+    let files = vec!["config.json".to_string(), "tokenizer.json".to_string()];
+    HashMap::from([
+        (ModelFormat::SafeTensors, files.clone()),
+        (ModelFormat::PyTorch, files),
+    ])
+}
+
+/// 解码十六进制字符串，格式不对时返回 `None`
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// 限时执行验证，超时或底层 I/O 超时都返回 `TimeoutError`
+async fn with_timeout<T>(
+    timeout: std::time::Duration,
+    future: impl std::future::Future<Output = Result<T, ValidatorError>>,
+) -> Result<T, ValidatorError> {
+    match tokio::time::timeout(timeout, future).await {
+        Ok(Err(ValidatorError::IoError(e))) if e.kind() == std::io::ErrorKind::TimedOut => {
+            Err(ValidatorError::TimeoutError)
+        }
+        Ok(result) => result,
+        Err(_) => Err(ValidatorError::TimeoutError),
+    }
+}
+
 impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
@@ -640,6 +1038,231 @@ impl Default for ValidationConfig {
             strict_mode: false,
             timeout_seconds: 120,
             quarantine_suspicious_files: false,
+            provider_trust: HashMap::new(),
+            trust_level: TrustLevel::Standard,
+            allowed_formats: None,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_checksum_type_from_length() {
+        assert_eq!(ChecksumType::infer_from(&"a".repeat(32)), Some(ChecksumType::MD5));
+        assert_eq!(ChecksumType::infer_from(&"b".repeat(64)), Some(ChecksumType::SHA256));
+        assert_eq!(ChecksumType::infer_from(&"c".repeat(128)), Some(ChecksumType::SHA512));
+        assert_eq!(ChecksumType::infer_from(&"d".repeat(40)), None);
+        assert_eq!(ChecksumType::infer_from(&"z".repeat(64)), None);
+    }
+
+    #[test]
+    fn test_resolve_prefixed_checksum() {
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        let (checksum_type, stripped) = ChecksumType::resolve(&format!("sha256:{}", hex), ChecksumType::MD5);
+        assert_eq!(checksum_type, ChecksumType::SHA256);
+        assert_eq!(stripped, hex);
+
+        let (checksum_type, stripped) = ChecksumType::resolve(hex, ChecksumType::MD5);
+        assert_eq!(checksum_type, ChecksumType::SHA256);
+        assert_eq!(stripped, hex);
+
+        let (checksum_type, stripped) = ChecksumType::resolve("", ChecksumType::SHA512);
+        assert_eq!(checksum_type, ChecksumType::SHA512);
+        assert_eq!(stripped, "");
+    }
+
+    #[test]
+    fn test_verify_detached_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let validator = ModelValidator::new(temp.path().to_path_buf()).unwrap()
+            .with_trusted_key(signing_key.verifying_key().to_bytes()).unwrap();
+
+        let signature: String = signing_key.sign(b"manifest").to_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        assert!(validator.verify_detached_signature(b"manifest", &format!("{}\n", signature)).is_ok());
+        assert!(matches!(
+            validator.verify_detached_signature(b"manifest!", &signature),
+            Err(ValidatorError::InvalidSignature(_))
+        ));
+        assert!(matches!(
+            validator.verify_detached_signature(b"manifest", "not hex"),
+            Err(ValidatorError::InvalidSignature(_))
+        ));
+
+        let untrusted = ModelValidator::new(temp.path().to_path_buf()).unwrap();
+        assert!(matches!(
+            untrusted.verify_detached_signature(b"manifest", &signature),
+            Err(ValidatorError::ConfigError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validation_timeout_returns_timeout_error() {
+        let elapsed = with_timeout(
+            std::time::Duration::from_millis(10),
+            std::future::pending::<Result<(), ValidatorError>>(),
+        ).await;
+        assert!(matches!(elapsed, Err(ValidatorError::TimeoutError)));
+
+        let io_timeout = with_timeout(std::time::Duration::from_secs(1), async {
+            Err::<(), _>(ValidatorError::IoError(std::io::Error::from(std::io::ErrorKind::TimedOut)))
+        }).await;
+        assert!(matches!(io_timeout, Err(ValidatorError::TimeoutError)));
+
+        let rejected = with_timeout(std::time::Duration::from_secs(1), async {
+            Err::<(), _>(ValidatorError::FileNotFound("model.gguf".to_string()))
+        }).await;
+        assert!(matches!(rejected, Err(ValidatorError::FileNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_provider_trust_controls_unsigned_file_strictness() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let model_path = temp.path().join("model.gguf");
+        std::fs::write(&model_path, b"GGUF unsigned weights").unwrap();
+        let validator = ModelValidator::new(temp.path().join("tmp")).unwrap();
+
+        let mut config = ValidationConfig::default();
+        config.provider_trust.insert("Meta".to_string(), TrustLevel::Trusted);
+
+        // 配置中可信的提供商：未签名也通过，且不告警
+        let trusted = validator.validate_model(&model_path, None, config.clone().for_provider("Meta", false)).await.unwrap();
+        assert!(trusted.is_valid);
+        assert!(!trusted.warnings.iter().any(|w| w.message.contains("未签名")));
+
+        // 没有签名库时无法核对签名，来源不明的提供商只告警
+        let unknown = validator.validate_model(&model_path, None, config.clone().for_provider("random-uploader", false)).await.unwrap();
+        assert!(unknown.is_valid);
+        assert!(unknown.warnings.iter().any(|w| w.message.contains("未签名")));
+
+        // 可信公钥只用于清单，不能据此核对模型文件，也不据此拒绝
+        let keyed = ModelValidator::new(temp.path().join("tmp")).unwrap()
+            .with_trusted_key(ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]).verifying_key().to_bytes())
+            .unwrap();
+        let unknown = keyed.validate_model(&model_path, None, config.clone().for_provider("random-uploader", false)).await.unwrap();
+        assert!(unknown.is_valid);
+
+        // 没有签名库时显式的严格模式也不拒绝未签名文件
+        let strict = ValidationConfig { strict_mode: true, ..config.clone() };
+        assert!(validator.validate_model(&model_path, None, strict.clone()).await.unwrap().is_valid);
+
+        // 加载已知签名后，来源不明的提供商的未签名文件不通过，已签名的文件通过
+        let signed_path = temp.path().join("signed.gguf");
+        let signed_bytes = b"GGUF signed weights";
+        std::fs::write(&signed_path, signed_bytes).unwrap();
+        let signatures_path = temp.path().join("signatures.json");
+        std::fs::write(&signatures_path, serde_json::json!({
+            "signed.gguf": {
+                "model_name": "signed",
+                "version": "1.0",
+                "provider": "registry",
+                "expected_size": signed_bytes.len(),
+                "expected_checksum": format!("{:x}", sha2::Sha256::digest(signed_bytes)),
+                "checksum_type": "SHA256",
+                "format": "GGUF",
+                "trusted": true,
+                "signature_date": "2024-01-01T00:00:00Z",
+            }
+        }).to_string()).unwrap();
+        let mut signed = ModelValidator::new(temp.path().join("tmp")).unwrap();
+        signed.load_signatures(&signatures_path).unwrap();
+
+        let unknown = signed.validate_model(&model_path, None, config.clone().for_provider("random-uploader", false)).await.unwrap();
+        assert!(!unknown.is_valid);
+        assert!(unknown.errors.iter().any(|e| matches!(e.error_type, ErrorType::SecurityRisk)));
+        let signed_file = signed.validate_model(&signed_path, None, config.clone().for_provider("random-uploader", false)).await.unwrap();
+        assert!(signed_file.is_valid, "{:?}", signed_file.errors);
+
+        // 严格模式同样只拒绝未签名文件，可信的提供商不受影响
+        assert!(!signed.validate_model(&model_path, None, strict.clone()).await.unwrap().is_valid);
+        assert!(signed.validate_model(&model_path, None, strict.for_provider("Meta", false)).await.unwrap().is_valid);
+
+        // 已认证的提供商默认可信
+        assert_eq!(config.for_provider("Alibaba", true).trust_level, TrustLevel::Trusted);
+    }
+
+    #[tokio::test]
+    async fn test_allowed_formats_rejects_other_formats() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let gguf_path = temp.path().join("model.gguf");
+        std::fs::write(&gguf_path, b"GGUF weights").unwrap();
+        let pytorch_path = temp.path().join("model.pt");
+        std::fs::write(&pytorch_path, b"pickled weights").unwrap();
+        std::fs::write(temp.path().join("config.json"), b"{}").unwrap();
+        std::fs::write(temp.path().join("tokenizer.json"), b"{}").unwrap();
+
+        let validator = ModelValidator::new(temp.path().join("tmp")).unwrap();
+        let config = ValidationConfig {
+            allowed_formats: Some(vec![ModelFormat::GGUF]),
+            ..Default::default()
+        };
+
+        let gguf = validator.validate_model(&gguf_path, None, config.clone()).await.unwrap();
+        assert!(gguf.is_valid);
+
+        let pytorch = validator.validate_model(&pytorch_path, None, config).await.unwrap();
+        assert!(!pytorch.is_valid);
+        let error = pytorch.errors.iter().find(|e| matches!(e.error_type, ErrorType::InvalidFormat)).unwrap();
+        assert!(error.message.contains("PyTorch"), "{}", error.message);
+
+        // 不限制格式时 PyTorch 文件照常通过
+        let unrestricted = validator.validate_model(&pytorch_path, None, ValidationConfig::default()).await.unwrap();
+        assert!(unrestricted.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_truncated_gguf_header_only_warns() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let model_path = temp.path().join("model.gguf");
+        // 魔术字节和版本完整，张量数量只写了一半
+        let mut header = b"GGUF".to_vec();
+        header.extend_from_slice(&3u32.to_le_bytes());
+        header.extend_from_slice(&[7, 0, 0, 0]);
+        std::fs::write(&model_path, &header).unwrap();
+
+        let validator = ModelValidator::new(temp.path().join("tmp")).unwrap();
+        let result = validator.validate_model(&model_path, None, ValidationConfig::default()).await.unwrap();
+
+        assert!(result.is_valid);
+        assert!(result.errors.iter().all(|e| !matches!(e.error_type, ErrorType::InvalidFormat)));
+        let check = result.checks_performed.iter().find(|c| matches!(c.check_type, CheckType::FileFormat)).unwrap();
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert_eq!(check.message, "GGUF 文件头无法读取");
+        let details = check.details.as_ref().unwrap();
+        assert!(details["parse_error"].as_str().unwrap().contains("tensor_count"));
+        assert!(result.warnings.iter().any(|w| w.message == "GGUF 文件头无法读取"));
+        assert_eq!(result.metadata.model_format, Some(ModelFormat::GGUF));
+    }
+
+    #[tokio::test]
+    async fn test_safetensors_without_tokenizer_warns() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let model_path = temp.path().join("model.safetensors");
+        std::fs::write(&model_path, b"weights").unwrap();
+        std::fs::write(temp.path().join("config.json"), b"{}").unwrap();
+
+        let validator = ModelValidator::new(temp.path().join("tmp")).unwrap();
+        let result = validator.validate_model(&model_path, None, ValidationConfig::default()).await.unwrap();
+
+        let check = result.checks_performed.iter()
+            .find(|c| matches!(c.check_type, CheckType::CompanionFiles))
+            .unwrap();
+        assert_eq!(check.status, CheckStatus::Warning);
+        assert_eq!(check.details, Some(serde_json::json!({ "missing": ["tokenizer.json"] })));
+        assert!(result.warnings.iter().any(|w| w.message.contains("tokenizer.json")));
+
+        // 配置为无需配套文件后不再告警
+        let relaxed = ModelValidator::new(temp.path().join("tmp")).unwrap()
+            .with_companion_files(ModelFormat::SafeTensors, Vec::new());
+        let result = relaxed.validate_model(&model_path, None, ValidationConfig::default()).await.unwrap();
+        let check = result.checks_performed.iter()
+            .find(|c| matches!(c.check_type, CheckType::CompanionFiles))
+            .unwrap();
+        assert_eq!(check.status, CheckStatus::Passed);
+    }
+}
@@ -0,0 +1,293 @@
+//! Pluggable checksum algorithm tests
+//!
+//! Registers a toy checksummer to make sure custom algorithms flow through
+//! both the validator and the download pipeline.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use burncloud_client_models::{
+    digest_file, CheckStatus, CheckType, ChecksumProgress, ChecksumType, Checksummer, DownloadError,
+    ModelDownloadManager, ModelValidator, StreamingHasher, ValidationConfig, ValidationResult, ValidatorError,
+};
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const MODEL_BYTES: &[u8] = b"fake gguf model weights";
+
+/// Sums all bytes; stands in for BLAKE3/xxHash in tests
+struct ByteSumChecksummer;
+
+#[async_trait]
+impl Checksummer for ByteSumChecksummer {
+    fn name(&self) -> &str {
+        "bytesum"
+    }
+
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content).await?;
+        let sum: u64 = content.iter().map(|b| *b as u64).sum();
+        Ok(format!("{:016x}", sum))
+    }
+}
+
+fn expected_bytesum() -> String {
+    format!("{:016x}", MODEL_BYTES.iter().map(|b| *b as u64).sum::<u64>())
+}
+
+#[tokio::test]
+async fn test_builtin_checksummers_match_known_digests() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("empty.bin");
+    std::fs::write(&file, b"").unwrap();
+
+    let md5 = digest_file(ChecksumType::MD5.checksummer().as_ref(), &file).await.unwrap();
+    assert_eq!(md5, "d41d8cd98f00b204e9800998ecf8427e");
+    let sha256 = digest_file(ChecksumType::SHA256.checksummer().as_ref(), &file).await.unwrap();
+    assert_eq!(sha256, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+}
+
+#[tokio::test]
+async fn test_validator_with_custom_checksummer() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("model.gguf");
+    std::fs::write(&file, MODEL_BYTES).unwrap();
+
+    let validator = ModelValidator::new(temp.path().join("validation"))
+        .unwrap()
+        .with_checksummer(Arc::new(ByteSumChecksummer));
+
+    let passed = validator.verify_file_checksum(&file, &format!("bytesum:{}", expected_bytesum())).await;
+    assert_eq!(passed.status, CheckStatus::Passed);
+
+    let failed = validator.verify_file_checksum(&file, "bytesum:0000000000000000").await;
+    assert_eq!(failed.status, CheckStatus::Failed);
+}
+
+/// Signature file listing `model.gguf` with the given expected checksum
+fn write_signatures(path: &std::path::Path, expected_checksum: &str) {
+    let signatures = serde_json::json!({
+        "model.gguf": {
+            "model_name": "model",
+            "version": "1.0",
+            "provider": "registry",
+            "expected_size": MODEL_BYTES.len(),
+            "expected_checksum": expected_checksum,
+            "checksum_type": "SHA256",
+            "format": "GGUF",
+            "trusted": true,
+            "signature_date": "2024-01-01T00:00:00Z",
+        }
+    });
+    std::fs::write(path, signatures.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_model_with_custom_checksummer() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("model.gguf");
+    std::fs::write(&file, MODEL_BYTES).unwrap();
+    let signatures = temp.path().join("signatures.json");
+    let checksum_check = |result: &ValidationResult| {
+        result.checks_performed.iter().find(|check| matches!(check.check_type, CheckType::Checksum)).unwrap().clone()
+    };
+
+    // A registry checksum without a built-in prefix is checked with the registered algorithm
+    write_signatures(&signatures, &expected_bytesum());
+    let mut validator = ModelValidator::new(temp.path().join("validation"))
+        .unwrap()
+        .with_checksummer(Arc::new(ByteSumChecksummer));
+    validator.load_signatures(&signatures).unwrap();
+    let result = validator.validate_model(&file, None, ValidationConfig::default()).await.unwrap();
+    let check = checksum_check(&result);
+    assert_eq!(check.status, CheckStatus::Passed);
+    assert_eq!(check.details.unwrap()["algorithm"], "bytesum");
+
+    write_signatures(&signatures, "bytesum:0000000000000000");
+    validator.load_signatures(&signatures).unwrap();
+    let result = validator.validate_model(&file, None, ValidationConfig::default()).await.unwrap();
+    assert_eq!(checksum_check(&result).status, CheckStatus::Failed);
+
+    // A built-in prefix wins over the registered algorithm
+    write_signatures(&signatures, &format!("sha256:{:x}", Sha256::digest(MODEL_BYTES)));
+    validator.load_signatures(&signatures).unwrap();
+    let result = validator.validate_model(&file, None, ValidationConfig::default()).await.unwrap();
+    let check = checksum_check(&result);
+    assert_eq!(check.status, CheckStatus::Passed);
+    assert_eq!(check.details.unwrap()["algorithm"], "sha256");
+}
+
+#[tokio::test]
+async fn test_download_with_custom_checksummer() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let url = format!("{}/model.gguf", server.uri());
+
+    let result = manager
+        .download_model_with_checksummer(
+            Uuid::new_v4(),
+            "custom.gguf".to_string(),
+            url.clone(),
+            expected_bytesum(),
+            Arc::new(ByteSumChecksummer),
+        )
+        .await;
+    assert!(result.is_ok());
+
+    let mismatch = manager
+        .download_model_with_checksummer(
+            Uuid::new_v4(),
+            "custom-bad.gguf".to_string(),
+            url,
+            "bytesum:0000000000000000".to_string(),
+            Arc::new(ByteSumChecksummer),
+        )
+        .await;
+    assert!(matches!(mismatch, Err(DownloadError::ChecksumMismatch { .. })));
+}
+
+#[tokio::test]
+async fn test_validation_reports_checksum_progress() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("model.gguf");
+    // Several read buffers' worth, with a partial final chunk
+    let content = vec![7u8; 3 * 64 * 1024 + 123];
+    std::fs::write(&file, &content).unwrap();
+
+    let validator = ModelValidator::new(temp.path().join("validation")).unwrap();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let result = validator
+        .validate_model_with_progress(&file, None, ValidationConfig::default(), Some(tx), &CancellationToken::new())
+        .await
+        .unwrap();
+
+    let checksum = result.checks_performed.iter().find(|c| matches!(c.check_type, CheckType::Checksum)).unwrap();
+    assert_eq!(checksum.status, CheckStatus::Passed);
+
+    let mut updates: Vec<ChecksumProgress> = Vec::new();
+    while let Ok(update) = rx.try_recv() {
+        updates.push(update);
+    }
+    assert!(updates.len() >= 4, "expected one update per chunk, got {:?}", updates);
+    assert!(updates.windows(2).all(|w| w[0].hashed < w[1].hashed));
+    assert!(updates.iter().all(|u| u.total == content.len() as u64));
+    assert_eq!(updates.last().unwrap().hashed, content.len() as u64);
+}
+
+#[tokio::test]
+async fn test_cancelled_validation_stops_checksum() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("model.gguf");
+    std::fs::write(&file, MODEL_BYTES).unwrap();
+
+    let validator = ModelValidator::new(temp.path().join("validation")).unwrap();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    let result = validator
+        .validate_model_with_progress(&file, None, ValidationConfig::default(), Some(tx), &cancel)
+        .await;
+    assert!(matches!(result, Err(ValidatorError::Cancelled)));
+    assert!(rx.try_recv().is_err());
+}
+
+/// Byte sum that can also be fed chunk by chunk, counting full-file reads
+#[derive(Default)]
+struct StreamingByteSumChecksummer {
+    file_reads: AtomicUsize,
+}
+
+struct ByteSumHasher(u64);
+
+impl StreamingHasher for ByteSumHasher {
+    fn update(&mut self, chunk: &[u8]) {
+        self.0 += chunk.iter().map(|b| *b as u64).sum::<u64>();
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+#[async_trait]
+impl Checksummer for StreamingByteSumChecksummer {
+    fn name(&self) -> &str {
+        "bytesum"
+    }
+
+    async fn digest(&self, reader: &mut (dyn AsyncRead + Unpin + Send)) -> std::io::Result<String> {
+        self.file_reads.fetch_add(1, Ordering::SeqCst);
+        ByteSumChecksummer.digest(reader).await
+    }
+
+    fn streaming(&self) -> Option<Box<dyn StreamingHasher>> {
+        Some(Box::new(ByteSumHasher(0)))
+    }
+}
+
+#[tokio::test]
+async fn test_builtin_streaming_hashers_match_file_digests() {
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("model.gguf");
+    std::fs::write(&file, MODEL_BYTES).unwrap();
+
+    for checksum_type in [ChecksumType::MD5, ChecksumType::SHA256, ChecksumType::SHA512] {
+        let checksummer = checksum_type.checksummer();
+        let mut hasher = checksummer.streaming().expect("built-in algorithms stream");
+        for chunk in MODEL_BYTES.chunks(5) {
+            hasher.update(chunk);
+        }
+        let expected = digest_file(checksummer.as_ref(), &file).await.unwrap();
+        assert_eq!(hasher.finalize(), expected, "{:?}", checksum_type);
+    }
+    assert!(ByteSumChecksummer.streaming().is_none());
+}
+
+#[tokio::test]
+async fn test_download_hashes_while_streaming_without_rereading() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let url = format!("{}/model.gguf", server.uri());
+    let checksummer = Arc::new(StreamingByteSumChecksummer::default());
+
+    let progress = manager
+        .download_model_with_checksummer(Uuid::new_v4(), "streamed.gguf".to_string(), url.clone(), expected_bytesum(), checksummer.clone())
+        .await
+        .unwrap();
+    assert_eq!(progress.actual_checksum, Some(expected_bytesum()));
+
+    let mismatch = manager
+        .download_model_with_checksummer(
+            Uuid::new_v4(),
+            "streamed-bad.gguf".to_string(),
+            url,
+            "bytesum:0000000000000000".to_string(),
+            checksummer.clone(),
+        )
+        .await;
+    assert!(matches!(mismatch, Err(DownloadError::ChecksumMismatch { .. })));
+
+    // Neither outcome needed a second pass over the downloaded file
+    assert_eq!(checksummer.file_reads.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,372 @@
+//! Discovery client tests against a mock discovery server
+//!
+//! These tests exercise ModelDiscoveryClient's HTTP behaviour (pagination,
+//! filtering, error mapping) without touching the real BurnCloud API.
+
+use burncloud_client_models::{
+    ChecksumType, DiscoveredModel, DiscoveryError, DiscoveryTimeouts, ModelDiscoveryClient, ModelRequirements,
+    ModelSearchRequest, ModelSearchResponse,
+};
+use burncloud_client_models::discovery::ModelType;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use wiremock::matchers::{body_partial_json, method, path, path_regex};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Build a discovered model with sensible defaults
+fn discovered_model(name: &str, provider: &str) -> DiscoveredModel {
+    DiscoveredModel {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+        display_name: name.to_string(),
+        description: format!("Mock model {}", name),
+        size_gb: 4.0,
+        model_type: ModelType::ChatCompletion,
+        provider: provider.to_string(),
+        tags: vec![],
+        capabilities: vec![],
+        requirements: ModelRequirements {
+            min_ram_gb: 8.0,
+            min_vram_gb: None,
+            gpu_required: false,
+            cpu_cores: 4,
+            disk_space_gb: 4.0,
+            supported_platforms: vec!["linux".to_string()],
+            cuda_version: None,
+            python_version: None,
+        },
+        download_url: format!("https://mock.example.com/{}.gguf", name),
+        checksum: String::new(),
+        checksum_type: ChecksumType::SHA256,
+        license: "Apache 2.0".to_string(),
+        rating: 4.5,
+        download_count: 100,
+        last_updated: Utc::now(),
+        is_featured: false,
+        is_verified: true,
+        repository_url: None,
+        documentation_url: None,
+        depends_on: vec![],
+    }
+}
+
+/// Wrap models into a search response page
+fn search_page(models: Vec<DiscoveredModel>, page: u32, has_next: bool) -> ModelSearchResponse {
+    ModelSearchResponse {
+        total_count: models.len() as u64,
+        page,
+        page_size: 2,
+        has_next,
+        search_time_ms: 1,
+        models,
+    }
+}
+
+#[tokio::test]
+async fn test_search_by_provider_walks_all_pages() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .and(body_partial_json(json!({ "provider": "Mistral AI", "page": 1 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![
+                discovered_model("mistral-7b", "Mistral AI"),
+                discovered_model("mixtral-8x7b", "Mistral AI"),
+            ],
+            1,
+            true,
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .and(body_partial_json(json!({ "provider": "Mistral AI", "page": 2 })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![discovered_model("codestral-22b", "Mistral AI")],
+            2,
+            false,
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .and(body_partial_json(json!({ "provider": "Meta" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![discovered_model("llama-3-8b", "Meta")],
+            1,
+            false,
+        )))
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let models = client.search_by_provider("Mistral AI", Some(2)).await.unwrap();
+
+    assert_eq!(models.len(), 3);
+    assert!(models.iter().all(|m| m.provider == "Mistral AI"));
+
+    let meta_models = client.search_by_provider("Meta", Some(2)).await.unwrap();
+    assert_eq!(meta_models.len(), 1);
+    assert_eq!(meta_models[0].name, "llama-3-8b");
+}
+
+/// Mount a search endpoint that ignores filters and returns a fixed catalog
+async fn mount_capability_catalog(server: &MockServer) {
+    let mut vision_tools = discovered_model("vision-tools", "Acme");
+    vision_tools.capabilities = vec!["vision".to_string(), "tools".to_string()];
+    let mut vision_only = discovered_model("vision-only", "Acme");
+    vision_only.capabilities = vec!["Vision".to_string()];
+    let mut tools_only = discovered_model("tools-only", "Acme");
+    tools_only.capabilities = vec!["tools".to_string()];
+    let plain = discovered_model("plain", "Acme");
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![vision_tools, vision_only, tools_only, plain],
+            1,
+            false,
+        )))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_search_by_capabilities_match_all() {
+    let server = MockServer::start().await;
+    mount_capability_catalog(&server).await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let caps = vec!["vision".to_string(), "tools".to_string()];
+    let models = client.search_by_capabilities(&caps, true).await.unwrap();
+
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["vision-tools"]);
+}
+
+#[tokio::test]
+async fn test_search_by_capabilities_match_any() {
+    let server = MockServer::start().await;
+    mount_capability_catalog(&server).await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let caps = vec!["vision".to_string(), "tools".to_string()];
+    let models = client.search_by_capabilities(&caps, false).await.unwrap();
+
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["vision-tools", "vision-only", "tools-only"]);
+}
+
+#[tokio::test]
+async fn test_get_recent_models_sorted_by_date_desc() {
+    let server = MockServer::start().await;
+    let now = Utc::now();
+
+    let mut older = discovered_model("older", "Acme");
+    older.last_updated = now - Duration::days(3);
+    let mut newest = discovered_model("newest", "Acme");
+    newest.last_updated = now - Duration::hours(1);
+    let mut stale = discovered_model("stale", "Acme");
+    stale.last_updated = now - Duration::days(30);
+    let mut middle = discovered_model("middle", "Acme");
+    middle.last_updated = now - Duration::days(1);
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .and(body_partial_json(json!({ "sort_by": "LastUpdated", "sort_order": "Desc" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![older, newest, stale, middle],
+            1,
+            false,
+        )))
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+
+    let models = client.get_recent_models(None, Some(10)).await.unwrap();
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["newest", "middle", "older", "stale"]);
+    assert!(models.windows(2).all(|w| w[0].last_updated >= w[1].last_updated));
+
+    let this_week = client.get_recent_models(Some(now - Duration::days(7)), Some(10)).await.unwrap();
+    let names: Vec<&str> = this_week.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["newest", "middle", "older"]);
+}
+
+#[tokio::test]
+async fn test_short_timeout_yields_timeout_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(search_page(vec![], 1, false))
+                .set_delay(std::time::Duration::from_millis(500)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri())
+        .unwrap()
+        .with_timeouts(DiscoveryTimeouts {
+            search: Some(std::time::Duration::from_millis(50)),
+            ..Default::default()
+        });
+
+    let result = client.search_models(ModelSearchRequest::default()).await;
+    assert!(matches!(result, Err(DiscoveryError::TimeoutError)));
+
+    // Other calls keep the client-wide timeout
+    Mock::given(method("HEAD"))
+        .and(path_regex(r"^/api/v1/models/.+/availability$"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_millis(100)))
+        .mount(&server)
+        .await;
+    assert!(client.check_model_availability(Uuid::new_v4()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_client_wide_timeout_yields_timeout_error() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/models/providers"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!(["Meta"]))
+                .set_delay(std::time::Duration::from_millis(500)),
+        )
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri())
+        .unwrap()
+        .with_timeout(std::time::Duration::from_millis(50));
+
+    let result = client.get_providers().await;
+    assert!(matches!(result, Err(DiscoveryError::TimeoutError)));
+}
+
+#[tokio::test]
+async fn test_offline_client_serves_only_cached_results() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v1/models/providers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec!["Meta", "Qwen"]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let online = client.get_providers().await.unwrap();
+
+    // Switching to offline keeps what was already fetched
+    let client = client.with_offline(true);
+    assert_eq!(client.get_providers().await.unwrap(), online);
+    assert!(matches!(client.get_available_tags().await, Err(DiscoveryError::Offline(_))));
+    assert!(matches!(client.ping().await, Err(DiscoveryError::Offline(_))));
+}
+
+#[tokio::test]
+async fn test_cache_file_survives_restart_for_offline_use() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/models/providers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(vec!["Meta", "Qwen"]))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let cache_file = temp.path().join("discovery_cache.json");
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap().with_cache_file(cache_file.clone());
+    let online = client.get_providers().await.unwrap();
+    drop(client);
+
+    // A new client stands in for a restarted process
+    let restarted = ModelDiscoveryClient::new(server.uri())
+        .unwrap()
+        .with_cache_file(cache_file)
+        .with_offline(true);
+    assert_eq!(restarted.get_providers().await.unwrap(), online);
+    assert!(matches!(restarted.get_available_tags().await, Err(DiscoveryError::Offline(_))));
+}
+
+#[tokio::test]
+async fn test_get_similar_models_uses_endpoint() {
+    let server = MockServer::start().await;
+    let target = discovered_model("qwen2-7b", "Alibaba");
+    let similar = vec![
+        discovered_model("qwen2-1.5b", "Alibaba"),
+        target.clone(),
+        discovered_model("yi-6b", "01.AI"),
+    ];
+
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/models/{}/similar", target.id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&similar))
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let models = client.get_similar_models(target.id, 5).await.unwrap();
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    // The model itself is never suggested
+    assert_eq!(names, vec!["qwen2-1.5b", "yi-6b"]);
+
+    let limited = client.get_similar_models(target.id, 1).await.unwrap();
+    assert_eq!(limited.len(), 1);
+}
+
+#[tokio::test]
+async fn test_get_similar_models_falls_back_to_local_ranking() {
+    let server = MockServer::start().await;
+    let mut target = discovered_model("qwen2-7b", "Alibaba");
+    target.tags = vec!["chinese".to_string(), "instruct".to_string()];
+
+    let mut same_provider_and_tags = discovered_model("qwen2-1.5b", "Alibaba");
+    same_provider_and_tags.tags = vec!["Chinese".to_string(), "instruct".to_string()];
+    let mut shared_tag = discovered_model("yi-6b", "01.AI");
+    shared_tag.tags = vec!["chinese".to_string()];
+    let same_type_only = discovered_model("llama-3-8b", "Meta");
+    let mut unrelated = discovered_model("bge-m3", "BAAI");
+    unrelated.model_type = ModelType::Embedding;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/api/v1/models/[0-9a-f-]+/similar$"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/models/{}", target.id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&target))
+        .mount(&server)
+        .await;
+    // The catalog ignores filters; ranking happens client-side
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(search_page(
+            vec![unrelated, same_type_only, target.clone(), shared_tag, same_provider_and_tags],
+            1,
+            false,
+        )))
+        .mount(&server)
+        .await;
+
+    let client = ModelDiscoveryClient::new(server.uri()).unwrap();
+    let models = client.get_similar_models(target.id, 10).await.unwrap();
+    let names: Vec<&str> = models.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(names, vec!["qwen2-1.5b", "yi-6b", "llama-3-8b"]);
+
+    // An unknown model still surfaces the catalog's 404
+    let missing = client.get_similar_models(Uuid::new_v4(), 10).await;
+    assert!(matches!(missing, Err(DiscoveryError::ApiError { status: 404, .. })));
+}
@@ -0,0 +1,1406 @@
+//! Download manager tests against a mock file server
+//!
+//! These tests exercise ModelDownloadManager's download and verification path
+//! without touching real model hosts.
+
+use burncloud_client_models::{
+    available_disk_space, parse_expected_checksum, ChecksumType, DownloadError, DownloadPriority, DownloadStatus, DownloadWarning,
+    InstallationConfig, ModelDownloadManager, QueueState, SpeedTracker, TotalBytesSource,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use wiremock::matchers::{header, method, path};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const MODEL_BYTES: &[u8] = b"fake gguf model weights";
+
+/// Serve fixed model bytes at /model.gguf
+async fn mount_model_file(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_download_accepts_prefixed_checksum() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let sha256 = format!("{:x}", Sha256::digest(MODEL_BYTES));
+
+    // The prefix decides the algorithm even if the caller passes a different one
+    let progress = manager
+        .download_model(
+            Uuid::new_v4(),
+            "prefixed.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            format!("sha256:{}", sha256),
+            ChecksumType::MD5,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert!(temp.path().join("prefixed.gguf").is_file());
+}
+
+#[tokio::test]
+async fn test_download_infers_checksum_type_from_length() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let md5 = format!("{:x}", md5::compute(MODEL_BYTES));
+
+    let result = manager
+        .download_model(
+            Uuid::new_v4(),
+            "inferred.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            md5,
+            ChecksumType::SHA256,
+        )
+        .await;
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_parse_expected_checksum() {
+    let sha256 = format!("{:x}", Sha256::digest(MODEL_BYTES));
+
+    let (checksum_type, hex) = parse_expected_checksum(&format!("sha256:{}", sha256), ChecksumType::MD5).unwrap();
+    assert_eq!(checksum_type, ChecksumType::SHA256);
+    assert_eq!(hex, sha256);
+
+    // Truncated example values and junk are rejected up front
+    assert!(matches!(
+        parse_expected_checksum("sha256:abc123def456", ChecksumType::SHA256),
+        Err(DownloadError::MalformedChecksum(_))
+    ));
+    assert!(matches!(
+        parse_expected_checksum("not-a-checksum", ChecksumType::SHA256),
+        Err(DownloadError::MalformedChecksum(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_download_rejects_malformed_checksum_before_fetching() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let result = manager
+        .download_model(
+            Uuid::new_v4(),
+            "malformed.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            "sha256:abc123def456".to_string(),
+            ChecksumType::SHA256,
+        )
+        .await;
+
+    assert!(matches!(result, Err(DownloadError::MalformedChecksum(_))));
+}
+
+/// Write a source model file into its own directory
+fn write_source_file(dir: &std::path::Path) -> std::path::PathBuf {
+    let source = dir.join("source").join("model.gguf");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(&source, MODEL_BYTES).unwrap();
+    source
+}
+
+#[tokio::test]
+async fn test_install_records_verification() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().join("downloads")).unwrap();
+    let source = write_source_file(temp.path());
+
+    let installation = manager
+        .install_model(Uuid::new_v4(), source, InstallationConfig::default())
+        .await
+        .unwrap();
+
+    let verification = installation.verification.unwrap();
+    assert_eq!(verification.actual_size, MODEL_BYTES.len() as u64);
+    assert_eq!(verification.expected_size, MODEL_BYTES.len() as u64);
+    assert!(verification.checksum_verified);
+}
+
+#[tokio::test]
+async fn test_truncated_install_rolls_back() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().join("downloads")).unwrap();
+    let source = write_source_file(temp.path());
+    let install_dir = temp.path().join("installed-model");
+
+    // The catalog expects more bytes than the copied file holds
+    let config = InstallationConfig {
+        custom_install_path: Some(install_dir.clone()),
+        expected_file_size: Some(MODEL_BYTES.len() as u64 * 2),
+        ..Default::default()
+    };
+    let result = manager.install_model(Uuid::new_v4(), source.clone(), config).await;
+
+    assert!(matches!(result, Err(DownloadError::InstallationFailed(_))));
+    assert!(!install_dir.exists());
+    assert!(source.is_file());
+}
+
+#[tokio::test]
+async fn test_install_checksum_mismatch_rolls_back() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().join("downloads")).unwrap();
+    let source = write_source_file(temp.path());
+    let install_dir = temp.path().join("installed-model");
+
+    let config = InstallationConfig {
+        custom_install_path: Some(install_dir.clone()),
+        expected_checksum: Some(format!("sha256:{}", "0".repeat(64))),
+        ..Default::default()
+    };
+    let result = manager.install_model(Uuid::new_v4(), source, config).await;
+
+    assert!(matches!(result, Err(DownloadError::InstallationFailed(_))));
+    assert!(!install_dir.exists());
+}
+
+#[tokio::test]
+async fn test_reinstall_from_cache_without_network() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().join("downloads")).unwrap();
+    let source = write_source_file(temp.path());
+    let model_id = Uuid::new_v4();
+
+    let config = InstallationConfig {
+        keep_download: true,
+        ..Default::default()
+    };
+    let first = manager.install_model(model_id, source.clone(), config).await.unwrap();
+    let cached = first.cached_download.clone().unwrap();
+    assert!(cached.is_file());
+
+    // Original download and installation are gone; only the cache remains
+    std::fs::remove_file(&source).unwrap();
+    std::fs::remove_dir_all(&first.install_path).unwrap();
+
+    let second = manager.reinstall_from_cache(model_id).await.unwrap();
+    assert!(second.verification.unwrap().checksum_verified);
+    assert_eq!(second.cached_download, Some(cached));
+    assert_eq!(std::fs::read(second.install_path.join("model.gguf")).unwrap(), MODEL_BYTES);
+}
+
+#[tokio::test]
+async fn test_reinstall_from_tampered_cache_fails() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().join("downloads")).unwrap();
+    let source = write_source_file(temp.path());
+    let model_id = Uuid::new_v4();
+
+    assert!(matches!(
+        manager.reinstall_from_cache(model_id).await,
+        Err(DownloadError::CacheUnavailable(_))
+    ));
+
+    let config = InstallationConfig {
+        keep_download: true,
+        ..Default::default()
+    };
+    let installation = manager.install_model(model_id, source, config).await.unwrap();
+    std::fs::write(installation.cached_download.unwrap(), b"corrupted").unwrap();
+
+    assert!(matches!(
+        manager.reinstall_from_cache(model_id).await,
+        Err(DownloadError::CacheUnavailable(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_download_falls_back_to_next_mirror() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/primary/model.gguf"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mirror/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let mirror = format!("{}/mirror/model.gguf", server.uri());
+
+    let progress = manager
+        .download_model_from_mirrors(
+            Uuid::new_v4(),
+            "mirrored.gguf".to_string(),
+            vec![format!("{}/primary/model.gguf", server.uri()), mirror.clone()],
+            format!("sha256:{:x}", Sha256::digest(MODEL_BYTES)),
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(progress.source_url, Some(mirror));
+}
+
+#[tokio::test]
+async fn test_download_falls_back_when_mirror_serves_wrong_bytes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/stale/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"stale model weights".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fresh/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let fresh = format!("{}/fresh/model.gguf", server.uri());
+
+    let progress = manager
+        .download_model_from_mirrors(
+            Uuid::new_v4(),
+            "verified.gguf".to_string(),
+            vec![format!("{}/stale/model.gguf", server.uri()), fresh.clone()],
+            format!("{:x}", Sha256::digest(MODEL_BYTES)),
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(progress.source_url, Some(fresh));
+    assert_eq!(std::fs::read(temp.path().join("verified.gguf")).unwrap(), MODEL_BYTES);
+}
+
+#[tokio::test]
+async fn test_download_fails_only_after_every_mirror_was_tried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/limited/model.gguf"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let started = std::time::Instant::now();
+    let result = manager
+        .download_model_from_mirrors(
+            Uuid::new_v4(),
+            "unavailable.gguf".to_string(),
+            vec![format!("{}/limited/model.gguf", server.uri()), "http://unreachable.invalid/model.gguf".to_string()],
+            None,
+            ChecksumType::SHA256,
+        )
+        .await;
+
+    // The rate-limited mirror is skipped and the last mirror's error is reported
+    assert!(matches!(result, Err(DownloadError::NetworkError(_))), "{:?}", result);
+    // An unreachable mirror isn't retried with backoff when there are others to try
+    assert!(started.elapsed() < Duration::from_secs(1), "{:?}", started.elapsed());
+    assert!(!temp.path().join("unavailable.gguf").exists());
+}
+
+#[tokio::test]
+async fn test_download_reports_network_error_when_every_mirror_returns_http_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/limited/model.gguf"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/down/model.gguf"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let result = manager
+        .download_model_from_mirrors(
+            Uuid::new_v4(),
+            "unavailable.gguf".to_string(),
+            vec![format!("{}/limited/model.gguf", server.uri()), format!("{}/down/model.gguf", server.uri())],
+            None,
+            ChecksumType::SHA256,
+        )
+        .await;
+
+    // Both mirrors were tried and the last one's status is reported
+    match result {
+        Err(DownloadError::NetworkError(e)) => assert_eq!(e.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)),
+        other => panic!("expected a network error, got {:?}", other.map(|progress| progress.status)),
+    }
+    assert!(!temp.path().join("unavailable.gguf").exists());
+}
+
+#[tokio::test]
+async fn test_download_prefers_configured_mirror_region() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf())
+        .unwrap()
+        .with_mirror_region("127.0.0.1");
+    let local = format!("{}/model.gguf", server.uri());
+
+    // The unreachable host is listed first but the preferred region is tried first
+    let progress = manager
+        .download_model_from_mirrors(
+            Uuid::new_v4(),
+            "regional.gguf".to_string(),
+            vec!["http://unreachable.invalid/model.gguf".to_string(), local.clone()],
+            format!("{:x}", Sha256::digest(MODEL_BYTES)),
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(progress.source_url, Some(local));
+}
+
+/// Wait until the queue holds `count` entries
+async fn wait_for_queue_len(manager: &ModelDownloadManager, count: usize) {
+    while manager.queue().list().len() < count {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn test_queue_priority_and_reorder_decide_start_order() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_max_concurrent(1);
+    let queue = manager.queue().clone();
+
+    // Occupy the only slot so everything else has to wait
+    let blocker = queue.acquire(Uuid::new_v4(), "blocker", DownloadPriority::Normal).await.unwrap();
+
+    let started = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = Vec::new();
+    let mut ids = Vec::new();
+    for (name, priority) in [
+        ("large", DownloadPriority::Normal),
+        ("medium", DownloadPriority::Normal),
+        ("small", DownloadPriority::High),
+    ] {
+        let id = Uuid::new_v4();
+        ids.push(id);
+        let queue = queue.clone();
+        let started = started.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = queue.acquire(id, name, priority).await.unwrap();
+            started.lock().unwrap().push(name);
+        }));
+        wait_for_queue_len(&manager, ids.len() + 1).await;
+    }
+
+    let waiting: Vec<String> = queue.list().into_iter()
+        .filter(|d| d.state == QueueState::Waiting)
+        .map(|d| d.model_name)
+        .collect();
+    assert_eq!(waiting, vec!["small", "large", "medium"]);
+
+    assert!(queue.move_to_front(ids[1]));
+    drop(blocker);
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(*started.lock().unwrap(), vec!["medium", "small", "large"]);
+    assert!(queue.list().is_empty());
+}
+
+#[tokio::test]
+async fn test_queue_estimates_later_starts_for_later_downloads() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_max_concurrent(1);
+    let queue = manager.queue().clone();
+
+    // The active download is halfway through 1 MB at 10 KB/s
+    let blocker_id = Uuid::new_v4();
+    let _blocker = queue.acquire_with_size(blocker_id, "blocker", DownloadPriority::Normal, 1_000_000).await.unwrap();
+    assert!(manager.estimated_start_and_finish(blocker_id).is_none(), "no throughput measured yet");
+    queue.record_progress(blocker_id, 500_000, 1_000_000, 10_000);
+
+    let mut ids = Vec::new();
+    for (name, size) in [("first", 300_000), ("second", 100_000), ("third", 200_000)] {
+        let id = Uuid::new_v4();
+        ids.push(id);
+        let queue = queue.clone();
+        tokio::spawn(async move { queue.acquire_with_size(id, name, DownloadPriority::Normal, size).await });
+        wait_for_queue_len(&manager, ids.len() + 1).await;
+    }
+
+    let (_, blocker_finish) = manager.estimated_start_and_finish(blocker_id).unwrap();
+    let estimates: Vec<_> = ids.iter().map(|id| manager.estimated_start_and_finish(*id).unwrap()).collect();
+    assert!(estimates[0].0 >= blocker_finish);
+    assert!(estimates[0].0 < estimates[1].0);
+    assert!(estimates[1].0 < estimates[2].0);
+    for (start, finish) in &estimates {
+        assert!(start < finish);
+    }
+    // Each waiting download starts when the one ahead of it finishes
+    assert!((estimates[1].0 - estimates[0].1).num_milliseconds().abs() < 1000);
+    // 500 KB left on the blocker plus 300 KB at 10 KB/s
+    let first_finish = (estimates[0].1 - chrono::Utc::now()).num_seconds();
+    assert!((79..=80).contains(&first_finish), "{}", first_finish);
+
+    assert!(manager.estimated_start_and_finish(Uuid::new_v4()).is_none());
+}
+
+#[tokio::test]
+async fn test_cancel_removes_waiting_download() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_max_concurrent(1);
+    let queue = manager.queue().clone();
+
+    let _blocker = queue.acquire(Uuid::new_v4(), "blocker", DownloadPriority::Normal).await.unwrap();
+    let id = Uuid::new_v4();
+    let waiter = {
+        let queue = queue.clone();
+        tokio::spawn(async move { queue.acquire(id, "queued", DownloadPriority::Low).await })
+    };
+    wait_for_queue_len(&manager, 2).await;
+
+    manager.cancel_download(id).await.unwrap();
+
+    assert!(matches!(waiter.await.unwrap(), Err(DownloadError::Cancelled(_))));
+    assert_eq!(queue.list().len(), 1);
+}
+
+#[tokio::test]
+async fn test_download_rejects_html_error_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("<html><body>Rate limit exceeded</body></html>", "text/html; charset=utf-8"),
+        )
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let md5 = format!("{:x}", md5::compute(MODEL_BYTES));
+
+    let result = manager
+        .download_model(
+            Uuid::new_v4(),
+            "html.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            md5,
+            ChecksumType::MD5,
+        )
+        .await;
+
+    assert!(matches!(result, Err(DownloadError::UnexpectedContentType(ref t)) if t.starts_with("text/html")));
+    assert!(!temp.path().join("html.gguf").exists());
+    assert_eq!(std::fs::read_dir(temp.path().join("temp")).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_download_sniffs_mislabeled_html() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_raw("\n<!DOCTYPE html><html></html>", "application/octet-stream"),
+        )
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let md5 = format!("{:x}", md5::compute(MODEL_BYTES));
+
+    let result = manager
+        .download_model(
+            Uuid::new_v4(),
+            "sniffed.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            md5,
+            ChecksumType::MD5,
+        )
+        .await;
+
+    assert!(matches!(result, Err(DownloadError::UnexpectedContentType(_))));
+    assert_eq!(std::fs::read_dir(temp.path().join("temp")).unwrap().count(), 0);
+}
+
+/// Serve `body` on every GET without a content-length header, closing the connection to end it
+async fn serve_without_content_length(body: &'static [u8]) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = vec![0u8; 4096];
+                let n = socket.read(&mut request).await.unwrap_or(0);
+                let head = b"HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(head).await;
+                if request[..n].starts_with(b"GET") {
+                    let _ = socket.write_all(body).await;
+                }
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+    format!("http://{}/model.gguf", addr)
+}
+
+#[tokio::test]
+async fn test_download_without_content_length_uses_estimated_total() {
+    let url = serve_without_content_length(MODEL_BYTES).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let checksum = format!("{:x}", Sha256::digest(MODEL_BYTES));
+
+    // The estimate undershoots the real size; progress stays within 0-100
+    let estimate = MODEL_BYTES.len() as u64 / 2;
+    let progress = manager
+        .download_model_with_expected_size(
+            Uuid::new_v4(),
+            "estimated.gguf".to_string(),
+            url.clone(),
+            checksum.clone(),
+            ChecksumType::SHA256,
+            Some(estimate),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(progress.total_source, TotalBytesSource::Estimate);
+    assert_eq!(progress.total_bytes, estimate);
+    assert_eq!(progress.downloaded_bytes, MODEL_BYTES.len() as u64);
+    assert_eq!(progress.progress_percent, 100.0);
+
+    // Without an estimate the total stays unknown
+    let progress = manager
+        .download_model(Uuid::new_v4(), "unknown.gguf".to_string(), url, checksum, ChecksumType::SHA256)
+        .await
+        .unwrap();
+    assert_eq!(progress.total_source, TotalBytesSource::Unknown);
+    assert_eq!(progress.total_bytes, 0);
+    assert_eq!(progress.progress_percent, 0.0);
+}
+
+#[tokio::test]
+async fn test_download_with_content_length_reports_header_total() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let progress = manager
+        .download_model_with_expected_size(
+            Uuid::new_v4(),
+            "header.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            format!("{:x}", Sha256::digest(MODEL_BYTES)),
+            ChecksumType::SHA256,
+            Some(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(progress.total_source, TotalBytesSource::Header);
+    assert_eq!(progress.total_bytes, MODEL_BYTES.len() as u64);
+}
+
+#[tokio::test]
+async fn test_download_with_checksum_is_verified() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let sha256 = format!("{:x}", Sha256::digest(MODEL_BYTES));
+
+    let progress = manager
+        .download_model(
+            Uuid::new_v4(),
+            "verified.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            Some(sha256.clone()),
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(progress.actual_checksum.as_deref(), Some(sha256.as_str()));
+    assert!(progress.warnings.is_empty());
+    assert!(!progress.needs_trust_confirmation());
+}
+
+#[tokio::test]
+async fn test_download_without_checksum_records_actual_and_warns() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let progress = manager
+        .download_model(
+            Uuid::new_v4(),
+            "unverified.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            None,
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert!(temp.path().join("unverified.gguf").is_file());
+    let sha256 = format!("{:x}", Sha256::digest(MODEL_BYTES));
+    assert_eq!(progress.actual_checksum.as_deref(), Some(sha256.as_str()));
+    assert_eq!(progress.warnings, vec![DownloadWarning::UnverifiedChecksum]);
+    assert!(progress.needs_trust_confirmation());
+
+    // The recorded checksum carries over to the installation record
+    let config = InstallationConfig {
+        expected_checksum: progress.actual_checksum.map(|actual| format!("sha256:{}", actual)),
+        ..InstallationConfig::default()
+    };
+    let installation = manager
+        .install_model(progress.model_id, temp.path().join("unverified.gguf"), config)
+        .await
+        .unwrap();
+    assert_eq!(installation.checksum, sha256);
+}
+
+#[test]
+fn test_smoothed_eta_is_stable_on_bursty_connection() {
+    const MB: u64 = 1024 * 1024;
+    let remaining = 600 * MB;
+    let mut tracker = SpeedTracker::new();
+    assert_eq!(tracker.estimate_remaining_seconds(remaining), None);
+
+    // One-second samples alternating between 10 MB/s and 1 MB/s
+    let mut raw_etas = Vec::new();
+    let mut smoothed_etas = Vec::new();
+    for second in 0..40 {
+        let rate = if second % 2 == 0 { 10 * MB } else { MB };
+        tracker.record(rate, Duration::from_secs(1));
+        raw_etas.push(remaining / rate);
+        if second >= 20 {
+            smoothed_etas.push(tracker.estimate_remaining_seconds(remaining).unwrap());
+        }
+    }
+
+    let spread = |etas: &[u64]| *etas.iter().max().unwrap() as f64 / *etas.iter().min().unwrap() as f64;
+    assert!(spread(&raw_etas) >= 10.0);
+    assert!(spread(&smoothed_etas) < 1.6, "smoothed ETAs oscillate: {:?}", smoothed_etas);
+
+    // Smoothed speed settles near the true average of 5.5 MB/s
+    let speed = tracker.current_speed_bps() as f64 / MB as f64;
+    assert!((4.0..7.0).contains(&speed), "unexpected smoothed speed {}", speed);
+    assert_eq!(tracker.history().count(), 20);
+}
+
+#[test]
+fn test_speed_tracker_accumulates_small_chunks() {
+    let mut tracker = SpeedTracker::new();
+    // Chunks shorter than the sample interval are combined before producing a sample
+    tracker.record(1000, Duration::from_millis(100));
+    assert_eq!(tracker.current_speed_bps(), 0);
+    for _ in 0..4 {
+        tracker.record(1000, Duration::from_millis(100));
+    }
+    assert_eq!(tracker.current_speed_bps(), 10_000);
+    assert_eq!(tracker.history().collect::<Vec<_>>(), vec![10_000]);
+}
+
+#[tokio::test]
+async fn test_download_respects_min_free_reserve() {
+    let server = MockServer::start().await;
+    let size = 1024 * 1024;
+    Mock::given(path("/big.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; size as usize]))
+        .mount(&server)
+        .await;
+    let url = format!("{}/big.gguf", server.uri());
+
+    let temp = tempfile::tempdir().unwrap();
+    let available = available_disk_space(temp.path()).expect("disk space is queryable on test hosts");
+    assert!(size <= available);
+
+    // The file alone fits, but not together with a reserve of all currently free space
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(available);
+    let result = manager
+        .download_model(Uuid::new_v4(), "big.gguf".to_string(), url.clone(), None, ChecksumType::SHA256)
+        .await;
+    match result {
+        Err(DownloadError::InsufficientSpace { required, .. }) => assert_eq!(required, size + available),
+        other => panic!("expected InsufficientSpace, got {:?}", other),
+    }
+    assert!(!temp.path().join("big.gguf").exists());
+
+    // Without a reserve the same download goes through
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0);
+    let progress = manager
+        .download_model(Uuid::new_v4(), "big.gguf".to_string(), url, None, ChecksumType::SHA256)
+        .await
+        .unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+}
+
+/// Leave an interrupted download behind: the first `partial_len` bytes and its `.meta` sidecar
+fn write_interrupted_download(download_dir: &std::path::Path, model_id: Uuid, url: String, partial_len: usize) {
+    let temp_dir = download_dir.join("temp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    std::fs::write(temp_dir.join(format!("{}.tmp", model_id)), &MODEL_BYTES[..partial_len]).unwrap();
+    let meta = serde_json::json!({
+        "model_name": "resumed.gguf",
+        "download_url": url,
+        "total_bytes": MODEL_BYTES.len(),
+        "total_source": "Header",
+        "expected_checksum": format!("{:x}", Sha256::digest(MODEL_BYTES)),
+        "checksum_algorithm": "sha256",
+    });
+    std::fs::write(temp_dir.join(format!("{}.meta", model_id)), meta.to_string()).unwrap();
+}
+
+/// A 206 response carrying `MODEL_BYTES` from `start` on
+fn partial_response(start: usize) -> ResponseTemplate {
+    let content_range = format!("bytes {}-{}/{}", start, MODEL_BYTES.len() - 1, MODEL_BYTES.len());
+    ResponseTemplate::new(206)
+        .insert_header("content-range", content_range.as_str())
+        .set_body_bytes(&MODEL_BYTES[start..])
+}
+
+/// Record in the resume metadata how many bytes the temp file should hold
+fn record_downloaded_bytes(download_dir: &std::path::Path, model_id: Uuid, downloaded_bytes: usize) {
+    let meta_path = download_dir.join("temp").join(format!("{}.meta", model_id));
+    let mut meta: serde_json::Value = serde_json::from_slice(&std::fs::read(&meta_path).unwrap()).unwrap();
+    meta["downloaded_bytes"] = serde_json::json!(downloaded_bytes);
+    std::fs::write(meta_path, meta.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_download_requests_only_the_missing_range() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .and(header("range", "bytes=10-"))
+        .respond_with(partial_response(10))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let model_id = Uuid::new_v4();
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), 10);
+
+    // A fresh manager stands in for a restarted process
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let progress = manager.resume_download(model_id).await.unwrap();
+
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(progress.downloaded_bytes, MODEL_BYTES.len() as u64);
+    assert_eq!(progress.total_bytes, MODEL_BYTES.len() as u64);
+    assert_eq!(progress.actual_checksum, Some(format!("{:x}", Sha256::digest(MODEL_BYTES))));
+    assert_eq!(std::fs::read(temp.path().join("resumed.gguf")).unwrap(), MODEL_BYTES);
+    assert!(!temp.path().join("temp").join(format!("{}.meta", model_id)).exists());
+
+    // Nothing is left to resume
+    assert!(matches!(manager.resume_download(model_id).await, Err(DownloadError::ConfigError(_))));
+}
+
+#[tokio::test]
+async fn test_resume_download_restarts_when_content_range_is_elsewhere() {
+    let server = MockServer::start().await;
+    // The server answers the range request with a slice that starts at the wrong byte
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .and(header("range", "bytes=10-"))
+        .respond_with(partial_response(5))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let model_id = Uuid::new_v4();
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), 10);
+
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let progress = manager.resume_download(model_id).await.unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(std::fs::read(temp.path().join("resumed.gguf")).unwrap(), MODEL_BYTES);
+}
+
+#[tokio::test]
+async fn test_resume_download_restarts_when_range_is_ignored() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let model_id = Uuid::new_v4();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), 10);
+
+    let progress = manager.resume_download(model_id).await.unwrap();
+
+    // The 200 response replaces the partial file instead of being appended to it
+    assert_eq!(progress.downloaded_bytes, MODEL_BYTES.len() as u64);
+    assert_eq!(std::fs::read(temp.path().join("resumed.gguf")).unwrap(), MODEL_BYTES);
+}
+
+#[tokio::test]
+async fn test_checksum_mismatch_discards_partial_download() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let model_id = Uuid::new_v4();
+    let temp_path = temp.path().join("temp").join(format!("{}.tmp", model_id));
+    let meta_path = temp.path().join("temp").join(format!("{}.meta", model_id));
+
+    // Complete but wrong content can't be fixed by resuming
+    let result = manager
+        .download_model(model_id, "kept.gguf".to_string(), format!("{}/model.gguf", server.uri()), "0".repeat(64), ChecksumType::SHA256)
+        .await;
+    assert!(matches!(result, Err(DownloadError::ChecksumMismatch { .. })));
+    assert!(!temp_path.exists());
+    assert!(!meta_path.exists());
+    assert!(matches!(manager.resume_download(model_id).await, Err(DownloadError::ConfigError(_))));
+
+    // Same when an already complete temp file fails verification on resume
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), MODEL_BYTES.len());
+    std::fs::write(&temp_path, vec![0u8; MODEL_BYTES.len()]).unwrap();
+    let result = manager.resume_download(model_id).await;
+    assert!(matches!(result, Err(DownloadError::ChecksumMismatch { .. })));
+    assert!(!temp_path.exists());
+    assert!(!meta_path.exists());
+    assert!(!temp.path().join("resumed.gguf").exists());
+}
+
+#[tokio::test]
+async fn test_download_larger_than_free_space_is_rejected() {
+    let temp = tempfile::tempdir().unwrap();
+    let available = available_disk_space(&temp.path().join("temp")).expect("disk space is queryable on test hosts");
+    let size = available + 1024 * 1024 * 1024;
+
+    let server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/huge.gguf"))
+        .respond_with(ResponseTemplate::new(200).insert_header("content-length", size.to_string().as_str()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/huge.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0);
+    let result = manager
+        .download_model(Uuid::new_v4(), "huge.gguf".to_string(), format!("{}/huge.gguf", server.uri()), None, ChecksumType::SHA256)
+        .await;
+    match result {
+        Err(DownloadError::InsufficientSpace { required, available: reported }) => {
+            assert_eq!(required, size);
+            // The real free space of the download volume, give or take other writers
+            assert!(reported.abs_diff(available) < 256 * 1024 * 1024, "{} vs {}", reported, available);
+        }
+        other => panic!("expected InsufficientSpace, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_download_with_progress_sends_throttled_snapshots() {
+    let body = vec![7u8; 8 * 1024 * 1024];
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/large.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0);
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+    let started = std::time::Instant::now();
+    let progress = manager
+        .download_model_with_progress(
+            Uuid::new_v4(),
+            "large.gguf".to_string(),
+            format!("{}/large.gguf", server.uri()),
+            format!("{:x}", Sha256::digest(&body)),
+            ChecksumType::SHA256,
+            tx,
+        )
+        .await
+        .unwrap();
+    let elapsed = started.elapsed();
+
+    let mut snapshots = Vec::new();
+    while let Some(snapshot) = rx.recv().await {
+        snapshots.push(snapshot);
+    }
+    let (last, running) = snapshots.split_last().expect("at least the final snapshot");
+    assert!(matches!(last.status, DownloadStatus::Completed));
+    assert_eq!(last.downloaded_bytes, progress.downloaded_bytes);
+
+    // The first chunk is reported right away, later ones at most every 250ms
+    assert!(!running.is_empty());
+    assert!(running.len() as u128 <= elapsed.as_millis() / 250 + 1, "{} snapshots in {:?}", running.len(), elapsed);
+    assert!(running.iter().all(|snapshot| matches!(snapshot.status, DownloadStatus::Downloading)));
+    assert!(running.windows(2).all(|pair| pair[0].downloaded_bytes <= pair[1].downloaded_bytes));
+}
+
+#[tokio::test]
+async fn test_download_with_progress_ignores_dropped_receiver() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    drop(rx);
+    let progress = manager
+        .download_model_with_progress(
+            Uuid::new_v4(),
+            "unwatched.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            None,
+            ChecksumType::SHA256,
+            tx,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(progress.downloaded_bytes, MODEL_BYTES.len() as u64);
+}
+
+#[tokio::test]
+async fn test_download_with_progress_returns_before_receiver_is_drained() {
+    let server = MockServer::start().await;
+    mount_model_file(&server).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+
+    // The running snapshot fills the only slot; nothing is read until the call returns
+    let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+    let progress = tokio::time::timeout(
+        Duration::from_secs(10),
+        manager.download_model_with_progress(
+            Uuid::new_v4(),
+            "undrained.gguf".to_string(),
+            format!("{}/model.gguf", server.uri()),
+            None,
+            ChecksumType::SHA256,
+            tx,
+        ),
+    )
+    .await
+    .expect("the final snapshot must not wait for the receiver")
+    .unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+
+    let mut snapshots = Vec::new();
+    while let Some(snapshot) = rx.recv().await {
+        snapshots.push(snapshot);
+    }
+    let last = snapshots.last().expect("the final snapshot is still delivered");
+    assert!(matches!(last.status, DownloadStatus::Completed));
+    assert_eq!(last.downloaded_bytes, MODEL_BYTES.len() as u64);
+}
+
+#[tokio::test]
+async fn test_queue_download_limits_concurrent_transfers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(MODEL_BYTES).set_delay(Duration::from_millis(100)))
+        .mount(&server)
+        .await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_max_concurrent(2);
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+    let mut ids = Vec::new();
+    for index in 0..4 {
+        let id = Uuid::new_v4();
+        ids.push(id);
+        let queued = manager
+            .queue_download(id, format!("slow-{}.gguf", index), format!("{}/slow.gguf", server.uri()), None, ChecksumType::SHA256, tx.clone())
+            .unwrap();
+        assert!(matches!(queued.status, DownloadStatus::Queued));
+    }
+    // Unknown paths fail in the background and are reported through the channel
+    let missing = Uuid::new_v4();
+    manager
+        .queue_download(missing, "missing.gguf".to_string(), format!("{}/missing.gguf", server.uri()), None, ChecksumType::SHA256, tx)
+        .unwrap();
+
+    let mut completed = Vec::new();
+    let mut failed = Vec::new();
+    while let Some(snapshot) = rx.recv().await {
+        assert!(manager.active_download_count() <= 2);
+        match snapshot.status {
+            DownloadStatus::Completed => completed.push(snapshot.model_id),
+            DownloadStatus::Failed => failed.push(snapshot),
+            _ => {}
+        }
+    }
+
+    completed.sort();
+    ids.sort();
+    assert_eq!(completed, ids);
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].model_id, missing);
+    assert!(failed[0].error_message.is_some());
+    assert_eq!(manager.active_download_count(), 0);
+
+    let offline = manager.clone().with_offline(true);
+    let (tx, _rx) = tokio::sync::mpsc::channel(1);
+    let result = offline.queue_download(Uuid::new_v4(), "offline.gguf".to_string(), server.uri(), None, ChecksumType::SHA256, tx);
+    assert!(matches!(result, Err(DownloadError::Offline(_))));
+}
+
+#[tokio::test]
+async fn test_reconciliation_trims_unconfirmed_bytes_and_discards_lost_ones() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .and(header("range", "bytes=10-"))
+        .respond_with(partial_response(10))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let model_id = Uuid::new_v4();
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), 10);
+    record_downloaded_bytes(temp.path(), model_id, 10);
+    // The crash left bytes after the last checkpoint, only the first 10 were confirmed
+    let temp_file = temp.path().join("temp").join(format!("{}.tmp", model_id));
+    let mut crashed = MODEL_BYTES[..10].to_vec();
+    crashed.extend_from_slice(&[0u8; 16]);
+    std::fs::write(&temp_file, crashed).unwrap();
+    // Confirmed bytes went missing, so nothing in this file can be trusted
+    let shrunk_id = Uuid::new_v4();
+    write_interrupted_download(temp.path(), shrunk_id, format!("{}/model.gguf", server.uri()), 5);
+    record_downloaded_bytes(temp.path(), shrunk_id, 10);
+    let shrunk_file = temp.path().join("temp").join(format!("{}.tmp", shrunk_id));
+    // A temp file without resume metadata can't be resumed at all
+    let orphan = temp.path().join("temp").join(format!("{}.tmp", Uuid::new_v4()));
+    std::fs::write(&orphan, b"orphaned").unwrap();
+
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    assert_eq!(manager.reconcile_partial_downloads().await.unwrap(), 3);
+    assert_eq!(std::fs::read(&temp_file).unwrap(), &MODEL_BYTES[..10]);
+    assert_eq!(std::fs::metadata(&shrunk_file).unwrap().len(), 0);
+    assert!(!orphan.exists());
+    // Nothing left to fix on a second run
+    assert_eq!(manager.reconcile_partial_downloads().await.unwrap(), 0);
+
+    let progress = manager.resume_download(model_id).await.unwrap();
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(std::fs::read(temp.path().join("resumed.gguf")).unwrap(), MODEL_BYTES);
+}
+
+#[tokio::test]
+async fn test_resume_download_trims_temp_file_larger_than_recorded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/model.gguf"))
+        .and(header("range", "bytes=10-"))
+        .respond_with(partial_response(10))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let model_id = Uuid::new_v4();
+    write_interrupted_download(temp.path(), model_id, format!("{}/model.gguf", server.uri()), MODEL_BYTES.len());
+    record_downloaded_bytes(temp.path(), model_id, 10);
+    let mut tampered = MODEL_BYTES.to_vec();
+    tampered[20] ^= 0xff;
+    std::fs::write(temp.path().join("temp").join(format!("{}.tmp", model_id)), tampered).unwrap();
+
+    // Without reconciling first, resume keeps the 10 confirmed bytes and fetches only the rest
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    let progress = manager.resume_download(model_id).await.unwrap();
+    assert_eq!(progress.actual_checksum, Some(format!("{:x}", Sha256::digest(MODEL_BYTES))));
+    assert!(!temp.path().join("temp").join(format!("{}.tmp", model_id)).exists());
+}
+
+/// Serve MODEL_BYTES over raw HTTP, dropping the first GET after `cut_at` body bytes
+///
+/// Returns the server address and the GET requests it received.
+async fn spawn_flaky_server(cut_at: usize) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = socket.read(&mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let request = String::from_utf8_lossy(&request).to_lowercase();
+            let headers = |status: &str, length: usize| format!(
+                "HTTP/1.1 {}\r\ncontent-length: {}\r\ncontent-type: application/octet-stream\r\nconnection: close\r\n\r\n",
+                status, length,
+            );
+
+            if request.starts_with("head") {
+                socket.write_all(headers("200 OK", MODEL_BYTES.len()).as_bytes()).await.unwrap();
+                continue;
+            }
+            let first_get = {
+                let mut seen = seen.lock().unwrap();
+                seen.push(request.clone());
+                seen.len() == 1
+            };
+            if first_get {
+                // Promise the whole file, send part of it, then reset
+                socket.write_all(headers("200 OK", MODEL_BYTES.len()).as_bytes()).await.unwrap();
+                socket.write_all(&MODEL_BYTES[..cut_at]).await.unwrap();
+            } else if request.contains(&format!("range: bytes={}-", cut_at)) {
+                let response = headers("206 Partial Content", MODEL_BYTES.len() - cut_at).replacen(
+                    "\r\n",
+                    &format!("\r\ncontent-range: bytes {}-{}/{}\r\n", cut_at, MODEL_BYTES.len() - 1, MODEL_BYTES.len()),
+                    1,
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.write_all(&MODEL_BYTES[cut_at..]).await.unwrap();
+            } else {
+                socket.write_all(headers("200 OK", MODEL_BYTES.len()).as_bytes()).await.unwrap();
+                socket.write_all(MODEL_BYTES).await.unwrap();
+            }
+        }
+    });
+    (address, requests)
+}
+
+#[tokio::test]
+async fn test_interrupted_transfer_is_retried_from_written_bytes() {
+    let (address, requests) = spawn_flaky_server(10).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0);
+
+    let progress = manager
+        .download_model(
+            Uuid::new_v4(),
+            "flaky.gguf".to_string(),
+            format!("{}/model.gguf", address),
+            format!("{:x}", Sha256::digest(MODEL_BYTES)),
+            ChecksumType::SHA256,
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(std::fs::read(temp.path().join("flaky.gguf")).unwrap(), MODEL_BYTES);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].contains("range: bytes=10-"), "{}", requests[1]);
+}
+
+#[tokio::test]
+async fn test_interrupted_transfer_fails_without_retries() {
+    let (address, requests) = spawn_flaky_server(10).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0).with_max_retries(0);
+
+    let result = manager
+        .download_model(Uuid::new_v4(), "flaky.gguf".to_string(), format!("{}/model.gguf", address), None, ChecksumType::SHA256)
+        .await;
+    match result {
+        Err(e) => assert!(e.is_transient(), "{:?}", e),
+        Ok(progress) => panic!("expected a transfer error, got {:?}", progress.status),
+    }
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_http_errors_are_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/gone.gguf"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_max_retries(3);
+
+    let started = std::time::Instant::now();
+    let result = manager
+        .download_model(Uuid::new_v4(), "gone.gguf".to_string(), format!("{}/gone.gguf", server.uri()), None, ChecksumType::SHA256)
+        .await;
+    assert!(
+        matches!(&result, Err(DownloadError::NetworkError(e)) if e.status() == Some(reqwest::StatusCode::NOT_FOUND)),
+        "{:?}",
+        result.map(|progress| progress.status),
+    );
+    assert!(started.elapsed() < Duration::from_secs(1), "no backoff for permanent failures");
+}
+
+#[tokio::test]
+async fn test_rate_limit_throttles_download_speed() {
+    const LIMIT: u64 = 64 * 1024;
+    let body = vec![7u8; 96 * 1024];
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow.gguf"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+        .mount(&server)
+        .await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap()
+        .with_min_free_reserve(0)
+        .with_rate_limit(LIMIT);
+    assert_eq!(manager.rate_limit(), Some(LIMIT));
+
+    let started = std::time::Instant::now();
+    let progress = manager
+        .download_model(Uuid::new_v4(), "slow.gguf".to_string(), format!("{}/slow.gguf", server.uri()), None, ChecksumType::SHA256)
+        .await
+        .unwrap();
+
+    // 96KB at 64KB/s takes about 1.5s
+    assert!(matches!(progress.status, DownloadStatus::Completed));
+    assert_eq!(progress.downloaded_bytes, body.len() as u64);
+    assert!(started.elapsed() >= Duration::from_millis(1400), "took {:?}", started.elapsed());
+    assert!(progress.download_speed_bps <= LIMIT * 11 / 10, "speed {}", progress.download_speed_bps);
+    assert!(progress.download_speed_bps >= LIMIT / 2, "speed {}", progress.download_speed_bps);
+}
+
+#[tokio::test]
+async fn test_zero_rate_limit_means_unlimited() {
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap();
+    assert_eq!(manager.rate_limit(), None);
+    assert_eq!(manager.with_rate_limit(1024).with_rate_limit(0).rate_limit(), None);
+}
+
+/// Promise a large file over raw HTTP, send the first `sent` bytes, then stall with the connection open
+async fn spawn_stalling_server(sent: usize) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut stalled = Vec::new();
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = socket.read(&mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..read]);
+            }
+            let headers = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\ncontent-type: application/octet-stream\r\nconnection: close\r\n\r\n",
+                sent * 1000,
+            );
+            socket.write_all(headers.as_bytes()).await.unwrap();
+            if !request.starts_with(b"HEAD") {
+                socket.write_all(&vec![1u8; sent]).await.unwrap();
+                stalled.push(socket);
+            }
+        }
+    });
+    address
+}
+
+#[tokio::test]
+async fn test_cancel_download_interrupts_transfer() {
+    let address = spawn_stalling_server(4096).await;
+    let temp = tempfile::tempdir().unwrap();
+    let manager = ModelDownloadManager::new(temp.path().to_path_buf()).unwrap().with_min_free_reserve(0);
+    let model_id = Uuid::new_v4();
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+    manager
+        .queue_download(model_id, "stalled.gguf".to_string(), format!("{}/model.gguf", address), None, ChecksumType::SHA256, tx)
+        .unwrap();
+    let temp_file = temp.path().join("temp").join(format!("{}.tmp", model_id));
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let snapshot = rx.recv().await.expect("download ended before any data arrived");
+            if snapshot.downloaded_bytes > 0 {
+                break;
+            }
+        }
+    })
+    .await
+    .expect("no data arrived");
+    assert!(temp_file.exists());
+    assert_eq!(manager.active_download_count(), 1);
+
+    // The server never sends the rest, so only the token can end the transfer
+    manager.cancel_download(model_id).await.unwrap();
+    let last = tokio::time::timeout(Duration::from_secs(2), async {
+        let mut last = None;
+        while let Some(snapshot) = rx.recv().await {
+            last = Some(snapshot);
+        }
+        last
+    })
+    .await
+    .expect("cancelled transfer did not stop")
+    .unwrap();
+
+    assert!(matches!(last.status, DownloadStatus::Cancelled));
+    assert!(last.error_message.is_some());
+    assert!(!temp_file.exists());
+    assert!(!temp.path().join("temp").join(format!("{}.meta", model_id)).exists());
+    assert_eq!(manager.active_download_count(), 0);
+}
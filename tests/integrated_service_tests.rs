@@ -0,0 +1,1814 @@
+//! IntegratedModelService feature tests
+//!
+//! Covers client-level behaviour layered on top of the service stack:
+//! install path resolution, persistence helpers and maintenance operations.
+
+use burncloud_client_models::{
+    AuditOperation, Capability, CatalogFormat, ChecksumType, ClientError, ConsistencyRule, DeleteReport, DiscoveredModel, DiscoveryError, DownloadError, InstallSource,
+    InstallationConfig, IntegratedModelService, IntegratedServiceConfig, LocalStatus, ModelEventKind, ModelRequirements,
+    ModelSearchRequest, ModelValidator, Notification, NotificationSink, NotificationType, Objective, RuleSeverity, ScrubClock, WebhookSink,
+    CURRENT_SCHEMA_VERSION, MODEL_LOG_CAPACITY,
+};
+use burncloud_database::Database;
+use burncloud_service_models::{CreateModelRequest, Model, ModelStatus, ModelType, RuntimeConfig, UpdateModelRequest};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Create a service backed by an in-memory database
+async fn setup_service(install_dir: &std::path::Path) -> IntegratedModelService {
+    IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: install_dir.to_path_buf(),
+        ..Default::default()
+    })
+    .await
+    .unwrap()
+}
+
+/// Create a minimal model request
+fn model_request(name: &str, model_type: ModelType) -> CreateModelRequest {
+    CreateModelRequest {
+        name: name.to_string(),
+        display_name: format!("{} Display", name),
+        version: "1.0.0".to_string(),
+        model_type,
+        provider: "TestProvider".to_string(),
+        file_size: 1024,
+        description: None,
+        license: None,
+        tags: vec![],
+        languages: vec![],
+        file_path: None,
+        download_url: None,
+        config: HashMap::new(),
+        is_official: false,
+    }
+}
+
+#[tokio::test]
+async fn test_install_model_uses_default_install_dir() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("default-dir-model", ModelType::Chat)).await.unwrap();
+    let installed = service.install_model(model.id, None).await.unwrap();
+
+    let expected = temp.path().join("default-dir-model");
+    assert_eq!(installed.install_path, expected.to_string_lossy());
+    assert!(expected.is_dir());
+}
+
+#[tokio::test]
+async fn test_install_model_with_explicit_override() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("override-model", ModelType::Chat)).await.unwrap();
+    let override_path = "/opt/custom/override-model".to_string();
+    let installed = service.install_model(model.id, override_path.clone()).await.unwrap();
+
+    assert_eq!(installed.install_path, override_path);
+    assert!(!temp.path().join("override-model").exists());
+}
+
+/// Build a runtime config with user-tuned values
+fn tuned_runtime_config() -> RuntimeConfig {
+    let mut custom_params = HashMap::new();
+    custom_params.insert("repeat_penalty".to_string(), serde_json::json!(1.1));
+
+    RuntimeConfig {
+        max_context_length: Some(8192),
+        temperature: Some(0.3),
+        top_p: Some(0.95),
+        top_k: Some(40),
+        max_tokens: Some(1024),
+        stop_sequences: vec!["</s>".to_string()],
+        batch_size: Some(16),
+        max_concurrent_requests: Some(4),
+        gpu_device_ids: vec![0],
+        memory_limit_mb: Some(8192),
+        enable_streaming: true,
+        custom_params,
+    }
+}
+
+#[tokio::test]
+async fn test_runtime_config_save_and_load() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("tuned-model", ModelType::Chat)).await.unwrap();
+    assert!(service.get_runtime_config(model.id).await.unwrap().is_none());
+
+    let runtime_config = tuned_runtime_config();
+    service.save_runtime_config(model.id, runtime_config.clone()).await.unwrap();
+
+    let loaded = service.get_runtime_config(model.id).await.unwrap();
+    assert_eq!(loaded, Some(runtime_config.clone()));
+
+    // Baked-in model config stays untouched
+    let stored_model = service.get_model(model.id).await.unwrap().unwrap();
+    assert_eq!(stored_model.config, model.config);
+
+    // Starting the model picks up the saved config
+    service.install_model(model.id, None).await.unwrap();
+    let started_with = service.start_model(model.id).await.unwrap();
+    assert_eq!(started_with, Some(runtime_config));
+}
+
+#[tokio::test]
+async fn test_install_seeds_default_runtime_config_by_model_type() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let code = service.create_model(model_request("coder", ModelType::Code)).await.unwrap();
+    service.install_model(code.id, None).await.unwrap();
+    let defaults = service.get_runtime_config(code.id).await.unwrap().expect("code models get defaults");
+    assert_eq!(defaults.custom_params.get("code_mode"), Some(&serde_json::json!(true)));
+    assert_eq!(defaults.temperature, Some(0.1));
+
+    let chat = service.register_and_install(model_request("chatter", ModelType::Chat), None).await.unwrap();
+    let defaults = service.get_runtime_config(chat.model.id).await.unwrap().unwrap();
+    assert_eq!((defaults.temperature, defaults.top_p), (Some(0.7), Some(0.9)));
+
+    // A config the user already saved is kept
+    let tuned = service.create_model(model_request("tuned-coder", ModelType::Code)).await.unwrap();
+    service.save_runtime_config(tuned.id, tuned_runtime_config()).await.unwrap();
+    service.install_model(tuned.id, None).await.unwrap();
+    assert_eq!(service.get_runtime_config(tuned.id).await.unwrap(), Some(tuned_runtime_config()));
+
+    // No sensible defaults for audio models
+    let audio = service.create_model(model_request("listener", ModelType::Audio)).await.unwrap();
+    service.install_model(audio.id, None).await.unwrap();
+    assert!(service.get_runtime_config(audio.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_initialize_is_idempotent_and_open_reattaches() {
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("models.db");
+    let config = IntegratedServiceConfig {
+        database_path: Some(db_path.to_string_lossy().to_string()),
+        default_install_dir: temp.path().join("models"),
+        ..Default::default()
+    };
+
+    let service = IntegratedModelService::with_config(config.clone()).await.unwrap();
+    let model = service.create_model(model_request("persisted-model", ModelType::Chat)).await.unwrap();
+    let runtime_config = tuned_runtime_config();
+    service.save_runtime_config(model.id, runtime_config.clone()).await.unwrap();
+    drop(service);
+
+    // Re-running initialization against the same file keeps existing data
+    let reinitialized = IntegratedModelService::with_config(config).await.unwrap();
+    assert_eq!(reinitialized.get_runtime_config(model.id).await.unwrap(), Some(runtime_config.clone()));
+    drop(reinitialized);
+
+    let reopened = IntegratedModelService::open(&db_path).await.unwrap();
+    assert_eq!(reopened.get_runtime_config(model.id).await.unwrap(), Some(runtime_config));
+}
+
+#[tokio::test]
+async fn test_open_missing_database_fails() {
+    let temp = tempfile::tempdir().unwrap();
+    let result = IntegratedModelService::open(temp.path().join("absent.db")).await;
+    assert!(matches!(result, Err(ClientError::SchemaMissing(_))));
+}
+
+/// Create a database file holding the given raw client tables
+async fn seed_raw_database(path: &std::path::Path, statements: &[&str]) {
+    let mut database = Database::new(&path.to_string_lossy());
+    database.initialize().await.unwrap();
+    for statement in statements {
+        database.execute_query(statement).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_older_schema_needs_migration() {
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("legacy.db");
+
+    // Version 1 layout: runtime configs existed, but no version was recorded
+    seed_raw_database(&db_path, &[
+        "CREATE TABLE model_runtime_configs (model_id TEXT PRIMARY KEY NOT NULL, config TEXT NOT NULL, updated_at TEXT NOT NULL)",
+    ]).await;
+
+    let service = IntegratedModelService::open(&db_path).await.unwrap();
+    assert_eq!(service.schema_version().await.unwrap(), 1);
+    assert!(service.needs_migration().await.unwrap());
+
+    assert_eq!(service.migrate().await.unwrap(), CURRENT_SCHEMA_VERSION);
+    assert_eq!(service.schema_version().await.unwrap(), CURRENT_SCHEMA_VERSION);
+    assert!(!service.needs_migration().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_with_config_migrates_older_schema_before_use() {
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("v2.db");
+
+    // Version 2 layout: runtime configs and client_meta, none of the later tables
+    seed_raw_database(&db_path, &[
+        "CREATE TABLE model_runtime_configs (model_id TEXT PRIMARY KEY NOT NULL, config TEXT NOT NULL, updated_at TEXT NOT NULL)",
+        "CREATE TABLE client_meta (key TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL)",
+        "INSERT INTO client_meta (key, value) VALUES ('schema_version', '2')",
+    ]).await;
+
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(db_path.to_string_lossy().to_string()),
+        default_install_dir: temp.path().join("models"),
+        ..Default::default()
+    }).await.unwrap();
+    assert_eq!(service.schema_version().await.unwrap(), CURRENT_SCHEMA_VERSION);
+
+    // Creating a model writes to model_events, which only exists after migration
+    let model = service.create_model(model_request("migrated-model", ModelType::Chat)).await.unwrap();
+    assert!(service.get_model(model.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_newer_schema_is_rejected() {
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("future.db");
+
+    seed_raw_database(&db_path, &[
+        "CREATE TABLE client_meta (key TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL)",
+        "INSERT INTO client_meta (key, value) VALUES ('schema_version', '99')",
+    ]).await;
+
+    let result = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(db_path.to_string_lossy().to_string()),
+        default_install_dir: temp.path().join("models"),
+        ..Default::default()
+    }).await;
+
+    assert!(matches!(
+        result,
+        Err(ClientError::UnsupportedSchemaVersion { found: 99, supported: CURRENT_SCHEMA_VERSION })
+    ));
+}
+
+#[tokio::test]
+async fn test_delete_models_reports_each_outcome() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let plain = service.create_model(model_request("plain-model", ModelType::Chat)).await.unwrap();
+    let installed = service.create_model(model_request("installed-model", ModelType::Chat)).await.unwrap();
+    service.install_model(installed.id, None).await.unwrap();
+    let missing = Uuid::new_v4();
+
+    let report = service.delete_models(&[plain.id, installed.id, missing], false).await.unwrap();
+    assert_eq!(report, DeleteReport {
+        deleted: vec![plain.id],
+        skipped_installed: vec![installed.id],
+        not_found: vec![missing],
+        failed: vec![],
+    });
+    assert!(service.get_model(installed.id).await.unwrap().is_some());
+
+    let forced = service.delete_models(&[installed.id], true).await.unwrap();
+    assert_eq!(forced.deleted, vec![installed.id]);
+    assert!(service.get_model(installed.id).await.unwrap().is_none());
+    assert!(service.get_installed_models().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_delete_models_continues_after_a_failure() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut ids = Vec::new();
+    for name in ["first-model", "stuck-model", "last-model"] {
+        ids.push(service.create_model(model_request(name, ModelType::Chat)).await.unwrap().id);
+    }
+    let stuck = ids[1];
+    service.install_model(stuck, None).await.unwrap();
+
+    // Cleaning up the middle model's records fails
+    let database = service.database();
+    database.execute_query(&format!(
+        "INSERT INTO model_validations (model_id, validated_at, is_valid) VALUES ('{}', '2024-01-01T00:00:00+00:00', 1)", stuck
+    )).await.unwrap();
+    database.execute_query(&format!(
+        "CREATE TRIGGER keep_validation BEFORE DELETE ON model_validations WHEN OLD.model_id = '{}' \
+         BEGIN SELECT RAISE(ABORT, 'validation row is locked'); END", stuck
+    )).await.unwrap();
+
+    let report = service.delete_models(&ids, true).await.unwrap();
+    assert_eq!(report.deleted, vec![ids[0], ids[2]]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, stuck);
+    assert!(report.failed[0].1.contains("validation row is locked"), "{}", report.failed[0].1);
+    assert!(service.get_model(ids[2]).await.unwrap().is_none());
+}
+
+/// Build a discovery result with the given name
+fn discovered_model(name: &str) -> DiscoveredModel {
+    DiscoveredModel {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        version: "1.0.0".to_string(),
+        display_name: name.to_string(),
+        description: String::new(),
+        size_gb: 1.0,
+        model_type: burncloud_client_models::discovery::ModelType::ChatCompletion,
+        provider: "TestProvider".to_string(),
+        tags: vec![],
+        capabilities: vec![],
+        requirements: ModelRequirements {
+            min_ram_gb: 4.0,
+            min_vram_gb: None,
+            gpu_required: false,
+            cpu_cores: 2,
+            disk_space_gb: 1.0,
+            supported_platforms: vec![],
+            cuda_version: None,
+            python_version: None,
+        },
+        download_url: String::new(),
+        checksum: String::new(),
+        checksum_type: ChecksumType::SHA256,
+        license: "MIT".to_string(),
+        rating: 4.0,
+        download_count: 0,
+        last_updated: chrono::Utc::now(),
+        is_featured: false,
+        is_verified: false,
+        repository_url: None,
+        documentation_url: None,
+        depends_on: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_annotate_availability() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    service.create_model(model_request("registered-model", ModelType::Chat)).await.unwrap();
+    let installed = service.create_model(model_request("installed-model", ModelType::Chat)).await.unwrap();
+    service.install_model(installed.id, None).await.unwrap();
+
+    let discovered = vec![
+        discovered_model("Installed-Model"),
+        discovered_model("registered-model"),
+        discovered_model("brand-new-model"),
+    ];
+    let statuses = service.annotate_availability(&discovered).await.unwrap();
+
+    assert_eq!(statuses, vec![LocalStatus::Installed, LocalStatus::Registered, LocalStatus::NotPresent]);
+    assert_eq!(statuses[0].badge(), Some("已安装"));
+    assert_eq!(statuses[2].badge(), None);
+}
+
+#[tokio::test]
+async fn test_model_logs_keep_only_most_recent_lines() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let model_id = Uuid::new_v4();
+
+    for i in 0..MODEL_LOG_CAPACITY + 10 {
+        service.append_model_log(model_id, format!("line {}", i));
+    }
+
+    let logs = service.get_model_logs(model_id, None);
+    assert_eq!(logs.len(), MODEL_LOG_CAPACITY);
+    assert_eq!(logs[0].line, "line 10");
+    assert_eq!(logs.last().unwrap().line, format!("line {}", MODEL_LOG_CAPACITY + 9));
+
+    let tail: Vec<String> = service.get_model_logs(model_id, Some(2)).into_iter().map(|e| e.line).collect();
+    assert_eq!(tail, vec![format!("line {}", MODEL_LOG_CAPACITY + 8), format!("line {}", MODEL_LOG_CAPACITY + 9)]);
+}
+
+#[tokio::test]
+async fn test_start_failure_is_logged() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("logged-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, None).await.unwrap();
+    service.start_model(model.id).await.unwrap();
+    assert!(service.start_model(model.id).await.is_err());
+
+    let last = service.get_model_logs(model.id, Some(1));
+    assert!(last[0].line.contains("cannot be started"));
+}
+
+#[tokio::test]
+async fn test_suggest_tags_by_prefix_and_frequency() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let catalog: [(&str, &[&str]); 4] = [
+        ("model-a", &["chat", "chinese", "large"]),
+        ("model-b", &["chat", "code"]),
+        ("model-c", &["chat", "chinese", "multichannel"]),
+        ("model-d", &["code", "chart"]),
+    ];
+    for (name, tags) in catalog {
+        let mut request = model_request(name, ModelType::Chat);
+        request.tags = tags.iter().map(|t| t.to_string()).collect();
+        service.create_model(request).await.unwrap();
+    }
+
+    // Prefix matches by frequency, then substring matches
+    let suggestions = service.suggest_tags("ch", 10).await.unwrap();
+    assert_eq!(suggestions, vec!["chat", "chinese", "chart", "multichannel"]);
+
+    let limited = service.suggest_tags("CH", 2).await.unwrap();
+    assert_eq!(limited, vec!["chat", "chinese"]);
+
+    assert!(service.suggest_tags("vision", 5).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_create_model_idempotent_dedupes_retries() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let key = Some("create-retry-1".to_string());
+
+    let first = service
+        .create_model_idempotent(model_request("retried-model", ModelType::Chat), key.clone())
+        .await
+        .unwrap();
+    let second = service
+        .create_model_idempotent(model_request("retried-model", ModelType::Chat), key)
+        .await
+        .unwrap();
+
+    assert_eq!(first.id, second.id);
+    assert_eq!(service.list_models(None).await.unwrap().len(), 1);
+
+    // Without a key the duplicate is still rejected
+    let unkeyed = service
+        .create_model_idempotent(model_request("retried-model", ModelType::Chat), None)
+        .await;
+    assert!(unkeyed.is_err());
+}
+
+#[tokio::test]
+async fn test_compact_after_bulk_delete() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(temp.path().join("models.db").to_string_lossy().to_string()),
+        default_install_dir: temp.path().join("models"),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut ids = Vec::new();
+    for i in 0..50 {
+        let model = service.create_model(model_request(&format!("churn-{}", i), ModelType::Chat)).await.unwrap();
+        service.save_runtime_config(model.id, tuned_runtime_config()).await.unwrap();
+        ids.push(model.id);
+    }
+    let report = service.delete_models(&ids, false).await.unwrap();
+    assert_eq!(report.deleted.len(), 50);
+
+    let compacted = service.compact().await.unwrap();
+    assert!(compacted.bytes_reclaimed >= 0);
+    assert!(compacted.size_after <= compacted.size_before);
+    assert!(service.list_models(None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_manifest_registers_models() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let manifest = r#"
+[[models]]
+name = "qwen2-7b-instruct"
+provider = "Alibaba"
+version = "2.0"
+type = "chat"
+download_url = "https://example.com/qwen2-7b.gguf"
+tags = ["qwen", "instruct"]
+
+[[models]]
+name = "bge-m3"
+provider = "BAAI"
+version = "1.0"
+type = "embedding"
+"#;
+
+    let report = service.import_manifest(manifest, None).await.unwrap();
+    assert_eq!(report.created.len(), 2);
+    assert!(report.updated.is_empty());
+
+    let models = service.list_models(None).await.unwrap();
+    let qwen = models.iter().find(|m| m.name == "qwen2-7b-instruct").unwrap();
+    assert_eq!(qwen.model_type, ModelType::Chat);
+    assert_eq!(qwen.download_url.as_deref(), Some("https://example.com/qwen2-7b.gguf"));
+    assert_eq!(qwen.tags, vec!["qwen".to_string(), "instruct".to_string()]);
+    assert!(models.iter().any(|m| m.name == "bge-m3" && m.model_type == ModelType::Embedding));
+
+    // Re-importing updates by name instead of creating duplicates
+    let report = service.import_manifest(manifest, None).await.unwrap();
+    assert!(report.created.is_empty());
+    assert_eq!(report.updated.len(), 2);
+    assert_eq!(service.list_models(None).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_import_manifest_rejects_invalid_entry_atomically() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let manifest = r#"
+[[models]]
+name = "valid-model"
+provider = "TestProvider"
+version = "1.0"
+type = "chat"
+
+[[models]]
+name = "broken-model"
+provider = ""
+version = "1.0"
+type = "chat"
+"#;
+
+    let err = service.import_manifest(manifest, None).await.unwrap_err();
+    match err {
+        ClientError::InvalidManifest(issues) => {
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].entry, Some(2));
+            assert_eq!(issues[0].line, Some(8));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(service.list_models(None).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_manifest_requires_valid_signature() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let temp = tempfile::tempdir().unwrap();
+    let signing_key = SigningKey::from_bytes(&[42; 32]);
+    let validator = ModelValidator::new(temp.path().join("validation")).unwrap()
+        .with_trusted_key(signing_key.verifying_key().to_bytes())
+        .unwrap();
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().join("models"),
+        require_signed_manifests: true,
+        ..Default::default()
+    })
+    .await
+    .unwrap()
+    .with_manifest_validator(validator);
+
+    let manifest = r#"
+[[models]]
+name = "signed-model"
+provider = "TestProvider"
+version = "1.0"
+type = "chat"
+"#;
+    let signature: String = signing_key.sign(manifest.as_bytes()).to_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    // Unsigned and tampered manifests are rejected before anything is written
+    let unsigned = service.import_manifest(manifest, None).await;
+    assert!(matches!(unsigned, Err(ClientError::ValidationFailed(_))));
+    let tampered = manifest.replace("signed-model", "evil-model");
+    let result = service.import_manifest(&tampered, Some(&signature)).await;
+    assert!(matches!(result, Err(ClientError::ValidationFailed(_))));
+    assert!(service.list_models(None).await.unwrap().is_empty());
+
+    let report = service.import_manifest(manifest, Some(&signature)).await.unwrap();
+    assert_eq!(report.created.len(), 1);
+    assert_eq!(service.list_models(None).await.unwrap()[0].name, "signed-model");
+}
+
+#[tokio::test]
+async fn test_force_delete_running_model() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("force-delete-model", ModelType::Chat)).await.unwrap();
+    let installed = service.install_model(model.id, None).await.unwrap();
+    let install_dir = std::path::PathBuf::from(&installed.install_path);
+    std::fs::write(install_dir.join("model.gguf"), b"weights").unwrap();
+    service.start_model(model.id).await.unwrap();
+
+    service.force_delete(model.id).await.unwrap();
+
+    assert!(service.get_model(model.id).await.unwrap().is_none());
+    assert!(service.get_installed_models().await.unwrap().is_empty());
+    assert!(!install_dir.exists());
+    assert_eq!(std::fs::read_dir(temp.path()).unwrap().count(), 0);
+
+    let logs: Vec<String> = service.get_model_logs(model.id, None).into_iter().map(|e| e.line).collect();
+    assert!(logs.contains(&"Model stopped".to_string()));
+    assert_eq!(logs.last().map(String::as_str), Some("Model deleted"));
+}
+
+#[tokio::test]
+async fn test_force_delete_missing_model() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let err = service.force_delete(Uuid::new_v4()).await.unwrap_err();
+    assert!(matches!(err, ClientError::ResourceNotFound(_)));
+}
+
+#[tokio::test]
+async fn test_scan_and_register_records_local_file_source() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let models_dir = tempfile::tempdir().unwrap();
+    std::fs::write(models_dir.path().join("llama-local.gguf"), b"GGUF weights").unwrap();
+    std::fs::write(models_dir.path().join("notes.txt"), b"not a model").unwrap();
+
+    let registered = service.scan_and_register(models_dir.path()).await.unwrap();
+    assert_eq!(registered.len(), 1);
+    let installed = &registered[0];
+    assert_eq!(installed.model.name, "llama-local");
+    assert_eq!(
+        service.get_install_source(installed.model.id).await.unwrap(),
+        Some(InstallSource::LocalFile)
+    );
+
+    // A manually installed model is recorded separately and filtered out
+    let manual = service.create_model(model_request("manual-model", ModelType::Chat)).await.unwrap();
+    service.install_model(manual.id, None).await.unwrap();
+    assert_eq!(service.get_install_source(manual.id).await.unwrap(), Some(InstallSource::Manual));
+
+    let local = service.get_installed_models_by_source(InstallSource::LocalFile).await.unwrap();
+    assert_eq!(local.len(), 1);
+    assert_eq!(local[0].model.id, installed.model.id);
+
+    // Rescanning doesn't register the same file twice
+    assert!(service.scan_and_register(models_dir.path()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_import_ollama_models_records_ollama_source() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    // Ollama's store: a manifest per tag naming its weights blob
+    let ollama = tempfile::tempdir().unwrap();
+    let weights = b"GGUF llama3 weights";
+    let digest = format!("sha256:{:x}", Sha256::digest(weights));
+    std::fs::create_dir_all(ollama.path().join("blobs")).unwrap();
+    let blob = ollama.path().join("blobs").join(digest.replace(':', "-"));
+    std::fs::write(&blob, weights).unwrap();
+    let library = ollama.path().join("manifests/registry.ollama.ai/library");
+    for (name, tag, digest) in [("llama3", "8b", digest.as_str()), ("mistral", "latest", "sha256:missing")] {
+        std::fs::create_dir_all(library.join(name)).unwrap();
+        let manifest = serde_json::json!({
+            "schemaVersion": 2,
+            "layers": [
+                { "mediaType": "application/vnd.ollama.image.model", "digest": digest, "size": weights.len() },
+                { "mediaType": "application/vnd.ollama.image.template", "digest": "sha256:template", "size": 10 },
+            ],
+        });
+        std::fs::write(library.join(name).join(tag), manifest.to_string()).unwrap();
+    }
+
+    // The tag whose blob is missing is skipped
+    let imported = service.import_ollama_models(ollama.path()).await.unwrap();
+    assert_eq!(imported.len(), 1);
+    let installed = &imported[0];
+    assert_eq!(installed.model.name, "llama3:8b");
+    assert_eq!(installed.model.file_size, weights.len() as u64);
+    assert_eq!(installed.install_path, blob.to_string_lossy());
+    assert_eq!(service.get_install_source(installed.model.id).await.unwrap(), Some(InstallSource::Ollama));
+    assert_eq!(
+        service.get_install_sources().await.unwrap(),
+        HashMap::from([(installed.model.id, InstallSource::Ollama)])
+    );
+
+    assert!(service.import_ollama_models(ollama.path()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_resource_overview_matches_manual_sum() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    for i in 0..50 {
+        let mut request = model_request(&format!("overview-model-{}", i), ModelType::Text);
+        request.file_size = 1_000_000 + i * 4096;
+        let model = service.create_model(request).await.unwrap();
+        service.install_model(model.id, None).await.unwrap();
+    }
+    // Registered but not installed models don't count
+    let mut request = model_request("not-installed", ModelType::Text);
+    request.file_size = 999_999_999;
+    service.create_model(request).await.unwrap();
+
+    let installed = service.get_installed_models().await.unwrap();
+    let expected: u64 = installed.iter().map(|m| m.model.file_size).sum();
+
+    let overview = service.resource_overview().await.unwrap();
+    assert_eq!(installed.len(), 50);
+    assert_eq!(overview.total_disk_usage_bytes, expected);
+    assert_eq!(overview.ports_in_use, installed.iter().filter_map(|m| m.port).collect::<Vec<_>>());
+    assert_eq!(overview.active_processes, installed.iter().filter_map(|m| m.process_id).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn test_rename_installed_model_moves_install_dir() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("old-name", ModelType::Chat)).await.unwrap();
+    let installed = service.install_model(model.id, None).await.unwrap();
+    let old_dir = std::path::PathBuf::from(&installed.install_path);
+    std::fs::write(old_dir.join("model.gguf"), b"weights").unwrap();
+    service.create_model(model_request("taken-name", ModelType::Chat)).await.unwrap();
+
+    let err = service.rename_model(model.id, "Taken-Name").await.unwrap_err();
+    assert!(matches!(err, ClientError::ServiceError(_)));
+    let err = service.rename_model(model.id, "  ").await.unwrap_err();
+    assert!(matches!(err, ClientError::ValidationFailed(_)));
+
+    let renamed = service.rename_model(model.id, "  new-name ").await.unwrap();
+    assert_eq!(renamed.name, "new-name");
+
+    let new_dir = temp.path().join("new-name");
+    assert!(!old_dir.exists());
+    assert_eq!(std::fs::read(new_dir.join("model.gguf")).unwrap(), b"weights");
+    let installed = service.get_installed_models().await.unwrap().remove(0);
+    assert_eq!(installed.install_path, new_dir.to_string_lossy());
+
+    service.start_model(model.id).await.unwrap();
+    let err = service.rename_model(model.id, "running-name").await.unwrap_err();
+    assert!(matches!(err, ClientError::OperationNotAllowed(_)));
+    assert!(new_dir.is_dir());
+}
+
+#[tokio::test]
+async fn test_rerank_discovered_favors_local_provider() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut request = model_request("favorite-local", ModelType::Chat);
+    request.provider = "FavoriteProvider".to_string();
+    let favorite = service.create_model(request).await.unwrap();
+    service.install_model(favorite.id, None).await.unwrap();
+
+    let dropped = service.create_model(model_request("dropped-model", ModelType::Chat)).await.unwrap();
+    service.install_model(dropped.id, None).await.unwrap();
+    service.uninstall_model(dropped.id).await.unwrap();
+    // Registered from a manifest but never installed, so not a dislike
+    let catalog_only = service.create_model(model_request("catalog-only", ModelType::Chat)).await.unwrap();
+    service.set_install_source(catalog_only.id, InstallSource::Manifest).await.unwrap();
+
+    let mut from_favorite = discovered_model("favorite-remote");
+    from_favorite.provider = "favoriteprovider".to_string();
+    let discovered = vec![
+        discovered_model("dropped-model"),
+        discovered_model("catalog-only"),
+        discovered_model("server-top"),
+        from_favorite,
+    ];
+
+    let names: Vec<String> = service.rerank_discovered(discovered).await
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+    assert_eq!(names, vec!["favorite-remote", "catalog-only", "server-top", "dropped-model"]);
+}
+
+#[tokio::test]
+async fn test_offline_mode_blocks_network_but_not_local_operations() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().join("models"),
+        offline: true,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    assert!(service.is_offline());
+
+    // A non-routable address would hang if a request were actually sent
+    let discovery = service.discovery_client("http://10.255.255.1").unwrap();
+    let search = tokio::time::timeout(
+        std::time::Duration::from_secs(1),
+        discovery.search_models(ModelSearchRequest::default()),
+    )
+    .await
+    .expect("offline discovery should not wait on the network");
+    assert!(matches!(search, Err(DiscoveryError::Offline(_))));
+
+    let downloads = service.download_manager(temp.path().join("downloads")).unwrap();
+    let download = downloads
+        .download_model(
+            Uuid::new_v4(),
+            "remote.gguf".to_string(),
+            "http://10.255.255.1/remote.gguf".to_string(),
+            "0".repeat(64),
+            ChecksumType::SHA256,
+        )
+        .await;
+    assert!(matches!(download, Err(DownloadError::Offline(_))));
+    // Managers share the service's queue, which the model page lists
+    assert!(std::sync::Arc::ptr_eq(downloads.queue(), service.download_queue()));
+
+    // Local catalog and install paths keep working
+    let model = service.create_model(model_request("offline-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, None).await.unwrap();
+    assert_eq!(service.get_installed_models().await.unwrap().len(), 1);
+
+    let source = temp.path().join("local.gguf");
+    std::fs::write(&source, b"local weights").unwrap();
+    let installation = downloads
+        .install_model(model.id, source, InstallationConfig::default())
+        .await
+        .unwrap();
+    assert!(installation.install_path.join("local.gguf").is_file());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_interleaved_start_stop_leaves_coherent_status() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = std::sync::Arc::new(setup_service(temp.path()).await);
+
+    let model = service.create_model(model_request("contended-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, None).await.unwrap();
+
+    let handles: Vec<_> = (0..20)
+        .map(|i| {
+            let service = service.clone();
+            tokio::spawn(async move {
+                // Requests that don't fit the current status are rejected, never half-applied
+                if i % 2 == 0 {
+                    let _ = service.start_model(model.id).await;
+                } else {
+                    let _ = service.stop_model(model.id).await;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let installed = service.get_installed_models().await.unwrap().remove(0);
+    assert!(matches!(installed.status, ModelStatus::Running | ModelStatus::Stopped));
+
+    // Every accepted transition finished before the next one began
+    let logs: Vec<String> = service.get_model_logs(model.id, None).into_iter().map(|e| e.line).collect();
+    for pair in logs.windows(2) {
+        if pair[0] == "Starting model" {
+            assert_eq!(pair[1], "Model running");
+        }
+        if pair[0] == "Stopping model" {
+            assert_eq!(pair[1], "Model stopped");
+        }
+    }
+}
+
+/// Mount a single-page discovery search response
+async fn mount_catalog(server: &wiremock::MockServer, models: &[DiscoveredModel]) {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    server.reset().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/models/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "models": models,
+            "total_count": models.len(),
+            "page": 1,
+            "page_size": 20,
+            "has_next": false,
+            "search_time_ms": 1,
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_sync_from_discovery_adds_updates_and_flags_stale() {
+    let server = wiremock::MockServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let client = service.discovery_client(server.uri()).unwrap();
+
+    let local = service.create_model(model_request("sync-local", ModelType::Chat)).await.unwrap();
+
+    let mut refreshed = discovered_model("Sync-Local");
+    refreshed.download_url = "https://example.com/sync-local-v2.gguf".to_string();
+    refreshed.checksum = "a".repeat(64);
+    let mut added = discovered_model("sync-new");
+    added.download_url = "https://example.com/sync-new.gguf".to_string();
+    added.requirements.gpu_required = true;
+    mount_catalog(&server, &[refreshed.clone(), added.clone()]).await;
+
+    let report = service.sync_from_discovery(&client, ModelSearchRequest::default()).await.unwrap();
+    assert_eq!(report.updated, vec![local.id]);
+    assert_eq!(report.added.len(), 1);
+    assert!(report.stale.is_empty());
+
+    let local = service.get_model(local.id).await.unwrap().unwrap();
+    assert_eq!(local.download_url.as_deref(), Some("https://example.com/sync-local-v2.gguf"));
+    assert_eq!(local.checksum, Some("a".repeat(64)));
+    assert_eq!(local.rating, Some(4.0));
+
+    let new_id = report.added[0];
+    let new_model = service.get_model(new_id).await.unwrap().unwrap();
+    assert_eq!(new_model.name, "sync-new");
+    assert_eq!(new_model.config.get("gpu_required"), Some(&serde_json::json!(true)));
+    assert_eq!(service.get_install_source(new_id).await.unwrap(), Some(InstallSource::Discovery));
+
+    // Unchanged entries are not reported again; dropped ones are flagged, not deleted
+    mount_catalog(&server, &[added]).await;
+    let report = service.sync_from_discovery(&client, ModelSearchRequest::default()).await.unwrap();
+    assert!(report.added.is_empty());
+    assert!(report.updated.is_empty());
+    assert_eq!(report.stale, vec![local.id]);
+
+    let stale: Vec<Uuid> = service.get_stale_models().await.unwrap().iter().map(|m| m.id).collect();
+    assert_eq!(stale, vec![local.id]);
+    assert!(service.get_model(local.id).await.unwrap().is_some());
+
+    // Listed again, the flag clears
+    mount_catalog(&server, &[refreshed]).await;
+    let report = service.sync_from_discovery(&client, ModelSearchRequest::default()).await.unwrap();
+    assert_eq!(report.stale, vec![new_id]);
+    let stale: Vec<Uuid> = service.get_stale_models().await.unwrap().iter().map(|m| m.id).collect();
+    assert_eq!(stale, vec![new_id]);
+}
+
+#[tokio::test]
+async fn test_get_model_details_merges_discovery_entry() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    let server = wiremock::MockServer::start().await;
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let client = service.discovery_client(server.uri()).unwrap();
+
+    let summary = discovered_model("detail-model");
+    mount_catalog(&server, std::slice::from_ref(&summary)).await;
+    let report = service.sync_from_discovery(&client, ModelSearchRequest::default()).await.unwrap();
+    let local_id = report.added[0];
+
+    // The details endpoint carries the richer fields; it should be hit only once
+    let mut details = summary.clone();
+    details.description = "A detailed chat model".to_string();
+    details.capabilities = vec!["chat".to_string(), "tools".to_string()];
+    details.requirements.min_ram_gb = 16.0;
+    details.requirements.gpu_required = true;
+    details.repository_url = Some("https://example.com/detail-model".to_string());
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/models/{}", summary.id)))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&details))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let merged = service.get_model_details(local_id, Some(&client)).await.unwrap();
+    assert_eq!(merged.model.id, local_id);
+    assert!(merged.installed.is_none());
+    assert_eq!(merged.capabilities, vec!["chat", "tools"]);
+    assert_eq!(merged.requirements.min_memory_gb, 16.0);
+    assert!(merged.requirements.gpu_required);
+    assert_eq!(merged.repository_url.as_deref(), Some("https://example.com/detail-model"));
+    assert_eq!(merged.description.as_deref(), Some("A detailed chat model"));
+
+    // Served from the cache the second time
+    let cached = service.get_model_details(local_id, Some(&client)).await.unwrap();
+    assert_eq!(cached.capabilities, merged.capabilities);
+
+    // Without a client only local data is returned
+    let local_only = service.get_model_details(local_id, None).await.unwrap();
+    assert!(local_only.discovered.is_none());
+    assert!(local_only.capabilities.is_empty());
+    assert!(local_only.repository_url.is_none());
+
+    // Installed models are fully local and skip the catalog
+    service.install_model(local_id, None).await.unwrap();
+    let installed = service.get_model_details(local_id, Some(&client)).await.unwrap();
+    assert!(installed.installed.is_some());
+    assert!(installed.discovered.is_none());
+
+    server.verify().await;
+}
+
+#[tokio::test]
+async fn test_get_model_details_unknown_model() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let result = service.get_model_details(Uuid::new_v4(), None).await;
+    assert!(matches!(result, Err(ClientError::ResourceNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_backfill_checksums_hashes_installed_files() {
+    use sha2::{Digest, Sha256};
+
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let bytes = b"manually added weights";
+    let file = temp.path().join("manual.gguf");
+    std::fs::write(&file, bytes).unwrap();
+    let manual = service.create_model(model_request("manual", ModelType::Chat)).await.unwrap();
+    assert!(manual.checksum.is_none());
+    service.install_model(manual.id, file.to_string_lossy().to_string()).await.unwrap();
+
+    // Installed, but its file is gone
+    let missing = service.create_model(model_request("missing", ModelType::Chat)).await.unwrap();
+    service.install_model(missing.id, temp.path().join("missing.gguf").to_string_lossy().to_string()).await.unwrap();
+
+    assert_eq!(service.backfill_checksums().await.unwrap(), 1);
+    let manual = service.get_model(manual.id).await.unwrap().unwrap();
+    assert_eq!(manual.checksum, Some(format!("{:x}", Sha256::digest(bytes))));
+    assert!(service.get_model(missing.id).await.unwrap().unwrap().checksum.is_none());
+    let trail = service.audit_trail(Some(manual.id), 10).await.unwrap();
+    let last = trail.last().unwrap();
+    assert_eq!(last.operation, AuditOperation::Update);
+    assert_eq!(last.details["checksum_backfilled"], manual.checksum.clone().unwrap().as_str());
+
+    // Models that already have a checksum are left alone
+    assert_eq!(service.backfill_checksums().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_diagnostics_report_is_redacted() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("diagnosed", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, None).await.unwrap();
+    service.append_model_log(model.id, "loading weights");
+    service.append_model_log(model.id, "Download failed: https://cdn.example.com/m.gguf?token=hunter2");
+
+    let report = service.diagnostics().await.unwrap();
+    assert_eq!(report.schema_version, CURRENT_SCHEMA_VERSION);
+    assert_eq!(report.model_count, 1);
+    assert_eq!(report.installed_count, 1);
+    assert_eq!(report.running_count, 0);
+    assert_eq!(report.database_path, ":memory:");
+    assert!(report.database_size_bytes.is_none());
+    assert_eq!(report.recent_errors.len(), 1);
+    assert_eq!(report.recent_errors[0].model_id, model.id);
+
+    let json = serde_json::to_value(&report).unwrap();
+    for key in [
+        "generated_at", "client_version", "schema_version", "model_count", "installed_count", "running_count",
+        "database_path", "database_size_bytes", "free_disk_space_bytes", "offline", "recent_errors", "host",
+    ] {
+        assert!(json.get(key).is_some(), "missing {}", key);
+    }
+    let text = json.to_string();
+    assert!(!text.contains("hunter2"));
+    assert!(text.contains("token=REDACTED"));
+}
+
+#[tokio::test]
+async fn test_copy_config_between_chat_models() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut tuned = model_request("tuned", ModelType::Chat);
+    tuned.config.insert("temperature".to_string(), serde_json::json!(0.4));
+    tuned.config.insert("max_tokens".to_string(), serde_json::json!(4096));
+    let source = service.create_model(tuned).await.unwrap();
+    service.save_runtime_config(source.id, tuned_runtime_config()).await.unwrap();
+    let target = service.create_model(model_request("fresh", ModelType::Chat)).await.unwrap();
+
+    service.copy_config(source.id, target.id).await.unwrap();
+    let copied = service.get_model(target.id).await.unwrap().unwrap();
+    assert_eq!(copied.config, source.config);
+    assert_eq!(service.get_runtime_config(target.id).await.unwrap(), Some(tuned_runtime_config()));
+    let trail = service.audit_trail(Some(target.id), 10).await.unwrap();
+    let last = trail.last().unwrap();
+    assert_eq!(last.operation, AuditOperation::Update);
+    assert_eq!(last.details["config_copied_from"], source.id.to_string());
+    assert_eq!(last.details["runtime_config_copied"], true);
+
+    // Sampling parameters don't apply to embedding models
+    let embedding = service.create_model(model_request("embedder", ModelType::Embedding)).await.unwrap();
+    let result = service.copy_config(source.id, embedding.id).await;
+    assert!(matches!(result, Err(ClientError::ValidationFailed(_))));
+    assert!(service.get_model(embedding.id).await.unwrap().unwrap().config.is_empty());
+
+    let missing = service.copy_config(source.id, Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(ClientError::ResourceNotFound(_))));
+    let missing = service.copy_config(Uuid::new_v4(), target.id).await;
+    assert!(matches!(missing, Err(ClientError::ResourceNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_cancelled_ndjson_export_leaves_parseable_lines() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    for i in 0..5 {
+        service.create_model(model_request(&format!("export-{}", i), ModelType::Chat)).await.unwrap();
+    }
+
+    let path = temp.path().join("catalog.ndjson");
+    let cancel = CancellationToken::new();
+    let mut progress = Vec::new();
+    let result = service.export_catalog_ndjson(&path, |written, total| {
+        progress.push((written, total));
+        if written == 2 {
+            cancel.cancel();
+        }
+    }, &cancel).await;
+    assert!(matches!(result, Err(ClientError::Cancelled(_))));
+    assert_eq!(progress, vec![(1, 5), (2, 5)]);
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<serde_json::Value> = content.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|m| m["name"].as_str().unwrap().starts_with("export-")));
+
+    // A cancelled JSON export leaves nothing behind
+    let json_path = temp.path().join("catalog.json");
+    let cancelled = CancellationToken::new();
+    cancelled.cancel();
+    let result = service.export_catalog(&json_path, CatalogFormat::Json, |_, _| {}, &cancelled).await;
+    assert!(matches!(result, Err(ClientError::Cancelled(_))));
+    assert!(std::fs::read_dir(temp.path()).unwrap().all(|entry| !entry.unwrap().file_name().to_string_lossy().contains("catalog.json")));
+
+    let written = service.export_catalog(&json_path, CatalogFormat::Json, |_, _| {}, &CancellationToken::new()).await.unwrap();
+    assert_eq!(written, 5);
+    let exported: Vec<serde_json::Value> = serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(exported.len(), 5);
+
+    let csv_path = temp.path().join("catalog.csv");
+    service.export_catalog(&csv_path, CatalogFormat::Csv, |_, _| {}, &CancellationToken::new()).await.unwrap();
+    assert_eq!(std::fs::read_to_string(&csv_path).unwrap().lines().count(), 6);
+}
+
+#[tokio::test]
+async fn test_normalize_tags_collapses_case_and_aliases() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().to_path_buf(),
+        tag_aliases: HashMap::from([("Conversational".to_string(), "chat".to_string())]),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut ids = Vec::new();
+    for (name, tags) in [
+        ("mixed-a", vec!["Chat", "chat", "CHAT"]),
+        ("mixed-b", vec![" Conversational ", "Llama"]),
+        ("clean", vec!["chat", "llama"]),
+    ] {
+        let mut request = model_request(name, ModelType::Chat);
+        request.tags = tags.into_iter().map(str::to_string).collect();
+        ids.push(service.create_model(request).await.unwrap().id);
+    }
+
+    assert_eq!(service.normalize_tags().await.unwrap(), 2);
+    let expected = [vec!["chat"], vec!["chat", "llama"], vec!["chat", "llama"]];
+    for (id, expected) in ids.iter().zip(expected) {
+        assert_eq!(service.get_model(*id).await.unwrap().unwrap().tags, expected);
+    }
+
+    // Already normalized tags are left alone
+    assert_eq!(service.normalize_tags().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_license_lookup_and_summary() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let licenses = [
+        ("apache-a", Some("Apache-2.0")),
+        ("apache-b", Some("apache-2.0")),
+        ("custom", Some("Custom License")),
+        ("no-license", None),
+        ("blank-license", Some("  ")),
+    ];
+    for (name, license) in licenses {
+        let mut request = model_request(name, ModelType::Chat);
+        request.license = license.map(str::to_string);
+        let model = service.create_model(request).await.unwrap();
+        service.install_model(model.id, None).await.unwrap();
+    }
+    // Catalog-only models are not part of the audit
+    let mut uninstalled = model_request("catalog-only", ModelType::Chat);
+    uninstalled.license = Some("Custom License".to_string());
+    service.create_model(uninstalled).await.unwrap();
+
+    let names = |models: Vec<burncloud_service_models::InstalledModel>| {
+        let mut names: Vec<String> = models.into_iter().map(|m| m.model.name).collect();
+        names.sort();
+        names
+    };
+    assert_eq!(names(service.get_models_by_license("custom license").await.unwrap()), vec!["custom"]);
+    assert_eq!(names(service.get_models_by_license("Apache-2.0").await.unwrap()), vec!["apache-a", "apache-b"]);
+    assert_eq!(names(service.get_models_by_license("Unknown").await.unwrap()), vec!["blank-license", "no-license"]);
+    assert!(service.get_models_by_license("MIT").await.unwrap().is_empty());
+
+    let summary = service.license_summary().await.unwrap();
+    let expected: HashMap<String, usize> = [("Apache-2.0", 2), ("Custom License", 1), ("Unknown", 2)]
+        .into_iter()
+        .map(|(license, count)| (license.to_string(), count))
+        .collect();
+    assert_eq!(summary, expected);
+}
+
+#[tokio::test]
+async fn test_register_and_install_records_both() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let installed = service.register_and_install(model_request("atomic-model", ModelType::Chat), None).await.unwrap();
+    assert_eq!(installed.install_path, temp.path().join("atomic-model").to_string_lossy());
+    assert!(service.get_model(installed.model.id).await.unwrap().is_some());
+    assert_eq!(service.get_installed_models().await.unwrap().len(), 1);
+
+    let sources = service.get_installed_models_by_source(InstallSource::Manual).await.unwrap();
+    assert_eq!(sources.len(), 1);
+}
+
+#[tokio::test]
+async fn test_register_and_install_rolls_back_registration_on_failure() {
+    let temp = tempfile::tempdir().unwrap();
+    // A regular file where the install directory should be makes the install step fail
+    let blocker = temp.path().join("not-a-dir");
+    std::fs::write(&blocker, b"").unwrap();
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: blocker,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let result = service.register_and_install(model_request("doomed-model", ModelType::Chat), None).await;
+    assert!(matches!(result, Err(ClientError::IoError(_))));
+
+    assert!(service.list_models(None).await.unwrap().is_empty());
+    assert!(service.get_installed_models().await.unwrap().is_empty());
+    assert!(service.get_model_events(chrono::DateTime::<chrono::Utc>::MIN_UTC).await.unwrap().is_empty());
+    assert!(service.audit_trail(None, 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_register_and_install_reports_an_incomplete_rollback() {
+    let temp = tempfile::tempdir().unwrap();
+    let blocker = temp.path().join("not-a-dir");
+    std::fs::write(&blocker, b"").unwrap();
+    let sink = std::sync::Arc::new(RecordingSink::default());
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: blocker,
+        ..Default::default()
+    })
+    .await
+    .unwrap()
+    .with_notification_sink(sink.clone());
+    // Clearing the install source during the rollback fails
+    service.database().execute_query("DROP TABLE model_install_sources").await.unwrap();
+
+    let result = service.register_and_install(model_request("half-undone-model", ModelType::Chat), None).await;
+    assert!(matches!(result, Err(ClientError::IoError(_))), "the install error is still returned");
+    assert!(service.list_models(None).await.unwrap().is_empty());
+
+    let notifications = sink.0.lock().unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].title, "Rollback incomplete");
+    assert!(notifications[0].message.contains("model_install_sources"), "{}", notifications[0].message);
+}
+
+#[tokio::test]
+async fn test_audit_trail_records_mutations_in_order() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let model = service.create_model(model_request("audited-model", ModelType::Chat)).await.unwrap();
+    let installed = service.install_model(model.id, None).await.unwrap();
+    service.update_model_status(model.id, ModelStatus::Running).await.unwrap();
+    // Operations on other models stay out of this model's trail
+    service.create_model(model_request("other-model", ModelType::Code)).await.unwrap();
+
+    let trail = service.audit_trail(Some(model.id), 10).await.unwrap();
+    let operations: Vec<AuditOperation> = trail.iter().map(|entry| entry.operation).collect();
+    assert_eq!(operations, vec![AuditOperation::Create, AuditOperation::Install, AuditOperation::StatusChange]);
+    assert!(trail.iter().all(|entry| entry.model_id == Some(model.id)));
+    assert!(trail.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+    assert_eq!(trail[0].details["name"], "audited-model");
+    assert_eq!(trail[1].details["install_path"], installed.install_path.as_str());
+    assert_eq!(trail[2].details["status"], ModelStatus::Running.to_string());
+
+    // The limit keeps the most recent entries
+    let latest = service.audit_trail(None, 2).await.unwrap();
+    assert_eq!(latest.len(), 2);
+    assert_eq!(latest[0].operation, AuditOperation::StatusChange);
+    assert_eq!(latest[1].operation, AuditOperation::Create);
+}
+
+#[tokio::test]
+async fn test_mutation_is_refused_when_its_history_cannot_be_written() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let model = service.create_model(model_request("kept-model", ModelType::Chat)).await.unwrap();
+
+    service.database().execute_query(
+        "CREATE TRIGGER refuse_delete_audit BEFORE INSERT ON audit_log WHEN NEW.operation = 'delete' \
+         BEGIN SELECT RAISE(ABORT, 'audit log is full'); END",
+    ).await.unwrap();
+
+    let result = service.delete_model(model.id).await;
+    assert!(matches!(result, Err(ClientError::DatabaseError(_))));
+    // Nothing was deleted, and no trace of the attempt is left behind
+    assert!(service.get_model(model.id).await.unwrap().is_some());
+    let events = service.get_model_events(chrono::DateTime::<chrono::Utc>::MIN_UTC).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].kind, ModelEventKind::Created);
+    let operations: Vec<AuditOperation> = service.audit_trail(Some(model.id), 10).await.unwrap()
+        .iter().map(|entry| entry.operation).collect();
+    assert_eq!(operations, vec![AuditOperation::Create]);
+}
+
+#[tokio::test]
+async fn test_history_stays_pending_when_it_cannot_be_committed() {
+    let temp = tempfile::tempdir().unwrap();
+    let sink = std::sync::Arc::new(RecordingSink::default());
+    let service = setup_service(temp.path()).await.with_notification_sink(sink.clone());
+    let model = service.create_model(model_request("pending-model", ModelType::Chat)).await.unwrap();
+
+    service.database().execute_query(
+        "CREATE TRIGGER freeze_audit_log BEFORE UPDATE ON audit_log \
+         BEGIN SELECT RAISE(ABORT, 'audit log is read-only'); END",
+    ).await.unwrap();
+
+    // The update itself went through, so it still succeeds
+    let request = UpdateModelRequest { description: Some("changed".to_string()), ..Default::default() };
+    service.update_model(model.id, request).await.unwrap();
+
+    let trail = service.audit_trail(Some(model.id), 10).await.unwrap();
+    assert_eq!(trail.len(), 2);
+    assert!(!trail[0].pending);
+    assert_eq!(trail[1].operation, AuditOperation::Update);
+    assert!(trail[1].pending);
+
+    let notifications = sink.0.lock().unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].title, "History left pending");
+}
+
+#[tokio::test]
+async fn test_prewarm_validates_without_changing_status() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let model_dir = temp.path().join("prewarm-model");
+    std::fs::create_dir_all(&model_dir).unwrap();
+    std::fs::write(model_dir.join("model.gguf"), b"GGUF prewarm weights").unwrap();
+
+    let model = service.create_model(model_request("prewarm-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, model_dir.to_string_lossy().to_string()).await.unwrap();
+    service.save_runtime_config(model.id, tuned_runtime_config()).await.unwrap();
+    assert_eq!(service.last_validated_at(model.id).await.unwrap(), None);
+
+    let before = chrono::Utc::now();
+    service.prewarm_model(model.id, &CancellationToken::new()).await.unwrap();
+
+    let validated_at = service.last_validated_at(model.id).await.unwrap().expect("prewarm records the validation");
+    assert!(validated_at >= before);
+    let installed = service.get_installed_models().await.unwrap();
+    assert_eq!(installed[0].status, ModelStatus::Stopped);
+
+    // The next start uses the config loaded during prewarm
+    let runtime_config = service.start_model(model.id).await.unwrap().unwrap();
+    assert_eq!(runtime_config.max_context_length, Some(8192));
+    assert_eq!(service.get_installed_models().await.unwrap()[0].status, ModelStatus::Running);
+}
+
+#[tokio::test]
+async fn test_prewarm_can_be_cancelled() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    let model_file = temp.path().join("cancelled.gguf");
+    std::fs::write(&model_file, b"GGUF weights").unwrap();
+
+    let model = service.create_model(model_request("cancelled-prewarm", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, model_file.to_string_lossy().to_string()).await.unwrap();
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let result = service.prewarm_model(model.id, &cancel).await;
+    assert!(matches!(result, Err(ClientError::Cancelled(_))), "{:?}", result);
+}
+
+/// Sink that keeps every notification it receives
+#[derive(Default)]
+struct RecordingSink(std::sync::Mutex<Vec<Notification>>);
+
+impl NotificationSink for RecordingSink {
+    fn emit(&self, notification: &Notification) {
+        self.0.lock().unwrap().push(notification.clone());
+    }
+}
+
+#[tokio::test]
+async fn test_failures_are_emitted_to_notification_sinks() {
+    let temp = tempfile::tempdir().unwrap();
+    let sink = std::sync::Arc::new(RecordingSink::default());
+    let service = setup_service(temp.path()).await.with_notification_sink(sink.clone());
+
+    let model = service.create_model(model_request("alerting-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, None).await.unwrap();
+    service.start_model(model.id).await.unwrap();
+    assert!(sink.0.lock().unwrap().is_empty(), "successful operations stay quiet");
+
+    // Starting a model that is already running fails
+    assert!(service.start_model(model.id).await.is_err());
+    service.update_model_status(model.id, ModelStatus::Error).await.unwrap();
+
+    let notifications = sink.0.lock().unwrap();
+    let titles: Vec<&str> = notifications.iter().map(|n| n.title.as_str()).collect();
+    assert_eq!(titles, vec!["Model failed to start", "Model error"]);
+    assert!(notifications.iter().all(|n| n.notification_type == NotificationType::Error));
+    assert!(notifications[0].message.contains(&model.id.to_string()));
+}
+
+#[tokio::test]
+async fn test_webhook_sink_posts_notification_json() {
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/alerts"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let sink = WebhookSink::new(format!("{}/alerts", server.uri()));
+    sink.emit(&Notification::error("Model error".to_string(), "boom".to_string()));
+
+    // The request is sent in the background
+    for _ in 0..100 {
+        if !server.received_requests().await.unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let requests = server.received_requests().await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(body["title"], "Model error");
+    assert_eq!(body["message"], "boom");
+    assert_eq!(body["notification_type"], "Error");
+}
+
+/// Fake clock: each wait is recorded and returns at once, until `budget` waits have passed
+struct FakeClock {
+    waits: std::sync::Mutex<Vec<std::time::Duration>>,
+    budget: usize,
+}
+
+#[async_trait::async_trait]
+impl ScrubClock for FakeClock {
+    async fn sleep(&self, duration: std::time::Duration) {
+        let waited = {
+            let mut waits = self.waits.lock().unwrap();
+            waits.push(duration);
+            waits.len()
+        };
+        if waited > self.budget {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_scrub_marks_corrupted_model_as_error() {
+    let temp = tempfile::tempdir().unwrap();
+    let sink = std::sync::Arc::new(RecordingSink::default());
+    let service = setup_service(temp.path()).await.with_notification_sink(sink.clone());
+
+    let intact_file = temp.path().join("intact.gguf");
+    std::fs::write(&intact_file, b"GGUF intact weights").unwrap();
+    let intact = service.create_model(model_request("intact-model", ModelType::Chat)).await.unwrap();
+    service.install_model(intact.id, intact_file.to_string_lossy().to_string()).await.unwrap();
+
+    let rotten_file = temp.path().join("rotten.gguf");
+    std::fs::write(&rotten_file, b"GGUF original weights").unwrap();
+    let rotten = service.create_model(model_request("rotten-model", ModelType::Chat)).await.unwrap();
+    service.install_model(rotten.id, rotten_file.to_string_lossy().to_string()).await.unwrap();
+    assert_eq!(service.backfill_checksums().await.unwrap(), 2);
+
+    // A flipped byte on disk
+    std::fs::write(&rotten_file, b"GGUF original weighTs").unwrap();
+
+    // One interval, one pause between the two models, then the next interval never ends
+    let interval = std::time::Duration::from_secs(60 * 60);
+    let clock = std::sync::Arc::new(FakeClock { waits: Default::default(), budget: 2 });
+    service.start_scrub_with_clock(interval, clock.clone());
+    while clock.waits.lock().unwrap().len() < 3 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    service.stop_scrub();
+
+    let waits = clock.waits.lock().unwrap().clone();
+    assert_eq!(waits[0], interval, "the first pass waits a full interval");
+    assert!(waits[1] < interval);
+    assert_eq!(waits[2], interval);
+
+    let installed = service.get_installed_models().await.unwrap();
+    let status = |id| installed.iter().find(|m| m.model.id == id).unwrap().status;
+    assert_eq!(status(rotten.id), ModelStatus::Error);
+    assert_eq!(status(intact.id), ModelStatus::Stopped);
+    assert!(service.last_validated_at(intact.id).await.unwrap().is_some());
+    assert!(service.last_validated_at(rotten.id).await.unwrap().is_some());
+    assert!(service.get_model_logs(rotten.id, None).iter().any(|entry| entry.line.contains("checksum mismatch")));
+    assert!(sink.0.lock().unwrap().iter().any(|n| n.notification_type == NotificationType::Error));
+}
+
+/// Fake clock that starts every stopped model during the pause after the first verified model
+struct StartingClock {
+    service: IntegratedModelService,
+    waits: std::sync::Mutex<usize>,
+}
+
+#[async_trait::async_trait]
+impl ScrubClock for StartingClock {
+    async fn sleep(&self, _duration: std::time::Duration) {
+        let waited = {
+            let mut waits = self.waits.lock().unwrap();
+            *waits += 1;
+            *waits
+        };
+        match waited {
+            1 => {}
+            2 => {
+                for model in self.service.get_installed_models().await.unwrap() {
+                    if model.status == ModelStatus::Stopped {
+                        self.service.update_model_status(model.model.id, ModelStatus::Running).await.unwrap();
+                    }
+                }
+            }
+            _ => std::future::pending::<()>().await,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_scrub_skips_model_started_after_listing() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut ids = Vec::new();
+    for name in ["first-model", "second-model"] {
+        let file = temp.path().join(format!("{}.gguf", name));
+        std::fs::write(&file, b"GGUF original weights").unwrap();
+        let model = service.create_model(model_request(name, ModelType::Chat)).await.unwrap();
+        service.install_model(model.id, file.to_string_lossy().to_string()).await.unwrap();
+        ids.push(model.id);
+    }
+    assert_eq!(service.backfill_checksums().await.unwrap(), 2);
+    for name in ["first-model", "second-model"] {
+        std::fs::write(temp.path().join(format!("{}.gguf", name)), b"GGUF original weighTs").unwrap();
+    }
+
+    // Both models are idle when the pass lists them; the second starts before its turn
+    let clock = std::sync::Arc::new(StartingClock { service: service.clone(), waits: Default::default() });
+    service.start_scrub_with_clock(std::time::Duration::from_secs(60 * 60), clock.clone());
+    while *clock.waits.lock().unwrap() < 3 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    service.stop_scrub();
+
+    let installed = service.get_installed_models().await.unwrap();
+    let mut statuses = Vec::new();
+    for id in ids {
+        let status = installed.iter().find(|m| m.model.id == id).unwrap().status;
+        statuses.push((status, service.last_validated_at(id).await.unwrap().is_some()));
+    }
+    statuses.sort_by_key(|(status, _)| *status == ModelStatus::Running);
+    assert_eq!(statuses, vec![(ModelStatus::Error, true), (ModelStatus::Running, false)]);
+}
+
+#[tokio::test]
+async fn test_replace_model_file_keeps_usage_stats() {
+    let temp = tempfile::tempdir().unwrap();
+    let db_path = temp.path().join("models.db");
+    let service = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(db_path.to_string_lossy().to_string()),
+        default_install_dir: temp.path().join("models"),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let old_file = temp.path().join("model-v1.gguf");
+    std::fs::write(&old_file, b"GGUF old weights").unwrap();
+    let model = service.create_model(model_request("replaceable-model", ModelType::Chat)).await.unwrap();
+    service.install_model(model.id, old_file.to_string_lossy().to_string()).await.unwrap();
+    seed_raw_database(&db_path, &[&format!(
+        "UPDATE installed_models SET usage_count = 7 WHERE model_id = '{}'", model.id
+    )]).await;
+
+    let new_file = temp.path().join("model-v2.gguf");
+    let new_weights = b"GGUF newer and larger weights";
+    std::fs::write(&new_file, new_weights).unwrap();
+    service.replace_model_file(model.id, &new_file).await.unwrap();
+
+    let installed = service.get_installed_models().await.unwrap().remove(0);
+    assert_eq!(installed.install_path, new_file.to_string_lossy());
+    assert_eq!(installed.model.file_size, new_weights.len() as u64);
+    let expected_checksum = format!("{:x}", Sha256::digest(new_weights));
+    assert_eq!(installed.model.checksum.as_deref(), Some(expected_checksum.as_str()));
+    assert_eq!(installed.usage_count, 7);
+    assert!(service.last_validated_at(model.id).await.unwrap().is_some());
+
+    // Running models keep their file
+    service.start_model(model.id).await.unwrap();
+    let result = service.replace_model_file(model.id, &old_file).await;
+    assert!(matches!(result, Err(ClientError::OperationNotAllowed(_))));
+}
+
+#[tokio::test]
+async fn test_select_within_budget_drops_the_largest_model() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut ids = Vec::new();
+    for (name, model_type, file_size, rating) in [
+        ("small-chat", ModelType::Chat, 100, 4.0),
+        ("medium-code", ModelType::Code, 200, 3.0),
+        ("large-chat", ModelType::Chat, 1000, 5.0),
+    ] {
+        let model = service.create_model(CreateModelRequest {
+            file_size,
+            ..model_request(name, model_type)
+        }).await.unwrap();
+        service.update_model(model.id, UpdateModelRequest {
+            rating: Some(rating),
+            ..Default::default()
+        }).await.unwrap();
+        ids.push(model.id);
+    }
+    let (small, medium, large) = (ids[0], ids[1], ids[2]);
+
+    // The largest model doesn't fit next to anything else
+    for objective in [Objective::MaxCount, Objective::MaxRating, Objective::MaxTypeCoverage] {
+        let chosen = service.select_within_budget(&ids, 400, objective).await.unwrap();
+        assert_eq!(chosen, vec![small, medium], "{:?}", objective);
+    }
+
+    // With more room, rating trades the medium model for the large one
+    assert_eq!(service.select_within_budget(&ids, 1100, Objective::MaxCount).await.unwrap(), vec![small, medium]);
+    assert_eq!(service.select_within_budget(&ids, 1100, Objective::MaxRating).await.unwrap(), vec![small, large]);
+    assert_eq!(service.select_within_budget(&ids, 1100, Objective::MaxTypeCoverage).await.unwrap(), vec![small, medium]);
+
+    assert!(service.select_within_budget(&ids, 50, Objective::MaxCount).await.unwrap().is_empty());
+    let unknown = service.select_within_budget(&[Uuid::new_v4()], 400, Objective::MaxCount).await;
+    assert!(matches!(unknown, Err(ClientError::ResourceNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_list_models_with_capabilities() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let vision = service.create_model(model_request("vision-model", ModelType::Multimodal)).await.unwrap();
+    let mut tools_request = model_request("tools-model", ModelType::Chat);
+    tools_request.config.insert("tools".to_string(), serde_json::json!(["search"]));
+    let tools = service.create_model(tools_request).await.unwrap();
+    service.create_model(model_request("plain-chat", ModelType::Chat)).await.unwrap();
+
+    let ids = |models: Vec<Model>| models.into_iter().map(|m| m.id).collect::<Vec<_>>();
+    assert_eq!(ids(service.list_models_with_capabilities(None, &[Capability::Vision]).await.unwrap()), vec![vision.id]);
+    assert_eq!(ids(service.list_models_with_capabilities(None, &[Capability::FunctionCalling]).await.unwrap()), vec![tools.id]);
+    assert!(service.list_models_with_capabilities(None, &[Capability::Vision, Capability::FunctionCalling]).await.unwrap().is_empty());
+    assert_eq!(service.list_models_with_capabilities(None, &[]).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_list_models_after_pages_without_duplicates_or_skips() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut expected = HashSet::new();
+    for index in 0..5 {
+        let model = service.create_model(model_request(&format!("paged-{}", index), ModelType::Chat)).await.unwrap();
+        expected.insert(model.id);
+    }
+
+    let (first_page, mut cursor) = service.list_models_after(None, 2).await.unwrap();
+    assert_eq!(first_page.len(), 2);
+    assert!(cursor.is_some());
+    let mut seen: Vec<Uuid> = first_page.iter().map(|m| m.id).collect();
+
+    // Added while the user is scrolling
+    let late = service.create_model(model_request("paged-late", ModelType::Chat)).await.unwrap();
+    expected.insert(late.id);
+
+    while let Some(after) = cursor {
+        let (page, next) = service.list_models_after(Some(after), 2).await.unwrap();
+        assert!(!page.is_empty());
+        seen.extend(page.iter().map(|m| m.id));
+        cursor = next;
+    }
+
+    assert_eq!(seen.len(), expected.len(), "no model is shown twice");
+    assert_eq!(seen.iter().copied().collect::<HashSet<_>>(), expected);
+    assert_eq!(seen.last(), Some(&late.id));
+    assert!(matches!(service.list_models_after(None, 0).await, Err(ClientError::ValidationFailed(_))));
+}
+
+#[tokio::test]
+async fn test_validate_create_request_warns_about_embedding_code_mode() {
+    let temp = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+
+    let mut request = model_request("code-embedder", ModelType::Embedding);
+    request.config.insert("code_mode".to_string(), serde_json::json!(true));
+
+    let issues = service.validate_create_request(&request).await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].rule, ConsistencyRule::EmbeddingCodeMode);
+    assert_eq!(issues[0].severity, RuleSeverity::Warn);
+    assert!(issues[0].message.contains("model_type") && issues[0].message.contains("code_mode"));
+
+    // The same flag on a code model is fine
+    let mut request = model_request("coder", ModelType::Code);
+    request.config.insert("code_mode".to_string(), serde_json::json!(true));
+    assert!(service.validate_create_request(&request).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_validate_create_request_checks_file_path_availability() {
+    let temp = tempfile::tempdir().unwrap();
+    let elsewhere = tempfile::tempdir().unwrap();
+    let service = setup_service(temp.path()).await;
+    // Left behind in the install directory by a model that is no longer installed
+    let leftover_dir = temp.path().join("old-model");
+    std::fs::create_dir_all(&leftover_dir).unwrap();
+    let leftover = leftover_dir.join("model.gguf");
+    std::fs::write(&leftover, b"GGUF weights").unwrap();
+
+    // Pointing at it without an install record is rejected by default, also by create_model
+    let mut request = model_request("contradictory", ModelType::Chat);
+    request.file_path = Some(leftover.to_string_lossy().to_string());
+    let error = service.validate_create_request(&request).await.unwrap_err();
+    assert!(matches!(&error, ClientError::ValidationFailed(message)
+        if message.contains("file_path") && message.contains("default_install_dir")));
+    assert!(matches!(service.create_model(request.clone()).await, Err(ClientError::ValidationFailed(_))));
+    assert!(service.list_models(None).await.unwrap().is_empty());
+
+    // Once an installed model owns the directory the same path is fine
+    let owner = service.create_model(model_request("old-model", ModelType::Chat)).await.unwrap();
+    service.install_model(owner.id, leftover_dir.to_string_lossy().to_string()).await.unwrap();
+    assert!(service.validate_create_request(&request).await.unwrap().is_empty());
+
+    // Files outside the install directory aren't install state
+    let mut request = model_request("outside", ModelType::Chat);
+    let outside = elsewhere.path().join("present.gguf");
+    std::fs::write(&outside, b"GGUF weights").unwrap();
+    request.file_path = Some(outside.to_string_lossy().to_string());
+    assert!(service.validate_create_request(&request).await.unwrap().is_empty());
+
+    // A missing file with nowhere to download it from only warns
+    let mut request = model_request("unavailable", ModelType::Chat);
+    request.file_path = Some(elsewhere.path().join("absent.gguf").to_string_lossy().to_string());
+    let issues = service.validate_create_request(&request).await.unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].rule, ConsistencyRule::UnavailableFile);
+    assert!(issues[0].message.contains("file_path") && issues[0].message.contains("download_url"));
+    let warned = service.create_model(request.clone()).await.unwrap();
+    assert!(service.get_model_logs(warned.id, None).iter().any(|entry| entry.line.contains("download_url")));
+    request.download_url = Some("https://example.com/unavailable.gguf".to_string());
+    assert!(service.validate_create_request(&request).await.unwrap().is_empty());
+
+    // Severities can be overridden per rule
+    let lenient = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().to_path_buf(),
+        consistency_rules: HashMap::from([
+            (ConsistencyRule::FilePathNotInstalled, RuleSeverity::Warn),
+            (ConsistencyRule::UnavailableFile, RuleSeverity::Ignore),
+        ]),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let mut request = model_request("contradictory", ModelType::Chat);
+    request.file_path = Some(temp.path().join("absent.gguf").to_string_lossy().to_string());
+    let issues = lenient.validate_create_request(&request).await.unwrap();
+    assert_eq!(issues.iter().map(|issue| issue.rule).collect::<Vec<_>>(), vec![ConsistencyRule::FilePathNotInstalled]);
+}
@@ -4,15 +4,17 @@
 //! by testing ModelDataService and AppState with actual database connections.
 
 use burncloud_client_models::{
-    ModelDataService,
+    ClientError, IntegratedModelService, IntegratedServiceConfig, LocalStatus, ModelDataService, ModelDataSource,
+    combined_requirements,
     state::AppState,
     burncloud_service_models::{
-        ModelsService, CreateModelRequest, ModelType, ModelStatus
+        AvailableModel, ModelsService, CreateModelRequest, ModelType, ModelStatus
     },
     burncloud_database::Database
 };
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Helper function to create test database
@@ -287,10 +289,10 @@ async fn test_concurrent_service_creation() {
 
 #[tokio::test]
 async fn test_app_state_new_with_database() {
-    // Test AppState::new() with database
+    // Test AppState::with_database()
     let database = create_test_database().await;
 
-    let app_state = AppState::new(database.clone()).await
+    let app_state = AppState::with_database(database.clone()).await
         .expect("Failed to create AppState");
 
     assert!(app_state.data_service.get_installed_models().is_empty());
@@ -300,6 +302,20 @@ async fn test_app_state_new_with_database() {
     assert!(app_state.filter_status.is_none());
 }
 
+#[tokio::test]
+async fn test_app_state_initialize_with_memory_database() {
+    let app_state = AppState::initialize(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: std::env::temp_dir(),
+        ..Default::default()
+    })
+    .await
+    .expect("Failed to initialize AppState");
+
+    assert!(app_state.data_service.get_installed_models().is_empty());
+    assert!(app_state.data_service.get_available_models().is_empty());
+}
+
 #[tokio::test]
 async fn test_app_state_with_populated_database() {
     // Test AppState with pre-populated database
@@ -315,7 +331,7 @@ async fn test_app_state_with_populated_database() {
     models_service.create_model(request2).await.expect("Failed to create model 2");
 
     // Create AppState
-    let app_state = AppState::new(database.clone()).await
+    let app_state = AppState::with_database(database.clone()).await
         .expect("Failed to create AppState");
 
     // Verify data is loaded
@@ -328,7 +344,7 @@ async fn test_app_state_database_reference() {
     let database = create_test_database().await;
     let db_ptr = Arc::as_ptr(&database);
 
-    let app_state = AppState::new(database.clone()).await
+    let app_state = AppState::with_database(database.clone()).await
         .expect("Failed to create AppState");
 
     // Verify database reference is maintained
@@ -375,6 +391,42 @@ async fn test_search_functionality_still_works() {
     assert_eq!(results.len(), 0, "Should find 0 models");
 }
 
+#[tokio::test]
+async fn test_unified_search_dedupes_and_ranks() {
+    let database = create_test_database().await;
+    let models_service = ModelsService::new(database.clone()).await
+        .expect("Failed to create ModelsService");
+
+    // Installed models are also part of the available list
+    let installed = models_service.create_model(create_test_model("qwen-chat", ModelType::Chat)).await
+        .expect("Failed to create model");
+    models_service.install_model(installed.id, "/opt/qwen-chat".to_string()).await
+        .expect("Failed to install model");
+    let exact = models_service.create_model(create_test_model("qwen", ModelType::Text)).await
+        .expect("Failed to create model");
+    models_service.create_model(create_test_model("tiny-qwen-coder", ModelType::Code)).await
+        .expect("Failed to create model");
+    models_service.create_model(create_test_model("unrelated", ModelType::Text)).await
+        .expect("Failed to create model");
+
+    let data_service = ModelDataService::new(database.clone()).await
+        .expect("Failed to create ModelDataService");
+    let hits = data_service.search("Qwen");
+
+    let names: Vec<&str> = hits.iter().map(|hit| hit.model.name.as_str()).collect();
+    assert_eq!(names, vec!["qwen", "qwen-chat", "tiny-qwen-coder"]);
+    assert!(hits.windows(2).all(|w| w[0].relevance >= w[1].relevance));
+
+    let both = hits.iter().filter(|hit| hit.model.id == installed.id).collect::<Vec<_>>();
+    assert_eq!(both.len(), 1, "model present in both lists should appear once");
+    assert_eq!(both[0].availability, LocalStatus::Installed);
+    let available_only = hits.iter().find(|hit| hit.model.id == exact.id).unwrap();
+    assert_eq!(available_only.availability, LocalStatus::Registered);
+
+    // An empty query lists every model once
+    assert_eq!(data_service.search("").len(), 4);
+}
+
 #[tokio::test]
 async fn test_filter_by_status_still_works() {
     // Test filtering by status with real data
@@ -613,4 +665,203 @@ async fn test_query_performance_with_many_models() {
 
     assert_eq!(filtered.len(), 20); // 100 / 5
     assert!(duration.as_millis() < 50, "Filter took too long: {:?}", duration);
-}
\ No newline at end of file
+}
+// =============================================================================
+// Async mutation API and ModelDataSource
+// =============================================================================
+
+/// Create a data service with one registered (not installed) model
+async fn data_service_with_model(name: &str) -> (ModelDataService, Uuid) {
+    let database = create_test_database().await;
+    let models_service = ModelsService::new(database.clone()).await
+        .expect("Failed to create ModelsService");
+    let model = models_service.create_model(create_test_model(name, ModelType::Chat)).await
+        .expect("Failed to create model");
+    let data_service = ModelDataService::new(database).await
+        .expect("Failed to create ModelDataService");
+    (data_service, model.id)
+}
+
+#[tokio::test]
+async fn test_data_service_async_lifecycle() {
+    let (mut service, model_id) = data_service_with_model("lifecycle-model").await;
+
+    service.install_model(&model_id, "/opt/lifecycle".to_string()).await.unwrap();
+    assert!(matches!(
+        service.install_model(&model_id, "/opt/lifecycle".to_string()).await,
+        Err(ClientError::OperationNotAllowed(_))
+    ));
+
+    service.start_model(&model_id, 8080).await.unwrap();
+    let installed = service.get_installed_model_by_id(&model_id).unwrap();
+    assert_eq!(installed.status, ModelStatus::Running);
+    assert_eq!(installed.port, Some(8080));
+    assert!(installed.process_id.is_some());
+    assert!(matches!(service.start_model(&model_id, 8081).await, Err(ClientError::OperationNotAllowed(_))));
+
+    service.stop_model(&model_id).await.unwrap();
+    assert_eq!(service.get_installed_model_by_id(&model_id).unwrap().status, ModelStatus::Stopped);
+    assert!(matches!(service.stop_model(&model_id).await, Err(ClientError::OperationNotAllowed(_))));
+
+    service.uninstall_model(&model_id).await.unwrap();
+    assert!(service.get_installed_models().is_empty());
+    assert!(matches!(service.uninstall_model(&model_id).await, Err(ClientError::ValidationFailed(_))));
+}
+
+#[tokio::test]
+async fn test_data_service_rejects_unknown_model_and_busy_port() {
+    let (mut service, model_id) = data_service_with_model("port-model").await;
+
+    assert!(matches!(
+        service.install_model(&Uuid::new_v4(), "/opt/missing".to_string()).await,
+        Err(ClientError::ValidationFailed(_))
+    ));
+    assert!(matches!(service.start_model(&Uuid::new_v4(), 9000).await, Err(ClientError::ValidationFailed(_))));
+
+    service.install_model(&model_id, "/opt/port".to_string()).await.unwrap();
+    service.start_model(&model_id, 9000).await.unwrap();
+
+    // The port check runs before the status check, even for the model holding the port
+    let err = service.start_model(&model_id, 9000).await.unwrap_err();
+    assert!(matches!(err, ClientError::OperationNotAllowed(ref msg) if msg.contains("9000")));
+}
+
+/// A local port with nothing listening on it
+fn closed_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+#[tokio::test]
+async fn test_start_when_ready_errors_if_port_never_opens() {
+    let (mut service, model_id) = data_service_with_model("never-ready").await;
+    service.install_model(&model_id, "/opt/never-ready".to_string()).await.unwrap();
+
+    let mut statuses = Vec::new();
+    let port = closed_port();
+    let result = service.start_model_when_ready(&model_id, port, Duration::from_millis(500), |status| statuses.push(status)).await;
+
+    assert!(matches!(result, Err(ClientError::OperationNotAllowed(ref msg)) if msg.contains(&port.to_string())));
+    assert_eq!(statuses, vec![ModelStatus::Starting, ModelStatus::Error]);
+    let installed = service.get_installed_model_by_id(&model_id).unwrap();
+    assert_eq!(installed.status, ModelStatus::Error);
+    assert!(installed.process_id.is_none());
+}
+
+#[tokio::test]
+async fn test_start_when_ready_runs_once_port_accepts() {
+    let (mut service, model_id) = data_service_with_model("ready").await;
+    service.install_model(&model_id, "/opt/ready".to_string()).await.unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let mut statuses = Vec::new();
+    service.start_model_when_ready(&model_id, port, Duration::from_secs(5), |status| statuses.push(status)).await.unwrap();
+
+    assert_eq!(statuses, vec![ModelStatus::Starting, ModelStatus::Running]);
+    assert_eq!(service.get_installed_model_by_id(&model_id).unwrap().status, ModelStatus::Running);
+}
+
+/// Install, start, stop and uninstall through the trait only
+async fn exercise_data_source(source: &mut dyn ModelDataSource, model_id: Uuid) {
+    assert!(source.installed_models().await.unwrap().is_empty());
+    assert!(source.available_models().await.unwrap().iter().any(|m| m.model.id == model_id));
+
+    source.install_model(model_id, "/opt/agnostic".to_string()).await.unwrap();
+    source.start_model(model_id, 8500).await.unwrap();
+    let installed = source.installed_models().await.unwrap();
+    assert_eq!(installed.len(), 1);
+    assert_eq!(installed[0].status, ModelStatus::Running);
+
+    source.stop_model(model_id).await.unwrap();
+    source.uninstall_model(model_id).await.unwrap();
+    assert!(source.installed_models().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_model_data_source_is_backend_agnostic() {
+    let (mut in_memory, model_id) = data_service_with_model("agnostic-memory").await;
+    exercise_data_source(&mut in_memory, model_id).await;
+
+    let temp = tempfile::tempdir().unwrap();
+    let mut integrated = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().to_path_buf(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    let model = integrated.create_model(create_test_model("agnostic-db", ModelType::Chat)).await.unwrap();
+    exercise_data_source(&mut integrated, model.id).await;
+}
+
+#[tokio::test]
+async fn test_search_installed_models_matches_across_backends() {
+    let temp = tempfile::tempdir().unwrap();
+    let integrated = IntegratedModelService::with_config(IntegratedServiceConfig {
+        database_path: Some(":memory:".to_string()),
+        default_install_dir: temp.path().to_path_buf(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    for (name, model_type) in [("parity-chat", ModelType::Chat), ("parity-code", ModelType::Code), ("other-text", ModelType::Text)] {
+        let model = integrated.create_model(create_test_model(name, model_type)).await.unwrap();
+        integrated.install_model(model.id, format!("/opt/{}", name)).await.unwrap();
+    }
+    // Registered but not installed, so neither backend may return it
+    integrated.create_model(create_test_model("parity-available", ModelType::Chat)).await.unwrap();
+    let in_memory = ModelDataService::new(integrated.database()).await.unwrap();
+
+    let backends: [&dyn ModelDataSource; 2] = [&in_memory, &integrated];
+    for query in ["parity", "PARITY-CODE", "other", "", "nonexistent"] {
+        let mut results = Vec::new();
+        for backend in backends {
+            let mut names: Vec<String> = backend.search_installed_models(query).await.unwrap()
+                .into_iter()
+                .map(|installed| installed.model.name)
+                .collect();
+            names.sort();
+            results.push(names);
+        }
+        assert_eq!(results[0], results[1], "query {:?}", query);
+    }
+
+    let names: Vec<String> = in_memory.search_installed_models("parity").await.unwrap()
+        .into_iter()
+        .map(|installed| installed.model.name)
+        .collect();
+    assert_eq!(names.len(), 2);
+    assert_eq!(in_memory.search_installed_models("").await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_combined_requirements_for_batch_install() {
+    let database = create_test_database().await;
+    let models_service = ModelsService::new(database.clone()).await.unwrap();
+
+    let mut small = create_test_model("cpu-small", ModelType::Chat);
+    small.file_size = 2 * 1024 * 1024 * 1024;
+    small.config.insert("supported_os".to_string(), serde_json::json!(["linux", "macos", "windows"]));
+    models_service.create_model(small).await.unwrap();
+
+    let mut large = create_test_model("gpu-large", ModelType::Chat);
+    large.file_size = 40 * 1024 * 1024 * 1024;
+    large.config.insert("gpu_required".to_string(), serde_json::json!(true));
+    large.config.insert("min_memory_gb".to_string(), serde_json::json!(48));
+    large.config.insert("recommended_memory_gb".to_string(), serde_json::json!(64));
+    large.config.insert("supported_os".to_string(), serde_json::json!(["linux"]));
+    models_service.create_model(large).await.unwrap();
+
+    let data_service = ModelDataService::new(database).await.unwrap();
+    let selected: Vec<&AvailableModel> = data_service.get_available_models().iter().collect();
+    assert_eq!(selected.len(), 2);
+
+    let combined = combined_requirements(&selected);
+    assert!(combined.gpu_required);
+    assert_eq!(combined.min_memory_gb, 48.0);
+    assert_eq!(combined.recommended_memory_gb, 64.0);
+    assert_eq!(combined.min_disk_space_gb, 42.0);
+    assert_eq!(combined.supported_os, Some(vec!["linux".to_string()]));
+    assert_eq!(combined.supported_arch, None);
+}
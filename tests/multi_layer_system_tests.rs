@@ -63,7 +63,7 @@ async fn test_full_model_lifecycle_through_all_layers() {
     let request = create_test_model_request("lifecycle-model", ModelType::Chat, 5_000_000_000);
 
     // Validate at client layer
-    service.validate_create_request(&request).unwrap();
+    service.validate_create_request(&request).await.unwrap();
 
     // Create model (flows through all layers)
     let created = service.create_model(request).await.unwrap();
@@ -192,7 +192,7 @@ async fn test_error_propagation_through_layers() {
     };
 
     // Should fail at validation layer
-    let validation_result = service.validate_create_request(&invalid_request);
+    let validation_result = service.validate_create_request(&invalid_request).await;
     assert!(validation_result.is_err());
 
     // Should also fail at service layer